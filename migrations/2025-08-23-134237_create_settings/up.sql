@@ -2,7 +2,4 @@ CREATE TABLE settings
 (
     key   TEXT PRIMARY KEY,
     value TEXT
-);
-
-INSERT INTO settings
-VALUES ('language', 'en');
\ No newline at end of file
+);
\ No newline at end of file
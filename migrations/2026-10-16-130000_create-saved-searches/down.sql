@@ -0,0 +1 @@
+DROP TABLE saved_searches;
\ No newline at end of file
@@ -0,0 +1,6 @@
+CREATE TABLE saved_searches
+(
+    name       TEXT PRIMARY KEY NOT NULL,
+    drive_name TEXT,
+    query      TEXT NOT NULL
+);
\ No newline at end of file
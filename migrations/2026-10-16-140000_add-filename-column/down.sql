@@ -0,0 +1,5 @@
+ALTER TABLE saved_searches
+    DROP COLUMN filename_only;
+
+ALTER TABLE file_entries
+    DROP COLUMN filename;
\ No newline at end of file
@@ -0,0 +1,10 @@
+ALTER TABLE file_entries
+    ADD COLUMN filename TEXT COLLATE NOCASE NOT NULL DEFAULT '';
+
+-- Keep what follows the last separator, whichever the platform used
+UPDATE file_entries
+SET filename = substr(replace(path, '\', '/'),
+                      length(rtrim(replace(path, '\', '/'), replace(replace(path, '\', '/'), '/', ''))) + 1);
+
+ALTER TABLE saved_searches
+    ADD COLUMN filename_only BOOLEAN NOT NULL DEFAULT FALSE;
\ No newline at end of file
@@ -0,0 +1,5 @@
+ALTER TABLE saved_searches
+    DROP COLUMN extension;
+
+ALTER TABLE file_entries
+    DROP COLUMN extension;
\ No newline at end of file
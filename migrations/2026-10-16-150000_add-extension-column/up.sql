@@ -0,0 +1,10 @@
+ALTER TABLE file_entries
+    ADD COLUMN extension TEXT NOT NULL DEFAULT '';
+
+-- Lowercased text after the last dot of the filename, leading dots excepted
+UPDATE file_entries
+SET extension = lower(substr(filename, length(rtrim(filename, replace(filename, '.', ''))) + 1))
+WHERE length(rtrim(filename, replace(filename, '.', ''))) > 1;
+
+ALTER TABLE saved_searches
+    ADD COLUMN extension TEXT;
\ No newline at end of file
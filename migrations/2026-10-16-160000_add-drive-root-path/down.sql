@@ -0,0 +1,2 @@
+ALTER TABLE drive_entries
+    DROP COLUMN root_path;
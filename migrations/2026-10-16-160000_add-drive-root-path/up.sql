@@ -0,0 +1,2 @@
+ALTER TABLE drive_entries
+    ADD COLUMN root_path TEXT;
@@ -0,0 +1,11 @@
+CREATE TABLE index_runs
+(
+    id            BLOB PRIMARY KEY NOT NULL,
+    indexed_at    TIMESTAMP        NOT NULL,
+    -- One directory per line
+    directories   TEXT             NOT NULL,
+    category_name TEXT             NOT NULL,
+    drive_name    TEXT             NOT NULL,
+    file_count    BIGINT           NOT NULL,
+    duration_ms   BIGINT           NOT NULL
+);
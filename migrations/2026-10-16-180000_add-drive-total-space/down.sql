@@ -0,0 +1,2 @@
+ALTER TABLE drive_entries
+    DROP COLUMN total_space;
@@ -0,0 +1,2 @@
+ALTER TABLE drive_entries
+    ADD COLUMN total_space BIGINT NOT NULL DEFAULT 0;
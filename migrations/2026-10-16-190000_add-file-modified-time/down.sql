@@ -0,0 +1,2 @@
+ALTER TABLE file_entries
+    DROP COLUMN modified_time;
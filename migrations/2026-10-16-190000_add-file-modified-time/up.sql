@@ -0,0 +1,2 @@
+ALTER TABLE file_entries
+    ADD COLUMN modified_time TIMESTAMP;
@@ -0,0 +1,2 @@
+ALTER TABLE saved_searches
+    ADD COLUMN whole_segments BOOLEAN NOT NULL DEFAULT FALSE;
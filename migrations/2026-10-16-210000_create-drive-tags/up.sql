@@ -0,0 +1,9 @@
+CREATE TABLE drive_tags
+(
+    drive_id BLOB NOT NULL,
+    tag      TEXT NOT NULL,
+    PRIMARY KEY (drive_id, tag),
+    FOREIGN KEY (drive_id)
+        REFERENCES drive_entries (id)
+        ON DELETE CASCADE
+);
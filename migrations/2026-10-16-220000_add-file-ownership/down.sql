@@ -0,0 +1,6 @@
+ALTER TABLE file_entries
+    DROP COLUMN mode;
+ALTER TABLE file_entries
+    DROP COLUMN owner_gid;
+ALTER TABLE file_entries
+    DROP COLUMN owner_uid;
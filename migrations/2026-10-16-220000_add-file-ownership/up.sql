@@ -0,0 +1,6 @@
+ALTER TABLE file_entries
+    ADD COLUMN owner_uid BIGINT;
+ALTER TABLE file_entries
+    ADD COLUMN owner_gid BIGINT;
+ALTER TABLE file_entries
+    ADD COLUMN mode BIGINT;
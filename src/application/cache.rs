@@ -0,0 +1,151 @@
+use crate::domain::model::file_entry::FileWithMetadata;
+use crate::domain::model::search_filters::SearchFilters;
+use crate::domain::model::search_terms::SearchTerms;
+use std::collections::VecDeque;
+
+/// Number of warmed datasets kept by default.
+pub const DEFAULT_CACHED_DATASETS: usize = 8;
+/// Total number of rows kept by default, across every dataset.
+pub const DEFAULT_CACHED_ROWS: usize = 50_000;
+
+type CacheKey = (Option<String>, String, SearchFilters);
+
+/// Least-recently-used cache of full search results, keyed by drive, query and filters.
+///
+/// Memory stays bounded both by the number of datasets and by their total number of rows.
+pub struct Cache {
+    // Most recently used first
+    entries: VecDeque<(CacheKey, Vec<FileWithMetadata>)>,
+    max_datasets: usize,
+    max_rows: usize,
+}
+
+impl Cache {
+    #[must_use]
+    pub const fn new(max_datasets: usize, max_rows: usize) -> Self {
+        Self {
+            entries: VecDeque::new(),
+            max_datasets,
+            max_rows,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Stores a full dataset, evicting the least recently used ones to stay within bounds.
+    ///
+    /// A dataset larger than the row budget on its own is not stored.
+    pub fn store(
+        &mut self,
+        drive: Option<String>,
+        query: String,
+        filters: SearchFilters,
+        results: Vec<FileWithMetadata>,
+    ) {
+        let key = (drive, query, filters);
+        self.entries.retain(|(existing, _)| *existing != key);
+        if self.max_datasets == 0 || results.len() > self.max_rows {
+            return;
+        }
+        self.entries.push_front((key, results));
+
+        while self.entries.len() > self.max_datasets || self.total_rows() > self.max_rows {
+            self.entries.pop_back();
+        }
+    }
+
+    #[must_use]
+    pub fn is_valid_for(
+        &self,
+        selected_drive: Option<&String>,
+        query: &str,
+        filters: &SearchFilters,
+    ) -> bool {
+        self.position(selected_drive, query, filters).is_some()
+    }
+
+    /// Returns the number of cached results whose path matches the `refinement` terms.
+    #[must_use]
+    pub fn count(
+        &self,
+        selected_drive: Option<&String>,
+        query: &str,
+        filters: &SearchFilters,
+        refinement: &str,
+    ) -> Option<u64> {
+        let index = self.position(selected_drive, query, filters)?;
+        Some(Self::refined(&self.entries[index].1, refinement).count() as u64)
+    }
+
+    /// Returns the combined size of the cached results whose path matches the `refinement` terms.
+    #[must_use]
+    pub fn total_size(
+        &self,
+        selected_drive: Option<&String>,
+        query: &str,
+        filters: &SearchFilters,
+        refinement: &str,
+    ) -> Option<u64> {
+        let index = self.position(selected_drive, query, filters)?;
+        Some(
+            Self::refined(&self.entries[index].1, refinement)
+                .map(|file| file.size_bytes)
+                .sum(),
+        )
+    }
+
+    /// Returns a page of the cached results, marking the dataset as the most recently used.
+    pub fn get_page(
+        &mut self,
+        selected_drive: Option<&String>,
+        query: &str,
+        filters: &SearchFilters,
+        refinement: &str,
+        page_index: usize,
+        items_per_page: usize,
+    ) -> Option<Vec<FileWithMetadata>> {
+        let index = self.position(selected_drive, query, filters)?;
+        let entry = self.entries.remove(index)?;
+        self.entries.push_front(entry);
+
+        let results: Vec<&FileWithMetadata> =
+            Self::refined(&self.entries[0].1, refinement).collect();
+        let start = page_index * items_per_page;
+        // The first page always exists, even when the refinement leaves nothing
+        if start < results.len() || start == 0 {
+            let end = (start + items_per_page).min(results.len());
+            return Some(results[start..end].iter().copied().cloned().collect());
+        }
+        None
+    }
+
+    #[must_use]
+    pub fn total_rows(&self) -> usize {
+        self.entries.iter().map(|(_, results)| results.len()).sum()
+    }
+
+    fn position(
+        &self,
+        selected_drive: Option<&String>,
+        query: &str,
+        filters: &SearchFilters,
+    ) -> Option<usize> {
+        self.entries
+            .iter()
+            .position(|((drive, cached_query, cached_filters), _)| {
+                drive.as_ref() == selected_drive
+                    && cached_query == query
+                    && cached_filters == filters
+            })
+    }
+
+    fn refined<'a>(
+        results: &'a [FileWithMetadata],
+        refinement: &str,
+    ) -> impl Iterator<Item = &'a FileWithMetadata> {
+        let terms = SearchTerms::parse(refinement);
+        results.iter().filter(move |file| terms.matches(&file.path))
+    }
+}
@@ -1,6 +1,17 @@
 use crate::domain::model::file_entry::FileEntry;
+use crate::domain::model::file_ownership::FileOwnership;
+use crate::domain::model::stored_path::encode_path;
+use crate::utils::localized_error::LocalizedError;
+use chrono::{DateTime, Local};
 use jwalk::{DirEntry, WalkDir};
-use std::path::{Path, StripPrefixError};
+use std::collections::HashSet;
+use std::fs::Metadata;
+use std::iter;
+use std::path::{Path, PathBuf, StripPrefixError};
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// Number of files handed over at once by [`scan_directory_in_batches`].
+pub const SCAN_BATCH_SIZE: usize = 10_000;
 
 #[derive(Debug, thiserror::Error)]
 pub enum DirectoryScannerError {
@@ -10,41 +21,370 @@ pub enum DirectoryScannerError {
     FileMetadata(#[from] jwalk::Error),
 }
 
+impl DirectoryScannerError {
+    /// Translation key of a message telling the user what likely went wrong and what to do.
+    #[must_use]
+    pub fn message_key(&self) -> &'static str {
+        match self {
+            Self::RelativePath(_) => "error_scan",
+            Self::FileMetadata(error) if error.loop_ancestor().is_some() => "error_scan_loop",
+            Self::FileMetadata(error) => match error.io_error().map(std::io::Error::kind) {
+                Some(std::io::ErrorKind::NotFound) => "error_path_not_found",
+                Some(std::io::ErrorKind::PermissionDenied) => "error_permission_denied",
+                _ => "error_scan",
+            },
+        }
+    }
+}
+
+impl LocalizedError for DirectoryScannerError {
+    fn message_key(&self) -> Option<&'static str> {
+        Some(Self::message_key(self))
+    }
+}
+
+/// What a scan does with an entry it cannot read, such as a folder it has no permission on.
+#[derive(Clone, Debug)]
+pub enum ErrorPolicy {
+    /// Leaves the entry out, recording its path so the index can be reported as incomplete.
+    SkipErrors(SkippedEntries),
+    /// Stops the scan at the first entry that cannot be read.
+    FailFast,
+}
+
+impl ErrorPolicy {
+    /// Records the failed entry when skipping errors, or hands the error back otherwise.
+    fn on_error<T>(
+        &self,
+        path: PathBuf,
+        error: DirectoryScannerError,
+    ) -> Option<Result<T, DirectoryScannerError>> {
+        match self {
+            Self::SkipErrors(skipped) => {
+                skipped.record(path);
+                None
+            }
+            Self::FailFast => Some(Err(error)),
+        }
+    }
+}
+
+/// Paths left out of a scan because they could not be read.
+///
+/// Clones share the same list, so it can be read once a scan running elsewhere is over.
+#[derive(Clone, Debug, Default)]
+pub struct SkippedEntries(Arc<Mutex<Vec<PathBuf>>>);
+
+impl SkippedEntries {
+    fn record(&self, path: PathBuf) {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(path);
+    }
+
+    /// Skipped paths, in the order the scan met them.
+    #[must_use]
+    pub fn paths(&self) -> Vec<PathBuf> {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
 /// Recursively scans a directory and returns a list of [`FileEntry`] values.
 ///
 /// Uses [`jwalk`](https://docs.rs/jwalk) to traverse all subdirectories,
 /// filtering out directories and keeping only files.
 ///
+/// Symbolic links are skipped unless `follow_symlinks` is set. When they are followed, a
+/// link looping back to one of its ancestors is not traversed, and a file reached through
+/// several paths is kept once, under the first path in sorted order.
+///
+/// Hidden files and folders are left out unless `include_hidden` is set: those whose name
+/// starts with a dot and, on Windows, those with the hidden attribute.
+///
+/// Entries that cannot be read are handled according to `policy`.
+///
 /// # Errors
 ///
-/// Returns a [`DirectoryScannerError`] if:
+/// With [`ErrorPolicy::FailFast`], returns a [`DirectoryScannerError`] if:
 /// - A [`RelativePath`](DirectoryScannerError::RelativePath) error occurs when
 ///   stripping the base directory prefix from a file path.
-/// - A [`FileMetadata`](DirectoryScannerError::FileMetadata) error occurs when retrieving
-///   file metadata (e.g., file size).
-pub fn scan_directory(directory: &Path) -> Result<Vec<FileEntry>, DirectoryScannerError> {
-    WalkDir::new(directory)
-        .skip_hidden(false)
-        .sort(true)
-        .into_iter()
-        .filter_map(Result::ok)
-        .filter(|e| e.file_type().is_file())
-        .map(|e| extract_file_info(&directory, &e))
+/// - A [`FileMetadata`](DirectoryScannerError::FileMetadata) error occurs when reading
+///   a folder or retrieving file metadata (e.g., file size).
+pub fn scan_directory(
+    directory: &Path,
+    follow_symlinks: bool,
+    include_hidden: bool,
+    policy: ErrorPolicy,
+) -> Result<Vec<FileEntry>, DirectoryScannerError> {
+    file_entries(directory, follow_symlinks, include_hidden, policy).collect()
+}
+
+/// Recursively scans a directory and yields its files in batches of at most `batch_size`
+/// entries, so they can be processed without holding the whole tree in memory.
+///
+/// A scan error is yielded in place of the batch being built.
+///
+/// # Errors
+///
+/// Yields a [`DirectoryScannerError`] under the same conditions as [`scan_directory`].
+pub fn scan_directory_in_batches(
+    directory: &Path,
+    batch_size: usize,
+    follow_symlinks: bool,
+    include_hidden: bool,
+    policy: ErrorPolicy,
+) -> impl Iterator<Item = Result<Vec<FileEntry>, DirectoryScannerError>> + '_ {
+    let mut entries = file_entries(directory, follow_symlinks, include_hidden, policy);
+    let batch_size = batch_size.max(1);
+
+    iter::from_fn(move || {
+        let mut batch = Vec::new();
+        for entry in entries.by_ref() {
+            match entry {
+                Ok(file) => batch.push(file),
+                Err(error) => return Some(Err(error)),
+            }
+            if batch.len() == batch_size {
+                break;
+            }
+        }
+        (!batch.is_empty()).then_some(Ok(batch))
+    })
+}
+
+/// Scans several directories as a single tree and yields their files in batches
+/// of at most `batch_size` entries.
+///
+/// With more than one directory, the files of each one are placed under a folder named
+/// after it, numbered when two directories share a name, so their paths cannot collide.
+/// A single directory is scanned like [`scan_directory_in_batches`].
+///
+/// # Errors
+///
+/// Yields a [`DirectoryScannerError`] under the same conditions as [`scan_directory`].
+pub fn scan_directories_in_batches(
+    directories: &[PathBuf],
+    batch_size: usize,
+    follow_symlinks: bool,
+    include_hidden: bool,
+    policy: ErrorPolicy,
+) -> impl Iterator<Item = Result<Vec<FileEntry>, DirectoryScannerError>> + '_ {
+    directories
+        .iter()
+        .zip(root_prefixes(directories))
+        .flat_map(move |(directory, prefix)| {
+            let batches = scan_directory_in_batches(
+                directory,
+                batch_size,
+                follow_symlinks,
+                include_hidden,
+                policy.clone(),
+            );
+            batches.map(move |batch| {
+                let mut files = batch?;
+                if let Some(prefix) = &prefix {
+                    for file in &mut files {
+                        file.path = Path::new(prefix)
+                            .join(&file.path)
+                            .to_string_lossy()
+                            .into_owned();
+                    }
+                }
+                Ok(files)
+            })
+        })
+}
+
+/// Scans several directories as a single tree, see [`scan_directories_in_batches`].
+///
+/// # Errors
+///
+/// Returns a [`DirectoryScannerError`] under the same conditions as [`scan_directory`].
+pub fn scan_directories(
+    directories: &[PathBuf],
+    follow_symlinks: bool,
+    include_hidden: bool,
+    policy: ErrorPolicy,
+) -> Result<Vec<FileEntry>, DirectoryScannerError> {
+    let mut files = Vec::new();
+    for batch in scan_directories_in_batches(
+        directories,
+        SCAN_BATCH_SIZE,
+        follow_symlinks,
+        include_hidden,
+        policy,
+    ) {
+        files.extend(batch?);
+    }
+    Ok(files)
+}
+
+/// Directory the scanned paths of `directories` are relative to, if there is one.
+///
+/// A single directory is its own root. Several directories have their parent as root when
+/// they all share it, since each one is then placed under its own name.
+#[must_use]
+pub fn common_root(directories: &[PathBuf]) -> Option<PathBuf> {
+    if let [directory] = directories {
+        return Some(directory.clone());
+    }
+    let parent = directories.first()?.parent()?;
+    let is_under_own_name =
+        directories
+            .iter()
+            .zip(root_prefixes(directories))
+            .all(|(directory, prefix)| {
+                directory.parent() == Some(parent)
+                    && directory
+                        .file_name()
+                        .map(|name| encode_path(Path::new(name)))
+                        .as_deref()
+                        == prefix.as_deref()
+            });
+    is_under_own_name.then(|| parent.to_path_buf())
+}
+
+/// Folder each directory is placed under, or `None` for all when there is a single one.
+fn root_prefixes(directories: &[PathBuf]) -> Vec<Option<String>> {
+    if directories.len() < 2 {
+        return vec![None; directories.len()];
+    }
+    let mut taken = HashSet::new();
+    directories
+        .iter()
+        .map(|directory| {
+            // Roots such as `/` or `C:\` have no name, keep their letters if any
+            let name = directory.file_name().map_or_else(
+                || {
+                    let letters: String = directory
+                        .to_string_lossy()
+                        .chars()
+                        .filter(|c| c.is_alphanumeric())
+                        .collect();
+                    if letters.is_empty() {
+                        "root".to_string()
+                    } else {
+                        letters
+                    }
+                },
+                |name| encode_path(Path::new(name)),
+            );
+            let prefix = (1..)
+                .map(|index| {
+                    if index == 1 {
+                        name.clone()
+                    } else {
+                        format!("{name}_{index}")
+                    }
+                })
+                .find(|prefix| !taken.contains(prefix))
+                .unwrap_or(name);
+            taken.insert(prefix.clone());
+            Some(prefix)
+        })
         .collect()
 }
 
+fn file_entries(
+    directory: &Path,
+    follow_symlinks: bool,
+    include_hidden: bool,
+    policy: ErrorPolicy,
+) -> impl Iterator<Item = Result<FileEntry, DirectoryScannerError>> + '_ {
+    let mut visited = HashSet::new();
+    let walk_dir = WalkDir::new(directory)
+        .skip_hidden(!include_hidden)
+        .follow_links(follow_symlinks)
+        .sort(true);
+    // Dotfiles are skipped above, Windows also hides files through an attribute
+    #[cfg(windows)]
+    let walk_dir = if include_hidden {
+        walk_dir
+    } else {
+        walk_dir.process_read_dir(|_, _, _, children| {
+            children.retain(|child| {
+                child
+                    .as_ref()
+                    .map_or(true, |entry| !has_hidden_attribute(entry))
+            });
+        })
+    };
+    walk_dir.into_iter().filter_map(move |entry| {
+        let mut entry = match entry {
+            Ok(entry) => entry,
+            // Links looping back to an ancestor are not traversed on purpose
+            Err(error) if error.loop_ancestor().is_some() => return None,
+            Err(error) => {
+                let path = error.path().unwrap_or(directory).to_path_buf();
+                return policy.on_error(path, error.into());
+            }
+        };
+        // A folder that cannot be listed still comes out, with the reason attached
+        if let Some(error) = entry.read_children_error.take() {
+            return policy.on_error(entry.path(), error.into());
+        }
+        if !entry.file_type().is_file()
+            || (follow_symlinks
+                && entry
+                    .path()
+                    .canonicalize()
+                    .is_ok_and(|real_path| !visited.insert(real_path)))
+        {
+            return None;
+        }
+        match extract_file_info(directory, &entry) {
+            Ok(file) => Some(Ok(file)),
+            Err(error) => policy.on_error(entry.path(), error),
+        }
+    })
+}
+
+#[cfg(windows)]
+fn has_hidden_attribute(entry: &DirEntry<((), ())>) -> bool {
+    use std::os::windows::fs::MetadataExt;
+    const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+    entry
+        .metadata()
+        .is_ok_and(|metadata| metadata.file_attributes() & FILE_ATTRIBUTE_HIDDEN != 0)
+}
+
 fn extract_file_info(
     base_directory: &Path,
     entry: &DirEntry<((), ())>,
 ) -> Result<FileEntry, DirectoryScannerError> {
+    let metadata = entry.metadata()?;
     Ok(FileEntry {
         path: relative_path(base_directory, &entry.path())?,
-        size_bytes: entry.metadata()?.len(),
+        size_bytes: metadata.len(),
+        // Some file systems do not record it, which leaves the file unsorted by age
+        modified_time: metadata
+            .modified()
+            .ok()
+            .map(|time| DateTime::<Local>::from(time).naive_local()),
+        ownership: file_ownership(&metadata),
+    })
+}
+
+#[cfg(unix)]
+fn file_ownership(metadata: &Metadata) -> Option<FileOwnership> {
+    use std::os::unix::fs::MetadataExt;
+    Some(FileOwnership {
+        uid: metadata.uid(),
+        gid: metadata.gid(),
+        mode: metadata.mode(),
     })
 }
 
+/// Windows has no owner ids or mode bits to record.
+#[cfg(not(unix))]
+const fn file_ownership(_metadata: &Metadata) -> Option<FileOwnership> {
+    None
+}
+
 fn relative_path(base_directory: &Path, file_path: &Path) -> Result<String, DirectoryScannerError> {
-    Ok(file_path
-        .strip_prefix(base_directory)
-        .map(|p| p.to_string_lossy().into_owned())?)
+    Ok(file_path.strip_prefix(base_directory).map(encode_path)?)
 }
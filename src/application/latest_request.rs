@@ -0,0 +1,26 @@
+/// Tracks the latest of a series of requests, so that the answers to outdated ones are ignored.
+///
+/// Each request is tagged with the generation returned when it starts, and its answer is
+/// applied only while that generation is still the latest one.
+#[derive(Debug, Default)]
+pub struct LatestRequest {
+    generation: u64,
+}
+
+impl LatestRequest {
+    /// Starts a new request, making every previous one outdated.
+    pub const fn start(&mut self) -> u64 {
+        self.generation += 1;
+        self.generation
+    }
+
+    /// Makes every started request outdated.
+    pub const fn cancel(&mut self) {
+        self.generation += 1;
+    }
+
+    #[must_use]
+    pub const fn is_latest(&self, generation: u64) -> bool {
+        self.generation == generation
+    }
+}
@@ -1 +1,7 @@
+pub mod cache;
 pub mod directory_scanner;
+pub mod latest_request;
+pub mod presence_check;
+pub mod search_timer;
+pub mod shortcut;
+pub mod undo_manager;
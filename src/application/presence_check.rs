@@ -0,0 +1,50 @@
+use crate::domain::model::file_entry::FileEntry;
+use crate::domain::model::stored_path::decode_path;
+use rayon::prelude::*;
+use std::path::{Path, PathBuf};
+
+/// Cataloged files of a drive that were looked for on disk, and those no longer there.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PresenceReport {
+    pub checked: usize,
+    /// Files not found anymore, in the order they were given.
+    pub missing: Vec<FileEntry>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum PresenceCheckError {
+    #[error("The folder the drive was indexed from is not known")]
+    UnknownLocation,
+    #[error("The drive is not mounted at {}", .0.display())]
+    NotMounted(PathBuf),
+}
+
+/// Looks for each cataloged file under `root`, the folder its drive was indexed from.
+///
+/// Gives up when the drive looks unplugged, since every file would otherwise be reported
+/// missing: when `root` is not a folder, or when not a single file is found under it. The
+/// latter covers a drive indexed at its root, whose mount point stays behind as an empty
+/// folder once unmounted, and a drive letter now given to another disk. A file that cannot be
+/// looked up, for lack of permission for instance, is not reported missing.
+///
+/// # Errors
+///
+/// Returns [`PresenceCheckError::NotMounted`] when `root` is not an existing folder or holds
+/// none of the files.
+pub fn check_presence(
+    root: &Path,
+    files: Vec<FileEntry>,
+) -> Result<PresenceReport, PresenceCheckError> {
+    if !root.is_dir() {
+        return Err(PresenceCheckError::NotMounted(root.to_path_buf()));
+    }
+    let checked = files.len();
+    let missing: Vec<FileEntry> = files
+        .into_par_iter()
+        .filter(|file| matches!(root.join(decode_path(&file.path)).try_exists(), Ok(false)))
+        .collect();
+    if checked > 0 && missing.len() == checked {
+        return Err(PresenceCheckError::NotMounted(root.to_path_buf()));
+    }
+    Ok(PresenceReport { checked, missing })
+}
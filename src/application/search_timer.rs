@@ -0,0 +1,48 @@
+use std::time::{Duration, Instant};
+
+/// Time after which a search still running is reported as slow.
+pub const SLOW_SEARCH_THRESHOLD: Duration = Duration::from_secs(2);
+
+/// Follows a running search to tell when it has been taking unusually long.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum SearchTimer {
+    #[default]
+    Idle,
+    Running {
+        started: Instant,
+    },
+    /// The search is still running past [`SLOW_SEARCH_THRESHOLD`].
+    Slow,
+}
+
+impl SearchTimer {
+    /// Starts timing a search, restarting the timer when another one was running.
+    pub const fn start(&mut self, now: Instant) {
+        *self = Self::Running { started: now };
+    }
+
+    /// Stops timing once the results arrived or the search was abandoned.
+    pub const fn stop(&mut self) {
+        *self = Self::Idle;
+    }
+
+    /// Marks a running search as slow once it has run past the threshold.
+    pub fn tick(&mut self, now: Instant) {
+        if let Self::Running { started } = *self
+            && now.saturating_duration_since(started) >= SLOW_SEARCH_THRESHOLD
+        {
+            *self = Self::Slow;
+        }
+    }
+
+    /// Whether ticks are still needed to tell if the search is slow.
+    #[must_use]
+    pub const fn needs_ticks(&self) -> bool {
+        matches!(self, Self::Running { .. })
+    }
+
+    #[must_use]
+    pub const fn is_slow(&self) -> bool {
+        matches!(self, Self::Slow)
+    }
+}
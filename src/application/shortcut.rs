@@ -0,0 +1,105 @@
+/// Key of an application-wide shortcut, the others being left to the pages.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShortcutKey {
+    Tab,
+    Digit(u8),
+    Escape,
+    F1,
+    QuestionMark,
+}
+
+/// Key pressed with the modifiers that matter to the application-wide shortcuts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeyPress {
+    pub key: ShortcutKey,
+    /// Whether `Ctrl` was held, or `Cmd` on macOS for the keys the system leaves free.
+    pub command: bool,
+    pub shift: bool,
+}
+
+/// What an application-wide shortcut does.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shortcut {
+    CloseHelp,
+    FocusNext,
+    FocusPrevious,
+    NextPage,
+    ReadPage,
+    ToggleHelp,
+    WritePage,
+}
+
+/// Keys of a shortcut as shown in the help, with the translation key of what they do.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ShortcutHelp {
+    pub keys: &'static str,
+    pub description_key: &'static str,
+}
+
+/// Shortcuts available in the same context, under the translation key of its title.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ShortcutGroup {
+    pub title_key: &'static str,
+    pub shortcuts: &'static [ShortcutHelp],
+}
+
+const fn help(keys: &'static str, description_key: &'static str) -> ShortcutHelp {
+    ShortcutHelp {
+        keys,
+        description_key,
+    }
+}
+
+/// Every keyboard shortcut, as listed in the help overlay.
+pub const SHORTCUT_GROUPS: &[ShortcutGroup] = &[
+    ShortcutGroup {
+        title_key: "shortcuts_everywhere",
+        shortcuts: &[
+            help("Ctrl+Tab", "shortcut_next_page"),
+            help("Ctrl+1", "shortcut_read_page"),
+            help("Ctrl+2", "shortcut_write_page"),
+            help("Tab / Shift+Tab", "shortcut_focus"),
+            help("F1 / ?", "shortcut_help"),
+            help("Escape", "shortcut_close_help"),
+        ],
+    },
+    ShortcutGroup {
+        title_key: "shortcuts_search",
+        shortcuts: &[
+            help("Ctrl+F", "shortcut_focus_search"),
+            help("Ctrl+L", "shortcut_clear_search"),
+            help("Ctrl+R / F5", "shortcut_refresh"),
+            help("Escape", "shortcut_leave_search"),
+            help("Up / Down", "shortcut_select"),
+            help("Shift+Up / Shift+Down", "shortcut_scroll"),
+            help("Page Up / Page Down", "shortcut_scroll_fast"),
+            help("Home / End", "shortcut_top_bottom"),
+            help("Enter", "shortcut_row_menu"),
+            help("Left / Right", "shortcut_prev_next_page"),
+            help("Shift+Left / Shift+Right", "shortcut_first_last_page"),
+        ],
+    },
+];
+
+impl KeyPress {
+    /// Shortcut triggered by the key press, given whether a text input has the focus.
+    ///
+    /// `Ctrl+Tab` does nothing while typing, so that a slip of the finger never leaves the
+    /// page being filled in, and neither does `?`, which is then typed as text. `Ctrl+1` and
+    /// `Ctrl+2` do not conflict with editing and jump to the search and indexing pages from
+    /// anywhere.
+    #[must_use]
+    pub const fn shortcut(self, is_typing: bool) -> Option<Shortcut> {
+        match (self.key, self.command, self.shift) {
+            (ShortcutKey::Tab, false, false) => Some(Shortcut::FocusNext),
+            (ShortcutKey::Tab, false, true) => Some(Shortcut::FocusPrevious),
+            (ShortcutKey::Tab, true, false) if !is_typing => Some(Shortcut::NextPage),
+            (ShortcutKey::Digit(1), true, false) => Some(Shortcut::ReadPage),
+            (ShortcutKey::Digit(2), true, false) => Some(Shortcut::WritePage),
+            (ShortcutKey::Escape, _, _) => Some(Shortcut::CloseHelp),
+            (ShortcutKey::F1, false, false) => Some(Shortcut::ToggleHelp),
+            (ShortcutKey::QuestionMark, false, _) if !is_typing => Some(Shortcut::ToggleHelp),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use std::time::{Duration, Instant};
+
+/// Time during which the last destructive operation can still be undone.
+pub const UNDO_WINDOW: Duration = Duration::from_secs(30);
+
+/// Keeps what the most recent destructive operation removed, for a short while.
+///
+/// Only one operation can be undone: recording a new one forgets the previous one.
+#[derive(Debug)]
+pub struct UndoManager<T> {
+    last: Option<(T, Instant)>,
+}
+
+impl<T> Default for UndoManager<T> {
+    fn default() -> Self {
+        Self { last: None }
+    }
+}
+
+impl<T> UndoManager<T> {
+    /// Records what an operation performed at `now` removed, replacing any earlier record.
+    pub fn record(&mut self, snapshot: T, now: Instant) {
+        self.last = Some((snapshot, now));
+    }
+
+    /// Whether the last operation can still be undone at `now`.
+    #[must_use]
+    pub fn can_undo(&self, now: Instant) -> bool {
+        self.last
+            .as_ref()
+            .is_some_and(|(_, recorded)| now.saturating_duration_since(*recorded) < UNDO_WINDOW)
+    }
+
+    /// Hands over what the last operation removed, if it can still be undone at `now`.
+    ///
+    /// The record is consumed either way, so the same operation is never undone twice.
+    pub fn take(&mut self, now: Instant) -> Option<T> {
+        let can_undo = self.can_undo(now);
+        self.last
+            .take()
+            .filter(|_| can_undo)
+            .map(|(snapshot, _)| snapshot)
+    }
+
+    /// Forgets the last operation once it can no longer be undone at `now`.
+    pub fn expire(&mut self, now: Instant) {
+        if !self.can_undo(now) {
+            self.last = None;
+        }
+    }
+
+    /// Forgets the last operation, for instance once something else changed the same rows.
+    pub fn clear(&mut self) {
+        self.last = None;
+    }
+}
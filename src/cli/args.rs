@@ -0,0 +1,61 @@
+use clap::{ArgGroup, CommandFactory, Parser};
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+/// Indexes and searches drives from the command line; the window opens when no argument is given.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+#[command(group(ArgGroup::new("mode").required(true).args(["index", "search"])))]
+pub struct CliArgs {
+    /// Directory to index, replacing the files previously indexed for the drive
+    #[arg(long, value_name = "DIR", requires = "category")]
+    pub index: Option<PathBuf>,
+
+    /// Follow symbolic links while indexing, counting each real file once
+    #[arg(long, requires = "index")]
+    pub follow_symlinks: bool,
+
+    /// Stop indexing at the first unreadable entry instead of skipping it
+    #[arg(long, requires = "index")]
+    pub fail_fast: bool,
+
+    /// Category of the indexed drive
+    #[arg(long, requires = "index")]
+    pub category: Option<String>,
+
+    /// Drive to index into, or to restrict the search to; detected from the directory when indexing
+    #[arg(long)]
+    pub drive: Option<String>,
+
+    /// Query to search for in the indexed paths
+    #[arg(long, value_name = "QUERY")]
+    pub search: Option<String>,
+
+    /// Print search results as JSON instead of a table
+    #[arg(long, requires = "search")]
+    pub json: bool,
+}
+
+impl CliArgs {
+    /// Whether the command line asks for the headless mode, through one of its options.
+    ///
+    /// Any other argument, such as a file dropped on the executable or the `-psn_…` process
+    /// serial number some macOS launchers add, still opens the window.
+    #[must_use]
+    pub fn is_requested(args: impl IntoIterator<Item = OsString>) -> bool {
+        let command = Self::command();
+        args.into_iter().skip(1).any(|arg| {
+            let arg = arg.to_string_lossy();
+            match arg.strip_prefix("--") {
+                Some(long) => {
+                    let name = long.split_once('=').map_or(long, |(name, _)| name);
+                    ["help", "version"].contains(&name)
+                        || command
+                            .get_arguments()
+                            .any(|option| option.get_long() == Some(name))
+                }
+                None => ["-h", "-V"].contains(&arg.as_ref()),
+            }
+        })
+    }
+}
@@ -0,0 +1,173 @@
+use crate::application::directory_scanner::{
+    scan_directory_in_batches, ErrorPolicy, SkippedEntries, SCAN_BATCH_SIZE,
+};
+use crate::cli::args::CliArgs;
+use crate::domain::clock::SystemClock;
+use crate::domain::model::directory::DirectoryData;
+use crate::domain::model::result_table::{result_table_rows, RESULT_TABLE_HEADER};
+use crate::domain::model::search_filters::SearchFilters;
+use crate::infrastructure::database::pool::{
+    InfrastructureError, SqliteRepositoryPool, DATABASE_URL,
+};
+use crate::infrastructure::database::repository::ListerRepository;
+use crate::infrastructure::filesystem::directory::{directory_data, SystemDisks};
+use serde::ser::{SerializeSeq, Serializer};
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Number of search results fetched and printed at a time.
+const SEARCH_BATCH_SIZE: u64 = 1000;
+
+/// Runs the headless command described by `args` against the application database.
+///
+/// # Errors
+///
+/// Returns a [`InfrastructureError`] if the database cannot be opened, or if indexing or
+/// searching fails.
+pub fn run(args: CliArgs) -> Result<(), InfrastructureError> {
+    let repository = ListerRepository::new(
+        SqliteRepositoryPool::new(DATABASE_URL)?,
+        Arc::new(SystemClock),
+    );
+
+    if let Some(directory) = args.index {
+        let category = args.category.unwrap_or_default();
+        index(
+            &repository,
+            &directory,
+            &category,
+            args.drive,
+            args.follow_symlinks,
+            args.fail_fast,
+        )
+    } else {
+        search(
+            &repository,
+            args.drive.as_deref(),
+            args.search.as_deref().unwrap_or_default(),
+            args.json,
+        )
+    }
+}
+
+fn index(
+    repository: &ListerRepository,
+    directory: &Path,
+    category: &str,
+    drive: Option<String>,
+    follow_symlinks: bool,
+    fail_fast: bool,
+) -> Result<(), InfrastructureError> {
+    // Without the `\\?\` prefix std adds on Windows, which no mount point starts with
+    let directory = dunce::canonicalize(directory)?;
+    // A drive given explicitly makes the disk detection optional
+    let directory_data = match (directory_data(&SystemDisks, &directory), &drive) {
+        (Ok(data), _) => data,
+        (Err(error), Some(_)) => {
+            eprintln!("{error}, indexing without the available space");
+            DirectoryData::without_disk(&directory)
+        }
+        (Err(error), None) => return Err(error),
+    };
+    let drive_capacity = directory_data.drive_capacity();
+    let drive = drive.unwrap_or(directory_data.drive_name);
+
+    let started = Instant::now();
+    let skipped = SkippedEntries::default();
+    let policy = if fail_fast {
+        ErrorPolicy::FailFast
+    } else {
+        ErrorPolicy::SkipErrors(skipped.clone())
+    };
+    let include_hidden = repository.get_index_hidden_files()?;
+    let batches = scan_directory_in_batches(
+        &directory,
+        SCAN_BATCH_SIZE,
+        follow_symlinks,
+        include_hidden,
+        policy,
+    )
+    .map(|batch| batch.map_err(|error| InfrastructureError::Scan(error.to_string())));
+    let files_indexed = repository.reindex(
+        category,
+        &drive,
+        drive_capacity,
+        Some(&directory),
+        batches,
+        |files_saved| eprintln!("{files_saved} files saved"),
+    )?;
+    if let Err(error) = repository.index_runs().record_run(
+        std::slice::from_ref(&directory),
+        category,
+        &drive,
+        files_indexed,
+        started.elapsed(),
+    ) {
+        eprintln!("Could not record the indexing history: {error}");
+    }
+
+    println!(
+        "Indexed {files_indexed} files from {} into {category}/{drive}",
+        directory.display()
+    );
+    let skipped = skipped.paths();
+    if !skipped.is_empty() {
+        eprintln!("{} entries skipped due to errors:", skipped.len());
+        for path in skipped {
+            eprintln!("  {}", path.display());
+        }
+    }
+    Ok(())
+}
+
+fn search(
+    repository: &ListerRepository,
+    drive: Option<&str>,
+    query: &str,
+    json: bool,
+) -> Result<(), InfrastructureError> {
+    let query = Some(query).filter(|query| !query.is_empty());
+    let filters = SearchFilters::default();
+    let batches = || repository.search_files_in_batches(drive, query, &filters, SEARCH_BATCH_SIZE);
+
+    if json {
+        // Written as the batches arrive, the same as the whole list pretty-printed at once
+        let mut serializer = serde_json::Serializer::pretty(io::stdout().lock());
+        let mut files = serializer.serialize_seq(None)?;
+        for batch in batches() {
+            for file in batch? {
+                files.serialize_element(&file)?;
+            }
+        }
+        SerializeSeq::end(files)?;
+        println!();
+    } else {
+        // A first pass measures the columns, so every batch lines up under the same header
+        let mut widths = RESULT_TABLE_HEADER.map(|title| title.chars().count());
+        for batch in batches() {
+            for row in result_table_rows(&batch?) {
+                for (width, cell) in widths.iter_mut().zip(&row) {
+                    *width = (*width).max(cell.chars().count());
+                }
+            }
+        }
+        print_row(&RESULT_TABLE_HEADER.map(String::from), &widths);
+        for batch in batches() {
+            for row in result_table_rows(&batch?) {
+                print_row(&row, &widths);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn print_row([drive, category, size, path]: &[String; 4], widths: &[usize; 4]) {
+    println!(
+        "{drive:<drive_width$}  {category:<category_width$}  {size:>size_width$}  {path}",
+        drive_width = widths[0],
+        category_width = widths[1],
+        size_width = widths[2],
+    );
+}
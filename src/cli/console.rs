@@ -0,0 +1,20 @@
+/// Lets the headless mode print to the console it was started from.
+///
+/// The binary targets the Windows subsystem so the window opens without a console window, which
+/// leaves the process without standard streams. Attaching to the console of the parent process
+/// gives them back. Started without one, from the Explorer for instance, nothing changes.
+#[cfg(target_os = "windows")]
+pub fn attach_parent_console() {
+    use windows_sys::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+
+    // SAFETY: AttachConsole only takes a process identifier and fails without side effects
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+/// Lets the headless mode print to the console it was started from.
+///
+/// Only needed on Windows, elsewhere the process always inherits the standard streams.
+#[cfg(not(target_os = "windows"))]
+pub const fn attach_parent_console() {}
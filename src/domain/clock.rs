@@ -0,0 +1,16 @@
+use chrono::{Local, NaiveDateTime};
+
+/// Source of the current local time, injected where time-dependent data is recorded.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> NaiveDateTime;
+}
+
+/// Wall clock reading the local time of the machine.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> NaiveDateTime {
+        Local::now().naive_local()
+    }
+}
@@ -0,0 +1,51 @@
+use crate::domain::model::mounted_disk::MountedDisk;
+use std::path::Path;
+
+/// Source of the disks currently mounted, injected where disks are offered.
+pub trait DiskLister: Send + Sync {
+    fn mounted_disks(&self) -> Vec<MountedDisk>;
+}
+
+/// Source of the disk holding a path, injected where directories are picked for indexing.
+pub trait DiskInfoProvider: Send + Sync {
+    /// Disk holding `path`, with its name and space, or `None` if no mounted disk holds it,
+    /// as happens with some network shares or unusual mounts.
+    fn disk_info(&self, path: &Path) -> Option<MountedDisk>;
+}
+
+/// Disk with the deepest mount point containing `path`, among `disks`.
+#[must_use]
+pub fn disk_holding(disks: Vec<MountedDisk>, path: &Path) -> Option<MountedDisk> {
+    disks
+        .into_iter()
+        .filter(|disk| path.starts_with(&disk.mount_point))
+        .max_by_key(|disk| disk.mount_point.components().count())
+}
+
+/// Removable disks currently mounted, sorted by label.
+///
+/// Disks reporting no space, such as a card reader without a card, are left out.
+#[must_use]
+pub fn removable_disks(lister: &impl DiskLister) -> Vec<MountedDisk> {
+    let mut disks: Vec<MountedDisk> = lister
+        .mounted_disks()
+        .into_iter()
+        .filter(|disk| disk.is_removable && disk.total_space > 0)
+        .collect();
+    disks.sort_by(|a, b| {
+        a.label()
+            .cmp(&b.label())
+            .then_with(|| a.mount_point.cmp(&b.mount_point))
+    });
+    disks.dedup_by(|a, b| a.mount_point == b.mount_point);
+    disks
+}
+
+/// Whether a disk is still mounted at `mount_point`.
+#[must_use]
+pub fn is_mounted(lister: &impl DiskLister, mount_point: &Path) -> bool {
+    lister
+        .mounted_disks()
+        .iter()
+        .any(|disk| disk.mount_point == mount_point)
+}
@@ -1 +1,3 @@
+pub mod clock;
+pub mod disk_lister;
 pub mod model;
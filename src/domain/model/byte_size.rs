@@ -0,0 +1,37 @@
+/// Parses a human-readable size such as `500 GB`, `1.5TB`, `256 MiB` or `1024`.
+///
+/// Decimal units (`kB`, `MB`, `GB`, ...) use powers of 1000 and binary units (`KiB`, `MiB`,
+/// `GiB`, ...) powers of 1024, matching the sizes displayed with `humansize`. Units are case
+/// insensitive and a bare number is read as bytes.
+#[must_use]
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+pub fn parse_size(input: &str) -> Option<u64> {
+    let input = input.trim();
+    let unit_start = input
+        .find(|c: char| c.is_ascii_alphabetic())
+        .unwrap_or(input.len());
+    let (number, unit) = input.split_at(unit_start);
+
+    let value: f64 = number.trim().parse().ok()?;
+    let multiplier: u64 = match unit.to_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kb" => 1_000,
+        "m" | "mb" => 1_000_000,
+        "g" | "gb" => 1_000_000_000,
+        "t" | "tb" => 1_000_000_000_000,
+        "p" | "pb" => 1_000_000_000_000_000,
+        "kib" => 1 << 10,
+        "mib" => 1 << 20,
+        "gib" => 1 << 30,
+        "tib" => 1 << 40,
+        "pib" => 1 << 50,
+        _ => return None,
+    };
+
+    let bytes = (value * multiplier as f64).round();
+    (value.is_finite() && value >= 0.0 && bytes <= u64::MAX as f64).then_some(bytes as u64)
+}
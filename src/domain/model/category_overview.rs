@@ -0,0 +1,8 @@
+/// Content of a category across all its drives, as shown on the statistics page.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CategoryOverview {
+    pub name: String,
+    pub drive_count: u64,
+    pub file_count: u64,
+    pub total_size: u64,
+}
@@ -0,0 +1,5 @@
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CategoryStats {
+    pub name: String,
+    pub file_count: u64,
+}
@@ -0,0 +1,14 @@
+/// Size of the database file before and after it was compacted.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct CompactionReport {
+    pub size_before: u64,
+    pub size_after: u64,
+}
+
+impl CompactionReport {
+    /// Space given back to the file system, zero if the file grew.
+    #[must_use]
+    pub const fn freed_space(&self) -> u64 {
+        self.size_before.saturating_sub(self.size_after)
+    }
+}
@@ -1,13 +1,48 @@
-use std::path::PathBuf;
+use crate::domain::model::drive_capacity::DriveCapacity;
+use std::path::{Component, Path, PathBuf};
 
 #[derive(Clone, Debug, Default)]
 pub struct DirectoryData {
     pub drive_name: String,
     pub drive_available_space: u64,
+    pub drive_total_space: u64,
     pub directory: PathBuf,
 }
 
 impl DirectoryData {
+    /// Describes a directory whose disk could not be detected.
+    ///
+    /// The drive is named after the path prefix, such as `Z:` or `\\server\share` on
+    /// Windows, or after the first folder of the path elsewhere, with no known available or
+    /// total space.
+    #[must_use]
+    pub fn without_disk(directory: &Path) -> Self {
+        let drive_name = directory
+            .components()
+            .find_map(|component| match component {
+                Component::Prefix(prefix) => Some(prefix.as_os_str()),
+                Component::Normal(name) => Some(name),
+                Component::RootDir | Component::CurDir | Component::ParentDir => None,
+            })
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        Self {
+            drive_name,
+            drive_available_space: 0,
+            drive_total_space: 0,
+            directory: directory.to_path_buf(),
+        }
+    }
+
+    #[must_use]
+    pub const fn drive_capacity(&self) -> DriveCapacity {
+        DriveCapacity {
+            available_space: self.drive_available_space,
+            total_space: self.drive_total_space,
+        }
+    }
+
     #[must_use]
     pub fn last_folder_name(&self) -> String {
         self.directory
@@ -0,0 +1,33 @@
+use crate::domain::model::file_entry::FileWithMetadata;
+use std::collections::BTreeMap;
+
+/// Files sharing a drive and a parent directory, with their combined size.
+#[derive(Clone, Debug)]
+pub struct DirectoryGroup {
+    pub drive_name: String,
+    pub directory: String,
+    pub total_size: u64,
+    pub files: Vec<FileWithMetadata>,
+}
+
+/// Groups files by drive and parent directory, sorted by drive then directory.
+///
+/// Files keep their relative order within a group.
+#[must_use]
+pub fn group_by_parent_directory(files: &[FileWithMetadata]) -> Vec<DirectoryGroup> {
+    let mut groups: BTreeMap<(String, String), DirectoryGroup> = BTreeMap::new();
+    for file in files {
+        let key = (file.drive_name.clone(), file.parent_directory());
+        let group = groups
+            .entry(key)
+            .or_insert_with_key(|(drive_name, directory)| DirectoryGroup {
+                drive_name: drive_name.clone(),
+                directory: directory.clone(),
+                total_size: 0,
+                files: Vec::new(),
+            });
+        group.total_size += file.size_bytes;
+        group.files.push(file.clone());
+    }
+    groups.into_values().collect()
+}
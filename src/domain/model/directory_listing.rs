@@ -0,0 +1,118 @@
+use crate::domain::model::file_entry::FileEntry;
+use std::collections::BTreeMap;
+use std::iter;
+
+/// Folder directly under the browsed one.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FolderEntry {
+    pub name: String,
+    /// Prefix of the paths of the files under the folder, as stored.
+    pub path: String,
+    /// Number of files anywhere under the folder.
+    pub file_count: u64,
+    pub total_size: u64,
+}
+
+/// Content of an indexed folder, browsed like in a file manager.
+///
+/// Folders are sorted by name and files by path.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DirectoryListing {
+    pub folders: Vec<FolderEntry>,
+    pub files: Vec<FileEntry>,
+}
+
+/// Folder leading to the browsed one, from the top of the drive.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Breadcrumb {
+    pub name: String,
+    pub path: String,
+}
+
+impl DirectoryListing {
+    /// Lists the folders and files directly under `prefix`, an empty prefix being the top of
+    /// the drive.
+    ///
+    /// Both `/` and `\` separate folders, as paths keep the separator of the system they were
+    /// indexed on, and trailing separators of `prefix` are ignored. Paths outside `prefix` are
+    /// skipped, and a path listed twice, as happens when it is indexed under several
+    /// categories, counts once with its last size.
+    #[must_use]
+    pub fn children_of(prefix: &str, files: impl IntoIterator<Item = (String, u64)>) -> Self {
+        let prefix = trim_separators(prefix);
+        let files: BTreeMap<String, u64> = files.into_iter().collect();
+        let mut folders: BTreeMap<String, FolderEntry> = BTreeMap::new();
+        let mut listing = Self::default();
+
+        for (path, size_bytes) in files {
+            let Some(rest) = relative_path(prefix, &path) else {
+                continue;
+            };
+            let Some(name_length) = rest.find(is_separator) else {
+                listing.files.push(FileEntry {
+                    path,
+                    size_bytes,
+                    modified_time: None,
+                    ownership: None,
+                });
+                continue;
+            };
+            let name = &rest[..name_length];
+            let folder = folders
+                .entry(name.to_string())
+                .or_insert_with(|| FolderEntry {
+                    name: name.to_string(),
+                    path: path[..path.len() - rest.len() + name_length].to_string(),
+                    file_count: 0,
+                    total_size: 0,
+                });
+            folder.file_count += 1;
+            folder.total_size += size_bytes;
+        }
+
+        listing.folders = folders.into_values().collect();
+        listing
+    }
+}
+
+/// Splits `prefix` into the folders leading to it, each with its own prefix.
+#[must_use]
+pub fn breadcrumbs(prefix: &str) -> Vec<Breadcrumb> {
+    let prefix = trim_separators(prefix);
+    let mut crumbs = vec![];
+    let mut start = 0;
+    for (index, c) in prefix.char_indices().chain(iter::once((prefix.len(), '/'))) {
+        if is_separator(c) {
+            if index > start {
+                crumbs.push(Breadcrumb {
+                    name: prefix[start..index].to_string(),
+                    path: prefix[..index].to_string(),
+                });
+            }
+            start = index + c.len_utf8();
+        }
+    }
+    crumbs
+}
+
+const fn is_separator(c: char) -> bool {
+    c == '/' || c == '\\'
+}
+
+fn trim_separators(prefix: &str) -> &str {
+    prefix.trim_end_matches(is_separator)
+}
+
+/// Part of `path` under `prefix`, or `None` when the path is not inside it.
+fn relative_path<'a>(prefix: &str, path: &'a str) -> Option<&'a str> {
+    let rest = if prefix.is_empty() {
+        path
+    } else {
+        let rest = path.strip_prefix(prefix)?;
+        if !rest.starts_with(is_separator) {
+            return None;
+        }
+        rest
+    };
+    Some(rest.trim_start_matches(is_separator))
+}
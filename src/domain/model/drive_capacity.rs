@@ -0,0 +1,54 @@
+/// Space of a drive, as reported by its disk when it was indexed.
+///
+/// A `total_space` of 0 means the capacity is unknown, as for drives indexed before it was
+/// recorded or whose disk could not be detected.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct DriveCapacity {
+    pub available_space: u64,
+    pub total_space: u64,
+}
+
+/// How full a drive is, as fractions of its total space between 0 and 1.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DriveFullness {
+    /// Share of the drive taken by any data, indexed or not.
+    pub used_fraction: f32,
+    /// Share of the drive taken by the indexed files, never above the used share.
+    pub indexed_fraction: f32,
+}
+
+impl DriveCapacity {
+    /// Capacity of a drive whose total space is unknown.
+    #[must_use]
+    pub const fn available_only(available_space: u64) -> Self {
+        Self {
+            available_space,
+            total_space: 0,
+        }
+    }
+
+    /// Space taken on the drive, or `None` when the total space is unknown.
+    #[must_use]
+    pub const fn used_space(&self) -> Option<u64> {
+        if self.total_space == 0 {
+            None
+        } else {
+            Some(self.total_space.saturating_sub(self.available_space))
+        }
+    }
+
+    /// Compares `indexed_size` and the used space to the total space of the drive.
+    ///
+    /// Returns `None` when the total space is unknown.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn fullness(&self, indexed_size: u64) -> Option<DriveFullness> {
+        let used_space = self.used_space()?;
+        let total_space = self.total_space as f64;
+
+        Some(DriveFullness {
+            used_fraction: (used_space as f64 / total_space) as f32,
+            indexed_fraction: (indexed_size.min(used_space) as f64 / total_space) as f32,
+        })
+    }
+}
@@ -0,0 +1,71 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+/// File found at the same path on both drives, with a different size on each.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SizeMismatch {
+    pub path: String,
+    pub size_a: u64,
+    pub size_b: u64,
+}
+
+/// Differences between the files of two drives, such as a primary drive and its backup.
+///
+/// Paths are sorted. `in_both` counts the paths present on both drives, size mismatches
+/// included.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DriveDiff {
+    pub only_in_a: Vec<String>,
+    pub only_in_b: Vec<String>,
+    pub size_mismatches: Vec<SizeMismatch>,
+    pub in_both: u64,
+}
+
+impl DriveDiff {
+    /// Compares two lists of paths and sizes.
+    ///
+    /// A path listed twice on the same drive, as happens when it is indexed under several
+    /// categories, counts once with its last size.
+    #[must_use]
+    pub fn between(files_a: Vec<(String, u64)>, files_b: Vec<(String, u64)>) -> Self {
+        let files_a: BTreeMap<String, u64> = files_a.into_iter().collect();
+        let files_b: BTreeMap<String, u64> = files_b.into_iter().collect();
+        let mut diff = Self::default();
+        let mut iter_a = files_a.into_iter().peekable();
+        let mut iter_b = files_b.into_iter().peekable();
+
+        // Both maps iterate in path order, so a single merge pass finds every difference
+        loop {
+            let order = match (iter_a.peek(), iter_b.peek()) {
+                (Some((path_a, _)), Some((path_b, _))) => path_a.cmp(path_b),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => break,
+            };
+            match order {
+                Ordering::Less => diff.only_in_a.extend(iter_a.next().map(|(path, _)| path)),
+                Ordering::Greater => diff.only_in_b.extend(iter_b.next().map(|(path, _)| path)),
+                Ordering::Equal => {
+                    if let (Some((path, size_a)), Some((_, size_b))) =
+                        (iter_a.next(), iter_b.next())
+                    {
+                        diff.in_both += 1;
+                        if size_a != size_b {
+                            diff.size_mismatches.push(SizeMismatch {
+                                path,
+                                size_a,
+                                size_b,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        diff
+    }
+
+    #[must_use]
+    pub fn is_identical(&self) -> bool {
+        self.only_in_a.is_empty() && self.only_in_b.is_empty() && self.size_mismatches.is_empty()
+    }
+}
@@ -0,0 +1,30 @@
+use crate::domain::model::file_entry::FileWithMetadata;
+use std::collections::BTreeMap;
+
+/// Files found on the same drive, with their combined size.
+#[derive(Clone, Debug)]
+pub struct DriveGroup {
+    pub drive_name: String,
+    pub total_size: u64,
+    pub files: Vec<FileWithMetadata>,
+}
+
+/// Groups files by drive, sorted by drive name.
+///
+/// Files keep their relative order within a group, so a sorted dataset stays sorted in each section.
+#[must_use]
+pub fn group_by_drive(files: &[FileWithMetadata]) -> Vec<DriveGroup> {
+    let mut groups: BTreeMap<String, DriveGroup> = BTreeMap::new();
+    for file in files {
+        let group = groups
+            .entry(file.drive_name.clone())
+            .or_insert_with_key(|drive_name| DriveGroup {
+                drive_name: drive_name.clone(),
+                total_size: 0,
+                files: Vec::new(),
+            });
+        group.total_size += file.size_bytes;
+        group.files.push(file.clone());
+    }
+    groups.into_values().collect()
+}
@@ -0,0 +1,8 @@
+use chrono::NaiveDateTime;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DriveInfo {
+    pub name: String,
+    /// Most recent indexing time of the drive across all its categories.
+    pub last_indexed: NaiveDateTime,
+}
@@ -0,0 +1,5 @@
+#[derive(Clone, Debug)]
+pub struct DriveSpace {
+    pub drive_name: String,
+    pub available_space: u64,
+}
@@ -0,0 +1,24 @@
+use crate::domain::model::drive_capacity::DriveCapacity;
+use chrono::NaiveDateTime;
+
+#[derive(Clone, Debug)]
+pub struct DriveStats {
+    pub drive_name: String,
+    pub category_name: String,
+    pub file_count: u64,
+    pub total_size: u64,
+    pub available_space: u64,
+    /// Total space of the drive, 0 when unknown.
+    pub total_space: u64,
+    pub last_indexed: NaiveDateTime,
+}
+
+impl DriveStats {
+    #[must_use]
+    pub const fn capacity(&self) -> DriveCapacity {
+        DriveCapacity {
+            available_space: self.available_space,
+            total_space: self.total_space,
+        }
+    }
+}
@@ -0,0 +1,13 @@
+/// Turns a typed label into the tag stored for a drive, such as `offsite` or `to-verify`.
+///
+/// Tags are trimmed and lowercased, with inner runs of spaces collapsed to one, so the same
+/// label typed twice is not stored twice. Returns `None` when nothing is left.
+#[must_use]
+pub fn normalize_tag(input: &str) -> Option<String> {
+    let tag = input
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+    (!tag.is_empty()).then_some(tag)
+}
@@ -0,0 +1,29 @@
+use chrono::NaiveDateTime;
+
+/// Time elapsed since an event, truncated to its largest whole unit.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ElapsedTime {
+    JustNow,
+    Minutes(i64),
+    Hours(i64),
+    Days(i64),
+}
+
+impl ElapsedTime {
+    /// Humanizes the time between `since` and `now`.
+    ///
+    /// Anything under a minute, including times in the future, counts as just now.
+    #[must_use]
+    pub fn between(since: NaiveDateTime, now: NaiveDateTime) -> Self {
+        let elapsed = now - since;
+        if elapsed.num_days() > 0 {
+            Self::Days(elapsed.num_days())
+        } else if elapsed.num_hours() > 0 {
+            Self::Hours(elapsed.num_hours())
+        } else if elapsed.num_minutes() > 0 {
+            Self::Minutes(elapsed.num_minutes())
+        } else {
+            Self::JustNow
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use chrono::NaiveDateTime;
+
+/// Age of a file since its last modification, truncated to its largest whole unit.
+///
+/// Coarser than [`ElapsedTime`](crate::domain::model::elapsed_time::ElapsedTime), as files
+/// often go untouched for years.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FileAge {
+    Today,
+    Days(i64),
+    Months(i64),
+    Years(i64),
+}
+
+impl FileAge {
+    /// Humanizes the time between `modified` and `now`, with months of 30 days and years of
+    /// 365 days.
+    ///
+    /// Anything under a day, including times in the future, counts as today.
+    #[must_use]
+    pub fn between(modified: NaiveDateTime, now: NaiveDateTime) -> Self {
+        let days = (now - modified).num_days();
+        if days >= 365 {
+            Self::Years(days / 365)
+        } else if days >= 30 {
+            Self::Months(days / 30)
+        } else if days > 0 {
+            Self::Days(days)
+        } else {
+            Self::Today
+        }
+    }
+}
@@ -1,13 +1,40 @@
+use crate::domain::model::file_ownership::FileOwnership;
 use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
 use std::path::Path;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct FileEntry {
     pub path: String,
     pub size_bytes: u64,
+    /// Last modification time, when the file system could tell it.
+    pub modified_time: Option<NaiveDateTime>,
+    /// Owner and permissions, only recorded on Unix.
+    pub ownership: Option<FileOwnership>,
 }
 
-#[derive(Clone, Debug)]
+impl FileEntry {
+    #[must_use]
+    pub fn filename(&self) -> String {
+        Path::new(&self.path)
+            .file_name()
+            .map(|f| f.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+
+    #[must_use]
+    pub fn extension(&self) -> String {
+        Path::new(&self.path)
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default()
+    }
+}
+
+/// Indexed file along with its drive, as shown in search results.
+///
+/// Serializes with the insertion time in ISO 8601.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct FileWithMetadata {
     pub category_name: String,
     pub drive_name: String,
@@ -15,6 +42,7 @@ pub struct FileWithMetadata {
     pub drive_insertion_time: NaiveDateTime,
     pub path: String,
     pub size_bytes: u64,
+    pub modified_time: Option<NaiveDateTime>,
 }
 
 impl FileWithMetadata {
@@ -0,0 +1,43 @@
+use std::path::Path;
+
+/// Broad type of a file, guessed from its extension to make results easier to scan.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum FileKind {
+    Image,
+    Video,
+    Audio,
+    Document,
+    Code,
+    Archive,
+    Other,
+}
+
+impl FileKind {
+    /// Classifies a filename by its last extension, ignoring case.
+    ///
+    /// Hidden files such as `.bashrc` have no extension and are [`Other`](Self::Other), while
+    /// `backup.tar.gz` is classified by `gz`.
+    #[must_use]
+    pub fn from_filename(filename: &str) -> Self {
+        let Some(extension) = Path::new(filename).extension() else {
+            return Self::Other;
+        };
+        match extension.to_string_lossy().to_lowercase().as_str() {
+            "jpg" | "jpeg" | "png" | "gif" | "bmp" | "webp" | "svg" | "tif" | "tiff" | "heic"
+            | "raw" | "cr2" | "nef" | "ico" | "psd" => Self::Image,
+            "mp4" | "mkv" | "avi" | "mov" | "wmv" | "webm" | "m4v" | "flv" | "mpg" | "mpeg" => {
+                Self::Video
+            }
+            "mp3" | "flac" | "wav" | "ogg" | "m4a" | "aac" | "opus" | "wma" | "aiff" => Self::Audio,
+            "pdf" | "doc" | "docx" | "odt" | "rtf" | "txt" | "md" | "xls" | "xlsx" | "ods"
+            | "csv" | "ppt" | "pptx" | "odp" | "epub" => Self::Document,
+            "rs" | "py" | "js" | "jsx" | "ts" | "tsx" | "java" | "c" | "h" | "cpp" | "hpp"
+            | "cs" | "go" | "rb" | "php" | "sh" | "html" | "css" | "json" | "toml" | "yaml"
+            | "yml" | "xml" | "sql" => Self::Code,
+            "zip" | "rar" | "7z" | "tar" | "gz" | "tgz" | "bz2" | "xz" | "zst" | "iso" | "dmg" => {
+                Self::Archive
+            }
+            _ => Self::Other,
+        }
+    }
+}
@@ -0,0 +1,33 @@
+/// Owner, group and permission bits of a file, as Unix file systems record them.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FileOwnership {
+    pub uid: u32,
+    pub gid: u32,
+    /// File type and permission bits, as in `st_mode`.
+    pub mode: u32,
+}
+
+/// Quick filter keeping only the files with risky permission bits, for auditing.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum PermissionFilter {
+    /// Anyone may modify the file.
+    WorldWritable,
+    /// Someone may run the file.
+    Executable,
+    /// The file runs with the rights of its owner or group.
+    SetId,
+}
+
+impl PermissionFilter {
+    pub const ALL: [Self; 3] = [Self::WorldWritable, Self::Executable, Self::SetId];
+
+    /// Permission bits of which at least one must be set for a file to match.
+    #[must_use]
+    pub const fn mask(self) -> u32 {
+        match self {
+            Self::WorldWritable => 0o002,
+            Self::Executable => 0o111,
+            Self::SetId => 0o6000,
+        }
+    }
+}
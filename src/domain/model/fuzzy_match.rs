@@ -0,0 +1,25 @@
+use crate::domain::model::file_entry::FileWithMetadata;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+use std::cmp::Reverse;
+
+/// Keeps the files whose filename fuzzily matches the query, best matches first, so that a
+/// misremembered or misspelled name still finds the file.
+///
+/// Case is ignored, and every letter of the query must appear in order in the filename.
+/// Files with the same score stay in their original order.
+#[must_use]
+pub fn rank_by_filename(files: Vec<FileWithMetadata>, query: &str) -> Vec<FileWithMetadata> {
+    let matcher = SkimMatcherV2::default().ignore_case();
+    let query = query.trim();
+    let mut scored: Vec<(i64, FileWithMetadata)> = files
+        .into_iter()
+        .filter_map(|file| {
+            matcher
+                .fuzzy_match(&file.filename(), query)
+                .map(|score| (score, file))
+        })
+        .collect();
+    scored.sort_by_key(|(score, _)| Reverse(*score));
+    scored.into_iter().map(|(_, file)| file).collect()
+}
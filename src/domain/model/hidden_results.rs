@@ -0,0 +1,61 @@
+use crate::domain::model::file_entry::FileWithMetadata;
+use std::collections::HashSet;
+
+/// Identifies a result row across pages and groupings.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ResultKey {
+    pub category_name: String,
+    pub drive_name: String,
+    pub path: String,
+}
+
+impl From<&FileWithMetadata> for ResultKey {
+    fn from(file: &FileWithMetadata) -> Self {
+        Self {
+            category_name: file.category_name.clone(),
+            drive_name: file.drive_name.clone(),
+            path: file.path.clone(),
+        }
+    }
+}
+
+/// Results the user chose to hide from the current search.
+///
+/// Lives on the client only: the database is untouched and nothing is persisted.
+#[derive(Debug, Default)]
+pub struct HiddenResults {
+    keys: HashSet<ResultKey>,
+}
+
+impl HiddenResults {
+    pub fn hide(&mut self, key: ResultKey) {
+        self.keys.insert(key);
+    }
+
+    #[must_use]
+    pub fn is_hidden(&self, file: &FileWithMetadata) -> bool {
+        !self.keys.is_empty() && self.keys.contains(&ResultKey::from(file))
+    }
+
+    /// Keeps the files that are not hidden, in their original order.
+    pub fn visible<'a>(
+        &'a self,
+        files: &'a [FileWithMetadata],
+    ) -> impl Iterator<Item = &'a FileWithMetadata> {
+        files.iter().filter(|file| !self.is_hidden(file))
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.keys.clear();
+    }
+}
@@ -0,0 +1,60 @@
+use crate::domain::model::stored_path::decode_path;
+use std::path::{Path, PathBuf};
+
+/// Extensions of the images a preview can be drawn for, in lowercase.
+const PREVIEWABLE_EXTENSIONS: [&str; 9] = [
+    "bmp", "gif", "ico", "jpeg", "jpg", "png", "tif", "tiff", "webp",
+];
+
+/// Whether a preview can be drawn for the file at this stored path, judging by its extension.
+///
+/// Raw camera files, `heic`, `psd` and other images that cannot be decoded are left out.
+#[must_use]
+pub fn is_previewable(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase())
+        .is_some_and(|extension| PREVIEWABLE_EXTENSIONS.contains(&extension.as_str()))
+}
+
+/// Where the image of a result can be read from, decided before reading it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PreviewSource {
+    /// Not an image a preview can be drawn for.
+    NotAnImage,
+    /// The drive was indexed without its location, so the file cannot be found.
+    NoLocation,
+    /// The file is not where it was indexed, usually because its drive is unplugged.
+    Missing(PathBuf),
+    /// The file can be read from this path.
+    Available(PathBuf),
+}
+
+impl PreviewSource {
+    /// Locates the file stored at `path` under `drive_root`, using `exists` to check the disk.
+    #[must_use]
+    pub fn locate(
+        path: &str,
+        drive_root: Option<&Path>,
+        exists: impl FnOnce(&Path) -> bool,
+    ) -> Self {
+        if !is_previewable(path) {
+            return Self::NotAnImage;
+        }
+        let Some(root) = drive_root else {
+            return Self::NoLocation;
+        };
+        let full_path = root.join(decode_path(path));
+        if exists(&full_path) {
+            Self::Available(full_path)
+        } else {
+            Self::Missing(full_path)
+        }
+    }
+
+    /// Whether a placeholder stands in for an image that cannot be read.
+    #[must_use]
+    pub const fn shows_placeholder(&self) -> bool {
+        matches!(self, Self::NoLocation | Self::Missing(_))
+    }
+}
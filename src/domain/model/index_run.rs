@@ -0,0 +1,14 @@
+use chrono::NaiveDateTime;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Successful indexing of one or more directories into a drive, kept as an audit trail.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IndexRun {
+    pub indexed_at: NaiveDateTime,
+    pub directories: Vec<PathBuf>,
+    pub category: String,
+    pub drive: String,
+    pub file_count: u64,
+    pub duration: Duration,
+}
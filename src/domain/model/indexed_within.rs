@@ -0,0 +1,29 @@
+use chrono::{Days, NaiveDateTime};
+
+/// Quick filter keeping only the drives indexed during a recent period.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum IndexedWithin {
+    Week,
+    Month,
+    Quarter,
+}
+
+impl IndexedWithin {
+    pub const ALL: [Self; 3] = [Self::Week, Self::Month, Self::Quarter];
+
+    #[must_use]
+    pub const fn days(self) -> u64 {
+        match self {
+            Self::Week => 7,
+            Self::Month => 30,
+            Self::Quarter => 90,
+        }
+    }
+
+    /// Returns the earliest indexing time covered by the period, counted back from `now`.
+    #[must_use]
+    pub fn since(self, now: NaiveDateTime) -> NaiveDateTime {
+        now.checked_sub_days(Days::new(self.days()))
+            .unwrap_or(NaiveDateTime::MIN)
+    }
+}
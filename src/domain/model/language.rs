@@ -1,34 +1,44 @@
 use std::fmt::{Display, Formatter};
 
-#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
 pub enum Language {
     #[default]
     English,
     French,
+    German,
+    Spanish,
 }
 
 impl Language {
+    pub const ALL: [Self; 4] = [Self::English, Self::French, Self::German, Self::Spanish];
+
     #[must_use]
     pub fn new(code: &str) -> Self {
-        match code.to_lowercase().as_str() {
-            "fr" => Self::French,
-            _ => Self::English,
-        }
+        let code = code.to_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|language| language.code() == code)
+            .unwrap_or_default()
     }
 
+    /// Maps a BCP 47 or POSIX locale (`fr-FR`, `de_AT.UTF-8`, `es`) to a supported language,
+    /// falling back to [`Language::English`].
     #[must_use]
-    pub const fn code(&self) -> &str {
-        match self {
-            Self::English => "en",
-            Self::French => "fr",
-        }
+    pub fn from_locale(locale: &str) -> Self {
+        let code = locale
+            .split(['-', '_', '.', '@'])
+            .next()
+            .unwrap_or_default();
+        Self::new(code)
     }
 
     #[must_use]
-    pub const fn toggle(&self) -> Self {
+    pub const fn code(&self) -> &'static str {
         match self {
-            Self::English => Self::French,
-            Self::French => Self::English,
+            Self::English => "en",
+            Self::French => "fr",
+            Self::German => "de",
+            Self::Spanish => "es",
         }
     }
 }
@@ -37,8 +47,10 @@ impl Display for Language {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         Display::fmt(
             match self {
-                Self::English => "EN",
-                Self::French => "FR",
+                Self::English => "English",
+                Self::French => "Français",
+                Self::German => "Deutsch",
+                Self::Spanish => "Español",
             },
             f,
         )
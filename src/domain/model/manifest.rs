@@ -0,0 +1,61 @@
+use crate::domain::model::file_entry::FileWithMetadata;
+use humansize::{format_size, DECIMAL};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Indentation added for each folder level of the listing.
+const INDENT: &str = "  ";
+
+/// Writes a plain text manifest of the files of a drive, to print or keep next to the disk.
+///
+/// A header gives the drive name, the number of files, their total size and when the drive
+/// was last indexed. The files follow as a tree, folders and files sorted by path, each file
+/// with its size. Both `/` and `\` separate folders, and a path listed twice, as happens when
+/// the drive is indexed under several categories, counts once with its last size.
+#[must_use]
+pub fn format_manifest(drive_name: &str, files: &[FileWithMetadata]) -> String {
+    let sizes: BTreeMap<Vec<&str>, u64> = files
+        .iter()
+        .map(|file| (path_components(&file.path), file.size_bytes))
+        .collect();
+    let total_size: u64 = sizes.values().sum();
+    let indexed_at = files.iter().map(|file| file.drive_insertion_time).max();
+
+    let mut manifest = String::new();
+    let _ = writeln!(manifest, "Drive: {drive_name}");
+    let _ = writeln!(manifest, "Files: {}", sizes.len());
+    let _ = writeln!(manifest, "Total size: {}", format_size(total_size, DECIMAL));
+    if let Some(indexed_at) = indexed_at {
+        let _ = writeln!(manifest, "Indexed: {}", indexed_at.format("%Y-%m-%d %H:%M"));
+    }
+    manifest.push('\n');
+
+    let mut current_folders: &[&str] = &[];
+    for (components, size_bytes) in &sizes {
+        let Some((name, folders)) = components.split_last() else {
+            continue;
+        };
+        let shared = current_folders
+            .iter()
+            .zip(folders)
+            .take_while(|(current, folder)| current == folder)
+            .count();
+        for (depth, folder) in folders.iter().enumerate().skip(shared) {
+            let _ = writeln!(manifest, "{}{folder}/", INDENT.repeat(depth));
+        }
+        let _ = writeln!(
+            manifest,
+            "{}{name} ({})",
+            INDENT.repeat(folders.len()),
+            format_size(*size_bytes, DECIMAL)
+        );
+        current_folders = folders;
+    }
+    manifest
+}
+
+fn path_components(path: &str) -> Vec<&str> {
+    path.split(['/', '\\'])
+        .filter(|component| !component.is_empty())
+        .collect()
+}
@@ -1,4 +1,43 @@
+pub mod byte_size;
+pub mod category_overview;
+pub mod category_stats;
+pub mod compaction_report;
 pub mod directory;
+pub mod directory_group;
+pub mod directory_listing;
+pub mod drive_capacity;
+pub mod drive_diff;
+pub mod drive_group;
+pub mod drive_info;
+pub mod drive_space;
+pub mod drive_stats;
+pub mod drive_tag;
+pub mod elapsed_time;
+pub mod file_age;
 pub mod file_entry;
+pub mod file_kind;
+pub mod file_ownership;
+pub mod fuzzy_match;
+pub mod hidden_results;
+pub mod image_preview;
+pub mod index_run;
+pub mod indexed_within;
 pub mod language;
+pub mod manifest;
+pub mod mounted_disk;
 pub mod pagination;
+pub mod path_tree;
+pub mod result_columns;
+pub mod result_table;
+pub mod row_density;
+pub mod row_selection;
+pub mod saved_search;
+pub mod scan_diff;
+pub mod scan_summary;
+pub mod search_constraint;
+pub mod search_filters;
+pub mod search_history;
+pub mod search_status;
+pub mod search_terms;
+pub mod sort_key;
+pub mod stored_path;
@@ -0,0 +1,36 @@
+use crate::domain::model::directory::DirectoryData;
+use std::path::PathBuf;
+
+/// Disk mounted on the machine, as listed by the system.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MountedDisk {
+    pub name: String,
+    pub mount_point: PathBuf,
+    pub available_space: u64,
+    pub total_space: u64,
+    /// Whether the disk can be unplugged, such as a USB stick or a memory card.
+    pub is_removable: bool,
+}
+
+impl MountedDisk {
+    /// Name to show and index the disk under, its mount point when it has no label.
+    #[must_use]
+    pub fn label(&self) -> String {
+        if self.name.is_empty() {
+            self.mount_point.display().to_string()
+        } else {
+            self.name.clone()
+        }
+    }
+
+    /// Describes the whole disk as the directory to index.
+    #[must_use]
+    pub fn directory_data(&self) -> DirectoryData {
+        DirectoryData {
+            drive_name: self.label(),
+            drive_available_space: self.available_space,
+            drive_total_space: self.total_space,
+            directory: self.mount_point.clone(),
+        }
+    }
+}
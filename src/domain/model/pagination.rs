@@ -1,7 +1,72 @@
 use crate::domain::model::file_entry::FileWithMetadata;
+use crate::domain::model::search_constraint::LiftedConstraint;
 
 #[derive(Clone, Debug)]
 pub struct PaginatedResult {
     pub items: Vec<FileWithMetadata>,
     pub total_count: u64,
+    /// Combined size of every matching file, not only the ones of this page.
+    pub total_size: u64,
+    /// Set when the searched drive is no longer indexed, as opposed to having no matches.
+    pub is_drive_missing: bool,
+    /// Set when fuzzy matching was asked for more files than are ranked in memory.
+    pub exceeds_fuzzy_limit: bool,
+    /// Constraints whose removal would bring results back, only looked for when there are none.
+    pub lifted_constraints: Vec<LiftedConstraint>,
+}
+
+/// Page sizes offered in the pagination bar.
+pub const PAGE_SIZES: [usize; 5] = [25, 50, 100, 250, 500];
+/// Page size used until another one is picked.
+pub const DEFAULT_PAGE_SIZE: usize = 100;
+
+/// Returns the number of pages needed to show `total_count` items.
+///
+/// An empty dataset has zero pages, so the displayed page count and the
+/// navigable range always agree.
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub const fn total_pages(total_count: u64, items_per_page: usize) -> usize {
+    if items_per_page == 0 {
+        return 0;
+    }
+    total_count.div_ceil(items_per_page as u64) as usize
+}
+
+/// Returns `page_index` while it still exists for `total_count` items, or the last page.
+///
+/// Results removed while a later page is shown would otherwise leave it blank, with no
+/// page to go back to.
+#[must_use]
+pub const fn clamp_page_index(page_index: usize, total_count: u64, items_per_page: usize) -> usize {
+    let last_page_index = total_pages(total_count, items_per_page).saturating_sub(1);
+    if page_index > last_page_index {
+        last_page_index
+    } else {
+        page_index
+    }
+}
+
+/// Page number typed by the user, once checked against the available pages.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PageInput {
+    /// Nothing was typed, so there is nothing to do.
+    Empty,
+    /// Zero-based index of an existing page.
+    Page(usize),
+    /// Not a number, or outside `1..=total_pages`.
+    Invalid,
+}
+
+/// Parses a one-based page number typed by the user.
+#[must_use]
+pub fn parse_page_input(input: &str, total_pages: usize) -> PageInput {
+    let input = input.trim();
+    if input.is_empty() {
+        return PageInput::Empty;
+    }
+    match input.parse::<usize>() {
+        Ok(page) if (1..=total_pages).contains(&page) => PageInput::Page(page - 1),
+        _ => PageInput::Invalid,
+    }
 }
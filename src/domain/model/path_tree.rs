@@ -0,0 +1,108 @@
+use crate::domain::model::file_entry::FileWithMetadata;
+use std::collections::BTreeMap;
+
+/// Folder of a result tree, or a drive at the top of it.
+///
+/// A chain of folders holding a single folder and no file is collapsed into one node, named
+/// after the whole chain such as `photos/2024`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PathNode {
+    pub drive_name: String,
+    /// Folders from the top of the drive to this node, joined with `/`, empty for a drive.
+    pub path: String,
+    pub name: String,
+    /// Number of files anywhere under the node.
+    pub file_count: u64,
+    pub total_size: u64,
+    /// Sub-folders, sorted by name.
+    pub children: Vec<PathNode>,
+    /// Files directly in the folder, in their order in the results.
+    pub files: Vec<FileWithMetadata>,
+}
+
+/// Builds a tree of the files per drive then per folder, drives being sorted by name.
+///
+/// Both `/` and `\` separate folders, as paths keep the separator of the system they were
+/// indexed on.
+#[must_use]
+pub fn build_path_tree(files: &[FileWithMetadata]) -> Vec<PathNode> {
+    let mut drives: BTreeMap<String, NodeBuilder> = BTreeMap::new();
+    for file in files {
+        let mut segments: Vec<&str> = file
+            .path
+            .split(['/', '\\'])
+            .filter(|segment| !segment.is_empty())
+            .collect();
+        segments.pop();
+
+        let mut node = drives.entry(file.drive_name.clone()).or_default();
+        node.add(file);
+        for segment in segments {
+            node = node.children.entry(segment.to_string()).or_default();
+            node.add(file);
+        }
+        node.files.push(file.clone());
+    }
+
+    drives
+        .into_iter()
+        .map(|(drive_name, builder)| {
+            let mut drive = builder.build(&drive_name, String::new(), drive_name.clone());
+            // The drive itself stays at the top, only its folders are collapsed
+            drive.children = drive.children.into_iter().map(collapse).collect();
+            drive
+        })
+        .collect()
+}
+
+#[derive(Default)]
+struct NodeBuilder {
+    file_count: u64,
+    total_size: u64,
+    children: BTreeMap<String, NodeBuilder>,
+    files: Vec<FileWithMetadata>,
+}
+
+impl NodeBuilder {
+    fn add(&mut self, file: &FileWithMetadata) {
+        self.file_count += 1;
+        self.total_size += file.size_bytes;
+    }
+
+    fn build(self, drive_name: &str, path: String, name: String) -> PathNode {
+        let children = self
+            .children
+            .into_iter()
+            .map(|(child_name, child)| {
+                let child_path = if path.is_empty() {
+                    child_name.clone()
+                } else {
+                    format!("{path}/{child_name}")
+                };
+                child.build(drive_name, child_path, child_name)
+            })
+            .collect();
+        PathNode {
+            drive_name: drive_name.to_string(),
+            path,
+            name,
+            file_count: self.file_count,
+            total_size: self.total_size,
+            children,
+            files: self.files,
+        }
+    }
+}
+
+/// Merges a folder with its only sub-folder while it has no file of its own.
+fn collapse(mut node: PathNode) -> PathNode {
+    while node.files.is_empty() && node.children.len() == 1 {
+        let child = node.children.remove(0);
+        node.name = format!("{}/{}", node.name, child.name);
+        node.path = child.path;
+        node.children = child.children;
+        node.files = child.files;
+    }
+    node.children = node.children.into_iter().map(collapse).collect();
+    node
+}
@@ -0,0 +1,108 @@
+/// Column of the result list, in display order.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum ResultColumn {
+    Category,
+    Drive,
+    AvailableSpace,
+    Indexed,
+    Directory,
+    /// Always shown, so a row never ends up empty.
+    Filename,
+    Size,
+    Age,
+}
+
+impl ResultColumn {
+    pub const ALL: [Self; 8] = [
+        Self::Category,
+        Self::Drive,
+        Self::AvailableSpace,
+        Self::Indexed,
+        Self::Directory,
+        Self::Filename,
+        Self::Size,
+        Self::Age,
+    ];
+
+    #[must_use]
+    pub const fn code(self) -> &'static str {
+        match self {
+            Self::Category => "category",
+            Self::Drive => "drive",
+            Self::AvailableSpace => "available_space",
+            Self::Indexed => "indexed",
+            Self::Directory => "directory",
+            Self::Filename => "filename",
+            Self::Size => "size",
+            Self::Age => "age",
+        }
+    }
+
+    /// Share of the row width taken by the column, relative to the other shown columns.
+    #[must_use]
+    pub const fn portion(self) -> u16 {
+        match self {
+            Self::Category | Self::AvailableSpace | Self::Size | Self::Age => 1,
+            Self::Drive | Self::Indexed => 2,
+            Self::Directory => 3,
+            Self::Filename => 4,
+        }
+    }
+}
+
+/// Columns of the result list the user chose to show.
+///
+/// Only the hidden columns are stored, so columns added later show up by default.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ResultColumns {
+    hidden: Vec<ResultColumn>,
+}
+
+impl ResultColumns {
+    /// Reads the comma separated codes of the hidden columns, skipping unknown ones.
+    #[must_use]
+    pub fn from_setting(setting: &str) -> Self {
+        let mut columns = Self::default();
+        for code in setting.split(',') {
+            if let Some(column) = ResultColumn::ALL
+                .into_iter()
+                .find(|column| column.code() == code.trim())
+            {
+                columns.set_shown(column, false);
+            }
+        }
+        columns
+    }
+
+    /// Writes the codes of the hidden columns, comma separated.
+    #[must_use]
+    pub fn to_setting(&self) -> String {
+        self.hidden
+            .iter()
+            .map(|column| column.code())
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    #[must_use]
+    pub fn is_shown(&self, column: ResultColumn) -> bool {
+        !self.hidden.contains(&column)
+    }
+
+    /// Shows or hides a column; the filename cannot be hidden.
+    pub fn set_shown(&mut self, column: ResultColumn, is_shown: bool) {
+        if is_shown {
+            self.hidden.retain(|hidden| *hidden != column);
+        } else if column != ResultColumn::Filename && self.is_shown(column) {
+            self.hidden.push(column);
+            self.hidden.sort();
+        }
+    }
+
+    /// Columns to show, in display order.
+    pub fn shown(&self) -> impl Iterator<Item = ResultColumn> + '_ {
+        ResultColumn::ALL
+            .into_iter()
+            .filter(|column| self.is_shown(*column))
+    }
+}
@@ -0,0 +1,77 @@
+use crate::domain::model::file_entry::FileWithMetadata;
+use humansize::{format_size, DECIMAL};
+use std::fmt::Write;
+
+/// Column titles of a table of search results.
+pub const RESULT_TABLE_HEADER: [&str; 4] = ["Drive", "Category", "Size", "Path"];
+
+/// Cells are padded to line up up to this many characters; a longer cell, usually a deep
+/// path, is written as is rather than widening every row of the column.
+const MAX_ALIGNED_WIDTH: usize = 40;
+
+/// Cells of a table of search results, one row per file in [`RESULT_TABLE_HEADER`] order.
+#[must_use]
+pub fn result_table_rows(files: &[FileWithMetadata]) -> Vec<[String; 4]> {
+    files
+        .iter()
+        .map(|file| {
+            [
+                file.drive_name.clone(),
+                file.category_name.clone(),
+                format_size(file.size_bytes, DECIMAL),
+                file.path.clone(),
+            ]
+        })
+        .collect()
+}
+
+/// Writes search results as a GitHub-flavored Markdown table, to paste into an issue or a wiki.
+///
+/// `|` is escaped and line breaks become spaces so an odd file name cannot break the table.
+/// The size column is right-aligned. The path column is never padded.
+#[must_use]
+pub fn format_markdown_table(files: &[FileWithMetadata]) -> String {
+    let header = RESULT_TABLE_HEADER.map(String::from);
+    let rows: Vec<[String; 4]> = result_table_rows(files)
+        .into_iter()
+        .map(|row| row.map(|cell| escape_cell(&cell)))
+        .collect();
+    let widths: Vec<usize> = (0..3)
+        .map(|column| {
+            rows.iter()
+                .chain([&header])
+                .map(|row| row[column].chars().count())
+                .filter(|&width| width <= MAX_ALIGNED_WIDTH)
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let mut table = String::new();
+    write_row(&mut table, &header, &widths);
+    let _ = writeln!(
+        table,
+        "| {} | {} | {}: | --- |",
+        "-".repeat(widths[0].max(3)),
+        "-".repeat(widths[1].max(3)),
+        "-".repeat(widths[2].max(3) - 1),
+    );
+    for row in &rows {
+        write_row(&mut table, row, &widths);
+    }
+    table
+}
+
+fn write_row(table: &mut String, [drive, category, size, path]: &[String; 4], widths: &[usize]) {
+    let _ = writeln!(
+        table,
+        "| {drive:<drive_width$} | {category:<category_width$} | {size:>size_width$} | {path} |",
+        drive_width = widths[0].max(3),
+        category_width = widths[1].max(3),
+        size_width = widths[2].max(3),
+    );
+}
+
+fn escape_cell(cell: &str) -> String {
+    cell.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
@@ -0,0 +1,61 @@
+/// How tightly result rows are packed, trading visible rows for readability.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum RowDensity {
+    /// Small text and thin rows, to see many results on a large screen.
+    Compact,
+    #[default]
+    Comfortable,
+    /// Larger text and taller rows, easier to read on a small screen.
+    Spacious,
+}
+
+impl RowDensity {
+    pub const ALL: [Self; 3] = [Self::Compact, Self::Comfortable, Self::Spacious];
+
+    #[must_use]
+    pub fn new(code: &str) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|density| density.code() == code)
+            .unwrap_or_default()
+    }
+
+    #[must_use]
+    pub const fn code(self) -> &'static str {
+        match self {
+            Self::Compact => "compact",
+            Self::Comfortable => "comfortable",
+            Self::Spacious => "spacious",
+        }
+    }
+
+    /// Padding around the content of a row, in pixels.
+    #[must_use]
+    pub const fn row_padding(self) -> u16 {
+        match self {
+            Self::Compact => 1,
+            Self::Comfortable => 3,
+            Self::Spacious => 6,
+        }
+    }
+
+    /// Size of the text in a row, in pixels.
+    #[must_use]
+    pub const fn text_size(self) -> f32 {
+        match self {
+            Self::Compact => 13.0,
+            Self::Comfortable => 16.0,
+            Self::Spacious => 19.0,
+        }
+    }
+
+    /// Fixed height of a row, so the selected row can be scrolled into view.
+    #[must_use]
+    pub const fn row_height(self) -> f32 {
+        match self {
+            Self::Compact => 20.0,
+            Self::Comfortable => 28.0,
+            Self::Spacious => 38.0,
+        }
+    }
+}
@@ -0,0 +1,36 @@
+/// Moves a row selection by `step`, staying within `0..len`.
+///
+/// Without a selection, moving down starts at the first row and moving up at the last one.
+/// Returns `None` when there are no rows to select.
+#[must_use]
+pub fn move_selection(selected: Option<usize>, step: isize, len: usize) -> Option<usize> {
+    let last = len.checked_sub(1)?;
+    let next = match selected {
+        Some(index) => index.min(last).saturating_add_signed(step),
+        None if step < 0 => last,
+        None => 0,
+    };
+    Some(next.min(last))
+}
+
+/// Returns the scroll offset that brings a row fully into view, or `None` if it already is.
+///
+/// Rows above the viewport are aligned to its top, rows below it to its bottom.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn reveal_offset(
+    row_index: usize,
+    row_height: f32,
+    view_offset: f32,
+    view_height: f32,
+) -> Option<f32> {
+    let row_top = row_index as f32 * row_height;
+    let row_bottom = row_top + row_height;
+    if row_top < view_offset {
+        Some(row_top)
+    } else if row_bottom > view_offset + view_height {
+        Some((row_bottom - view_height).max(0.0))
+    } else {
+        None
+    }
+}
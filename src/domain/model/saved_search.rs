@@ -0,0 +1,10 @@
+/// Search parameters stored under a name so they can be restored later.
+use crate::domain::model::search_filters::SearchFilters;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SavedSearch {
+    pub name: String,
+    pub drive: Option<String>,
+    pub query: String,
+    pub filters: SearchFilters,
+}
@@ -0,0 +1,45 @@
+use crate::domain::model::drive_diff::{DriveDiff, SizeMismatch};
+use crate::domain::model::file_entry::FileEntry;
+
+/// Changes a fresh scan would bring to the files stored for a category and drive.
+///
+/// Paths are sorted. In `resized`, `size_a` is the stored size and `size_b` the scanned one.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ScanDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub resized: Vec<SizeMismatch>,
+    /// Paths found with the same size in both.
+    pub unchanged: u64,
+}
+
+impl ScanDiff {
+    /// Compares the stored files with the scanned ones by path and size.
+    #[must_use]
+    pub fn between(stored: &[FileEntry], scanned: &[FileEntry]) -> Self {
+        let sizes = |files: &[FileEntry]| {
+            files
+                .iter()
+                .map(|file| (file.path.clone(), file.size_bytes))
+                .collect()
+        };
+        let diff = DriveDiff::between(sizes(stored), sizes(scanned));
+        Self {
+            unchanged: diff.in_both - diff.size_mismatches.len() as u64,
+            added: diff.only_in_b,
+            removed: diff.only_in_a,
+            resized: diff.size_mismatches,
+        }
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.resized.is_empty()
+    }
+
+    /// Number of stored paths, all replaced when the scan is committed.
+    #[must_use]
+    pub fn stored_count(&self) -> u64 {
+        self.unchanged + (self.removed.len() + self.resized.len()) as u64
+    }
+}
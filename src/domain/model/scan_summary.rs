@@ -0,0 +1,52 @@
+use crate::domain::model::file_entry::FileEntry;
+use std::path::PathBuf;
+
+/// Available space under which a drive is considered nearly full.
+pub const NEARLY_FULL_SPACE: u64 = 100_000_000;
+/// How many times the available space the scanned files may weigh before it looks suspicious.
+pub const EXCESS_SIZE_FACTOR: u64 = 10;
+
+/// Advisory about a scan that does not fit what is known of its drive.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CapacityAdvice {
+    /// The scanned files weigh far more than the available space, which may be the wrong disk.
+    ExceedsAvailableSpace,
+    /// The drive has almost no space left, so the scan might be incomplete.
+    NearlyFull,
+}
+
+/// Overview of a scanned directory, shown before and after it is indexed.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ScanSummary {
+    pub file_count: usize,
+    pub total_size: u64,
+    /// Entries left out because they could not be read, making the index incomplete.
+    pub skipped: Vec<PathBuf>,
+}
+
+impl ScanSummary {
+    #[must_use]
+    pub fn from_files(files: &[FileEntry]) -> Self {
+        Self {
+            file_count: files.len(),
+            total_size: files.iter().map(|file| file.size_bytes).sum(),
+            skipped: Vec::new(),
+        }
+    }
+
+    /// Compares the scanned size with the available space reported for the drive.
+    ///
+    /// An available space of zero means it is unknown, and never raises an advisory.
+    #[must_use]
+    pub const fn capacity_advice(&self, available_space: u64) -> Option<CapacityAdvice> {
+        if available_space == 0 {
+            None
+        } else if self.total_size > available_space.saturating_mul(EXCESS_SIZE_FACTOR) {
+            Some(CapacityAdvice::ExceedsAvailableSpace)
+        } else if available_space < NEARLY_FULL_SPACE {
+            Some(CapacityAdvice::NearlyFull)
+        } else {
+            None
+        }
+    }
+}
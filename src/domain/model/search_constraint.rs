@@ -0,0 +1,71 @@
+use crate::domain::model::search_filters::SearchFilters;
+
+/// Part of a search that narrows its results, and can be lifted on its own.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SearchConstraint {
+    Drive,
+    Query,
+    Extension,
+    FilenameOnly,
+    WholeSegments,
+    IndexedSince,
+    Tag,
+    Permission,
+}
+
+/// Number of results a search would have without one of its constraints.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct LiftedConstraint {
+    pub constraint: SearchConstraint,
+    pub result_count: u64,
+}
+
+impl SearchConstraint {
+    /// Lists the constraints set on a search, in the order they are suggested.
+    ///
+    /// Matching file names or whole segments only narrows a search that has a query.
+    #[must_use]
+    pub fn active(
+        selected_drive: Option<&str>,
+        query: Option<&str>,
+        filters: &SearchFilters,
+    ) -> Vec<Self> {
+        let has_query = query.is_some_and(|query| !query.trim().is_empty());
+        [
+            (Self::Drive, selected_drive.is_some()),
+            (Self::Query, has_query),
+            (Self::Extension, filters.extension.is_some()),
+            (Self::FilenameOnly, has_query && filters.filename_only),
+            (Self::WholeSegments, has_query && filters.whole_segments),
+            (Self::IndexedSince, filters.indexed_since.is_some()),
+            (Self::Tag, filters.tag.is_some()),
+            (Self::Permission, filters.permission.is_some()),
+        ]
+        .into_iter()
+        .filter_map(|(constraint, is_set)| is_set.then_some(constraint))
+        .collect()
+    }
+
+    /// Returns the search criteria without this constraint, the others being kept.
+    #[must_use]
+    pub fn lift<'a>(
+        self,
+        selected_drive: Option<&'a str>,
+        query: Option<&'a str>,
+        filters: &SearchFilters,
+    ) -> (Option<&'a str>, Option<&'a str>, SearchFilters) {
+        let mut filters = filters.clone();
+        let (mut selected_drive, mut query) = (selected_drive, query);
+        match self {
+            Self::Drive => selected_drive = None,
+            Self::Query => query = None,
+            Self::Extension => filters.extension = None,
+            Self::FilenameOnly => filters.filename_only = false,
+            Self::WholeSegments => filters.whole_segments = false,
+            Self::IndexedSince => filters.indexed_since = None,
+            Self::Tag => filters.tag = None,
+            Self::Permission => filters.permission = None,
+        }
+        (selected_drive, query, filters)
+    }
+}
@@ -0,0 +1,47 @@
+use crate::domain::model::file_ownership::PermissionFilter;
+use crate::domain::model::sort_key::SortKey;
+use chrono::NaiveDateTime;
+
+/// Number of files listed when looking for the largest ones.
+pub const LARGEST_FILES_COUNT: u64 = 100;
+
+/// Criteria narrowing a file search beyond the drive and the query.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct SearchFilters {
+    /// Matches the query against the last path segment instead of the full path.
+    pub filename_only: bool,
+    /// Matches the query against complete path segments only, so `doc` misses `documents`.
+    pub whole_segments: bool,
+    /// Ranks files by how closely their filename matches the query, instead of matching it
+    /// exactly.
+    ///
+    /// Ranking happens in memory, so the query is not sent to the database in this mode.
+    pub fuzzy: bool,
+    /// Keeps only files with this lowercase extension, given without the leading dot.
+    pub extension: Option<String>,
+    /// Keeps only files from drives indexed at or after this time.
+    pub indexed_since: Option<NaiveDateTime>,
+    /// Keeps only files from drives carrying this tag.
+    pub tag: Option<String>,
+    /// Keeps only files with these permission bits, so files indexed without them never match.
+    pub permission: Option<PermissionFilter>,
+    /// Order of the results, which does not change which files match.
+    pub sort: SortKey,
+    /// Keeps only this many results, the first ones in the [`SortKey`] order, on a single page.
+    ///
+    /// Counting still covers every match, so the kept results can be told apart from the total.
+    pub top: Option<u64>,
+}
+
+impl SearchFilters {
+    /// Switches between listing only the [`LARGEST_FILES_COUNT`] largest files, biggest first,
+    /// and listing every result in the default order.
+    pub fn toggle_largest_files(&mut self) {
+        if self.top.take().is_some() {
+            self.sort = SortKey::default();
+        } else {
+            self.top = Some(LARGEST_FILES_COUNT);
+            self.sort = SortKey::Size;
+        }
+    }
+}
@@ -0,0 +1,61 @@
+/// Maximum number of queries kept in the search history.
+pub const SEARCH_HISTORY_LIMIT: usize = 20;
+
+/// Most recent distinct search queries, newest first.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SearchHistory {
+    entries: Vec<String>,
+    capacity: usize,
+}
+
+impl SearchHistory {
+    #[must_use]
+    pub const fn new(capacity: usize) -> Self {
+        Self {
+            entries: Vec::new(),
+            capacity,
+        }
+    }
+
+    /// Builds a history from stored entries, dropping blanks, duplicates and overflow.
+    #[must_use]
+    pub fn from_entries(entries: impl IntoIterator<Item = String>, capacity: usize) -> Self {
+        let mut history = Self::new(capacity);
+        for entry in entries {
+            let query = entry.trim();
+            if !query.is_empty()
+                && history.entries.len() < capacity
+                && !history.entries.iter().any(|existing| existing == query)
+            {
+                history.entries.push(query.to_string());
+            }
+        }
+        history
+    }
+
+    /// Moves `query` to the front, returning `false` when it is blank.
+    pub fn record(&mut self, query: &str) -> bool {
+        let query = query.trim();
+        if query.is_empty() || self.capacity == 0 {
+            return false;
+        }
+        self.entries.retain(|existing| existing != query);
+        self.entries.insert(0, query.to_string());
+        self.entries.truncate(self.capacity);
+        true
+    }
+
+    #[must_use]
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
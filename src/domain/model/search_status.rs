@@ -0,0 +1,72 @@
+use crate::domain::model::file_ownership::PermissionFilter;
+use crate::domain::model::search_filters::SearchFilters;
+
+/// Piece of the summary of the active search, in display order.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StatusPart {
+    Drive(String),
+    AllDrives,
+    Query(String),
+    Extension(String),
+    Tag(String),
+    Permission(PermissionFilter),
+    FilenameOnly,
+    WholeSegments,
+    Fuzzy,
+    Results(u64),
+    /// Only the first `shown` of `total` results are listed.
+    Top {
+        shown: u64,
+        total: u64,
+    },
+    TotalSize(u64),
+}
+
+/// Summarizes the active search: the drive, the query and filters in use, then the number
+/// and combined size of the results.
+///
+/// A blank query and unset filters are left out.
+#[must_use]
+pub fn status_parts(
+    drive: Option<&str>,
+    query: &str,
+    filters: &SearchFilters,
+    total_count: u64,
+    total_size: u64,
+) -> Vec<StatusPart> {
+    let mut parts = vec![drive.map_or(StatusPart::AllDrives, |drive| {
+        StatusPart::Drive(drive.to_string())
+    })];
+    let query = query.trim();
+    if !query.is_empty() {
+        parts.push(StatusPart::Query(query.to_string()));
+    }
+    if let Some(extension) = &filters.extension {
+        parts.push(StatusPart::Extension(extension.clone()));
+    }
+    if let Some(tag) = &filters.tag {
+        parts.push(StatusPart::Tag(tag.clone()));
+    }
+    if let Some(permission) = filters.permission {
+        parts.push(StatusPart::Permission(permission));
+    }
+    if filters.filename_only {
+        parts.push(StatusPart::FilenameOnly);
+    }
+    if filters.whole_segments {
+        parts.push(StatusPart::WholeSegments);
+    }
+    if filters.fuzzy {
+        parts.push(StatusPart::Fuzzy);
+    }
+    parts.push(
+        filters
+            .top
+            .map_or(StatusPart::Results(total_count), |top| StatusPart::Top {
+                shown: top.min(total_count),
+                total: total_count,
+            }),
+    );
+    parts.push(StatusPart::TotalSize(total_size));
+    parts
+}
@@ -0,0 +1,71 @@
+use std::iter;
+
+/// Whitespace-separated terms of a search query.
+///
+/// Every term must be present, in any order, except terms prefixed with `-` which exclude the
+/// paths containing them. Double quotes group words into a phrase matched as written.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SearchTerms {
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+}
+
+impl SearchTerms {
+    /// Splits a query into include and exclude terms. A lone `-` is searched for literally.
+    #[must_use]
+    pub fn parse(query: &str) -> Self {
+        let mut terms = Self::default();
+        let mut chars = query.chars().peekable();
+        while let Some(&first) = chars.peek() {
+            if first.is_whitespace() {
+                chars.next();
+                continue;
+            }
+
+            let mut is_excluded = false;
+            if first == '-' {
+                chars.next();
+                match chars.peek() {
+                    Some(next) if !next.is_whitespace() => is_excluded = true,
+                    _ => {
+                        terms.include.push("-".to_string());
+                        continue;
+                    }
+                }
+            }
+
+            let term: String = if chars.next_if_eq(&'"').is_some() {
+                chars.by_ref().take_while(|&c| c != '"').collect()
+            } else {
+                iter::from_fn(|| chars.next_if(|c| !c.is_whitespace())).collect()
+            };
+            if term.is_empty() {
+                continue;
+            }
+            if is_excluded {
+                terms.exclude.push(term);
+            } else {
+                terms.include.push(term);
+            }
+        }
+        terms
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    /// Checks `text` in memory the way the database matches paths, ignoring case.
+    #[must_use]
+    pub fn matches(&self, text: &str) -> bool {
+        let text = text.to_lowercase();
+        self.include
+            .iter()
+            .all(|term| text.contains(&term.to_lowercase()))
+            && !self
+                .exclude
+                .iter()
+                .any(|term| text.contains(&term.to_lowercase()))
+    }
+}
@@ -0,0 +1,15 @@
+/// Order in which search results are listed.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum SortKey {
+    /// In the order the files were indexed.
+    #[default]
+    Indexed,
+    /// Most recently modified first, files without a known modification time last.
+    Modified,
+    /// Largest first.
+    Size,
+}
+
+impl SortKey {
+    pub const ALL: [Self; 3] = [Self::Indexed, Self::Modified, Self::Size];
+}
@@ -0,0 +1,95 @@
+use std::path::{Path, PathBuf};
+
+/// Turns a path into the text stored in the catalog, without losing any of its bytes.
+///
+/// Paths that are valid Unicode are kept as they are, so they display and search as usual.
+/// On Unix, bytes that are not valid UTF-8 are written as `%XX` escapes instead of being
+/// replaced by `�`, and [`decode_path`] turns them back into the original path.
+/// A literal `%` that would read as an escape is itself written as `%25`.
+#[must_use]
+pub fn encode_path(path: &Path) -> String {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        encode_bytes(path.as_os_str().as_bytes())
+    }
+    #[cfg(not(unix))]
+    {
+        path.to_string_lossy().into_owned()
+    }
+}
+
+/// Rebuilds the path a text from [`encode_path`] was made of.
+#[must_use]
+pub fn decode_path(stored: &str) -> PathBuf {
+    #[cfg(unix)]
+    {
+        use std::ffi::OsString;
+        use std::os::unix::ffi::OsStringExt;
+        PathBuf::from(OsString::from_vec(decode_bytes(stored.as_bytes())))
+    }
+    #[cfg(not(unix))]
+    {
+        PathBuf::from(stored)
+    }
+}
+
+#[cfg(unix)]
+fn encode_bytes(mut bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len());
+    while !bytes.is_empty() {
+        let (valid, invalid) = match std::str::from_utf8(bytes) {
+            Ok(text) => (text, &[][..]),
+            Err(error) => {
+                let (valid, rest) = bytes.split_at(error.valid_up_to());
+                let invalid_len = error.error_len().unwrap_or(rest.len());
+                // The prefix was just checked to be valid
+                (
+                    std::str::from_utf8(valid).unwrap_or_default(),
+                    &rest[..invalid_len],
+                )
+            }
+        };
+        for (index, c) in valid.char_indices() {
+            if c == '%' && escape_at(&valid.as_bytes()[index..]).is_some() {
+                encoded.push_str("%25");
+            } else {
+                encoded.push(c);
+            }
+        }
+        for byte in invalid {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+        bytes = &bytes[valid.len() + invalid.len()..];
+    }
+    encoded
+}
+
+#[cfg(unix)]
+fn decode_bytes(bytes: &[u8]) -> Vec<u8> {
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if let Some(byte) = escape_at(&bytes[index..]) {
+            decoded.push(byte);
+            index += 3;
+        } else {
+            decoded.push(bytes[index]);
+            index += 1;
+        }
+    }
+    decoded
+}
+
+/// Byte escaped at the start of `bytes`, if it starts with one.
+///
+/// Only non-ASCII bytes and `%` itself are escaped, so other `%XX` sequences stay literal.
+#[cfg(unix)]
+fn escape_at(bytes: &[u8]) -> Option<u8> {
+    let [b'%', high, low, ..] = *bytes else {
+        return None;
+    };
+    let digit = |d: u8| char::from(d).to_digit(16);
+    let byte = u8::try_from(digit(high)? * 16 + digit(low)?).ok()?;
+    (byte >= 0x80 || byte == b'%').then_some(byte)
+}
@@ -1,6 +1,21 @@
+use crate::domain::model::category_overview::CategoryOverview;
+use crate::domain::model::category_stats::CategoryStats;
+use crate::domain::model::drive_info::DriveInfo;
+use crate::domain::model::drive_space::DriveSpace;
+use crate::domain::model::drive_stats::DriveStats;
 use crate::domain::model::file_entry::{FileEntry, FileWithMetadata};
+use crate::domain::model::file_ownership::FileOwnership;
+use crate::domain::model::index_run::IndexRun;
+use crate::domain::model::saved_search::SavedSearch;
+use crate::domain::model::search_filters::SearchFilters;
+use crate::domain::model::sort_key::SortKey;
 use crate::infrastructure::database::binary_format::UuidSqlite;
-use crate::infrastructure::database::entities::{FileWithMetadataDto, NewFileEntryDto};
+use crate::infrastructure::database::entities::{
+    CategoryOverviewDto, CategoryStatsDto, DriveInfoDto, DriveSpaceDto, DriveStatsDto,
+    FileWithMetadataDto, IndexRunDto, NewFileEntryDto, SavedSearchDto,
+};
+use std::path::PathBuf;
+use std::time::Duration;
 
 pub trait ToI64 {
     fn to_i64_or_zero(self) -> i64;
@@ -22,6 +37,19 @@ impl ToU64 for i64 {
     }
 }
 
+/// Rebuilds the ownership of a file from its nullable columns, left unset when any is missing.
+pub fn to_ownership(
+    uid: Option<i64>,
+    gid: Option<i64>,
+    mode: Option<i64>,
+) -> Option<FileOwnership> {
+    Some(FileOwnership {
+        uid: u32::try_from(uid?).ok()?,
+        gid: u32::try_from(gid?).ok()?,
+        mode: u32::try_from(mode?).ok()?,
+    })
+}
+
 impl From<FileWithMetadataDto> for FileWithMetadata {
     fn from(dto: FileWithMetadataDto) -> Self {
         Self {
@@ -31,6 +59,59 @@ impl From<FileWithMetadataDto> for FileWithMetadata {
             drive_insertion_time: dto.drive_insertion_time,
             path: dto.path,
             size_bytes: dto.weight.to_u64_or_zero(),
+            modified_time: dto.modified_time,
+        }
+    }
+}
+
+impl From<CategoryStatsDto> for CategoryStats {
+    fn from(dto: CategoryStatsDto) -> Self {
+        Self {
+            name: dto.name,
+            file_count: dto.file_count.to_u64_or_zero(),
+        }
+    }
+}
+
+impl From<CategoryOverviewDto> for CategoryOverview {
+    fn from(dto: CategoryOverviewDto) -> Self {
+        Self {
+            name: dto.name,
+            drive_count: dto.drive_count.to_u64_or_zero(),
+            file_count: dto.file_count.to_u64_or_zero(),
+            total_size: dto.total_size.to_u64_or_zero(),
+        }
+    }
+}
+
+impl From<DriveInfoDto> for DriveInfo {
+    fn from(dto: DriveInfoDto) -> Self {
+        Self {
+            name: dto.name,
+            last_indexed: dto.last_indexed,
+        }
+    }
+}
+
+impl From<DriveSpaceDto> for DriveSpace {
+    fn from(dto: DriveSpaceDto) -> Self {
+        Self {
+            drive_name: dto.drive_name,
+            available_space: dto.available_space.to_u64_or_zero(),
+        }
+    }
+}
+
+impl From<DriveStatsDto> for DriveStats {
+    fn from(dto: DriveStatsDto) -> Self {
+        Self {
+            drive_name: dto.drive_name,
+            category_name: dto.category_name,
+            file_count: dto.file_count.to_u64_or_zero(),
+            total_size: dto.total_size.to_u64_or_zero(),
+            available_space: dto.available_space.to_u64_or_zero(),
+            total_space: dto.total_space.to_u64_or_zero(),
+            last_indexed: dto.last_indexed,
         }
     }
 }
@@ -42,6 +123,80 @@ impl From<(&FileEntry, UuidSqlite)> for NewFileEntryDto {
             drive_id,
             path: file.path.clone(),
             weight: file.size_bytes.to_i64_or_zero(),
+            filename: file.filename(),
+            extension: file.extension(),
+            modified_time: file.modified_time,
+            owner_uid: file.ownership.map(|ownership| i64::from(ownership.uid)),
+            owner_gid: file.ownership.map(|ownership| i64::from(ownership.gid)),
+            mode: file.ownership.map(|ownership| i64::from(ownership.mode)),
+        }
+    }
+}
+
+impl From<SavedSearchDto> for SavedSearch {
+    fn from(dto: SavedSearchDto) -> Self {
+        Self {
+            name: dto.name,
+            drive: dto.drive_name,
+            query: dto.query,
+            filters: SearchFilters {
+                filename_only: dto.filename_only,
+                whole_segments: dto.whole_segments,
+                fuzzy: false,
+                extension: dto.extension,
+                indexed_since: None,
+                tag: None,
+                permission: None,
+                sort: SortKey::default(),
+                top: None,
+            },
+        }
+    }
+}
+
+impl From<&SavedSearch> for SavedSearchDto {
+    fn from(search: &SavedSearch) -> Self {
+        Self {
+            name: search.name.clone(),
+            drive_name: search.drive.clone(),
+            query: search.query.clone(),
+            filename_only: search.filters.filename_only,
+            extension: search.filters.extension.clone(),
+            whole_segments: search.filters.whole_segments,
+        }
+    }
+}
+
+impl From<IndexRunDto> for IndexRun {
+    fn from(dto: IndexRunDto) -> Self {
+        Self {
+            indexed_at: dto.indexed_at,
+            directories: dto.directories.lines().map(PathBuf::from).collect(),
+            category: dto.category_name,
+            drive: dto.drive_name,
+            file_count: dto.file_count.to_u64_or_zero(),
+            duration: Duration::from_millis(dto.duration_ms.to_u64_or_zero()),
+        }
+    }
+}
+
+impl From<(&IndexRun, UuidSqlite)> for IndexRunDto {
+    fn from((run, id): (&IndexRun, UuidSqlite)) -> Self {
+        Self {
+            id,
+            indexed_at: run.indexed_at,
+            directories: run
+                .directories
+                .iter()
+                .map(|directory| directory.to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("\n"),
+            category_name: run.category.clone(),
+            drive_name: run.drive.clone(),
+            file_count: run.file_count.to_i64_or_zero(),
+            duration_ms: u64::try_from(run.duration.as_millis())
+                .unwrap_or(u64::MAX)
+                .to_i64_or_zero(),
         }
     }
 }
@@ -0,0 +1,49 @@
+use crate::infrastructure::database::binary_format::UuidSqlite;
+use crate::infrastructure::database::entities::{
+    NewDriveEntryDto, NewFileCategoryDto, NewFileEntryDto,
+};
+use std::fmt;
+
+/// Rows of drives, with their categories, tags and files, captured before a destructive operation so
+/// that they can be put back afterwards.
+///
+/// Taken by [`ListerRepository::snapshot`](super::repository::ListerRepository::snapshot) and
+/// given back to [`ListerRepository::restore`](super::repository::ListerRepository::restore).
+pub struct DriveSnapshot {
+    pub(super) categories: Vec<NewFileCategoryDto>,
+    pub(super) drives: Vec<NewDriveEntryDto>,
+    /// Drive identifier and tag of each `drive_tags` row.
+    pub(super) tags: Vec<(UuidSqlite, String)>,
+    pub(super) files: Vec<NewFileEntryDto>,
+}
+
+impl DriveSnapshot {
+    /// Number of captured drive entries, one per category the drive is indexed under.
+    #[must_use]
+    pub const fn drive_count(&self) -> usize {
+        self.drives.len()
+    }
+
+    /// Number of captured files.
+    #[must_use]
+    pub const fn file_count(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Whether no drive matched, leaving nothing to put back.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.drives.is_empty()
+    }
+}
+
+impl fmt::Debug for DriveSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DriveSnapshot")
+            .field("categories", &self.categories.len())
+            .field("drives", &self.drives.len())
+            .field("tags", &self.tags.len())
+            .field("files", &self.files.len())
+            .finish()
+    }
+}
@@ -0,0 +1,127 @@
+use crate::infrastructure::database::binary_format::UuidSqlite;
+use crate::infrastructure::database::pool::{InfrastructureError, SqliteRepositoryPool};
+use crate::infrastructure::database::schema::{drive_entries, drive_tags};
+use diesel::prelude::*;
+use std::collections::BTreeMap;
+
+/// Repository for the labels attached to drives, sharing the connection pool of its parent
+/// repository.
+///
+/// Like the available space, tags belong to a drive whatever the categories it is indexed
+/// under: they are stored on each of its entries.
+pub struct DriveTagRepository<'a> {
+    pool: &'a SqliteRepositoryPool,
+}
+
+impl<'a> DriveTagRepository<'a> {
+    #[must_use]
+    /// Creates a new [`DriveTagRepository`] on top of the given pool.
+    pub const fn new(pool: &'a SqliteRepositoryPool) -> Self {
+        Self { pool }
+    }
+
+    /// Attaches a tag to every entry of the given drive, keeping it once if already there.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during the insert operation.
+    pub fn add(&self, drive: &str, tag: &str) -> Result<(), InfrastructureError> {
+        self.pool.execute_in_transaction(|conn| {
+            let drive_ids = drive_entries::table
+                .filter(drive_entries::name.eq(drive))
+                .select(drive_entries::id)
+                .load::<UuidSqlite>(conn)?;
+            for drive_id in drive_ids {
+                diesel::insert_or_ignore_into(drive_tags::table)
+                    .values((drive_tags::drive_id.eq(drive_id), drive_tags::tag.eq(tag)))
+                    .execute(conn)?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Detaches a tag from every entry of the given drive.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during the delete operation.
+    pub fn remove(&self, drive: &str, tag: &str) -> Result<(), InfrastructureError> {
+        self.pool.execute_db_operation(|conn| {
+            diesel::delete(
+                drive_tags::table.filter(drive_tags::tag.eq(tag)).filter(
+                    drive_tags::drive_id.eq_any(
+                        drive_entries::table
+                            .filter(drive_entries::name.eq(drive))
+                            .select(drive_entries::id),
+                    ),
+                ),
+            )
+            .execute(conn)?;
+            Ok(())
+        })
+    }
+
+    /// Retrieves the tags of every tagged drive, keyed by drive name, tags sorted.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during query execution.
+    pub fn find_all(&self) -> Result<BTreeMap<String, Vec<String>>, InfrastructureError> {
+        self.pool.execute_db_operation(|conn| {
+            let rows = drive_tags::table
+                .inner_join(drive_entries::table)
+                .select((drive_entries::name, drive_tags::tag))
+                .distinct()
+                .order((drive_entries::name, drive_tags::tag))
+                .load::<(String, String)>(conn)?;
+
+            let mut tags: BTreeMap<String, Vec<String>> = BTreeMap::new();
+            for (drive, tag) in rows {
+                tags.entry(drive).or_default().push(tag);
+            }
+            Ok(tags)
+        })
+    }
+
+    /// Retrieves every tag in use, sorted.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during query execution.
+    pub fn find_all_tags(&self) -> Result<Vec<String>, InfrastructureError> {
+        self.pool.execute_db_operation(|conn| {
+            Ok(drive_tags::table
+                .select(drive_tags::tag)
+                .distinct()
+                .order(drive_tags::tag)
+                .load::<String>(conn)?)
+        })
+    }
+
+    /// Retrieves the names of the drives carrying the given tag, sorted.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during query execution.
+    pub fn find_drives(&self, tag: &str) -> Result<Vec<String>, InfrastructureError> {
+        self.pool.execute_db_operation(|conn| {
+            Ok(drive_tags::table
+                .inner_join(drive_entries::table)
+                .filter(drive_tags::tag.eq(tag))
+                .select(drive_entries::name)
+                .distinct()
+                .order(drive_entries::name)
+                .load::<String>(conn)?)
+        })
+    }
+}
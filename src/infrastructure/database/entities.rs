@@ -1,5 +1,7 @@
 use crate::infrastructure::database::binary_format::UuidSqlite;
-use crate::infrastructure::database::schema::{drive_entries, file_categories, file_entries};
+use crate::infrastructure::database::schema::{
+    drive_entries, file_categories, file_entries, index_runs, saved_searches,
+};
 use chrono::NaiveDateTime;
 use diesel::{Insertable, Queryable};
 
@@ -11,16 +13,77 @@ pub struct FileWithMetadataDto {
     pub drive_insertion_time: NaiveDateTime,
     pub path: String,
     pub weight: i64,
+    pub modified_time: Option<NaiveDateTime>,
 }
 
-#[derive(Insertable)]
+#[derive(Queryable)]
+pub struct CategoryStatsDto {
+    pub name: String,
+    pub file_count: i64,
+}
+
+#[derive(Queryable)]
+pub struct CategoryOverviewDto {
+    pub name: String,
+    pub drive_count: i64,
+    pub file_count: i64,
+    pub total_size: i64,
+}
+
+#[derive(Queryable)]
+pub struct DriveInfoDto {
+    pub name: String,
+    pub last_indexed: NaiveDateTime,
+}
+
+#[derive(Queryable)]
+pub struct DriveSpaceDto {
+    pub drive_name: String,
+    pub available_space: i64,
+}
+
+#[derive(Queryable)]
+pub struct DriveStatsDto {
+    pub drive_name: String,
+    pub category_name: String,
+    pub file_count: i64,
+    pub total_size: i64,
+    pub available_space: i64,
+    pub total_space: i64,
+    pub last_indexed: NaiveDateTime,
+}
+
+#[derive(Queryable, Insertable)]
+#[diesel(table_name = index_runs)]
+pub struct IndexRunDto {
+    pub id: UuidSqlite,
+    pub indexed_at: NaiveDateTime,
+    pub directories: String,
+    pub category_name: String,
+    pub drive_name: String,
+    pub file_count: i64,
+    pub duration_ms: i64,
+}
+
+#[derive(Queryable, Insertable)]
+#[diesel(table_name = saved_searches)]
+pub struct SavedSearchDto {
+    pub name: String,
+    pub drive_name: Option<String>,
+    pub query: String,
+    pub filename_only: bool,
+    pub extension: Option<String>,
+    pub whole_segments: bool,
+}
+
+#[derive(Queryable, Insertable)]
 #[diesel(table_name = file_categories)]
 pub struct NewFileCategoryDto {
     pub id: UuidSqlite,
     pub name: String,
 }
 
-#[derive(Insertable)]
+#[derive(Clone, Queryable, Insertable)]
 #[diesel(table_name = drive_entries)]
 pub struct NewDriveEntryDto {
     pub id: UuidSqlite,
@@ -28,13 +91,23 @@ pub struct NewDriveEntryDto {
     pub name: String,
     pub available_space: i64,
     pub insertion_time: NaiveDateTime,
+    pub root_path: Option<String>,
+    pub total_space: i64,
 }
 
-#[derive(Insertable)]
-#[diesel(table_name = file_entries)]
+/// Missing values are bound as `NULL` rather than `DEFAULT`, which `SQLite` lacks in a
+/// multi-row insert and would otherwise make Diesel insert files one row at a time.
+#[derive(Queryable, Insertable)]
+#[diesel(table_name = file_entries, treat_none_as_default_value = false)]
 pub struct NewFileEntryDto {
     pub id: UuidSqlite,
     pub drive_id: UuidSqlite,
     pub path: String,
     pub weight: i64,
+    pub filename: String,
+    pub extension: String,
+    pub modified_time: Option<NaiveDateTime>,
+    pub owner_uid: Option<i64>,
+    pub owner_gid: Option<i64>,
+    pub mode: Option<i64>,
 }
@@ -0,0 +1,75 @@
+use crate::domain::clock::Clock;
+use crate::domain::model::index_run::IndexRun;
+use crate::infrastructure::database::binary_format::UuidSqlite;
+use crate::infrastructure::database::entities::IndexRunDto;
+use crate::infrastructure::database::pool::{InfrastructureError, SqliteRepositoryPool};
+use crate::infrastructure::database::schema::index_runs;
+use diesel::prelude::*;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Repository for the history of indexing runs, sharing the connection pool and clock of its
+/// parent repository.
+pub struct IndexRunRepository<'a> {
+    pool: &'a SqliteRepositoryPool,
+    clock: &'a dyn Clock,
+}
+
+impl<'a> IndexRunRepository<'a> {
+    #[must_use]
+    /// Creates a new [`IndexRunRepository`] on top of the given pool, stamping runs with `clock`.
+    pub const fn new(pool: &'a SqliteRepositoryPool, clock: &'a dyn Clock) -> Self {
+        Self { pool, clock }
+    }
+
+    /// Records a successful indexing run, finished now, and returns it.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during the insert operation.
+    pub fn record_run(
+        &self,
+        directories: &[PathBuf],
+        category: &str,
+        drive: &str,
+        file_count: usize,
+        duration: Duration,
+    ) -> Result<IndexRun, InfrastructureError> {
+        let run = IndexRun {
+            indexed_at: self.clock.now(),
+            directories: directories.to_vec(),
+            category: category.to_string(),
+            drive: drive.to_string(),
+            file_count: file_count as u64,
+            duration,
+        };
+        self.pool.execute_db_operation(|conn| {
+            diesel::insert_into(index_runs::table)
+                .values(IndexRunDto::from((&run, UuidSqlite::new())))
+                .execute(conn)?;
+            Ok(())
+        })?;
+        Ok(run)
+    }
+
+    /// Retrieves every recorded run, most recent first.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during query execution.
+    pub fn list_runs(&self) -> Result<Vec<IndexRun>, InfrastructureError> {
+        self.pool.execute_db_operation(|conn| {
+            Ok(index_runs::table
+                // Identifiers grow over time, ordering runs recorded within the same instant
+                .order((index_runs::indexed_at.desc(), index_runs::id.desc()))
+                .load::<IndexRunDto>(conn)?
+                .into_iter()
+                .map(IndexRun::from)
+                .collect())
+        })
+    }
+}
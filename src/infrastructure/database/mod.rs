@@ -1,6 +1,10 @@
 mod binary_format;
 mod conversion;
+pub mod drive_snapshot;
+pub mod drive_tag_repository;
 pub mod entities;
+pub mod index_run_repository;
 pub mod pool;
 pub mod repository;
+pub mod saved_search_repository;
 pub mod schema;
@@ -1,9 +1,31 @@
+use crate::domain::model::compaction_report::CompactionReport;
+use crate::utils::dialogs::popup_warning;
+use crate::utils::localized_error::{io_message_key, LocalizedError};
+use chrono::Local;
 use diesel::prelude::*;
-use diesel::r2d2::{ConnectionManager, Pool, PoolError, PooledConnection};
+use diesel::r2d2::{ConnectionManager, CustomizeConnection, Pool, PoolError, PooledConnection};
+use diesel::result::{DatabaseErrorKind, Error as DieselError};
 use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
 
 pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
 
+/// Database file shared by the window and the headless mode.
+pub const DATABASE_URL: &str = "app.db";
+
+/// Maximum number of connections kept open by default.
+pub const DEFAULT_MAX_POOL_SIZE: u32 = 10;
+/// How long a connection waits by default for a lock held by another one before failing.
+pub const DEFAULT_BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Number of attempts made on an operation that keeps finding the database locked.
+pub const BUSY_MAX_ATTEMPTS: u32 = 5;
+/// Delay before the first retry of a locked operation, doubled on each following retry.
+const BUSY_RETRY_DELAY: Duration = Duration::from_millis(10);
+
 type DieselPool = Pool<ConnectionManager<SqliteConnection>>;
 pub type DieselConnection = PooledConnection<ConnectionManager<SqliteConnection>>;
 
@@ -19,6 +41,124 @@ pub enum InfrastructureError {
     IoError(#[from] std::io::Error),
     #[error("Error deserializing json: {0}")]
     DeserializeError(#[from] serde_json::Error),
+    #[error("Directory scan error: {0}")]
+    Scan(String),
+    #[error("Cannot find the disk for directory {}", .0.display())]
+    DiskNotFound(PathBuf),
+    #[error("The disk mounted at {} was disconnected", .0.display())]
+    DiskUnmounted(PathBuf),
+}
+
+impl InfrastructureError {
+    /// Whether the error comes from another connection holding a lock on the database.
+    ///
+    /// `SQLite` reports these as `SQLITE_BUSY`, which goes away once the other connection is done.
+    #[must_use]
+    pub fn is_database_busy(&self) -> bool {
+        match self {
+            Self::Database(DieselError::DatabaseError(DatabaseErrorKind::Unknown, info)) => {
+                let message = info.message();
+                message.contains("database is locked") || message.contains("database is busy")
+            }
+            _ => false,
+        }
+    }
+
+    /// Translation key of a message telling the user what likely went wrong and what to do.
+    ///
+    /// The message is meant for people, the error itself keeps the raw detail.
+    #[must_use]
+    pub fn message_key(&self) -> &'static str {
+        match self {
+            _ if self.is_database_busy() => "error_database_busy",
+            Self::Database(DieselError::DatabaseError(_, info))
+                if is_unreachable_file(info.message()) =>
+            {
+                "error_database_unavailable"
+            }
+            Self::Database(_) => "error_database",
+            Self::ConnectionPool(_) => "error_database_unavailable",
+            Self::Migration(_) => "error_migration",
+            Self::IoError(error) => io_message_key(error),
+            Self::DeserializeError(_) => "error_corrupted_data",
+            Self::Scan(_) => "error_scan",
+            Self::DiskNotFound(_) => "error_disk_not_found",
+            Self::DiskUnmounted(_) => "error_disk_unmounted",
+        }
+    }
+}
+
+impl LocalizedError for InfrastructureError {
+    fn message_key(&self) -> Option<&'static str> {
+        Some(Self::message_key(self))
+    }
+}
+
+/// Whether `SQLite` failed because the database file went away or cannot be reached anymore,
+/// as happens when the drive holding it is disconnected.
+fn is_unreachable_file(message: &str) -> bool {
+    [
+        "disk I/O error",
+        "unable to open database file",
+        "no such file",
+    ]
+    .iter()
+    .any(|symptom| message.contains(symptom))
+}
+
+/// Sizing of the connection pool and settings applied to each of its connections.
+#[derive(Clone, Copy, Debug)]
+pub struct PoolConfig {
+    /// Maximum number of open connections.
+    pub max_size: u32,
+    /// Number of idle connections kept open, or `None` to keep up to `max_size`.
+    pub min_idle: Option<u32>,
+    /// How long a connection waits for a lock held by another one, through `PRAGMA busy_timeout`.
+    pub busy_timeout: Duration,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            max_size: DEFAULT_MAX_POOL_SIZE,
+            min_idle: None,
+            busy_timeout: DEFAULT_BUSY_TIMEOUT,
+        }
+    }
+}
+
+/// PRAGMAs scoped to a single connection, run on each one the pool opens.
+///
+/// Foreign keys in particular are off on a fresh connection, which would silently skip the
+/// `ON DELETE CASCADE` clauses.
+const CONNECTION_PRAGMAS: [&str; 4] = [
+    "PRAGMA foreign_keys = ON;",
+    "PRAGMA synchronous = NORMAL;",
+    "PRAGMA cache_size = -80000;", // ~80MB cache
+    "PRAGMA temp_store = MEMORY;",
+];
+
+/// Applies the per-connection PRAGMAs to every connection the pool opens.
+#[derive(Debug)]
+struct ConnectionCustomizer {
+    busy_timeout: Duration,
+}
+
+impl CustomizeConnection<SqliteConnection, diesel::r2d2::Error> for ConnectionCustomizer {
+    fn on_acquire(&self, conn: &mut SqliteConnection) -> Result<(), diesel::r2d2::Error> {
+        diesel::sql_query(format!(
+            "PRAGMA busy_timeout = {};",
+            self.busy_timeout.as_millis()
+        ))
+        .execute(conn)
+        .map_err(diesel::r2d2::Error::QueryError)?;
+        for pragma in CONNECTION_PRAGMAS {
+            diesel::sql_query(pragma)
+                .execute(conn)
+                .map_err(diesel::r2d2::Error::QueryError)?;
+        }
+        Ok(())
+    }
 }
 
 /// Core database pool and infrastructure for `SQLite` repositories.
@@ -27,6 +167,7 @@ pub enum InfrastructureError {
 /// and PRAGMA tuning for performance.
 pub struct SqliteRepositoryPool {
     pool: DieselPool,
+    database_path: PathBuf,
 }
 
 impl SqliteRepositoryPool {
@@ -36,6 +177,9 @@ impl SqliteRepositoryPool {
     /// constraints, applies necessary `SQLite` PRAGMA settings, and runs any pending
     /// database migrations before returning a fully initialized repository instance.
     ///
+    /// An existing database is copied next to itself before pending migrations run.
+    /// Failing to make that copy only shows a warning.
+    ///
     /// # Errors
     ///
     /// Returns a [`InfrastructureError`] if:
@@ -51,42 +195,87 @@ impl SqliteRepositoryPool {
     ///
     /// Returns the initialized [`SqliteRepositoryPool`] instance upon success.
     pub fn new(database_url: &str) -> Result<Self, InfrastructureError> {
-        let pool = Self::create_pool(database_url)?;
+        Self::with_config(database_url, PoolConfig::default())
+    }
+
+    /// Creates a new [`SqliteRepositoryPool`] like [`Self::new`], sized and tuned by `config`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Self::new`].
+    pub fn with_config(
+        database_url: &str,
+        config: PoolConfig,
+    ) -> Result<Self, InfrastructureError> {
+        let database_path = PathBuf::from(
+            database_url
+                .strip_prefix("sqlite://")
+                .unwrap_or(database_url),
+        );
+        let is_existing_database = database_path.exists();
+        let pool = Self::create_pool(database_url, config)?;
         let mut conn = pool.get().map_err(InfrastructureError::ConnectionPool)?;
-        Self::enable_foreign_keys(&mut conn)?;
-        Self::apply_pragmas(&mut conn)?;
+        Self::enable_wal(&mut conn)?;
+        if is_existing_database
+            && Self::has_pending_migrations(&mut conn)?
+            && let Err(error) = Self::back_up(&database_path, &mut conn)
+        {
+            popup_warning(format!(
+                "Could not back up the database before updating it: {error}"
+            ));
+        }
         Self::run_migrations(&mut conn)?;
-        Ok(Self { pool })
+        Ok(Self {
+            pool,
+            database_path,
+        })
     }
 
-    fn create_pool(database_url: &str) -> Result<DieselPool, InfrastructureError> {
+    fn create_pool(
+        database_url: &str,
+        config: PoolConfig,
+    ) -> Result<DieselPool, InfrastructureError> {
         let manager = ConnectionManager::<SqliteConnection>::new(database_url);
         Pool::builder()
+            .max_size(config.max_size)
+            .min_idle(config.min_idle)
+            .connection_customizer(Box::new(ConnectionCustomizer {
+                busy_timeout: config.busy_timeout,
+            }))
             .build(manager)
             .map_err(InfrastructureError::ConnectionPool)
     }
 
-    fn enable_foreign_keys(conn: &mut SqliteConnection) -> Result<(), InfrastructureError> {
-        diesel::sql_query("PRAGMA foreign_keys = ON;")
+    /// Switches the database to write-ahead logging, which persists in the file.
+    ///
+    /// The PRAGMAs every connection needs are applied by [`ConnectionCustomizer`]. The
+    /// database is not locked exclusively, so pooled connections can read and write in turn.
+    fn enable_wal(conn: &mut SqliteConnection) -> Result<(), InfrastructureError> {
+        diesel::sql_query("PRAGMA journal_mode = WAL;")
             .execute(conn)
             .map_err(InfrastructureError::Database)?;
         Ok(())
     }
 
-    fn apply_pragmas(conn: &mut SqliteConnection) -> Result<(), InfrastructureError> {
-        let pragmas = [
-            "PRAGMA journal_mode = WAL;",
-            "PRAGMA synchronous = NORMAL;",
-            "PRAGMA cache_size = -80000;", // ~80MB cache
-            "PRAGMA temp_store = MEMORY;",
-            "PRAGMA locking_mode = EXCLUSIVE;",
-        ];
-        for pragma in pragmas {
-            diesel::sql_query(pragma)
-                .execute(conn)
-                .map_err(InfrastructureError::Database)?;
-        }
-        Ok(())
+    fn has_pending_migrations(conn: &mut SqliteConnection) -> Result<bool, InfrastructureError> {
+        conn.has_pending_migration(MIGRATIONS)
+            .map_err(|err| InfrastructureError::Migration(err.to_string()))
+    }
+
+    /// Copies the database file next to itself as `<file>.bak-<timestamp>`, so a failed
+    /// migration can be recovered from.
+    ///
+    /// The write-ahead log is checkpointed first so the copy holds every committed change.
+    fn back_up(
+        database_path: &Path,
+        conn: &mut SqliteConnection,
+    ) -> Result<PathBuf, InfrastructureError> {
+        diesel::sql_query("PRAGMA wal_checkpoint(TRUNCATE);").execute(conn)?;
+        let mut backup_path = database_path.as_os_str().to_owned();
+        backup_path.push(format!(".bak-{}", Local::now().format("%Y%m%d-%H%M%S")));
+        let backup_path = PathBuf::from(backup_path);
+        fs::copy(database_path, &backup_path)?;
+        Ok(backup_path)
     }
 
     fn run_migrations(conn: &mut SqliteConnection) -> Result<(), InfrastructureError> {
@@ -95,6 +284,44 @@ impl SqliteRepositoryPool {
         Ok(())
     }
 
+    /// Rebuilds the database file to give the space of deleted rows back to the file system.
+    ///
+    /// `VACUUM` cannot run inside a transaction, so it runs on a plain pooled connection,
+    /// waiting for other connections to release their locks. The write-ahead log is then
+    /// checkpointed and truncated, since the rebuilt pages first land in it.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during the compaction.
+    /// - An [`IoError`](InfrastructureError::IoError) occurs while reading the size of the database file.
+    pub fn vacuum(&self) -> Result<CompactionReport, InfrastructureError> {
+        let size_before = self.database_size()?;
+        self.execute_db_operation(|conn| {
+            diesel::sql_query("VACUUM;").execute(conn)?;
+            diesel::sql_query("PRAGMA wal_checkpoint(TRUNCATE);").execute(conn)?;
+            diesel::sql_query("PRAGMA shrink_memory;").execute(conn)?;
+            Ok(())
+        })?;
+        Ok(CompactionReport {
+            size_before,
+            size_after: self.database_size()?,
+        })
+    }
+
+    /// Size on disk of the database file and its write-ahead log.
+    fn database_size(&self) -> Result<u64, InfrastructureError> {
+        let mut wal_path = self.database_path.clone().into_os_string();
+        wal_path.push("-wal");
+        let wal_size = match fs::metadata(Path::new(&wal_path)) {
+            Ok(metadata) => metadata.len(),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => 0,
+            Err(error) => return Err(error.into()),
+        };
+        Ok(fs::metadata(&self.database_path)?.len() + wal_size)
+    }
+
     /// Retrieves a single database connection from the internal connection pool.
     ///
     /// This function is typically used internally by repository methods or
@@ -116,12 +343,15 @@ impl SqliteRepositoryPool {
     ///
     /// This function acquires a connection from the pool, executes the provided
     /// closure with it, and automatically handles connection release afterward.
+    /// An operation finding the database locked is retried with a growing delay,
+    /// up to [`BUSY_MAX_ATTEMPTS`] times.
     ///
     /// # Errors
     ///
     /// Returns a [`InfrastructureError`] if:
     /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
-    /// - A [`Database`](InfrastructureError::Database) error occurs during the operation.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during the operation,
+    ///   or the database is still locked after the last attempt.
     ///
     /// # Parameters
     ///
@@ -130,12 +360,14 @@ impl SqliteRepositoryPool {
     /// # Returns
     ///
     /// Returns the result of the provided operation if successful.
-    pub fn execute_db_operation<F, R>(&self, operation: F) -> Result<R, InfrastructureError>
+    pub fn execute_db_operation<F, R>(&self, mut operation: F) -> Result<R, InfrastructureError>
     where
-        F: FnOnce(&mut DieselConnection) -> Result<R, InfrastructureError>,
+        F: FnMut(&mut DieselConnection) -> Result<R, InfrastructureError>,
     {
-        let mut conn = self.get_connection()?;
-        operation(&mut conn)
+        retry_while_busy(|| {
+            let mut conn = self.get_connection()?;
+            operation(&mut conn)
+        })
     }
 
     /// Executes a database operation within an **immediate transaction**.
@@ -146,11 +378,16 @@ impl SqliteRepositoryPool {
     /// This is particularly useful for ensuring atomic updates across multiple
     /// database statements or operations that must succeed or fail as a unit.
     ///
+    /// Starting the transaction is retried like in [`Self::execute_db_operation`] while
+    /// the database is locked. The operation itself runs at most once, since it may
+    /// consume its inputs, so a lock error it returns is not retried.
+    ///
     /// # Errors
     ///
     /// Returns a [`InfrastructureError`] if:
     /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
-    /// - A [`Database`](InfrastructureError::Database) error occurs during the transaction.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during the transaction,
+    ///   or the database is still locked after the last attempt.
     ///
     /// # Parameters
     ///
@@ -163,7 +400,48 @@ impl SqliteRepositoryPool {
     where
         F: FnOnce(&mut SqliteConnection) -> Result<R, InfrastructureError>,
     {
-        let mut conn = self.get_connection()?;
-        conn.immediate_transaction(|conn| operation(conn))
+        let mut operation = Some(operation);
+        let mut attempt = 1;
+        loop {
+            let result = self.get_connection().and_then(|mut conn| {
+                conn.immediate_transaction(|conn| match operation.take() {
+                    Some(operation) => operation(conn),
+                    None => {
+                        unreachable!("the operation only runs in the first started transaction")
+                    }
+                })
+            });
+            match result {
+                Err(error)
+                    if error.is_database_busy()
+                        && operation.is_some()
+                        && attempt < BUSY_MAX_ATTEMPTS =>
+                {
+                    thread::sleep(busy_retry_delay(attempt));
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// Runs `operation` again while it fails because the database is locked.
+fn retry_while_busy<R>(
+    mut operation: impl FnMut() -> Result<R, InfrastructureError>,
+) -> Result<R, InfrastructureError> {
+    let mut attempt = 1;
+    loop {
+        match operation() {
+            Err(error) if error.is_database_busy() && attempt < BUSY_MAX_ATTEMPTS => {
+                thread::sleep(busy_retry_delay(attempt));
+                attempt += 1;
+            }
+            result => return result,
+        }
     }
 }
+
+const fn busy_retry_delay(attempt: u32) -> Duration {
+    BUSY_RETRY_DELAY.saturating_mul(1 << (attempt - 1))
+}
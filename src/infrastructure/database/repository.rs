@@ -1,39 +1,119 @@
+use crate::domain::clock::Clock;
+use crate::domain::model::category_overview::CategoryOverview;
+use crate::domain::model::category_stats::CategoryStats;
+use crate::domain::model::compaction_report::CompactionReport;
+use crate::domain::model::directory_listing::DirectoryListing;
+use crate::domain::model::drive_capacity::DriveCapacity;
+use crate::domain::model::drive_diff::DriveDiff;
+use crate::domain::model::drive_info::DriveInfo;
+use crate::domain::model::drive_space::DriveSpace;
+use crate::domain::model::drive_stats::DriveStats;
 use crate::domain::model::file_entry::{FileEntry, FileWithMetadata};
 use crate::domain::model::language::Language;
+use crate::domain::model::pagination::{DEFAULT_PAGE_SIZE, PAGE_SIZES};
+use crate::domain::model::result_columns::ResultColumns;
+use crate::domain::model::row_density::RowDensity;
+use crate::domain::model::search_constraint::{LiftedConstraint, SearchConstraint};
+use crate::domain::model::search_filters::SearchFilters;
+use crate::domain::model::search_history::{SearchHistory, SEARCH_HISTORY_LIMIT};
+use crate::domain::model::search_terms::SearchTerms;
+use crate::domain::model::sort_key::SortKey;
+use crate::domain::model::stored_path::{decode_path, encode_path};
 use crate::infrastructure::database::binary_format::UuidSqlite;
-use crate::infrastructure::database::conversion::{ToI64, ToU64};
+use crate::infrastructure::database::conversion::{to_ownership, ToI64, ToU64};
+use crate::infrastructure::database::drive_snapshot::DriveSnapshot;
+use crate::infrastructure::database::drive_tag_repository::DriveTagRepository;
 use crate::infrastructure::database::entities::{
+    CategoryOverviewDto, CategoryStatsDto, DriveInfoDto, DriveSpaceDto, DriveStatsDto,
     FileWithMetadataDto, NewDriveEntryDto, NewFileCategoryDto, NewFileEntryDto,
 };
+use crate::infrastructure::database::index_run_repository::IndexRunRepository;
 use crate::infrastructure::database::pool::{InfrastructureError, SqliteRepositoryPool};
+use crate::infrastructure::database::saved_search_repository::SavedSearchRepository;
 use crate::infrastructure::database::schema::{
-    drive_entries, file_categories, file_entries, settings,
+    drive_entries, drive_tags, file_categories, file_entries, settings,
 };
 use crate::infrastructure::i18n::json_translation_loader::load_translations;
 use crate::utils::dialogs::popup_error;
-use chrono::Local;
-use diesel::dsl::{exists, update};
+use chrono::NaiveDateTime;
+use diesel::dsl::{count, exists, sql, update, InnerJoin, IntoBoxed};
+use diesel::expression::SqlLiteral;
 use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Bool, Text, Timestamp};
+use diesel::sqlite::Sqlite;
 use diesel::{QueryDsl, RunQueryDsl, SqliteConnection};
 use rayon::prelude::*;
 use std::collections::HashMap;
+use std::iter;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use sys_locale::get_locale;
+
+/// Number of files inserted per statement.
+///
+/// Each file binds up to ten values, which keeps a statement under the 32766 variables
+/// `SQLite` accepts at most.
+const INSERT_CHUNK_SIZE: usize = 3000;
+
+/// Path, size, modification time, owner, group and mode of a stored file.
+type StoredFile = (
+    String,
+    i64,
+    Option<NaiveDateTime>,
+    Option<i64>,
+    Option<i64>,
+    Option<i64>,
+);
+
+type SearchQuery<'a> = IntoBoxed<
+    'a,
+    InnerJoin<file_entries::table, InnerJoin<drive_entries::table, file_categories::table>>,
+    Sqlite,
+>;
+
+/// Drive entry written along with its files.
+struct DriveRecord<'a> {
+    name: &'a str,
+    capacity: DriveCapacity,
+    root_path: Option<&'a Path>,
+    insertion_time: NaiveDateTime,
+}
 
 /// Repository for write operations on files, drives, and categories.
 pub struct ListerRepository {
     pool: SqliteRepositoryPool,
+    clock: Arc<dyn Clock>,
 }
 
 impl ListerRepository {
     #[must_use]
-    /// Creates a new [`ListerRepository`] with the given pool.
-    pub const fn new(pool: SqliteRepositoryPool) -> Self {
-        Self { pool }
+    /// Creates a new [`ListerRepository`] with the given pool, stamping new drives with `clock`.
+    pub fn new(pool: SqliteRepositoryPool, clock: Arc<dyn Clock>) -> Self {
+        Self { pool, clock }
+    }
+
+    #[must_use]
+    /// Gives access to the history of indexing runs stored in the same database.
+    pub fn index_runs(&self) -> IndexRunRepository<'_> {
+        IndexRunRepository::new(&self.pool, self.clock.as_ref())
+    }
+
+    #[must_use]
+    /// Gives access to the named searches stored in the same database.
+    pub const fn saved_searches(&self) -> SavedSearchRepository<'_> {
+        SavedSearchRepository::new(&self.pool)
+    }
+
+    #[must_use]
+    /// Gives access to the labels attached to drives in the same database.
+    pub const fn drive_tags(&self) -> DriveTagRepository<'_> {
+        DriveTagRepository::new(&self.pool)
     }
 
     /// Removes duplicate file entries for the specified category and drive.
     ///
     /// Deletes existing records in the database that match the given
-    /// category and drive combination.
+    /// category and drive combination, and returns how many were deleted.
     ///
     /// # Errors
     ///
@@ -44,20 +124,139 @@ impl ListerRepository {
         &self,
         category: &str,
         drive: &str,
-    ) -> Result<(), InfrastructureError> {
+    ) -> Result<usize, InfrastructureError> {
+        self.pool
+            .execute_in_transaction(|conn| Self::delete_files(category, drive, conn))
+    }
+
+    /// Removes the given files of the specified category and drive, such as files found missing
+    /// from the drive, and returns how many were removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during the delete operation.
+    pub fn remove_files(
+        &self,
+        category: &str,
+        drive: &str,
+        paths: &[String],
+    ) -> Result<usize, InfrastructureError> {
         self.pool.execute_in_transaction(|conn| {
-            diesel::delete(
-                file_entries::table.filter(exists(
-                    drive_entries::table
-                        .inner_join(file_categories::table)
-                        .filter(drive_entries::id.eq(file_entries::drive_id))
-                        .filter(file_categories::name.eq(category))
-                        .filter(drive_entries::name.eq(drive)),
-                )),
-            )
-            .execute(conn)?;
+            // One value bound per path, so chunks sized for inserts stay within the limit
+            paths
+                .chunks(INSERT_CHUNK_SIZE)
+                .try_fold(0, |removed, chunk| {
+                    Ok(removed
+                        + diesel::delete(
+                            file_entries::table
+                                .filter(file_entries::path.eq_any(chunk))
+                                .filter(exists(
+                                    drive_entries::table
+                                        .inner_join(file_categories::table)
+                                        .filter(drive_entries::id.eq(file_entries::drive_id))
+                                        .filter(file_categories::name.eq(category))
+                                        .filter(drive_entries::name.eq(drive)),
+                                )),
+                        )
+                        .execute(conn)?)
+                })
+        })
+    }
 
-            Ok(())
+    /// Counts the files currently stored for the specified category and drive.
+    ///
+    /// These are the files a re-index of the same category and drive replaces.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during query execution.
+    pub fn count_for(&self, category: &str, drive: &str) -> Result<u64, InfrastructureError> {
+        self.pool.execute_db_operation(|conn| {
+            Ok(file_entries::table
+                .inner_join(drive_entries::table.inner_join(file_categories::table))
+                .filter(file_categories::name.eq(category))
+                .filter(drive_entries::name.eq(drive))
+                .count()
+                .get_result::<i64>(conn)?
+                .to_u64_or_zero())
+        })
+    }
+
+    /// Retrieves the files currently stored for the specified category and drive, by path.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during query execution.
+    pub fn files_for(
+        &self,
+        category: &str,
+        drive: &str,
+    ) -> Result<Vec<FileEntry>, InfrastructureError> {
+        self.pool.execute_db_operation(|conn| {
+            Ok(file_entries::table
+                .inner_join(drive_entries::table.inner_join(file_categories::table))
+                .filter(file_categories::name.eq(category))
+                .filter(drive_entries::name.eq(drive))
+                .select((
+                    file_entries::path,
+                    file_entries::weight,
+                    file_entries::modified_time,
+                    file_entries::owner_uid,
+                    file_entries::owner_gid,
+                    file_entries::mode,
+                ))
+                .order(file_entries::path)
+                .load::<StoredFile>(conn)?
+                .into_iter()
+                .map(|(path, weight, modified_time, uid, gid, mode)| FileEntry {
+                    path,
+                    size_bytes: weight.to_u64_or_zero(),
+                    modified_time,
+                    ownership: to_ownership(uid, gid, mode),
+                })
+                .collect())
+        })
+    }
+
+    /// Replaces the files of the specified category and drive with files received in batches.
+    ///
+    /// The removal of the previous files and the insertion of the new ones happen within a
+    /// single transaction, so an interrupted re-index leaves the previous files untouched.
+    /// `on_progress` is called with the cumulative number of inserted files after each batch.
+    /// `capacity` is the space of the drive as reported by its disk, and `root_path` is the
+    /// directory the file paths are relative to, when there is one.
+    /// Returns the total number of inserted files.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during delete or insert operations.
+    /// - A batch is an error, in which case it is returned and the whole re-index is rolled back.
+    pub fn reindex(
+        &self,
+        category: &str,
+        drive: &str,
+        capacity: DriveCapacity,
+        root_path: Option<&Path>,
+        batches: impl IntoIterator<Item = Result<Vec<FileEntry>, InfrastructureError>>,
+        on_progress: impl FnMut(usize),
+    ) -> Result<usize, InfrastructureError> {
+        let drive_record = DriveRecord {
+            name: drive,
+            capacity,
+            root_path,
+            insertion_time: self.clock.now(),
+        };
+        self.pool.execute_in_transaction(|conn| {
+            Self::delete_files(category, drive, conn)?;
+            Self::save_batches(category, &drive_record, batches, on_progress, conn)
         })
     }
 
@@ -78,13 +277,50 @@ impl ListerRepository {
         drive_available_space: u64,
         files: &[FileEntry],
     ) -> Result<usize, InfrastructureError> {
+        let drive_record = DriveRecord {
+            name: drive,
+            capacity: DriveCapacity::available_only(drive_available_space),
+            root_path: None,
+            insertion_time: self.clock.now(),
+        };
         self.pool.execute_in_transaction(|conn| {
             let category_id = Self::save_category(category, conn)?;
-            let drive_id = Self::save_drive(drive, drive_available_space, category_id, conn)?;
+            let drive_id = Self::save_drive(&drive_record, category_id, conn)?;
             Self::save_files(files, drive_id, conn)
         })
     }
 
+    /// Saves a category, its drive, and files received in batches to the database.
+    ///
+    /// Every batch is inserted as soon as it is received, within a single transaction, and
+    /// `on_progress` is called with the cumulative number of inserted files after each batch.
+    /// Returns the total number of inserted files.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during insert operations.
+    /// - A batch is an error, in which case it is returned and the whole save is rolled back.
+    pub fn save_in_batches(
+        &self,
+        category: &str,
+        drive: &str,
+        drive_available_space: u64,
+        batches: impl IntoIterator<Item = Result<Vec<FileEntry>, InfrastructureError>>,
+        on_progress: impl FnMut(usize),
+    ) -> Result<usize, InfrastructureError> {
+        let drive_record = DriveRecord {
+            name: drive,
+            capacity: DriveCapacity::available_only(drive_available_space),
+            root_path: None,
+            insertion_time: self.clock.now(),
+        };
+        self.pool.execute_in_transaction(|conn| {
+            Self::save_batches(category, &drive_record, batches, on_progress, conn)
+        })
+    }
+
     /// Deletes a drive, optionally filtered by category, from the database.
     ///
     /// If a category is provided, only the drive entries associated with that
@@ -129,6 +365,162 @@ impl ListerRepository {
         })
     }
 
+    /// Captures the entries of a drive, optionally limited to one category, with their
+    /// categories, tags and files, so that [`restore`](Self::restore) can put them back later.
+    ///
+    /// Taken right before a destructive operation on the same drive and category.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during query execution.
+    pub fn snapshot(
+        &self,
+        drive: &str,
+        category: Option<&str>,
+    ) -> Result<DriveSnapshot, InfrastructureError> {
+        self.pool.execute_db_operation(|conn| {
+            let mut drives_query = drive_entries::table
+                .filter(drive_entries::name.eq(drive))
+                .into_boxed();
+            if let Some(category_name) = category {
+                drives_query = drives_query.filter(
+                    drive_entries::category_id.eq_any(
+                        file_categories::table
+                            .select(file_categories::id)
+                            .filter(file_categories::name.eq(category_name)),
+                    ),
+                );
+            }
+            let drives = drives_query.load::<NewDriveEntryDto>(conn)?;
+
+            let categories = file_categories::table
+                .filter(file_categories::id.eq_any(drives.iter().map(|drive| drive.category_id)))
+                .load::<NewFileCategoryDto>(conn)?;
+            let tags = drive_tags::table
+                .filter(drive_tags::drive_id.eq_any(drives.iter().map(|drive| drive.id)))
+                .select((drive_tags::drive_id, drive_tags::tag))
+                .load::<(UuidSqlite, String)>(conn)?;
+            let files = file_entries::table
+                .filter(file_entries::drive_id.eq_any(drives.iter().map(|drive| drive.id)))
+                .load::<NewFileEntryDto>(conn)?;
+
+            Ok(DriveSnapshot {
+                categories,
+                drives,
+                tags,
+                files,
+            })
+        })
+    }
+
+    /// Puts back the rows captured by [`snapshot`](Self::snapshot) and returns how many files
+    /// were restored.
+    ///
+    /// Whatever the captured drives hold now is replaced, within a single transaction, so the
+    /// drives end up exactly as they were captured, tags included. Categories deleted since are
+    /// re-created.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during delete or insert operations.
+    pub fn restore(&self, snapshot: &DriveSnapshot) -> Result<usize, InfrastructureError> {
+        self.pool.execute_in_transaction(|conn| {
+            // A category re-created under the same name since keeps its new identifier
+            let mut category_ids = HashMap::new();
+            for category in &snapshot.categories {
+                let existing_id = file_categories::table
+                    .filter(file_categories::name.eq(&category.name))
+                    .select(file_categories::id)
+                    .first::<UuidSqlite>(conn)
+                    .optional()?;
+                let id = match existing_id {
+                    Some(id) => id,
+                    None => diesel::insert_into(file_categories::table)
+                        .values(category)
+                        .returning(file_categories::id)
+                        .get_result(conn)?,
+                };
+                category_ids.insert(category.id.0, id);
+            }
+
+            for drive in &snapshot.drives {
+                let category_id = category_ids
+                    .get(&drive.category_id.0)
+                    .copied()
+                    .unwrap_or(drive.category_id);
+                // Files of the replaced entry go along with it
+                diesel::delete(
+                    drive_entries::table.filter(
+                        drive_entries::id.eq(drive.id).or(drive_entries::name
+                            .eq(&drive.name)
+                            .and(drive_entries::category_id.eq(category_id))),
+                    ),
+                )
+                .execute(conn)?;
+                diesel::insert_into(drive_entries::table)
+                    .values(NewDriveEntryDto {
+                        category_id,
+                        ..drive.clone()
+                    })
+                    .execute(conn)?;
+            }
+            for (drive_id, tag) in &snapshot.tags {
+                diesel::insert_or_ignore_into(drive_tags::table)
+                    .values((drive_tags::drive_id.eq(drive_id), drive_tags::tag.eq(tag)))
+                    .execute(conn)?;
+            }
+
+            snapshot
+                .files
+                .chunks(INSERT_CHUNK_SIZE)
+                .try_fold(0, |inserted, chunk| {
+                    Ok(inserted
+                        + diesel::insert_into(file_entries::table)
+                            .values(chunk)
+                            .execute(conn)?)
+                })
+        })
+    }
+
+    fn delete_files(
+        category: &str,
+        drive: &str,
+        conn: &mut SqliteConnection,
+    ) -> Result<usize, InfrastructureError> {
+        Ok(diesel::delete(
+            file_entries::table.filter(exists(
+                drive_entries::table
+                    .inner_join(file_categories::table)
+                    .filter(drive_entries::id.eq(file_entries::drive_id))
+                    .filter(file_categories::name.eq(category))
+                    .filter(drive_entries::name.eq(drive)),
+            )),
+        )
+        .execute(conn)?)
+    }
+
+    fn save_batches(
+        category: &str,
+        drive: &DriveRecord,
+        batches: impl IntoIterator<Item = Result<Vec<FileEntry>, InfrastructureError>>,
+        mut on_progress: impl FnMut(usize),
+        conn: &mut SqliteConnection,
+    ) -> Result<usize, InfrastructureError> {
+        let category_id = Self::save_category(category, conn)?;
+        let drive_id = Self::save_drive(drive, category_id, conn)?;
+
+        let mut files_saved = 0;
+        for batch in batches {
+            files_saved += Self::save_files(&batch?, drive_id, conn)?;
+            on_progress(files_saved);
+        }
+        Ok(files_saved)
+    }
+
     fn save_category(
         category: &str,
         conn: &mut SqliteConnection,
@@ -153,15 +545,15 @@ impl ListerRepository {
     }
 
     fn save_drive(
-        drive: &str,
-        drive_available_space: u64,
+        drive: &DriveRecord,
         category_id: UuidSqlite,
         conn: &mut SqliteConnection,
     ) -> Result<UuidSqlite, InfrastructureError> {
+        let root_path = drive.root_path.map(encode_path);
         let existing_id = drive_entries::table
             .filter(
                 drive_entries::name
-                    .eq(drive)
+                    .eq(drive.name)
                     .and(drive_entries::category_id.eq(&category_id)),
             )
             .select(drive_entries::id)
@@ -169,21 +561,50 @@ impl ListerRepository {
             .optional()?;
 
         if let Some(id) = existing_id {
+            update(drive_entries::table.filter(drive_entries::id.eq(&id)))
+                .set(drive_entries::root_path.eq(root_path))
+                .execute(conn)?;
+            Self::update_same_drives_total_space(drive.name, drive.capacity.total_space, conn)?;
             return Ok(id);
         }
 
-        Self::update_same_drives_available_space(drive, drive_available_space, conn)?;
+        Self::update_same_drives_available_space(drive.name, drive.capacity.available_space, conn)?;
+        Self::update_same_drives_total_space(drive.name, drive.capacity.total_space, conn)?;
 
-        Ok(diesel::insert_into(drive_entries::table)
+        let id = diesel::insert_into(drive_entries::table)
             .values(NewDriveEntryDto {
                 id: UuidSqlite::new(),
                 category_id,
-                name: drive.to_string(),
-                available_space: drive_available_space.to_i64_or_zero(),
-                insertion_time: Local::now().naive_local(),
+                name: drive.name.to_string(),
+                available_space: drive.capacity.available_space.to_i64_or_zero(),
+                insertion_time: drive.insertion_time,
+                root_path,
+                total_space: drive.capacity.total_space.to_i64_or_zero(),
             })
             .returning(drive_entries::id)
-            .get_result(conn)?)
+            .get_result::<UuidSqlite>(conn)?;
+        Self::copy_same_drives_tags(drive.name, &id, conn)?;
+        Ok(id)
+    }
+
+    /// Gives a new entry of the drive the tags its entries in other categories already carry.
+    fn copy_same_drives_tags(
+        drive: &str,
+        drive_id: &UuidSqlite,
+        conn: &mut SqliteConnection,
+    ) -> Result<(), InfrastructureError> {
+        let tags = drive_tags::table
+            .inner_join(drive_entries::table)
+            .filter(drive_entries::name.eq(drive))
+            .select(drive_tags::tag)
+            .distinct()
+            .load::<String>(conn)?;
+        for tag in tags {
+            diesel::insert_or_ignore_into(drive_tags::table)
+                .values((drive_tags::drive_id.eq(drive_id), drive_tags::tag.eq(tag)))
+                .execute(conn)?;
+        }
+        Ok(())
     }
 
     fn update_same_drives_available_space(
@@ -197,6 +618,21 @@ impl ListerRepository {
         Ok(())
     }
 
+    /// Overwrites the total space of every entry of the drive, unless it is unknown.
+    fn update_same_drives_total_space(
+        drive: &str,
+        drive_total_space: u64,
+        conn: &mut SqliteConnection,
+    ) -> Result<(), InfrastructureError> {
+        if drive_total_space == 0 {
+            return Ok(());
+        }
+        update(drive_entries::table.filter(drive_entries::name.eq(drive)))
+            .set(drive_entries::total_space.eq(drive_total_space.to_i64_or_zero()))
+            .execute(conn)?;
+        Ok(())
+    }
+
     fn save_files(
         files: &[FileEntry],
         drive_id: UuidSqlite,
@@ -207,13 +643,113 @@ impl ListerRepository {
             .map(|file_entry| (file_entry, drive_id).into())
             .collect();
 
-        Ok(diesel::insert_into(file_entries::table)
-            .values(&dto_files)
-            .execute(conn)?)
+        dto_files
+            .chunks(INSERT_CHUNK_SIZE)
+            .try_fold(0, |inserted, chunk| {
+                Ok(inserted
+                    + diesel::insert_into(file_entries::table)
+                        .values(chunk)
+                        .execute(conn)?)
+            })
+    }
+
+    /// Builds the filtered file search shared by counting and fetching, so both always agree.
+    fn search_query<'a>(
+        selected_drive: Option<&'a str>,
+        query: Option<&str>,
+        filters: &SearchFilters,
+    ) -> SearchQuery<'a> {
+        let mut query_builder = file_entries::table
+            .inner_join(drive_entries::table.inner_join(file_categories::table))
+            .into_boxed();
+
+        if let Some(drive) = selected_drive {
+            query_builder = query_builder.filter(drive_entries::name.eq(drive));
+        }
+
+        if let Some(extension) = filters.extension.clone() {
+            query_builder = query_builder.filter(file_entries::extension.eq(extension));
+        }
+
+        if let Some(since) = filters.indexed_since {
+            query_builder = query_builder.filter(drive_entries::insertion_time.ge(since));
+        }
+
+        if let Some(tag) = filters.tag.clone() {
+            query_builder = query_builder.filter(exists(
+                drive_tags::table
+                    .filter(drive_tags::drive_id.eq(drive_entries::id))
+                    .filter(drive_tags::tag.eq(tag)),
+            ));
+        }
+
+        if let Some(permission) = filters.permission {
+            query_builder = query_builder.filter(sql::<Bool>(&format!(
+                "file_entries.mode & {} != 0",
+                permission.mask()
+            )));
+        }
+
+        let terms = SearchTerms::parse(query.unwrap_or_default());
+        for term in terms.include {
+            let pattern = Self::like_pattern(&term);
+            query_builder = if filters.whole_segments {
+                query_builder.filter(
+                    Self::segments(filters.filename_only)
+                        .like(Self::segment_pattern(&term))
+                        .escape('\\'),
+                )
+            } else if filters.filename_only {
+                query_builder.filter(file_entries::filename.like(pattern).escape('\\'))
+            } else {
+                query_builder.filter(file_entries::path.like(pattern).escape('\\'))
+            };
+        }
+        for term in terms.exclude {
+            let pattern = Self::like_pattern(&term);
+            query_builder = if filters.whole_segments {
+                query_builder.filter(
+                    Self::segments(filters.filename_only)
+                        .not_like(Self::segment_pattern(&term))
+                        .escape('\\'),
+                )
+            } else if filters.filename_only {
+                query_builder.filter(file_entries::filename.not_like(pattern).escape('\\'))
+            } else {
+                query_builder.filter(file_entries::path.not_like(pattern).escape('\\'))
+            };
+        }
+
+        query_builder
+    }
+
+    /// Matches `term` anywhere, with `%` and `_` taken literally.
+    fn like_pattern(term: &str) -> String {
+        format!("%{}%", Self::escape_like(term))
+    }
+
+    /// The path, or only the filename, written as `/segment/segment/` with Windows separators
+    /// turned into `/`, so that every segment is enclosed in separators.
+    fn segments(filename_only: bool) -> SqlLiteral<Text> {
+        sql::<Text>(if filename_only {
+            "'/' || file_entries.filename || '/'"
+        } else {
+            "'/' || REPLACE(file_entries.path, '\\', '/') || '/'"
+        })
+    }
+
+    /// Matches `term` as one or more complete segments of the text built by
+    /// [`segments`](Self::segments), with `%` and `_` taken literally.
+    fn segment_pattern(term: &str) -> String {
+        let term = term.replace('\\', "/");
+        format!("%/{}/%", Self::escape_like(term.trim_matches('/')))
     }
 
-    fn search_pattern(query: &str) -> String {
-        format!("%{query}%").replace(' ', "_")
+    /// Escapes `term` so that `%` and `_` are taken literally, with `\` as escape character.
+    fn escape_like(term: &str) -> String {
+        term.replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_")
     }
 
     /// Retrieves all used category names from the database based on a drive name.
@@ -240,125 +776,537 @@ impl ListerRepository {
         })
     }
 
-    /// Compacts the `SQLite` database file.
+    /// Lists every category with the number of files indexed under it.
     ///
-    /// This operation runs the `VACUUM` command, which rebuilds the database
-    /// file to reclaim unused space and reduce fragmentation.
+    /// Categories sharing the same name are counted together. Results are sorted by name.
     ///
     /// # Errors
     ///
     /// Returns a [`InfrastructureError`] if:
     /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
     /// - A [`Database`](InfrastructureError::Database) error occurs during query execution.
-    pub fn compact(&self) -> Result<u64, InfrastructureError> {
-        let size_before = std::fs::metadata("app.db")?.len();
+    pub fn find_all_categories(&self) -> Result<Vec<CategoryStats>, InfrastructureError> {
         self.pool.execute_db_operation(|conn| {
-            diesel::sql_query("VACUUM").execute(conn)?;
-            diesel::sql_query("PRAGMA shrink_memory;").execute(conn)?;
-            let size_after = std::fs::metadata("app.db")?.len();
-            Ok(size_before - size_after)
+            let entities = file_categories::table
+                .left_join(drive_entries::table.left_join(file_entries::table))
+                .group_by(file_categories::name)
+                .select((file_categories::name, count(file_entries::id.nullable())))
+                .order(file_categories::name)
+                .load::<CategoryStatsDto>(conn)?;
+
+            Ok(entities.into_iter().map(CategoryStatsDto::into).collect())
         })
     }
 
-    /// Retrieves all distinct drive names from the database.
+    /// Lists every category with the number of drives and files it holds and their total size.
     ///
-    /// Returns a sorted list of unique drive names.
+    /// Categories sharing the same name are counted together, and a category left without
+    /// drives is listed with zeros. Results are sorted by name.
     ///
     /// # Errors
     ///
     /// Returns a [`InfrastructureError`] if:
     /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
     /// - A [`Database`](InfrastructureError::Database) error occurs during query execution.
-    pub fn find_all_drive_names(&self) -> Result<Vec<String>, InfrastructureError> {
+    pub fn category_overview(&self) -> Result<Vec<CategoryOverview>, InfrastructureError> {
         self.pool.execute_db_operation(|conn| {
-            Ok(drive_entries::table
-                .select(drive_entries::name)
-                .distinct()
-                .order(drive_entries::name)
-                .load::<String>(conn)?)
+            let entities = file_categories::table
+                .left_join(drive_entries::table.left_join(file_entries::table))
+                .group_by(file_categories::name)
+                .select((
+                    file_categories::name,
+                    sql::<BigInt>("COUNT(DISTINCT drive_entries.id)"),
+                    count(file_entries::id.nullable()),
+                    sql::<BigInt>("COALESCE(SUM(file_entries.weight), 0)"),
+                ))
+                .order(file_categories::name)
+                .load::<CategoryOverviewDto>(conn)?;
+
+            Ok(entities
+                .into_iter()
+                .map(CategoryOverviewDto::into)
+                .collect())
         })
     }
 
-    /// Counts the total number of files matching the provided search criteria.
+    /// Moves the drives of the `from` category into the `into` category, then deletes `from`.
     ///
-    /// The search can be filtered by drive name and optional query pattern.
+    /// A drive already indexed under `into` receives the files of its namesake instead
+    /// of being duplicated. Every category named `from` is merged, so merging a name into
+    /// itself folds duplicate categories together. Everything happens in one transaction.
     ///
     /// # Errors
     ///
     /// Returns a [`InfrastructureError`] if:
     /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
-    /// - A [`Database`](InfrastructureError::Database) error occurs during query execution.
-    pub fn count_search_results(
-        &self,
-        selected_drive: Option<&str>,
-        query: Option<&str>,
-    ) -> Result<u64, InfrastructureError> {
-        let search_pattern = query.map(Self::search_pattern);
-
-        self.pool.execute_db_operation(move |conn| {
-            let mut query_builder = file_entries::table
-                .inner_join(drive_entries::table)
-                .into_boxed();
+    /// - A [`Database`](InfrastructureError::Database) error occurs during the update, including
+    ///   when no category is named `into`.
+    pub fn merge_categories(&self, from: &str, into: &str) -> Result<(), InfrastructureError> {
+        self.pool.execute_in_transaction(|conn| {
+            let into_id = file_categories::table
+                .filter(file_categories::name.eq(into))
+                .select(file_categories::id)
+                .first::<UuidSqlite>(conn)?;
+            let from_ids = file_categories::table
+                .filter(file_categories::name.eq(from))
+                .filter(file_categories::id.ne(&into_id))
+                .select(file_categories::id)
+                .load::<UuidSqlite>(conn)?;
+            let from_drives = drive_entries::table
+                .filter(drive_entries::category_id.eq_any(&from_ids))
+                .select((drive_entries::id, drive_entries::name))
+                .load::<(UuidSqlite, String)>(conn)?;
 
-            if let Some(drive) = selected_drive {
-                query_builder = query_builder.filter(drive_entries::name.eq(drive));
-            }
+            for (drive_id, drive_name) in from_drives {
+                let existing_id = drive_entries::table
+                    .filter(drive_entries::category_id.eq(&into_id))
+                    .filter(drive_entries::name.eq(&drive_name))
+                    .select(drive_entries::id)
+                    .first::<UuidSqlite>(conn)
+                    .optional()?;
 
-            if let Some(pattern) = search_pattern {
-                query_builder = query_builder.filter(file_entries::path.like(pattern));
+                if let Some(existing_id) = existing_id {
+                    update(file_entries::table.filter(file_entries::drive_id.eq(&drive_id)))
+                        .set(file_entries::drive_id.eq(&existing_id))
+                        .execute(conn)?;
+                    diesel::delete(drive_entries::table.filter(drive_entries::id.eq(&drive_id)))
+                        .execute(conn)?;
+                } else {
+                    update(drive_entries::table.filter(drive_entries::id.eq(&drive_id)))
+                        .set(drive_entries::category_id.eq(&into_id))
+                        .execute(conn)?;
+                }
             }
 
-            Ok(query_builder
-                .count()
-                .get_result::<i64>(conn)?
-                .to_u64_or_zero())
+            diesel::delete(file_categories::table.filter(file_categories::id.eq_any(&from_ids)))
+                .execute(conn)?;
+            Ok(())
         })
     }
 
-    /// Searches for files matching the given criteria with pagination support.
+    /// Compacts the `SQLite` database file.
     ///
-    /// Results can be filtered by drive and search query, and limited by
-    /// offset and page size.
+    /// This operation runs the `VACUUM` command, which rebuilds the database
+    /// file to reclaim unused space and reduce fragmentation. Returns the size
+    /// of the file before and after.
     ///
     /// # Errors
     ///
     /// Returns a [`InfrastructureError`] if:
     /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
     /// - A [`Database`](InfrastructureError::Database) error occurs during query execution.
-    pub fn search_files_paginated(
+    /// - An [`IoError`](InfrastructureError::IoError) occurs while reading the size of the database file.
+    pub fn compact(&self) -> Result<CompactionReport, InfrastructureError> {
+        self.pool.vacuum()
+    }
+
+    /// Retrieves the extensions of the indexed files, optionally restricted to one drive.
+    ///
+    /// Returns a sorted list of unique lowercase extensions, without the leading dot.
+    /// Files without an extension are left out.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during query execution.
+    pub fn list_extensions(
         &self,
         selected_drive: Option<&str>,
-        query: Option<&str>,
-        page: u64,
-        page_size: u64,
-    ) -> Result<Vec<FileWithMetadata>, InfrastructureError> {
-        let offset = page * page_size;
-        let limit = page_size;
+    ) -> Result<Vec<String>, InfrastructureError> {
+        self.pool.execute_db_operation(|conn| {
+            let mut query_builder = file_entries::table
+                .inner_join(drive_entries::table)
+                .filter(file_entries::extension.ne(""))
+                .select(file_entries::extension)
+                .distinct()
+                .order(file_entries::extension)
+                .into_boxed();
 
-        let search_pattern = query.map(Self::search_pattern);
+            if let Some(drive) = selected_drive {
+                query_builder = query_builder.filter(drive_entries::name.eq(drive));
+            }
 
-        self.pool.execute_db_operation(move |conn| {
-            let mut query_builder = file_entries::table
-                .inner_join(drive_entries::table.inner_join(file_categories::table))
+            Ok(query_builder.load::<String>(conn)?)
+        })
+    }
+
+    /// Retrieves all distinct drives from the database with the time they were last indexed.
+    ///
+    /// Returns a list sorted by drive name. A drive indexed under several categories
+    /// keeps its most recent indexing time.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during query execution.
+    pub fn find_all_drives(&self) -> Result<Vec<DriveInfo>, InfrastructureError> {
+        self.pool.execute_db_operation(|conn| {
+            let entities = drive_entries::table
+                .group_by(drive_entries::name)
+                .select((
+                    drive_entries::name,
+                    sql::<Timestamp>("MAX(drive_entries.insertion_time)"),
+                ))
+                .order(drive_entries::name)
+                .load::<DriveInfoDto>(conn)?;
+
+            Ok(entities.into_iter().map(DriveInfoDto::into).collect())
+        })
+    }
+
+    /// Compares the files of two drives by path and size, whatever their categories.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during query execution.
+    pub fn diff_drives(
+        &self,
+        drive_a: &str,
+        drive_b: &str,
+    ) -> Result<DriveDiff, InfrastructureError> {
+        self.pool.execute_db_operation(|conn| {
+            let files_a = Self::drive_files(drive_a, conn)?;
+            let files_b = Self::drive_files(drive_b, conn)?;
+            Ok(DriveDiff::between(files_a, files_b))
+        })
+    }
+
+    fn drive_files(
+        drive: &str,
+        conn: &mut SqliteConnection,
+    ) -> Result<Vec<(String, u64)>, InfrastructureError> {
+        Ok(file_entries::table
+            .inner_join(drive_entries::table)
+            .filter(drive_entries::name.eq(drive))
+            .select((file_entries::path, file_entries::weight))
+            .load::<(String, i64)>(conn)?
+            .into_iter()
+            .map(|(path, weight)| (path, weight.to_u64_or_zero()))
+            .collect())
+    }
+
+    /// Lists the folders and files directly under `prefix` on a drive, whatever their categories.
+    ///
+    /// An empty `prefix` lists the top of the drive. See [`DirectoryListing::children_of`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during query execution.
+    pub fn list_immediate_children(
+        &self,
+        drive: &str,
+        prefix: &str,
+    ) -> Result<DirectoryListing, InfrastructureError> {
+        // LIKE ignores case, the listing keeps only the paths really under the prefix
+        let pattern = format!(
+            "{}%",
+            Self::escape_like(prefix.trim_end_matches(['/', '\\']))
+        );
+        self.pool.execute_db_operation(|conn| {
+            let files = file_entries::table
+                .inner_join(drive_entries::table)
+                .filter(drive_entries::name.eq(drive))
+                .filter(file_entries::path.like(&pattern).escape('\\'))
+                .select((file_entries::path, file_entries::weight))
+                .load::<(String, i64)>(conn)?
+                .into_iter()
+                .map(|(path, weight)| (path, weight.to_u64_or_zero()));
+            Ok(DirectoryListing::children_of(prefix, files))
+        })
+    }
+
+    /// Checks whether a drive with the given name is indexed.
+    ///
+    /// Lets callers tell a drive without matches from one that was deleted.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during query execution.
+    pub fn drive_exists(&self, name: &str) -> Result<bool, InfrastructureError> {
+        self.pool.execute_db_operation(|conn| {
+            Ok(diesel::select(exists(
+                drive_entries::table.filter(drive_entries::name.eq(name)),
+            ))
+            .get_result::<bool>(conn)?)
+        })
+    }
+
+    /// Checks whether a drive with the given name is already indexed under a category.
+    ///
+    /// Indexing the same drive and category again replaces the files stored for them.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during query execution.
+    pub fn drive_exists_in_category(
+        &self,
+        drive: &str,
+        category: &str,
+    ) -> Result<bool, InfrastructureError> {
+        self.pool.execute_db_operation(|conn| {
+            Ok(diesel::select(exists(
+                drive_entries::table
+                    .inner_join(file_categories::table)
+                    .filter(drive_entries::name.eq(drive))
+                    .filter(file_categories::name.eq(category)),
+            ))
+            .get_result::<bool>(conn)?)
+        })
+    }
+
+    /// Retrieves the directory the files of a drive and category were indexed from.
+    ///
+    /// Returns `None` for drives indexed before it was recorded, or from several
+    /// directories that share no parent.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during query execution.
+    pub fn find_drive_root(
+        &self,
+        drive: &str,
+        category: &str,
+    ) -> Result<Option<PathBuf>, InfrastructureError> {
+        self.pool.execute_db_operation(|conn| {
+            let root_path = drive_entries::table
+                .inner_join(file_categories::table)
+                .filter(drive_entries::name.eq(drive))
+                .filter(file_categories::name.eq(category))
+                .select(drive_entries::root_path)
+                .first::<Option<String>>(conn)
+                .optional()?;
+
+            Ok(root_path.flatten().as_deref().map(decode_path))
+        })
+    }
+
+    /// Retrieves the stored available space of every drive.
+    ///
+    /// Returns one entry per drive name, sorted by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during query execution.
+    pub fn find_all_drives_available_space(&self) -> Result<Vec<DriveSpace>, InfrastructureError> {
+        self.pool.execute_db_operation(|conn| {
+            let entities = drive_entries::table
+                .group_by(drive_entries::name)
                 .select((
+                    drive_entries::name,
+                    sql::<BigInt>("MAX(drive_entries.available_space)"),
+                ))
+                .order(drive_entries::name)
+                .load::<DriveSpaceDto>(conn)?;
+
+            Ok(entities.into_iter().map(DriveSpaceDto::into).collect())
+        })
+    }
+
+    /// Overwrites the available space of every entry of the given drive.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during the update operation.
+    pub fn set_drive_available_space(
+        &self,
+        drive: &str,
+        available_space: u64,
+    ) -> Result<(), InfrastructureError> {
+        self.pool.execute_db_operation(|conn| {
+            Self::update_same_drives_available_space(drive, available_space, conn)
+        })
+    }
+
+    /// Computes per-drive statistics for every indexed drive and category.
+    ///
+    /// Each entry holds the number of files, their total size, the drive's
+    /// available space and the time it was last indexed. Results are sorted
+    /// by drive name, then category name.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during query execution.
+    pub fn drive_statistics(&self) -> Result<Vec<DriveStats>, InfrastructureError> {
+        self.pool.execute_db_operation(|conn| {
+            let entities = drive_entries::table
+                .inner_join(file_categories::table)
+                .left_join(file_entries::table)
+                .group_by((
+                    drive_entries::id,
+                    drive_entries::name,
                     file_categories::name,
+                    drive_entries::available_space,
+                    drive_entries::total_space,
+                    drive_entries::insertion_time,
+                ))
+                .select((
                     drive_entries::name,
+                    file_categories::name,
+                    count(file_entries::id.nullable()),
+                    sql::<BigInt>("COALESCE(SUM(file_entries.weight), 0)"),
                     drive_entries::available_space,
+                    drive_entries::total_space,
                     drive_entries::insertion_time,
-                    file_entries::path,
-                    file_entries::weight,
                 ))
-                .into_boxed();
+                .order((drive_entries::name, file_categories::name))
+                .load::<DriveStatsDto>(conn)?;
 
-            if let Some(drive) = selected_drive {
-                query_builder = query_builder.filter(drive_entries::name.eq(drive));
-            }
+            Ok(entities.into_iter().map(DriveStatsDto::into).collect())
+        })
+    }
 
-            if let Some(search) = search_pattern {
-                query_builder = query_builder.filter(file_entries::path.like(search));
+    /// Counts the total number of files matching the provided search criteria.
+    ///
+    /// The search can be filtered by drive name, optional query pattern and [`SearchFilters`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during query execution.
+    pub fn count_search_results(
+        &self,
+        selected_drive: Option<&str>,
+        query: Option<&str>,
+        filters: &SearchFilters,
+    ) -> Result<u64, InfrastructureError> {
+        self.pool.execute_db_operation(|conn| {
+            Ok(Self::search_query(selected_drive, query, filters)
+                .count()
+                .get_result::<i64>(conn)?
+                .to_u64_or_zero())
+        })
+    }
+
+    /// Finds which constraints of a search without results keep it empty.
+    ///
+    /// Counts the results of the search once without each of its constraints, and returns the
+    /// ones that would bring results back. Meant for searches already known to be empty, as it
+    /// runs one count per constraint.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during query execution.
+    pub fn diagnose_empty(
+        &self,
+        selected_drive: Option<&str>,
+        query: Option<&str>,
+        filters: &SearchFilters,
+    ) -> Result<Vec<LiftedConstraint>, InfrastructureError> {
+        let mut lifted = Vec::new();
+        for constraint in SearchConstraint::active(selected_drive, query, filters) {
+            let (drive, query, filters) = constraint.lift(selected_drive, query, filters);
+            let result_count = self.count_search_results(drive, query, &filters)?;
+            if result_count > 0 {
+                lifted.push(LiftedConstraint {
+                    constraint,
+                    result_count,
+                });
             }
+        }
+        Ok(lifted)
+    }
 
-            let entities = query_builder
+    /// Sums the size of every file matching the provided search criteria.
+    ///
+    /// Takes the same criteria as [`count_search_results`](Self::count_search_results).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during query execution.
+    pub fn sum_search_results_size(
+        &self,
+        selected_drive: Option<&str>,
+        query: Option<&str>,
+        filters: &SearchFilters,
+    ) -> Result<u64, InfrastructureError> {
+        self.pool.execute_db_operation(|conn| {
+            Ok(Self::search_query(selected_drive, query, filters)
+                .select(sql::<BigInt>("COALESCE(SUM(file_entries.weight), 0)"))
+                .get_result::<i64>(conn)?
+                .to_u64_or_zero())
+        })
+    }
+
+    /// Searches for files matching the given criteria with pagination support.
+    ///
+    /// Results can be filtered by drive, search query and [`SearchFilters`], listed in the order
+    /// of their [`SortKey`], and limited by offset and page size. Pages stop at the first
+    /// [`top`](SearchFilters::top) results when it is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during query execution.
+    pub fn search_files_paginated(
+        &self,
+        selected_drive: Option<&str>,
+        query: Option<&str>,
+        filters: &SearchFilters,
+        page: u64,
+        page_size: u64,
+    ) -> Result<Vec<FileWithMetadata>, InfrastructureError> {
+        let offset = page * page_size;
+        let limit = filters
+            .top
+            .map_or(page_size, |top| page_size.min(top.saturating_sub(offset)));
+        if limit == 0 {
+            return Ok(Vec::new());
+        }
+
+        self.pool.execute_db_operation(|conn| {
+            let search = Self::search_query(selected_drive, query, filters).select((
+                file_categories::name,
+                drive_entries::name,
+                drive_entries::available_space,
+                drive_entries::insertion_time,
+                file_entries::path,
+                file_entries::weight,
+                file_entries::modified_time,
+            ));
+            // The id breaks the remaining ties, so pages read one after the other never repeat
+            // or skip a file
+            let search = match filters.sort {
+                SortKey::Indexed => search.order(file_entries::id),
+                // Files without a modification time come last
+                SortKey::Modified => search.order((
+                    file_entries::modified_time.is_null(),
+                    file_entries::modified_time.desc(),
+                    file_entries::path,
+                    drive_entries::name,
+                    file_entries::id,
+                )),
+                SortKey::Size => search.order((
+                    file_entries::weight.desc(),
+                    file_entries::path,
+                    drive_entries::name,
+                    file_entries::id,
+                )),
+            };
+            let entities = search
                 .limit(limit.to_i64_or_zero())
                 .offset(offset.to_i64_or_zero())
                 .load::<FileWithMetadataDto>(conn)?;
@@ -370,6 +1318,73 @@ impl ListerRepository {
         })
     }
 
+    /// Finds the files indexed most recently across all drives, newest first.
+    ///
+    /// Files carry no timestamp of their own, so each one is dated by the indexing of its
+    /// drive; files of the same drive are listed by path.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during query execution.
+    pub fn recent_files(&self, limit: u64) -> Result<Vec<FileWithMetadata>, InfrastructureError> {
+        self.pool.execute_db_operation(|conn| {
+            let entities = Self::search_query(None, None, &SearchFilters::default())
+                .select((
+                    file_categories::name,
+                    drive_entries::name,
+                    drive_entries::available_space,
+                    drive_entries::insertion_time,
+                    file_entries::path,
+                    file_entries::weight,
+                    file_entries::modified_time,
+                ))
+                .order((
+                    drive_entries::insertion_time.desc(),
+                    drive_entries::name,
+                    file_entries::path,
+                ))
+                .limit(limit.to_i64_or_zero())
+                .load::<FileWithMetadataDto>(conn)?;
+
+            Ok(entities
+                .into_iter()
+                .map(FileWithMetadataDto::into)
+                .collect())
+        })
+    }
+
+    /// Searches for files matching the given criteria, yielding the results in batches.
+    ///
+    /// Each batch is fetched lazily with its own query, so callers can process rows as they
+    /// arrive instead of loading the whole result set at once. The iteration stops after the
+    /// first error or the first incomplete batch.
+    pub fn search_files_in_batches<'a>(
+        &'a self,
+        selected_drive: Option<&'a str>,
+        query: Option<&'a str>,
+        filters: &'a SearchFilters,
+        batch_size: u64,
+    ) -> impl Iterator<Item = Result<Vec<FileWithMetadata>, InfrastructureError>> + 'a {
+        let mut page = 0;
+        let mut is_done = batch_size == 0;
+        iter::from_fn(move || {
+            if is_done {
+                return None;
+            }
+            let batch =
+                self.search_files_paginated(selected_drive, query, filters, page, batch_size);
+            page += 1;
+            is_done = batch
+                .as_ref()
+                .map_or(true, |files| (files.len() as u64) < batch_size);
+            batch
+                .map(|files| (!files.is_empty()).then_some(files))
+                .transpose()
+        })
+    }
+
     #[must_use]
     pub fn translations(&self) -> (Language, HashMap<String, String>) {
         let current_language = self.get_language().unwrap_or_else(|error| {
@@ -386,7 +1401,8 @@ impl ListerRepository {
 
     /// Retrieves the current application language from the database.
     ///
-    /// Returns the stored language if present; otherwise defaults to [`Language::English`].
+    /// Returns the stored language if present; otherwise detects it from the system locale and
+    /// stores it as the initial choice.
     ///
     /// # Errors
     ///
@@ -394,15 +1410,27 @@ impl ListerRepository {
     /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
     /// - A [`Database`](InfrastructureError::Database) error occurs during query execution.
     pub fn get_language(&self) -> Result<Language, InfrastructureError> {
-        self.pool.execute_db_operation(|conn| {
-            let lang: Option<String> = settings::table
-                .filter(settings::key.eq("language"))
-                .select(settings::value)
-                .first(conn)
-                .optional()?;
+        self.get_language_or_detect(|| Language::from_locale(&get_locale().unwrap_or_default()))
+    }
 
-            Ok(lang.map_or_else(|| Language::English, |l| Language::new(&l)))
-        })
+    /// Retrieves the stored application language, or stores and returns the one given by `detect`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during query execution.
+    pub fn get_language_or_detect(
+        &self,
+        detect: impl FnOnce() -> Language,
+    ) -> Result<Language, InfrastructureError> {
+        if let Some(code) = self.get_setting("language")? {
+            return Ok(Language::new(&code));
+        }
+
+        let language = detect();
+        self.set_language(&language)?;
+        Ok(language)
     }
 
     /// Sets the application language in the database.
@@ -415,12 +1443,184 @@ impl ListerRepository {
     /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
     /// - A [`Database`](InfrastructureError::Database) error occurs during the update operation.
     pub fn set_language(&self, language: &Language) -> Result<(), InfrastructureError> {
+        self.set_setting("language", language.code())
+    }
+
+    /// Tells whether the application should look for updates when it starts.
+    ///
+    /// Returns the stored preference if present; otherwise defaults to `true`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during query execution.
+    pub fn get_auto_update(&self) -> Result<bool, InfrastructureError> {
+        Ok(self
+            .get_setting("auto_update")?
+            .is_none_or(|value| value == "true"))
+    }
+
+    /// Stores whether the application should look for updates when it starts.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during the update operation.
+    pub fn set_auto_update(&self, enabled: bool) -> Result<(), InfrastructureError> {
+        self.set_setting("auto_update", &enabled.to_string())
+    }
+
+    /// Tells whether scans include hidden files and folders.
+    ///
+    /// Returns the stored preference if present; otherwise defaults to `true`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during query execution.
+    pub fn get_index_hidden_files(&self) -> Result<bool, InfrastructureError> {
+        Ok(self
+            .get_setting("index_hidden_files")?
+            .is_none_or(|value| value == "true"))
+    }
+
+    /// Stores whether scans include hidden files and folders.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during the update operation.
+    pub fn set_index_hidden_files(&self, enabled: bool) -> Result<(), InfrastructureError> {
+        self.set_setting("index_hidden_files", &enabled.to_string())
+    }
+
+    /// Retrieves the number of results shown per page.
+    ///
+    /// Returns the stored page size if it is one of [`PAGE_SIZES`]; otherwise defaults to
+    /// [`DEFAULT_PAGE_SIZE`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during query execution.
+    pub fn get_items_per_page(&self) -> Result<usize, InfrastructureError> {
+        Ok(self
+            .get_setting("items_per_page")?
+            .and_then(|value| value.parse().ok())
+            .filter(|size| PAGE_SIZES.contains(size))
+            .unwrap_or(DEFAULT_PAGE_SIZE))
+    }
+
+    /// Stores the number of results shown per page.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during the update operation.
+    pub fn set_items_per_page(&self, items_per_page: usize) -> Result<(), InfrastructureError> {
+        self.set_setting("items_per_page", &items_per_page.to_string())
+    }
+
+    /// Retrieves how tightly result rows are packed.
+    ///
+    /// Returns the stored density if present; otherwise defaults to [`RowDensity::Comfortable`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during query execution.
+    pub fn get_row_density(&self) -> Result<RowDensity, InfrastructureError> {
+        Ok(self
+            .get_setting("row_density")?
+            .map(|code| RowDensity::new(&code))
+            .unwrap_or_default())
+    }
+
+    /// Stores how tightly result rows are packed.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during the update operation.
+    pub fn set_row_density(&self, density: RowDensity) -> Result<(), InfrastructureError> {
+        self.set_setting("row_density", density.code())
+    }
+
+    /// Retrieves the columns shown in the result list.
+    ///
+    /// Returns every column when none was hidden yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during query execution.
+    pub fn get_result_columns(&self) -> Result<ResultColumns, InfrastructureError> {
+        Ok(self
+            .get_setting("hidden_columns")?
+            .map(|setting| ResultColumns::from_setting(&setting))
+            .unwrap_or_default())
+    }
+
+    /// Stores the columns shown in the result list.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during the update operation.
+    pub fn set_result_columns(&self, columns: &ResultColumns) -> Result<(), InfrastructureError> {
+        self.set_setting("hidden_columns", &columns.to_setting())
+    }
+
+    /// Retrieves the recent search queries, newest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during query execution.
+    pub fn get_search_history(&self) -> Result<SearchHistory, InfrastructureError> {
+        let stored = self.get_setting("search_history")?.unwrap_or_default();
+        Ok(SearchHistory::from_entries(
+            stored.lines().map(str::to_string),
+            SEARCH_HISTORY_LIMIT,
+        ))
+    }
+
+    /// Stores the recent search queries, one per line.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during the update operation.
+    pub fn set_search_history(&self, history: &SearchHistory) -> Result<(), InfrastructureError> {
+        self.set_setting("search_history", &history.entries().join("\n"))
+    }
+
+    fn get_setting(&self, key: &str) -> Result<Option<String>, InfrastructureError> {
+        self.pool.execute_db_operation(|conn| {
+            Ok(settings::table
+                .filter(settings::key.eq(key))
+                .select(settings::value)
+                .first(conn)
+                .optional()?)
+        })
+    }
+
+    fn set_setting(&self, key: &str, value: &str) -> Result<(), InfrastructureError> {
         self.pool.execute_db_operation(|conn| {
             diesel::replace_into(settings::table)
-                .values((
-                    settings::key.eq("language"),
-                    settings::value.eq(language.code()),
-                ))
+                .values((settings::key.eq(key), settings::value.eq(value)))
                 .execute(conn)?;
             Ok(())
         })
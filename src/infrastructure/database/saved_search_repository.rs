@@ -0,0 +1,84 @@
+use crate::domain::model::saved_search::SavedSearch;
+use crate::infrastructure::database::entities::SavedSearchDto;
+use crate::infrastructure::database::pool::{InfrastructureError, SqliteRepositoryPool};
+use crate::infrastructure::database::schema::saved_searches;
+use diesel::prelude::*;
+
+/// Repository for named searches, sharing the connection pool of its parent repository.
+pub struct SavedSearchRepository<'a> {
+    pool: &'a SqliteRepositoryPool,
+}
+
+impl<'a> SavedSearchRepository<'a> {
+    #[must_use]
+    /// Creates a new [`SavedSearchRepository`] on top of the given pool.
+    pub const fn new(pool: &'a SqliteRepositoryPool) -> Self {
+        Self { pool }
+    }
+
+    /// Stores a saved search, replacing any existing one with the same name.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during the insert operation.
+    pub fn save(&self, search: &SavedSearch) -> Result<(), InfrastructureError> {
+        self.pool.execute_db_operation(|conn| {
+            diesel::replace_into(saved_searches::table)
+                .values(SavedSearchDto::from(search))
+                .execute(conn)?;
+            Ok(())
+        })
+    }
+
+    /// Retrieves every saved search, ordered by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during query execution.
+    pub fn find_all(&self) -> Result<Vec<SavedSearch>, InfrastructureError> {
+        self.pool.execute_db_operation(|conn| {
+            Ok(saved_searches::table
+                .order(saved_searches::name.asc())
+                .load::<SavedSearchDto>(conn)?
+                .into_iter()
+                .map(SavedSearch::from)
+                .collect())
+        })
+    }
+
+    /// Retrieves the saved search with the given name, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during query execution.
+    pub fn find_by_name(&self, name: &str) -> Result<Option<SavedSearch>, InfrastructureError> {
+        self.pool.execute_db_operation(|conn| {
+            Ok(saved_searches::table
+                .filter(saved_searches::name.eq(name))
+                .first::<SavedSearchDto>(conn)
+                .optional()?
+                .map(SavedSearch::from))
+        })
+    }
+
+    /// Deletes the saved search with the given name.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`InfrastructureError`] if:
+    /// - A [`ConnectionPool`](InfrastructureError::ConnectionPool) error occurs while acquiring a connection.
+    /// - A [`Database`](InfrastructureError::Database) error occurs during the delete operation.
+    pub fn delete(&self, name: &str) -> Result<(), InfrastructureError> {
+        self.pool.execute_db_operation(|conn| {
+            diesel::delete(saved_searches::table.filter(saved_searches::name.eq(name)))
+                .execute(conn)?;
+            Ok(())
+        })
+    }
+}
@@ -1,4 +1,7 @@
-use diesel::{allow_tables_to_appear_in_same_query, joinable, table};
+use diesel::{
+    allow_columns_to_appear_in_same_group_by_clause, allow_tables_to_appear_in_same_query,
+    joinable, table,
+};
 
 table! {
     file_categories (id) {
@@ -14,6 +17,15 @@ table! {
         name -> Text,
         available_space -> BigInt,
         insertion_time -> Timestamp,
+        root_path -> Nullable<Text>,
+        total_space -> BigInt,
+    }
+}
+
+table! {
+    drive_tags (drive_id, tag) {
+        drive_id -> Binary,
+        tag -> Text,
     }
 }
 
@@ -23,6 +35,35 @@ table! {
         drive_id -> Binary,
         path -> Text,
         weight -> BigInt,
+        filename -> Text,
+        extension -> Text,
+        modified_time -> Nullable<Timestamp>,
+        owner_uid -> Nullable<BigInt>,
+        owner_gid -> Nullable<BigInt>,
+        mode -> Nullable<BigInt>,
+    }
+}
+
+table! {
+    index_runs (id) {
+        id -> Binary,
+        indexed_at -> Timestamp,
+        directories -> Text,
+        category_name -> Text,
+        drive_name -> Text,
+        file_count -> BigInt,
+        duration_ms -> BigInt,
+    }
+}
+
+table! {
+    saved_searches (name) {
+        name -> Text,
+        drive_name -> Nullable<Text>,
+        query -> Text,
+        filename_only -> Bool,
+        extension -> Nullable<Text>,
+        whole_segments -> Bool,
     }
 }
 
@@ -34,6 +75,25 @@ table! {
 }
 
 joinable!(drive_entries -> file_categories (category_id));
+joinable!(drive_tags -> drive_entries (drive_id));
 joinable!(file_entries -> drive_entries (drive_id));
 
-allow_tables_to_appear_in_same_query!(file_categories, drive_entries, file_entries,);
+allow_tables_to_appear_in_same_query!(file_categories, drive_entries, drive_tags, file_entries,);
+
+allow_columns_to_appear_in_same_group_by_clause!(
+    file_categories::id,
+    file_categories::name,
+    drive_entries::id,
+    drive_entries::category_id,
+    drive_entries::name,
+    drive_entries::available_space,
+    drive_entries::insertion_time,
+    drive_entries::root_path,
+    drive_entries::total_space,
+    file_entries::id,
+    file_entries::drive_id,
+    file_entries::path,
+    file_entries::weight,
+    file_entries::filename,
+    file_entries::extension,
+);
@@ -1,27 +1,99 @@
+use crate::domain::disk_lister::{disk_holding, is_mounted, DiskInfoProvider, DiskLister};
 use crate::domain::model::directory::DirectoryData;
-use crate::utils::dialogs::popup_error_and_exit;
-use std::path::Path;
+use crate::domain::model::mounted_disk::MountedDisk;
+use crate::infrastructure::database::pool::InfrastructureError;
+use std::path::{Path, PathBuf};
 use sysinfo::{DiskRefreshKind, Disks};
 
-#[must_use]
-pub fn directory_data(directory: &Path) -> DirectoryData {
-    let disks = Disks::new_with_refreshed_list_specifics(DiskRefreshKind::with_storage(
-        DiskRefreshKind::default(),
-    ));
+/// Disks listed by the operating system.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemDisks;
 
+impl DiskLister for SystemDisks {
+    fn mounted_disks(&self) -> Vec<MountedDisk> {
+        let disks = Disks::new_with_refreshed_list_specifics(DiskRefreshKind::with_storage(
+            DiskRefreshKind::default(),
+        ));
+        disks
+            .iter()
+            .map(|disk| MountedDisk {
+                name: disk.name().to_string_lossy().to_string(),
+                mount_point: disk.mount_point().to_path_buf(),
+                available_space: disk.available_space(),
+                total_space: disk.total_space(),
+                is_removable: disk.is_removable(),
+            })
+            .collect()
+    }
+}
+
+impl DiskInfoProvider for SystemDisks {
+    fn disk_info(&self, path: &Path) -> Option<MountedDisk> {
+        disk_holding(self.mounted_disks(), path)
+    }
+}
+
+/// What a path handed to the indexing form, picked or dropped on the window, turned out to be.
+#[derive(Debug)]
+pub enum PickedDirectory {
+    /// A folder on a detected disk.
+    Found(DirectoryData),
+    /// A folder whose disk could not be detected, described from its path instead.
+    WithoutDisk(DirectoryData, InfrastructureError),
+    /// Anything else, such as a file or a path that no longer exists.
+    NotADirectory(PathBuf),
+}
+
+/// Finds the disk holding `directory` through `disks`.
+///
+/// # Errors
+///
+/// Returns [`DiskNotFound`](InfrastructureError::DiskNotFound) if no mounted disk holds the
+/// directory, as happens with some network shares or unusual mounts.
+pub fn directory_data(
+    disks: &impl DiskInfoProvider,
+    directory: &Path,
+) -> Result<DirectoryData, InfrastructureError> {
     let disk = disks
-        .iter()
-        .find(|disk| directory.starts_with(disk.mount_point()))
-        .unwrap_or_else(|| {
-            popup_error_and_exit(format!(
-                "Cannot find the disk for directory {}",
-                directory.display()
-            ))
-        });
-
-    DirectoryData {
-        drive_name: disk.name().to_string_lossy().to_string(),
-        drive_available_space: disk.available_space(),
+        .disk_info(directory)
+        .ok_or_else(|| InfrastructureError::DiskNotFound(directory.to_path_buf()))?;
+
+    Ok(DirectoryData {
+        drive_name: disk.name,
+        drive_available_space: disk.available_space,
+        drive_total_space: disk.total_space,
         directory: directory.to_path_buf(),
+    })
+}
+
+/// Checks that `path` is a folder and finds the disk holding it through `disks`.
+///
+/// A folder on none of them is still accepted, with its drive guessed from the path.
+pub fn pick_directory(disks: &impl DiskInfoProvider, path: &Path) -> PickedDirectory {
+    if !path.is_dir() {
+        return PickedDirectory::NotADirectory(path.to_path_buf());
+    }
+    match directory_data(disks, path) {
+        Ok(data) => PickedDirectory::Found(data),
+        Err(error) => PickedDirectory::WithoutDisk(DirectoryData::without_disk(path), error),
+    }
+}
+
+/// Checks that the disk picked for indexing is still plugged in.
+///
+/// # Errors
+///
+/// Returns [`DiskUnmounted`](InfrastructureError::DiskUnmounted) if no disk is mounted at
+/// `mount_point` anymore.
+pub fn ensure_mounted(
+    lister: &impl DiskLister,
+    mount_point: &Path,
+) -> Result<(), InfrastructureError> {
+    if is_mounted(lister, mount_point) {
+        Ok(())
+    } else {
+        Err(InfrastructureError::DiskUnmounted(
+            mount_point.to_path_buf(),
+        ))
     }
 }
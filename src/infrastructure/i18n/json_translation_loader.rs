@@ -1,9 +1,23 @@
 use crate::domain::model::language::Language;
 use crate::infrastructure::database::pool::InfrastructureError;
 use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const TRANSLATION_FILES: [(&str, &str); 4] = [
+    ("en", include_str!("../../../translations/en.json")),
+    ("fr", include_str!("../../../translations/fr.json")),
+    ("de", include_str!("../../../translations/de.json")),
+    ("es", include_str!("../../../translations/es.json")),
+];
 
 /// Loads translation strings for the given language.
 ///
+/// The embedded translations are loaded first, then overridden key by key by the
+/// `translations/<code>.json` file located next to the executable, if there is one
+/// (e.g. `translations/de.json` for German).
+///
 /// # Parameters
 ///
 /// - `language`: The language for which to load translations.
@@ -15,13 +29,45 @@ use std::collections::HashMap;
 /// # Errors
 ///
 /// Returns an [`InfrastructureError`] if:
-/// - The JSON translation file for the requested language cannot be deserialized.
+/// - The external translation file exists but cannot be read.
+/// - The embedded or external JSON translation file cannot be deserialized.
 pub fn load_translations(
     language: &Language,
 ) -> Result<HashMap<String, String>, InfrastructureError> {
-    let data = match language {
-        Language::English => include_str!("../../../translations/en.json"),
-        Language::French => include_str!("../../../translations/fr.json"),
-    };
-    Ok(serde_json::from_str(data)?)
+    load_translations_from(language, external_translations_dir().as_deref())
+}
+
+/// Loads translation strings for the given language, using `external_dir` as the override
+/// directory instead of the one next to the executable.
+///
+/// # Errors
+///
+/// Returns an [`InfrastructureError`] if:
+/// - The external translation file exists but cannot be read.
+/// - The embedded or external JSON translation file cannot be deserialized.
+pub fn load_translations_from(
+    language: &Language,
+    external_dir: Option<&Path>,
+) -> Result<HashMap<String, String>, InfrastructureError> {
+    let data = TRANSLATION_FILES
+        .iter()
+        .find(|(code, _)| *code == language.code())
+        .map_or("{}", |(_, data)| data);
+    let mut translations: HashMap<String, String> = serde_json::from_str(data)?;
+
+    if let Some(path) = external_dir
+        .map(|dir| dir.join(format!("{}.json", language.code())))
+        .filter(|path| path.is_file())
+    {
+        let overrides: HashMap<String, String> = serde_json::from_str(&fs::read_to_string(path)?)?;
+        translations.extend(overrides);
+    }
+
+    Ok(translations)
+}
+
+fn external_translations_dir() -> Option<PathBuf> {
+    env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.join("translations")))
 }
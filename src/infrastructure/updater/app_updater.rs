@@ -1,10 +1,40 @@
-use crate::utils::dialogs::{popup_error, popup_info};
-use self_update::backends::github::Update;
-use std::path::PathBuf;
+use crate::utils::localized_error::{io_message_key, LocalizedError};
+use self_update::backends::github::{Update, UpdateBuilder};
+use self_update::update::{Release, ReleaseAsset, ReleaseUpdate};
+use self_update::version::{bump_is_compatible, bump_is_greater};
+use self_update::{get_target, self_replace, TempDir};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io::{Read, Write};
 use std::process::{exit, Command};
+use std::time::Duration;
 use std::{env, io};
 use thiserror::Error;
 
+/// Argument passed to the restarted binary, so it does not look for updates again.
+pub const UPDATED_ARG: &str = "--updated";
+
+/// Where releases are looked up, set at build time so a fork can redirect updates.
+///
+/// Building with `LISTER_UPDATE_REPO_OWNER=` (empty) turns updates off.
+pub const UPDATE_SOURCE: UpdateSource = UpdateSource {
+    repo_owner: or_default(option_env!("LISTER_UPDATE_REPO_OWNER"), "nashception"),
+    repo_name: or_default(option_env!("LISTER_UPDATE_REPO_NAME"), "lister"),
+    bin_name: or_default(option_env!("LISTER_UPDATE_BIN_NAME"), "lister"),
+};
+
+const DOWNLOAD_CHUNK_SIZE: usize = 64 * 1024;
+
+const GITHUB_API_URL: &str = "https://api.github.com";
+
+/// Connecting to GitHub longer than this means the network is unusable, as when offline.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// A release lookup not answered within this time is given up.
+const LOOKUP_TIMEOUT: Duration = Duration::from_secs(10);
+
 #[derive(Debug, Error)]
 pub enum UpdateError {
     #[error("I/O error: {0}")]
@@ -12,53 +42,371 @@ pub enum UpdateError {
 
     #[error("Self-update error: {0}")]
     SelfUpdate(#[from] self_update::errors::Error),
+
+    #[error("Download error: {0}")]
+    Download(#[from] reqwest::Error),
+
+    #[error("Release asset not found: {0}")]
+    MissingAsset(String),
+
+    #[error("Checksum mismatch: expected {expected}, got {actual}")]
+    ChecksumMismatch { expected: String, actual: String },
 }
 
-pub fn self_update() {
-    let exe_path = match env::current_exe() {
-        Ok(path) => path,
-        Err(e) => {
-            popup_error(format!("Failed to get current exe path: {e}"));
-            return;
-        }
-    };
-    let just_updated = env::args().any(|arg| arg == "--updated");
-
-    if !just_updated {
-        match try_update() {
-            Ok(new_version) => {
-                if !new_version.is_empty() {
-                    popup_info(format!("New version has been installed: {new_version}"));
-                    if let Err(e) = restart(exe_path) {
-                        popup_error(format!("Failed to restart: {e}"));
-                    }
-                }
+impl UpdateError {
+    /// Translation key of a message telling the user what likely went wrong and what to do.
+    #[must_use]
+    pub fn message_key(&self) -> &'static str {
+        match self {
+            Self::Io(error) => io_message_key(error),
+            Self::Download(_) => "error_update_download",
+            Self::SelfUpdate(_) | Self::MissingAsset(_) | Self::ChecksumMismatch { .. } => {
+                "error_update"
             }
-            Err(e) => popup_error(format!("Update failed: {e}")),
         }
     }
 }
 
-fn try_update() -> Result<String, UpdateError> {
-    let status = Update::configure()
-        .repo_owner("nashception")
-        .repo_name("lister")
-        .bin_name("lister")
-        .current_version(env!("CARGO_PKG_VERSION"))
-        .no_confirm(true)
-        .show_output(false)
-        .build()?
-        .update()?;
+impl LocalizedError for UpdateError {
+    fn message_key(&self) -> Option<&'static str> {
+        Some(Self::message_key(self))
+    }
+}
 
-    Ok(if status.updated() {
-        String::from(status.version())
-    } else {
-        String::new()
+/// GitHub repository publishing the releases, and the binary name in their assets.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct UpdateSource {
+    pub repo_owner: &'static str,
+    pub repo_name: &'static str,
+    pub bin_name: &'static str,
+}
+
+impl UpdateSource {
+    /// Tells whether a repository is set; without one, looking for updates is skipped.
+    #[must_use]
+    pub const fn is_configured(&self) -> bool {
+        !self.repo_owner.is_empty() && !self.repo_name.is_empty()
+    }
+}
+
+/// Fields of a GitHub release read by the lookups.
+#[derive(Deserialize)]
+struct GitHubRelease {
+    tag_name: String,
+    body: Option<String>,
+}
+
+impl From<GitHubRelease> for AvailableUpdate {
+    fn from(release: GitHubRelease) -> Self {
+        Self {
+            version: release.tag_name.trim_start_matches('v').to_string(),
+            release_notes: release.body.unwrap_or_default(),
+        }
+    }
+}
+
+/// A release published on GitHub that is newer than the running binary.
+#[derive(Clone, Debug)]
+pub struct AvailableUpdate {
+    pub version: String,
+    pub release_notes: String,
+}
+
+/// Bytes of the release binary received so far.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct DownloadProgress {
+    pub downloaded: u64,
+    /// Size announced by the server, when it sends one.
+    pub total: Option<u64>,
+}
+
+impl DownloadProgress {
+    /// Returns the completed share of the download, between 0 and 1.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn fraction(&self) -> Option<f32> {
+        self.total
+            .filter(|&total| total > 0)
+            .map(|total| (self.downloaded as f32 / total as f32).min(1.0))
+    }
+}
+
+/// Tells whether this process was started by [`restart`] right after an update.
+#[must_use]
+pub fn was_just_updated() -> bool {
+    env::args().any(|arg| arg == UPDATED_ARG)
+}
+
+/// Runs an update lookup the user did not ask for, such as the one on startup.
+///
+/// A failure, most often being offline, is only logged: it must not interrupt the user.
+pub fn look_up_quietly(
+    lookup: impl FnOnce() -> Result<Option<AvailableUpdate>, UpdateError>,
+) -> Option<AvailableUpdate> {
+    lookup().unwrap_or_else(|error| {
+        eprintln!("Update check skipped: {error}");
+        None
     })
 }
 
-fn restart(exe_path: PathBuf) -> Result<(), UpdateError> {
-    Command::new(exe_path).arg("--updated").spawn()?;
+/// Source of releases, injected where the application looks for and installs updates.
+pub trait Updater: Send + Sync {
+    /// Tells whether updates can be looked up at all; when not, checking is not offered.
+    fn is_enabled(&self) -> bool;
+
+    /// Looks up the latest release, if it is newer than the running binary.
+    ///
+    /// # Errors
+    /// Returns an error if the releases cannot be fetched.
+    fn check(&self) -> Result<Option<AvailableUpdate>, UpdateError>;
+
+    /// Looks up the newest release that is semver-compatible with the running binary.
+    ///
+    /// # Errors
+    /// Returns an error if the releases cannot be fetched.
+    fn check_compatible(&self) -> Result<Option<AvailableUpdate>, UpdateError>;
+
+    /// Downloads, verifies and installs the given release in place of the running binary.
+    ///
+    /// `on_progress` is called as the release binary downloads.
+    ///
+    /// # Errors
+    /// Returns an error if the release cannot be downloaded, verified or installed.
+    fn apply(
+        &self,
+        update: &AvailableUpdate,
+        on_progress: &mut dyn FnMut(DownloadProgress),
+    ) -> Result<(), UpdateError>;
+}
+
+/// Updater fetching the releases published on a GitHub repository.
+#[derive(Clone, Copy, Debug)]
+pub struct GitHubUpdater {
+    source: UpdateSource,
+}
+
+impl GitHubUpdater {
+    #[must_use]
+    pub const fn new(source: UpdateSource) -> Self {
+        Self { source }
+    }
+
+    /// Fetches a document of the repository from the GitHub API.
+    ///
+    /// Unlike `self_update`, the request gives up within [`CONNECT_TIMEOUT`] when offline and
+    /// within [`LOOKUP_TIMEOUT`] overall. Like any reqwest client, it goes through the proxy
+    /// set in the environment.
+    fn fetch<T: DeserializeOwned>(&self, path: &str) -> Result<T, UpdateError> {
+        let url = format!(
+            "{GITHUB_API_URL}/repos/{}/{}/{path}",
+            self.source.repo_owner, self.source.repo_name
+        );
+        let body = reqwest::blocking::Client::builder()
+            .use_rustls_tls()
+            .connect_timeout(CONNECT_TIMEOUT)
+            .timeout(LOOKUP_TIMEOUT)
+            .user_agent(concat!("lister/", env!("CARGO_PKG_VERSION")))
+            .build()?
+            .get(url)
+            .header("accept", "application/vnd.github+json")
+            .send()?
+            .error_for_status()?
+            .bytes()?;
+        Ok(serde_json::from_slice(&body).map_err(self_update::errors::Error::from)?)
+    }
+}
+
+impl Updater for GitHubUpdater {
+    fn is_enabled(&self) -> bool {
+        self.source.is_configured()
+    }
+
+    /// Returns `None` without going online when the source is not configured.
+    ///
+    /// # Errors
+    /// Returns [`Download`](UpdateError::Download) if the latest release cannot be fetched in
+    /// time, or [`SelfUpdate`](UpdateError::SelfUpdate) if it cannot be read.
+    fn check(&self) -> Result<Option<AvailableUpdate>, UpdateError> {
+        if !self.is_enabled() {
+            return Ok(None);
+        }
+        let release: GitHubRelease = self.fetch("releases/latest")?;
+        let update = AvailableUpdate::from(release);
+
+        Ok(is_newer_version(env!("CARGO_PKG_VERSION"), &update.version).then_some(update))
+    }
+
+    /// Returns `None` without going online when the source is not configured.
+    ///
+    /// # Errors
+    /// Returns [`Download`](UpdateError::Download) if the release list cannot be fetched in
+    /// time, or [`SelfUpdate`](UpdateError::SelfUpdate) if it cannot be read.
+    fn check_compatible(&self) -> Result<Option<AvailableUpdate>, UpdateError> {
+        if !self.is_enabled() {
+            return Ok(None);
+        }
+        let releases: Vec<GitHubRelease> = self.fetch("releases")?;
+
+        // Listed newest first
+        Ok(releases
+            .into_iter()
+            .map(AvailableUpdate::from)
+            .find(|update| is_compatible_version(env!("CARGO_PKG_VERSION"), &update.version)))
+    }
+
+    /// # Errors
+    /// Returns [`SelfUpdate`](UpdateError::SelfUpdate) if the release lookup or the replacement
+    /// fails, [`Download`](UpdateError::Download) if an asset cannot be downloaded, or
+    /// [`MissingAsset`](UpdateError::MissingAsset) or [`ChecksumMismatch`](UpdateError::ChecksumMismatch)
+    /// if the release binary cannot be verified.
+    fn apply(
+        &self,
+        update: &AvailableUpdate,
+        on_progress: &mut dyn FnMut(DownloadProgress),
+    ) -> Result<(), UpdateError> {
+        let updater = configure_update(&self.source).build()?;
+        let release = updater.get_release_version(&format!("v{}", update.version))?;
+        install_release(updater.as_ref(), &release, on_progress)
+    }
+}
+
+/// Starts the updated binary and exits the current process.
+///
+/// # Errors
+/// Returns [`Io`](UpdateError::Io) if the updated binary cannot be started.
+pub fn restart() -> Result<(), UpdateError> {
+    Command::new(env::current_exe()?).arg(UPDATED_ARG).spawn()?;
 
     exit(0);
 }
+
+#[must_use]
+pub fn is_newer_version(current: &str, candidate: &str) -> bool {
+    bump_is_greater(current, candidate).unwrap_or(false)
+}
+
+/// Tells whether `candidate` is newer than `current` without a breaking version bump.
+#[must_use]
+pub fn is_compatible_version(current: &str, candidate: &str) -> bool {
+    bump_is_compatible(current, candidate).unwrap_or(false)
+}
+
+/// Checks `data` against the content of a `.sha256` release asset.
+///
+/// The checksum file is expected in the `sha256sum` format: the hex digest optionally followed
+/// by the file name.
+///
+/// # Errors
+/// Returns [`ChecksumMismatch`](UpdateError::ChecksumMismatch) if the digests differ.
+pub fn verify_checksum(data: &[u8], checksum_file: &str) -> Result<(), UpdateError> {
+    let expected = checksum_file
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    let actual = format!("{:x}", Sha256::digest(data));
+
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(UpdateError::ChecksumMismatch { expected, actual })
+    }
+}
+
+fn install_release(
+    updater: &dyn ReleaseUpdate,
+    release: &Release,
+    on_progress: &mut dyn FnMut(DownloadProgress),
+) -> Result<(), UpdateError> {
+    let target = get_target();
+    let binary_asset = release
+        .assets
+        .iter()
+        .find(|asset| asset.name.contains(target) && !asset.name.ends_with(".sha256"))
+        .ok_or_else(|| UpdateError::MissingAsset(format!("{}-{target}", updater.bin_name())))?;
+    let checksum_name = format!("{}.sha256", binary_asset.name);
+    let checksum_asset = release
+        .assets
+        .iter()
+        .find(|asset| asset.name == checksum_name)
+        .ok_or(UpdateError::MissingAsset(checksum_name))?;
+
+    let temp_dir = TempDir::new()?;
+    let binary_path = temp_dir.path().join(&binary_asset.name);
+    download(
+        updater,
+        binary_asset,
+        File::create(&binary_path)?,
+        on_progress,
+    )?;
+
+    let mut checksum_file = Vec::new();
+    download(updater, checksum_asset, &mut checksum_file, &mut |_| {})?;
+
+    verify_checksum(
+        &fs::read(&binary_path)?,
+        &String::from_utf8_lossy(&checksum_file),
+    )?;
+
+    self_replace::self_replace(&binary_path)?;
+
+    Ok(())
+}
+
+fn download(
+    updater: &dyn ReleaseUpdate,
+    asset: &ReleaseAsset,
+    mut destination: impl Write,
+    on_progress: &mut dyn FnMut(DownloadProgress),
+) -> Result<(), UpdateError> {
+    let mut headers = updater.api_headers(&None)?;
+    headers.insert(
+        "accept",
+        "application/octet-stream"
+            .parse()
+            .expect("Static header value is valid"),
+    );
+
+    let mut response = reqwest::blocking::Client::builder()
+        .use_rustls_tls()
+        .connect_timeout(CONNECT_TIMEOUT)
+        .build()?
+        .get(&asset.download_url)
+        .headers(headers)
+        .send()?
+        .error_for_status()?;
+
+    let mut progress = DownloadProgress {
+        downloaded: 0,
+        total: response.content_length(),
+    };
+    let mut buffer = vec![0; DOWNLOAD_CHUNK_SIZE];
+    loop {
+        let read = response.read(&mut buffer)?;
+        if read == 0 {
+            return Ok(());
+        }
+        destination.write_all(&buffer[..read])?;
+        progress.downloaded += read as u64;
+        on_progress(progress);
+    }
+}
+
+const fn or_default(value: Option<&'static str>, default: &'static str) -> &'static str {
+    match value {
+        Some(value) => value,
+        None => default,
+    }
+}
+
+fn configure_update(source: &UpdateSource) -> UpdateBuilder {
+    let mut builder = Update::configure();
+    builder
+        .repo_owner(source.repo_owner)
+        .repo_name(source.repo_name)
+        .bin_name(source.bin_name)
+        .current_version(env!("CARGO_PKG_VERSION"))
+        .no_confirm(true)
+        .show_output(false);
+    builder
+}
@@ -1,4 +1,5 @@
 pub mod application;
+pub mod cli;
 pub mod domain;
 pub mod infrastructure;
 pub mod ui;
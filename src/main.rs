@@ -1,17 +1,33 @@
 #![windows_subsystem = "windows"]
 
-use lister::infrastructure::updater::app_updater::self_update;
+use clap::Parser;
+use lister::cli::args::CliArgs;
+use lister::cli::commands;
+use lister::cli::console::attach_parent_console;
 use lister::ui::app::ListerApp;
-use lister::ui::app_factory::create;
+use lister::ui::app_factory::{create, create_updater};
+use std::env;
+use std::process::exit;
 
 #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
 compile_error!("This application only supports Linux, macOS and Windows");
 
 fn main() -> iced::Result {
-    self_update();
+    // An option of the headless mode switches to it; the window stays the default
+    if CliArgs::is_requested(env::args_os()) {
+        attach_parent_console();
+        if let Err(error) = commands::run(CliArgs::parse()) {
+            eprintln!("{error}");
+            exit(1);
+        }
+        return Ok(());
+    }
+
+    let repository = create();
+    let updater = create_updater();
 
     iced::application(
-        || ListerApp::new(create()),
+        move || ListerApp::new(repository.clone(), updater.clone()),
         ListerApp::update,
         ListerApp::view,
     )
@@ -1,60 +1,132 @@
+use crate::application::shortcut::{KeyPress, Shortcut, ShortcutKey};
 use crate::domain::model::language::Language;
 use crate::infrastructure::database::repository::ListerRepository;
 use crate::infrastructure::i18n::json_translation_loader::load_translations;
+use crate::infrastructure::updater::app_updater::{
+    look_up_quietly, restart, was_just_updated, AvailableUpdate, DownloadProgress, Updater,
+};
 use crate::tr;
+use crate::ui::components::shortcut_help;
 use crate::ui::messages::app_message::AppMessage;
 use crate::ui::messages::toaster_message::ToasterMessage;
+use crate::ui::messages::write_message::WriteMessage;
+use crate::ui::pages::browse_page::BrowsePage;
+use crate::ui::pages::compare_page::ComparePage;
 use crate::ui::pages::delete_page::DeletePage;
+use crate::ui::pages::manage_page::ManagePage;
 use crate::ui::pages::read_page::ReadPage;
+use crate::ui::pages::settings_page::SettingsPage;
+use crate::ui::pages::stats_page::StatsPage;
 use crate::ui::pages::write_page::WritePage;
-use crate::utils::dialogs::popup_error;
+use crate::utils::blocking::run_blocking;
+use crate::utils::dialogs::{confirm, popup_error, popup_info, use_dialog_translations};
 use humansize::{format_size, DECIMAL};
-use iced::keyboard::key::Named;
-use iced::keyboard::Modifiers;
+use iced::advanced::widget;
+use iced::advanced::widget::operation::focusable;
+use iced::futures::channel::mpsc;
+use iced::keyboard::key::{Code, Named, Physical};
 use iced::widget::operation::{focus_next, focus_previous};
-use iced::widget::{button, column, row, text, Space};
-use iced::window::{icon, Icon, Settings};
-use iced::{event, keyboard, Alignment, Element, Event, Length, Subscription, Task};
+use iced::widget::{
+    button, center, column, container, opaque, pick_list, progress_bar, row, stack, text, Space,
+};
+use iced::window::{self, icon, Icon, Settings};
+use iced::{event, keyboard, Alignment, Color, Element, Event, Length, Subscription, Task};
 use iced_toaster::{info_toast, toaster, Toaster};
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// Time left to read the restart notice before the updated binary replaces the window.
+const RESTART_DELAY: Duration = Duration::from_secs(2);
 
 enum Page {
+    Browse(BrowsePage),
+    Compare(ComparePage),
     Delete(DeletePage),
-    Read(ReadPage),
+    Manage(ManagePage),
+    Read(Box<ReadPage>),
+    Settings(SettingsPage),
+    Stats(StatsPage),
     Write(WritePage),
 }
 
+impl Page {
+    const fn kind(&self) -> PageKind {
+        match self {
+            Self::Browse(_) => PageKind::Browse,
+            Self::Compare(_) => PageKind::Compare,
+            Self::Delete(_) => PageKind::Delete,
+            Self::Manage(_) => PageKind::Manage,
+            Self::Read(_) => PageKind::Read,
+            Self::Settings(_) => PageKind::Settings,
+            Self::Stats(_) => PageKind::Stats,
+            Self::Write(_) => PageKind::Write,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum PageKind {
+    Browse,
+    Compare,
     Delete,
+    Manage,
     Read,
+    Settings,
+    Stats,
     Write,
 }
 
 pub struct ListerApp {
     repository: Arc<ListerRepository>,
+    updater: Arc<dyn Updater>,
     current_language: Language,
     translations: HashMap<String, String>,
     current_page: Page,
     toaster: Toaster<AppMessage>,
+    update_progress: Option<UpdateProgress>,
+    is_help_shown: bool,
+}
+
+/// Download of a release, shown in a modal until the application restarts.
+struct UpdateProgress {
+    version: String,
+    download: DownloadProgress,
+    is_installed: bool,
 }
 
 impl ListerApp {
-    pub fn new(repository: Arc<ListerRepository>) -> (Self, Task<AppMessage>) {
+    pub fn new(
+        repository: Arc<ListerRepository>,
+        updater: Arc<dyn Updater>,
+    ) -> (Self, Task<AppMessage>) {
         let (current_language, translations) = repository.translations();
+        use_dialog_translations(translations.clone());
 
         let (read_page, task) = ReadPage::new(repository.clone());
+        let auto_update = repository.get_auto_update().unwrap_or_else(|err| {
+            popup_error(err);
+            true
+        });
+        let update_task = if auto_update && !was_just_updated() {
+            Self::auto_update(updater.clone())
+        } else {
+            Task::none()
+        };
 
         (
             Self {
                 repository,
+                updater,
                 current_language,
                 translations,
-                current_page: Page::Read(read_page),
+                current_page: Page::Read(Box::new(read_page)),
                 toaster: toaster(),
+                update_progress: None,
+                is_help_shown: false,
             },
-            task.map(AppMessage::Read),
+            Task::batch([task.map(AppMessage::Read), update_task]),
         )
     }
 
@@ -71,8 +143,13 @@ impl ListerApp {
         format!(
             "{} (v{})",
             match &self.current_page {
+                Page::Browse(_) => BrowsePage::title(&self.translations),
+                Page::Compare(_) => ComparePage::title(&self.translations),
                 Page::Delete(_) => DeletePage::title(&self.translations),
+                Page::Manage(_) => ManagePage::title(&self.translations),
                 Page::Read(_) => ReadPage::title(&self.translations),
+                Page::Settings(_) => SettingsPage::title(&self.translations),
+                Page::Stats(_) => StatsPage::title(&self.translations),
                 Page::Write(_) => WritePage::title(&self.translations),
             },
             env!("CARGO_PKG_VERSION")
@@ -85,15 +162,31 @@ impl ListerApp {
         let nav_bar = self.nav_bar();
 
         let content = match &self.current_page {
+            Page::Browse(page) => page.view(&self.translations).map(AppMessage::Browse),
+            Page::Compare(page) => page.view(&self.translations).map(AppMessage::Compare),
             Page::Delete(page) => page.view(&self.translations).map(AppMessage::Delete),
+            Page::Manage(page) => page.view(&self.translations).map(AppMessage::Manage),
             Page::Read(page) => page
                 .view(&self.translations, &self.current_language)
                 .map(AppMessage::Read),
+            Page::Settings(page) => page.view(&self.translations).map(AppMessage::Settings),
+            Page::Stats(page) => page
+                .view(&self.translations, &self.current_language)
+                .map(AppMessage::Stats),
             Page::Write(page) => page.view(&self.translations).map(AppMessage::Write),
         };
 
+        let page = column![toolbar, Space::new().height(10), nav_bar, content].padding(20);
+        let body: Element<'_, AppMessage> = match &self.update_progress {
+            Some(update) => stack![page, self.update_modal(update)].into(),
+            None if self.is_help_shown => {
+                stack![page, shortcut_help::view(&self.translations)].into()
+            }
+            None => page.into(),
+        };
+
         self.toaster.view(
-            column![toolbar, Space::new().height(10), nav_bar, content].padding(20),
+            body,
             |toast_id| AppMessage::ToastMessage(ToasterMessage::DismissToast(toast_id)),
             |toast_id, bool| AppMessage::ToastMessage(ToasterMessage::HoverToast(toast_id, bool)),
         )
@@ -105,36 +198,74 @@ impl ListerApp {
             AppMessage::ChangePage(page_kind) => self.change_page(page_kind),
             AppMessage::ChangePageNext => {
                 let next = match self.current_page {
-                    Page::Delete(_) => PageKind::Read,
-                    Page::Read(_) => PageKind::Write,
+                    Page::Browse(_) => PageKind::Write,
+                    Page::Compare(_) => PageKind::Stats,
+                    Page::Delete(_) => PageKind::Manage,
+                    Page::Manage(_) => PageKind::Compare,
+                    Page::Read(_) => PageKind::Browse,
+                    Page::Settings(_) => PageKind::Read,
+                    Page::Stats(_) => PageKind::Settings,
                     Page::Write(_) => PageKind::Delete,
                 };
 
                 self.change_page(next)
             }
+            AppMessage::CheckForUpdates => self.check_for_updates(),
+            AppMessage::UpdateAccepted(update) => self.start_update(update),
+            AppMessage::UpdateProgressed(download) => {
+                if let Some(update) = &mut self.update_progress {
+                    update.download = download;
+                }
+                Task::none()
+            }
+            AppMessage::UpdateFinished(is_installed) => {
+                if !is_installed {
+                    self.update_progress = None;
+                    return Task::none();
+                }
+                if let Some(update) = &mut self.update_progress {
+                    update.is_installed = true;
+                }
+                Task::future(run_blocking(|| {
+                    thread::sleep(RESTART_DELAY);
+                    restart().unwrap_or_else(popup_error);
+                }))
+                .discard()
+            }
             AppMessage::CompactDatabase => {
                 let repository = self.repository.clone();
                 Task::perform(
-                    async move {
-                        repository.compact().unwrap_or_else(|err| {
-                            popup_error(err);
-                            0
-                        })
-                    },
+                    run_blocking(move || repository.compact().map_err(popup_error).ok()),
                     AppMessage::DatabaseCompacted,
                 )
             }
-            AppMessage::DatabaseCompacted(freed_space) => {
+            AppMessage::DatabaseCompacted(None) => Task::none(),
+            AppMessage::DatabaseCompacted(Some(report)) => {
                 let translations = self.translations.clone();
                 Task::perform(
                     async move {
-                        info_toast!(
-                            tr!(&translations, "compacted", "freed_space" => &format_size(freed_space, DECIMAL))
-                        )
+                        info_toast!(tr!(&translations, "compacted",
+                            "size_before" => &format_size(report.size_before, DECIMAL),
+                            "size_after" => &format_size(report.size_after, DECIMAL),
+                            "freed_space" => &format_size(report.freed_space(), DECIMAL)))
                     },
                     |toast| AppMessage::ToastMessage(ToasterMessage::PushToast(toast)),
                 )
             }
+            AppMessage::Browse(msg) => {
+                if let Page::Browse(page) = &mut self.current_page {
+                    page.update(msg).map(AppMessage::Browse)
+                } else {
+                    Task::none()
+                }
+            }
+            AppMessage::Compare(msg) => {
+                if let Page::Compare(page) = &mut self.current_page {
+                    page.update(msg).map(AppMessage::Compare)
+                } else {
+                    Task::none()
+                }
+            }
             AppMessage::Delete(msg) => {
                 if let Page::Delete(page) = &mut self.current_page {
                     page.update(msg).map(AppMessage::Delete)
@@ -144,9 +275,17 @@ impl ListerApp {
             }
             AppMessage::LanguageChanged(language, translations) => {
                 self.current_language = language;
+                use_dialog_translations(translations.clone());
                 self.translations = translations;
                 Task::none()
             }
+            AppMessage::Manage(msg) => {
+                if let Page::Manage(page) = &mut self.current_page {
+                    page.update(msg).map(AppMessage::Manage)
+                } else {
+                    Task::none()
+                }
+            }
             AppMessage::Read(msg) => {
                 if let Page::Read(page) = &mut self.current_page {
                     page.update(msg).map(AppMessage::Read)
@@ -154,14 +293,59 @@ impl ListerApp {
                     Task::none()
                 }
             }
-            AppMessage::TabPressed { shift } => {
-                if shift {
-                    focus_previous()
+            AppMessage::Settings(msg) => {
+                if let Page::Settings(page) = &mut self.current_page {
+                    page.update(msg).map(AppMessage::Settings)
+                } else {
+                    Task::none()
+                }
+            }
+            AppMessage::Stats(msg) => {
+                if let Page::Stats(page) = &mut self.current_page {
+                    page.update(msg).map(AppMessage::Stats)
                 } else {
-                    focus_next()
+                    Task::none()
                 }
             }
+            AppMessage::ShortcutPressed(key_press) => {
+                // Text inputs are the only widgets taking the focus
+                widget::operate(focusable::count()).then(move |count| {
+                    match key_press.shortcut(count.focused.is_some()) {
+                        Some(Shortcut::CloseHelp) => Task::done(AppMessage::CloseHelp),
+                        Some(Shortcut::FocusNext) => focus_next(),
+                        Some(Shortcut::FocusPrevious) => focus_previous(),
+                        Some(Shortcut::NextPage) => Task::done(AppMessage::ChangePageNext),
+                        Some(Shortcut::ReadPage) => {
+                            Task::done(AppMessage::ChangePage(PageKind::Read))
+                        }
+                        Some(Shortcut::ToggleHelp) => Task::done(AppMessage::ToggleHelp),
+                        Some(Shortcut::WritePage) => {
+                            Task::done(AppMessage::ChangePage(PageKind::Write))
+                        }
+                        None => Task::none(),
+                    }
+                })
+            }
             AppMessage::ToastMessage(msg) => self.toast_message(msg),
+            AppMessage::ToggleHelp => {
+                self.is_help_shown = !self.is_help_shown;
+                Task::none()
+            }
+            AppMessage::CloseHelp => {
+                self.is_help_shown = false;
+                Task::none()
+            }
+            AppMessage::FileDropped(path) => {
+                // Dropped folders are always meant for indexing
+                let page_task = self.change_page(PageKind::Write);
+                let Page::Write(page) = &mut self.current_page else {
+                    return page_task;
+                };
+                let drop_task = page
+                    .update(WriteMessage::DirectoryDropped(path))
+                    .map(AppMessage::Write);
+                Task::batch([page_task, drop_task])
+            }
             AppMessage::Write(msg) => {
                 if let Page::Write(page) = &mut self.current_page {
                     page.update(msg).map(AppMessage::Write)
@@ -174,19 +358,35 @@ impl ListerApp {
 
     pub fn subscription(&self) -> Subscription<AppMessage> {
         let app_subscription = event::listen_with(|event, _status, _window| match event {
-            Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. }) => {
-                let keyboard::Key::Named(key) = key else {
-                    return None;
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key,
+                physical_key,
+                modifiers,
+                ..
+            }) => {
+                // Digits are matched by position, as some layouts type them with Shift
+                let key = match (key, physical_key) {
+                    (keyboard::Key::Named(Named::Tab), _) => ShortcutKey::Tab,
+                    (keyboard::Key::Named(Named::Escape), _) => ShortcutKey::Escape,
+                    (keyboard::Key::Named(Named::F1), _) => ShortcutKey::F1,
+                    (keyboard::Key::Character(c), _) if c == "?" => ShortcutKey::QuestionMark,
+                    (_, Physical::Code(Code::Digit1 | Code::Numpad1)) => ShortcutKey::Digit(1),
+                    (_, Physical::Code(Code::Digit2 | Code::Numpad2)) => ShortcutKey::Digit(2),
+                    _ => return None,
                 };
-
-                match (key, modifiers) {
-                    (Named::Tab, Modifiers::CTRL) => Some(AppMessage::ChangePageNext),
-                    (Named::Tab, _) => Some(AppMessage::TabPressed {
-                        shift: modifiers.shift(),
-                    }),
-                    _ => None,
-                }
+                // Cmd+Tab belongs to macOS, so page cycling keeps Ctrl there
+                let command = if key == ShortcutKey::Tab {
+                    modifiers.control()
+                } else {
+                    modifiers.command()
+                };
+                Some(AppMessage::ShortcutPressed(KeyPress {
+                    key,
+                    command,
+                    shift: modifiers.shift(),
+                }))
             }
+            Event::Window(window::Event::FileDropped(path)) => Some(AppMessage::FileDropped(path)),
             _ => None,
         });
 
@@ -198,8 +398,14 @@ impl ListerApp {
         };
 
         let page_subscription = match &self.current_page {
-            Page::Delete(_) | Page::Write(_) => Subscription::none(),
-            Page::Read(_) => ReadPage::subscription().map(AppMessage::Read),
+            Page::Browse(_)
+            | Page::Compare(_)
+            | Page::Delete(_)
+            | Page::Manage(_)
+            | Page::Settings(_)
+            | Page::Stats(_)
+            | Page::Write(_) => Subscription::none(),
+            Page::Read(page) => page.subscription().map(AppMessage::Read),
         };
 
         Subscription::batch(vec![
@@ -217,41 +423,45 @@ impl ListerApp {
 
     fn nav_bar(&'_ self) -> Element<'_, AppMessage> {
         row![
-            button(text(tr!(&self.translations, "read_page")).align_x(Alignment::Center))
-                .on_press(AppMessage::ChangePage(PageKind::Read))
-                .style(if matches!(&self.current_page, Page::Read(_)) {
-                    button::primary
-                } else {
-                    button::secondary
-                })
-                .width(Length::Fill),
-            button(text(tr!(&self.translations, "write_page")).align_x(Alignment::Center))
-                .on_press(AppMessage::ChangePage(PageKind::Write))
-                .style(if matches!(&self.current_page, Page::Write(_)) {
-                    button::primary
-                } else {
-                    button::secondary
-                })
-                .width(Length::Fill),
-            button(text(tr!(&self.translations, "delete_page")).align_x(Alignment::Center))
-                .on_press(AppMessage::ChangePage(PageKind::Delete))
-                .style(if matches!(&self.current_page, Page::Delete(_)) {
-                    button::primary
-                } else {
-                    button::secondary
-                })
-                .width(Length::Fill)
+            self.nav_button("read_page", PageKind::Read),
+            self.nav_button("browse_page", PageKind::Browse),
+            self.nav_button("write_page", PageKind::Write),
+            self.nav_button("delete_page", PageKind::Delete),
+            self.nav_button("manage_page", PageKind::Manage),
+            self.nav_button("compare_page", PageKind::Compare),
+            self.nav_button("stats_page", PageKind::Stats),
+            self.nav_button("settings_page", PageKind::Settings),
         ]
         .spacing(10)
         .into()
     }
 
+    fn nav_button(&'_ self, label_key: &str, page_kind: PageKind) -> Element<'_, AppMessage> {
+        button(text(tr!(&self.translations, label_key)).align_x(Alignment::Center))
+            .on_press(AppMessage::ChangePage(page_kind))
+            .style(if self.current_page.kind() == page_kind {
+                button::primary
+            } else {
+                button::secondary
+            })
+            .width(Length::Fill)
+            .into()
+    }
+
     fn toolbar(&'_ self) -> Element<'_, AppMessage> {
         row![
             Space::new().width(Length::Fill),
+            button(text(tr!(&self.translations, "check_for_updates"))).on_press_maybe(
+                self.updater
+                    .is_enabled()
+                    .then_some(AppMessage::CheckForUpdates)
+            ),
             button(text(tr!(&self.translations, "compact"))).on_press(AppMessage::CompactDatabase),
-            button(text(self.current_language.to_string()))
-                .on_press(AppMessage::ChangeLanguage(self.current_language.toggle()))
+            pick_list(
+                Language::ALL,
+                Some(self.current_language),
+                AppMessage::ChangeLanguage
+            )
         ]
         .spacing(5)
         .width(Length::Fill)
@@ -261,7 +471,7 @@ impl ListerApp {
     fn change_language(&self, language: Language) -> Task<AppMessage> {
         let language_use_case = self.repository.clone();
         Task::perform(
-            async move {
+            run_blocking(move || {
                 language_use_case
                     .set_language(&language)
                     .unwrap_or_else(popup_error);
@@ -270,33 +480,138 @@ impl ListerApp {
                     HashMap::default()
                 });
                 (language, translations)
-            },
+            }),
             |(language, translations)| AppMessage::LanguageChanged(language, translations),
         )
     }
 
-    fn change_page(&mut self, page_kind: PageKind) -> Task<AppMessage> {
-        let already_on_page = matches!(
-            (&self.current_page, page_kind),
-            (Page::Delete(_), PageKind::Delete)
-                | (Page::Read(_), PageKind::Read)
-                | (Page::Write(_), PageKind::Write)
+    fn check_for_updates(&self) -> Task<AppMessage> {
+        let translations = self.translations.clone();
+        let updater = self.updater.clone();
+        Task::future(run_blocking(move || match updater.check() {
+            Ok(Some(update)) => {
+                let title = tr!(&translations, "update_available", "version" => &update.version);
+                confirm(title, &update.release_notes).then_some(update)
+            }
+            Ok(None) => {
+                popup_info(tr!(&translations, "up_to_date"));
+                None
+            }
+            Err(err) => {
+                popup_error(err);
+                None
+            }
+        }))
+        .and_then(|update| Task::done(AppMessage::UpdateAccepted(update)))
+    }
+
+    /// Installs the newest compatible release without asking, as done on startup.
+    ///
+    /// The lookup runs in the background once the window is shown, and stays silent offline.
+    fn auto_update(updater: Arc<dyn Updater>) -> Task<AppMessage> {
+        Task::future(run_blocking(move || {
+            look_up_quietly(|| updater.check_compatible())
+        }))
+        .and_then(|update| Task::done(AppMessage::UpdateAccepted(update)))
+    }
+
+    fn start_update(&mut self, update: AvailableUpdate) -> Task<AppMessage> {
+        if self.update_progress.is_some() {
+            return Task::none();
+        }
+        self.update_progress = Some(UpdateProgress {
+            version: update.version.clone(),
+            download: DownloadProgress::default(),
+            is_installed: false,
+        });
+        let (progress_sender, progress_receiver) = mpsc::unbounded();
+        let updater = self.updater.clone();
+
+        let install = Task::perform(
+            run_blocking(move || {
+                updater
+                    .apply(&update, &mut |download| {
+                        let _ = progress_sender.unbounded_send(download);
+                    })
+                    .inspect_err(|error| popup_error(error))
+                    .is_ok()
+            }),
+            AppMessage::UpdateFinished,
         );
 
-        if already_on_page {
+        Task::batch([
+            Task::stream(progress_receiver).map(AppMessage::UpdateProgressed),
+            install,
+        ])
+    }
+
+    fn update_modal<'a>(&self, update: &UpdateProgress) -> Element<'a, AppMessage> {
+        let dialog = if update.is_installed {
+            column![text(
+                tr!(&self.translations, "update_restarting", "version" => &update.version)
+            )]
+        } else {
+            let status = update.download.fraction().map_or_else(
+                || format_size(update.download.downloaded, DECIMAL),
+                |fraction| format!("{:.0} %", fraction * 100.0),
+            );
+            column![
+                text(tr!(&self.translations, "update_downloading", "version" => &update.version)),
+                progress_bar(0.0..=1.0, update.download.fraction().unwrap_or_default()),
+                text(status).size(14),
+            ]
+        };
+
+        opaque(
+            center(
+                container(dialog.spacing(10).width(Length::Fixed(400.0)))
+                    .style(container::rounded_box)
+                    .padding(20),
+            )
+            .style(|_theme| container::background(Color::from_rgba(0.0, 0.0, 0.0, 0.6))),
+        )
+    }
+
+    fn change_page(&mut self, page_kind: PageKind) -> Task<AppMessage> {
+        if self.current_page.kind() == page_kind {
             return Task::none();
         }
         match page_kind {
+            PageKind::Browse => {
+                let (page, task) = BrowsePage::new(self.repository.clone());
+                self.current_page = Page::Browse(page);
+                task.map(AppMessage::Browse)
+            }
+            PageKind::Compare => {
+                let (page, task) = ComparePage::new(self.repository.clone());
+                self.current_page = Page::Compare(page);
+                task.map(AppMessage::Compare)
+            }
             PageKind::Delete => {
                 let (page, task) = DeletePage::new(self.repository.clone());
                 self.current_page = Page::Delete(page);
                 task.map(AppMessage::Delete)
             }
+            PageKind::Manage => {
+                let (page, task) = ManagePage::new(self.repository.clone());
+                self.current_page = Page::Manage(page);
+                task.map(AppMessage::Manage)
+            }
             PageKind::Read => {
                 let (page, task) = ReadPage::new(self.repository.clone());
-                self.current_page = Page::Read(page);
+                self.current_page = Page::Read(Box::new(page));
                 task.map(AppMessage::Read)
             }
+            PageKind::Settings => {
+                let (page, task) = SettingsPage::new(self.repository.clone());
+                self.current_page = Page::Settings(page);
+                task.map(AppMessage::Settings)
+            }
+            PageKind::Stats => {
+                let (page, task) = StatsPage::new(self.repository.clone());
+                self.current_page = Page::Stats(page);
+                task.map(AppMessage::Stats)
+            }
             PageKind::Write => {
                 let (page, task) = WritePage::new(self.repository.clone());
                 self.current_page = Page::Write(page);
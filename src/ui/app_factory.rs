@@ -1,11 +1,19 @@
-use crate::infrastructure::database::pool::SqliteRepositoryPool;
+use crate::domain::clock::SystemClock;
+use crate::infrastructure::database::pool::{SqliteRepositoryPool, DATABASE_URL};
 use crate::infrastructure::database::repository::ListerRepository;
+use crate::infrastructure::updater::app_updater::{GitHubUpdater, Updater, UPDATE_SOURCE};
 use crate::utils::dialogs::popup_error_and_exit;
 use std::sync::Arc;
 
 #[must_use]
 pub fn create() -> Arc<ListerRepository> {
     Arc::new(ListerRepository::new(
-        SqliteRepositoryPool::new("app.db").unwrap_or_else(|error| popup_error_and_exit(error)),
+        SqliteRepositoryPool::new(DATABASE_URL).unwrap_or_else(|error| popup_error_and_exit(error)),
+        Arc::new(SystemClock),
     ))
 }
+
+#[must_use]
+pub fn create_updater() -> Arc<dyn Updater> {
+    Arc::new(GitHubUpdater::new(UPDATE_SOURCE))
+}
@@ -1,18 +1,37 @@
+use crate::domain::model::drive_info::DriveInfo;
+use crate::domain::model::elapsed_time::ElapsedTime;
 use crate::infrastructure::database::repository::ListerRepository;
 use crate::tr;
 use crate::ui::messages::drive_combo_box::DriveComboBoxMessage;
+use crate::ui::utils::format_date_time::format_indexed_ago;
+use crate::utils::blocking::run_blocking;
 use crate::utils::dialogs::popup_error;
+use chrono::Local;
 use iced::widget::pick_list;
 use iced::{Element, Task};
 use std::collections::HashMap;
+use std::fmt;
 use std::sync::Arc;
 
 pub struct DriveComboBox {
     repository: Arc<ListerRepository>,
-    pub drives: Vec<String>,
+    pub drives: Vec<DriveInfo>,
     pub selected_drive: Option<String>,
 }
 
+/// Pick list entry showing a drive name along with how long ago it was indexed.
+#[derive(Clone, PartialEq)]
+struct DriveOption {
+    name: String,
+    label: String,
+}
+
+impl fmt::Display for DriveOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.label)
+    }
+}
+
 impl DriveComboBox {
     pub fn new(repository: Arc<ListerRepository>) -> (Self, Task<DriveComboBoxMessage>) {
         let drive_combo_box = Self {
@@ -28,11 +47,29 @@ impl DriveComboBox {
         &'_ self,
         translations: &HashMap<String, String>,
     ) -> Element<'_, DriveComboBoxMessage> {
-        pick_list(
-            self.drives.clone(),
-            self.selected_drive.clone(),
-            DriveComboBoxMessage::DriveSelected,
-        )
+        let now = Local::now().naive_local();
+        let options: Vec<DriveOption> = self
+            .drives
+            .iter()
+            .map(|drive| DriveOption {
+                name: drive.name.clone(),
+                label: format!(
+                    "{} ({})",
+                    drive.name,
+                    format_indexed_ago(ElapsedTime::between(drive.last_indexed, now), translations)
+                ),
+            })
+            .collect();
+        let selected = self.selected_drive.as_ref().and_then(|selected| {
+            options
+                .iter()
+                .find(|option| &option.name == selected)
+                .cloned()
+        });
+
+        pick_list(options, selected, |option| {
+            DriveComboBoxMessage::DriveSelected(option.name)
+        })
         .placeholder(tr!(translations, "select_drive_placeholder"))
         .into()
     }
@@ -40,12 +77,12 @@ impl DriveComboBox {
     pub fn find_drives(&self) -> Task<DriveComboBoxMessage> {
         let repository = self.repository.clone();
         Task::perform(
-            async move {
-                repository.find_all_drive_names().unwrap_or_else(|err| {
+            run_blocking(move || {
+                repository.find_all_drives().unwrap_or_else(|err| {
                     popup_error(err);
                     vec![]
                 })
-            },
+            }),
             DriveComboBoxMessage::DrivesFetched,
         )
     }
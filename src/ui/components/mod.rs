@@ -1,3 +1,4 @@
 pub mod drive_combo_box;
 pub mod read;
+pub mod shortcut_help;
 pub mod write;
@@ -1,60 +1,646 @@
+use crate::domain::model::directory_group::DirectoryGroup;
+use crate::domain::model::drive_group::DriveGroup;
+use crate::domain::model::file_age::FileAge;
 use crate::domain::model::file_entry::FileWithMetadata;
+use crate::domain::model::file_kind::FileKind;
+use crate::domain::model::hidden_results::{HiddenResults, ResultKey};
 use crate::domain::model::language::Language;
+use crate::domain::model::path_tree::PathNode;
+use crate::domain::model::result_columns::{ResultColumn, ResultColumns};
+use crate::domain::model::row_density::RowDensity;
+use crate::domain::model::row_selection::{move_selection, reveal_offset};
+use crate::domain::model::search_constraint::{LiftedConstraint, SearchConstraint};
+use crate::domain::model::search_filters::SearchFilters;
+use crate::tr;
+use crate::ui::components::read::tree_view::TreeView;
 use crate::ui::messages::read_message::ReadMessage;
-use crate::ui::utils::format_date_time::format_date_time;
+use crate::ui::utils::format_date_time::{format_date_time, format_file_age};
+use chrono::Local;
 use humansize::{format_size, DECIMAL};
-use iced::widget::scrollable::{AbsoluteOffset, RelativeOffset};
+use iced::widget::scrollable::{AbsoluteOffset, RelativeOffset, Viewport};
 use iced::widget::Id;
-use iced::widget::{column, operation, row, rule, text, Scrollable};
-use iced::{Element, Length};
+use iced::widget::{
+    button, column, container, mouse_area, operation, row, rule, text, toggler, Scrollable,
+};
+use iced::{Alignment, Color, Element, Length, Theme};
+use std::collections::{HashMap, HashSet};
+
+/// Colors given to categories, picked from the category name so it keeps its color.
+const CATEGORY_COLORS: [Color; 6] = [
+    Color::from_rgb8(0x3b, 0x82, 0xf6),
+    Color::from_rgb8(0x10, 0xb9, 0x81),
+    Color::from_rgb8(0xf5, 0x9e, 0x0b),
+    Color::from_rgb8(0xef, 0x44, 0x44),
+    Color::from_rgb8(0x8b, 0x5c, 0xf6),
+    Color::from_rgb8(0x06, 0xb6, 0xd4),
+];
+
+/// Identifies what a page of files shows, to tell a page change from a refresh in place.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PageKey {
+    pub drive: Option<String>,
+    pub query: String,
+    pub filters: SearchFilters,
+    pub page_index: usize,
+    pub items_per_page: usize,
+}
 
 pub struct FileList {
     pub files: Vec<FileWithMetadata>,
+    /// Files indexed last, shown before any search.
+    recent_files: Vec<FileWithMetadata>,
     pub scroll_bar_id: Id,
+    has_searched: bool,
+    is_drive_missing: bool,
+    /// Number of files a fuzzy search ranks at most, set when the last one covered more.
+    exceeded_fuzzy_limit: Option<u64>,
+    pub group_by_folder: bool,
+    pub group_by_drive: bool,
+    pub tree_view: bool,
+    pub density: RowDensity,
+    pub columns: ResultColumns,
+    pub is_columns_menu_open: bool,
+    shown_page: Option<PageKey>,
+    scroll_offset: AbsoluteOffset,
+    groups: Option<Vec<DirectoryGroup>>,
+    expanded_groups: HashSet<(String, String)>,
+    drive_groups: Option<Vec<DriveGroup>>,
+    expanded_drives: HashSet<String>,
+    tree: TreeView,
+    hidden: HiddenResults,
+    /// Constraints keeping the last search empty, offered as ways to bring results back.
+    lifted_constraints: Vec<LiftedConstraint>,
+    row_menu: Option<ResultKey>,
+    selected_index: Option<usize>,
+    viewport_height: f32,
 }
 
 impl FileList {
-    pub fn new() -> Self {
+    pub fn new(density: RowDensity, columns: ResultColumns) -> Self {
         Self {
             files: Vec::new(),
+            recent_files: Vec::new(),
             scroll_bar_id: Id::unique(),
+            has_searched: false,
+            is_drive_missing: false,
+            exceeded_fuzzy_limit: None,
+            group_by_folder: false,
+            group_by_drive: false,
+            tree_view: false,
+            density,
+            columns,
+            is_columns_menu_open: false,
+            shown_page: None,
+            scroll_offset: AbsoluteOffset::default(),
+            groups: None,
+            expanded_groups: HashSet::new(),
+            drive_groups: None,
+            expanded_drives: HashSet::new(),
+            tree: TreeView::new(),
+            hidden: HiddenResults::default(),
+            lifted_constraints: Vec::new(),
+            row_menu: None,
+            selected_index: None,
+            viewport_height: 0.0,
         }
     }
 
     pub fn set_files(&mut self, files: Vec<FileWithMetadata>) {
         self.files = files;
+        self.has_searched = true;
+        self.is_drive_missing = false;
+        self.exceeded_fuzzy_limit = None;
+        self.lifted_constraints.clear();
+    }
+
+    pub fn set_recent_files(&mut self, recent_files: Vec<FileWithMetadata>) {
+        self.recent_files = recent_files;
+    }
+
+    pub fn set_lifted_constraints(&mut self, lifted_constraints: Vec<LiftedConstraint>) {
+        self.lifted_constraints = lifted_constraints;
+    }
+
+    /// Replaces the results with a notice that the searched drive is no longer indexed.
+    pub fn show_missing_drive(&mut self) {
+        self.show_notice();
+        self.is_drive_missing = true;
+    }
+
+    /// Replaces the results with a notice that the fuzzy search must be narrowed first.
+    pub fn show_fuzzy_limit_exceeded(&mut self, limit: u64) {
+        self.show_notice();
+        self.exceeded_fuzzy_limit = Some(limit);
+    }
+
+    fn show_notice(&mut self) {
+        self.files.clear();
+        self.has_searched = true;
+        self.is_drive_missing = false;
+        self.exceeded_fuzzy_limit = None;
+        self.lifted_constraints.clear();
+        self.shown_page = None;
+        self.selected_index = None;
+        self.groups = None;
+        self.drive_groups = None;
+        self.tree.set_nodes(None);
     }
 
     pub fn clear(&mut self) {
         self.files.clear();
+        self.has_searched = false;
+        self.is_drive_missing = false;
+        self.exceeded_fuzzy_limit = None;
+        self.lifted_constraints.clear();
+        self.shown_page = None;
+        self.selected_index = None;
+        self.groups = None;
+        self.drive_groups = None;
+        self.tree.set_nodes(None);
+        self.clear_hidden();
     }
 
-    pub fn view<'a>(&'a self, language: &Language) -> Element<'a, ReadMessage> {
-        let file_rows: Vec<Element<'a, ReadMessage>> = self
-            .files
-            .iter()
-            .map(|file| {
-                row![
-                    text(&file.category_name).width(Length::FillPortion(1)),
-                    text(&file.drive_name).width(Length::FillPortion(2)),
+    /// Hides a result until the next search, without touching the database.
+    pub fn hide(&mut self, key: ResultKey) {
+        self.row_menu = None;
+        self.hidden.hide(key);
+    }
+
+    pub fn clear_hidden(&mut self) {
+        self.hidden.clear();
+        self.row_menu = None;
+    }
+
+    /// Opens the action menu under a row, or closes it when it is already open there.
+    pub fn toggle_row_menu(&mut self, key: ResultKey) {
+        if self.row_menu.as_ref() == Some(&key) {
+            self.row_menu = None;
+        } else {
+            self.row_menu = Some(key);
+        }
+    }
+
+    pub fn close_row_menu(&mut self) {
+        self.row_menu = None;
+    }
+
+    /// Whether the rows are shown in collapsible sections rather than as a flat list.
+    pub const fn is_grouped(&self) -> bool {
+        (self.group_by_folder && self.groups.is_some())
+            || (self.group_by_drive && self.drive_groups.is_some())
+            || (self.tree_view && self.tree.is_available())
+    }
+
+    /// Results of the page as listed, leaving out the hidden ones.
+    pub fn visible_files(&self) -> impl Iterator<Item = &FileWithMetadata> {
+        self.hidden.visible(&self.files)
+    }
+
+    /// Moves the selected row by `step` and scrolls so that it stays in view.
+    pub fn move_selection(&mut self, step: isize) -> iced::Task<ReadMessage> {
+        let len = self.hidden.visible(&self.files).count();
+        self.selected_index = move_selection(self.selected_index, step, len);
+        self.row_menu = None;
+        let Some(index) = self.selected_index else {
+            return iced::Task::none();
+        };
+        let row_height = self.density.row_height();
+        // Until the list has been scrolled its height is unknown, so the row is shown at the top
+        let view_height = if self.viewport_height > 0.0 {
+            self.viewport_height
+        } else {
+            row_height
+        };
+        match reveal_offset(index, row_height, self.scroll_offset.y, view_height) {
+            Some(y) => {
+                self.scroll_offset.y = y;
+                operation::scroll_to(self.scroll_bar_id.clone(), self.scroll_offset)
+            }
+            None => iced::Task::none(),
+        }
+    }
+
+    /// The result whose menu is open, or else the selected row.
+    pub fn focused_file(&self) -> Option<&FileWithMetadata> {
+        match &self.row_menu {
+            Some(key) => self
+                .files
+                .iter()
+                .find(|file| ResultKey::from(*file) == *key),
+            None => self
+                .selected_index
+                .and_then(|index| self.hidden.visible(&self.files).nth(index)),
+        }
+    }
+
+    /// Opens or closes the action menu of the selected row.
+    pub fn toggle_selected_row_menu(&mut self) {
+        let key = self
+            .selected_index
+            .and_then(|index| self.hidden.visible(&self.files).nth(index))
+            .map(ResultKey::from);
+        if let Some(key) = key {
+            self.toggle_row_menu(key);
+        }
+    }
+
+    /// Sets the directory groups of the whole dataset, or `None` when it is not cached.
+    pub fn set_groups(&mut self, groups: Option<Vec<DirectoryGroup>>) {
+        self.groups = groups;
+    }
+
+    /// Sets the drive sections of the whole dataset, or `None` when it is not cached.
+    pub fn set_drive_groups(&mut self, drive_groups: Option<Vec<DriveGroup>>) {
+        self.drive_groups = drive_groups;
+    }
+
+    /// Sets the tree of the whole dataset, or `None` when it is not cached.
+    pub fn set_tree(&mut self, nodes: Option<Vec<PathNode>>) {
+        self.tree.set_nodes(nodes);
+    }
+
+    pub fn toggle_tree_node(&mut self, drive_name: String, path: String) {
+        self.tree.toggle(drive_name, path);
+    }
+
+    pub const fn can_group(&self) -> bool {
+        self.groups.is_some()
+    }
+
+    pub fn toggle_group(&mut self, drive_name: String, directory: String) {
+        let key = (drive_name, directory);
+        if !self.expanded_groups.remove(&key) {
+            self.expanded_groups.insert(key);
+        }
+    }
+
+    pub fn toggle_drive_group(&mut self, drive_name: String) {
+        if !self.expanded_drives.remove(&drive_name) {
+            self.expanded_drives.insert(drive_name);
+        }
+    }
+
+    /// Shows the files of a page, keeping the scroll position when the same page is refreshed.
+    ///
+    /// Showing another page, or the same page with other search parameters, snaps to the top.
+    pub fn show(&mut self, files: Vec<FileWithMetadata>, page: PageKey) -> iced::Task<ReadMessage> {
+        let is_refresh = self.shown_page.as_ref() == Some(&page);
+        if !is_refresh {
+            self.selected_index = None;
+        }
+        self.set_files(files);
+        self.shown_page = Some(page);
+        if is_refresh {
+            operation::scroll_to(self.scroll_bar_id.clone(), self.scroll_offset)
+        } else {
+            self.scroll_offset = AbsoluteOffset::default();
+            self.snap_to_top()
+        }
+    }
+
+    pub fn scrolled(&mut self, viewport: &Viewport) {
+        self.scroll_offset = viewport.absolute_offset();
+        self.viewport_height = viewport.bounds().height;
+    }
+
+    pub fn view<'a>(
+        &'a self,
+        translations: &HashMap<String, String>,
+        language: &Language,
+    ) -> Element<'a, ReadMessage> {
+        if self.files.is_empty() {
+            if !self.has_searched && !self.recent_files.is_empty() {
+                return self.recent_feed(translations, language);
+            }
+            return self.empty_state(translations);
+        }
+
+        let folder_groups = self.groups.as_ref().filter(|_| self.group_by_folder);
+        let drive_groups = self.drive_groups.as_ref().filter(|_| self.group_by_drive);
+        let file_rows: Vec<Element<'a, ReadMessage>> = match (folder_groups, drive_groups) {
+            _ if self.tree_view && self.tree.is_available() => {
+                self.tree.rows(&self.hidden, translations, &|file| {
+                    self.file_row(file, translations, language)
+                })
+            }
+            (Some(groups), _) => groups
+                .iter()
+                .flat_map(|group| self.group_rows(group, translations, language))
+                .collect(),
+            (None, Some(drive_groups)) => drive_groups
+                .iter()
+                .flat_map(|group| self.drive_group_rows(group, translations, language))
+                .collect(),
+            (None, None) => self
+                .hidden
+                .visible(&self.files)
+                .enumerate()
+                .map(|(index, file)| {
+                    let file_row = self.file_row(file, translations, language);
+                    if self.selected_index == Some(index) {
+                        container(file_row)
+                            .style(|theme: &Theme| {
+                                container::background(theme.extended_palette().primary.weak.color)
+                            })
+                            .into()
+                    } else {
+                        file_row
+                    }
+                })
+                .collect(),
+        };
+
+        column![
+            rule::horizontal(1),
+            Scrollable::new(column(file_rows))
+                .id(self.scroll_bar_id.clone())
+                .on_scroll(ReadMessage::FileListScrolled)
+                .height(Length::Fill),
+            rule::horizontal(1),
+        ]
+        .into()
+    }
+
+    /// Renders a file, with its action menu below when it was right-clicked.
+    fn file_row<'a>(
+        &self,
+        file: &'a FileWithMetadata,
+        translations: &HashMap<String, String>,
+        language: &Language,
+    ) -> Element<'a, ReadMessage> {
+        let key = ResultKey::from(file);
+        let filename = file.filename();
+        let now = Local::now().naive_local();
+        let age = file
+            .modified_time
+            .map(|modified_time| FileAge::between(modified_time, now));
+        let kind = FileKind::from_filename(&filename);
+        let size = self.density.text_size();
+        let cells = self.columns.shown().map(|column| {
+            let cell: Element<'a, ReadMessage> = match column {
+                ResultColumn::Category => text(&file.category_name)
+                    .size(size)
+                    .color(category_color(&file.category_name))
+                    .into(),
+                ResultColumn::Drive => text(&file.drive_name).size(size).into(),
+                ResultColumn::AvailableSpace => {
                     text(format_size(file.drive_available_space, DECIMAL))
-                        .width(Length::FillPortion(1)),
+                        .size(size)
+                        .into()
+                }
+                ResultColumn::Indexed => {
                     text(format_date_time(file.drive_insertion_time, language))
-                        .width(Length::FillPortion(2)),
-                    text(file.parent_directory()).width(Length::FillPortion(3)),
-                    text(file.filename()).width(Length::FillPortion(4)),
-                    text(format_size(file.size_bytes, DECIMAL)).width(Length::FillPortion(1))
+                        .size(size)
+                        .into()
+                }
+                ResultColumn::Directory => text(file.parent_directory()).size(size).into(),
+                ResultColumn::Filename => row![
+                    text(kind_badge(kind, translations))
+                        .size(12)
+                        .color(kind_color(kind))
+                        .width(72),
+                    text(filename.clone()).size(size)
                 ]
-                .padding(3)
+                .into(),
+                ResultColumn::Size => text(format_size(file.size_bytes, DECIMAL))
+                    .size(size)
+                    .into(),
+                ResultColumn::Age => text(format_file_age(age, translations))
+                    .size(size)
+                    .style(text::secondary)
+                    .into(),
+            };
+            container(cell)
+                .width(Length::FillPortion(column.portion()))
                 .into()
-            })
-            .collect();
+        });
+        let file_row = mouse_area(
+            row(cells)
+                .height(self.density.row_height())
+                .padding(self.density.row_padding()),
+        )
+        .on_right_press(ReadMessage::RowMenuToggled(key.clone()))
+        .on_double_click(Self::open_file_message(&key, translations));
+
+        if self.row_menu.as_ref() != Some(&key) {
+            return file_row.into();
+        }
+
+        let menu = row![
+            button(text(tr!(translations, "open_file")))
+                .on_press(Self::open_file_message(&key, translations))
+                .style(button::secondary),
+            button(text(tr!(translations, "copy_path")))
+                .on_press(ReadMessage::CopyToClipboard(file.path.clone()))
+                .style(button::secondary),
+            button(text(tr!(translations, "copy_size")))
+                .on_press(ReadMessage::CopyToClipboard(format_size(
+                    file.size_bytes,
+                    DECIMAL
+                )))
+                .style(button::secondary),
+            button(text(tr!(translations, "hide_result")))
+                .on_press(ReadMessage::ResultHidden(key.clone()))
+                .style(button::secondary),
+            button(text(tr!(translations, "close_menu")))
+                .on_press(ReadMessage::RowMenuToggled(key))
+                .style(button::text),
+        ]
+        .spacing(5)
+        .padding([3, 20]);
+
+        column![file_row, menu].into()
+    }
+
+    fn open_file_message(key: &ResultKey, translations: &HashMap<String, String>) -> ReadMessage {
+        ReadMessage::OpenFile {
+            key: key.clone(),
+            no_location: tr!(translations, "open_file_no_location"),
+            not_found: tr!(translations, "open_file_not_found"),
+        }
+    }
+
+    fn group_rows<'a>(
+        &'a self,
+        group: &'a DirectoryGroup,
+        translations: &HashMap<String, String>,
+        language: &Language,
+    ) -> Vec<Element<'a, ReadMessage>> {
+        let is_expanded = self
+            .expanded_groups
+            .contains(&(group.drive_name.clone(), group.directory.clone()));
+        let marker = if is_expanded { "▾" } else { "▸" };
+        let header = button(row![
+            text(format!(
+                "{marker} {} / {}",
+                group.drive_name, group.directory
+            ))
+            .width(Length::FillPortion(12)),
+            text(
+                tr!(translations, "group_file_count", "nb_files" => &group.files.len().to_string())
+            )
+            .width(Length::FillPortion(2)),
+            text(format_size(group.total_size, DECIMAL)).width(Length::FillPortion(1))
+        ])
+        .on_press(ReadMessage::GroupToggled(
+            group.drive_name.clone(),
+            group.directory.clone(),
+        ))
+        .style(button::secondary)
+        .width(Length::Fill);
+
+        let mut rows = vec![header.into()];
+        if is_expanded {
+            rows.extend(self.hidden.visible(&group.files).map(|file| {
+                container(self.file_row(file, translations, language))
+                    .padding([0, 20])
+                    .into()
+            }));
+        }
+        rows
+    }
+
+    fn drive_group_rows<'a>(
+        &'a self,
+        group: &'a DriveGroup,
+        translations: &HashMap<String, String>,
+        language: &Language,
+    ) -> Vec<Element<'a, ReadMessage>> {
+        let is_expanded = self.expanded_drives.contains(&group.drive_name);
+        let marker = if is_expanded { "▾" } else { "▸" };
+        let header = button(row![
+            text(format!("{marker} {}", group.drive_name)).width(Length::FillPortion(12)),
+            text(
+                tr!(translations, "group_file_count", "nb_files" => &group.files.len().to_string())
+            )
+            .width(Length::FillPortion(2)),
+            text(format_size(group.total_size, DECIMAL)).width(Length::FillPortion(1))
+        ])
+        .on_press(ReadMessage::DriveGroupToggled(group.drive_name.clone()))
+        .style(button::primary)
+        .width(Length::Fill);
+
+        let mut rows = vec![header.into()];
+        if is_expanded {
+            rows.extend(self.hidden.visible(&group.files).map(|file| {
+                container(self.file_row(file, translations, language))
+                    .padding([0, 20])
+                    .into()
+            }));
+        }
+        rows
+    }
+
+    /// Lists a toggle per column, the filename excepted since it is always shown.
+    pub fn columns_menu<'a>(
+        &self,
+        translations: &HashMap<String, String>,
+    ) -> Element<'a, ReadMessage> {
+        let toggles = ResultColumn::ALL
+            .into_iter()
+            .filter(|column| *column != ResultColumn::Filename)
+            .map(|column| {
+                let key = match column {
+                    ResultColumn::Category => "category_column",
+                    ResultColumn::Drive => "drive_column",
+                    ResultColumn::AvailableSpace => "available_space_column",
+                    ResultColumn::Indexed => "indexed_at_column",
+                    ResultColumn::Directory => "directory_column",
+                    ResultColumn::Filename => "filename_column",
+                    ResultColumn::Size => "size_column",
+                    ResultColumn::Age => "age_column",
+                };
+                toggler(self.columns.is_shown(column))
+                    .label(tr!(translations, key))
+                    .on_toggle(move |is_shown| ReadMessage::ColumnToggled(column, is_shown))
+                    .into()
+            });
+        row(toggles).spacing(20).into()
+    }
+
+    /// Lists the files indexed last, so the page is useful before the first search.
+    fn recent_feed<'a>(
+        &'a self,
+        translations: &HashMap<String, String>,
+        language: &Language,
+    ) -> Element<'a, ReadMessage> {
+        let rows = self.recent_files.iter().map(|file| {
+            let key = ResultKey::from(file);
+            let size = self.density.text_size();
+            mouse_area(
+                row![
+                    text(file.filename())
+                        .size(size)
+                        .width(Length::FillPortion(4)),
+                    text(file.parent_directory())
+                        .size(size)
+                        .style(text::secondary)
+                        .width(Length::FillPortion(4)),
+                    text(&file.drive_name)
+                        .size(size)
+                        .width(Length::FillPortion(2)),
+                    text(format_date_time(file.drive_insertion_time, language))
+                        .size(size)
+                        .width(Length::FillPortion(2)),
+                ]
+                .height(self.density.row_height())
+                .padding(self.density.row_padding()),
+            )
+            .on_double_click(Self::open_file_message(&key, translations))
+            .into()
+        });
 
         column![
             rule::horizontal(1),
-            Scrollable::new(column(file_rows))
-                .id(self.scroll_bar_id.clone())
-                .height(Length::Fill),
+            text(tr!(translations, "recent_files_title")).size(18),
+            Scrollable::new(column(rows)).height(Length::Fill),
+            rule::horizontal(1),
+        ]
+        .spacing(5)
+        .into()
+    }
+
+    fn empty_state<'a>(&self, translations: &HashMap<String, String>) -> Element<'a, ReadMessage> {
+        let message = if self.is_drive_missing {
+            tr!(translations, "drive_not_indexed")
+        } else if let Some(limit) = self.exceeded_fuzzy_limit {
+            tr!(translations, "fuzzy_limit_exceeded", "count" => &limit.to_string())
+        } else if self.has_searched {
+            tr!(translations, "no_files_found")
+        } else {
+            tr!(translations, "no_search_yet")
+        };
+
+        let suggestions = self.lifted_constraints.iter().map(|lifted| {
+            let key = match lifted.constraint {
+                SearchConstraint::Drive => "lift_drive",
+                SearchConstraint::Query => "lift_query",
+                SearchConstraint::Extension => "lift_extension",
+                SearchConstraint::FilenameOnly => "lift_filename_only",
+                SearchConstraint::WholeSegments => "lift_whole_segments",
+                SearchConstraint::IndexedSince => "lift_indexed_since",
+                SearchConstraint::Tag => "lift_tag",
+                SearchConstraint::Permission => "lift_permission",
+            };
+            button(text(
+                tr!(translations, key, "count" => &lifted.result_count.to_string()),
+            ))
+            .on_press(ReadMessage::ConstraintLifted(lifted.constraint))
+            .style(button::secondary)
+            .into()
+        });
+
+        column![
+            rule::horizontal(1),
+            container(
+                column![
+                    text(message).style(text::secondary),
+                    column(suggestions).spacing(5)
+                ]
+                .spacing(15)
+                .align_x(Alignment::Center)
+            )
+            .center(Length::Fill),
             rule::horizontal(1),
         ]
         .into()
@@ -76,3 +662,34 @@ impl FileList {
         )
     }
 }
+
+fn kind_badge(kind: FileKind, translations: &HashMap<String, String>) -> String {
+    match kind {
+        FileKind::Image => tr!(translations, "file_kind_image"),
+        FileKind::Video => tr!(translations, "file_kind_video"),
+        FileKind::Audio => tr!(translations, "file_kind_audio"),
+        FileKind::Document => tr!(translations, "file_kind_document"),
+        FileKind::Code => tr!(translations, "file_kind_code"),
+        FileKind::Archive => tr!(translations, "file_kind_archive"),
+        FileKind::Other => String::new(),
+    }
+}
+
+const fn kind_color(kind: FileKind) -> Color {
+    match kind {
+        FileKind::Image => Color::from_rgb8(0xec, 0x48, 0x99),
+        FileKind::Video => Color::from_rgb8(0x8b, 0x5c, 0xf6),
+        FileKind::Audio => Color::from_rgb8(0x14, 0xb8, 0xa6),
+        FileKind::Document => Color::from_rgb8(0x3b, 0x82, 0xf6),
+        FileKind::Code => Color::from_rgb8(0x22, 0xc5, 0x5e),
+        FileKind::Archive => Color::from_rgb8(0xf5, 0x9e, 0x0b),
+        FileKind::Other => Color::from_rgb8(0x9c, 0xa3, 0xaf),
+    }
+}
+
+fn category_color(category_name: &str) -> Color {
+    let hash = category_name.bytes().fold(0usize, |hash, byte| {
+        hash.wrapping_mul(31).wrapping_add(byte.into())
+    });
+    CATEGORY_COLORS[hash % CATEGORY_COLORS.len()]
+}
@@ -0,0 +1,150 @@
+use crate::domain::model::file_entry::FileWithMetadata;
+use crate::domain::model::hidden_results::ResultKey;
+use crate::domain::model::image_preview::{is_previewable, PreviewSource};
+use crate::infrastructure::database::repository::ListerRepository;
+use crate::tr;
+use crate::ui::messages::read_message::ReadMessage;
+use crate::utils::blocking::run_blocking;
+use ::image::{DynamicImage, ImageDecoder, ImageReader, ImageResult};
+use iced::widget::{column, container, image, text};
+use iced::{Alignment, ContentFit, Element, Length, Task};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Side length of the square the thumbnail is fitted in.
+const THUMBNAIL_SIZE: f32 = 240.0;
+
+/// Thumbnail of the image result in focus, read from its drive when it is mounted.
+#[derive(Default)]
+pub enum ImagePreview {
+    /// The result in focus is not an image, or there is none.
+    #[default]
+    Hidden,
+    Loading(ResultKey),
+    Shown(ResultKey, image::Handle),
+    /// The image cannot be read, with the translation key of the reason.
+    Placeholder(ResultKey, &'static str),
+}
+
+impl ImagePreview {
+    /// Follows the result in focus, loading its image off the UI thread when it is a new one.
+    pub fn follow(
+        &mut self,
+        file: Option<&FileWithMetadata>,
+        repository: &Arc<ListerRepository>,
+    ) -> Task<ReadMessage> {
+        let Some(file) = file.filter(|file| is_previewable(&file.path)) else {
+            *self = Self::Hidden;
+            return Task::none();
+        };
+        let key = ResultKey::from(file);
+        if self.key() == Some(&key) {
+            return Task::none();
+        }
+        *self = Self::Loading(key.clone());
+        let repository = repository.clone();
+        Task::perform(
+            run_blocking({
+                let key = key.clone();
+                move || Self::load(&repository, &key)
+            }),
+            move |image| ReadMessage::PreviewLoaded(key.clone(), image),
+        )
+    }
+
+    /// Shows a loaded image, unless the focus moved to another result meanwhile.
+    pub fn loaded(&mut self, key: ResultKey, image: Result<image::Handle, &'static str>) {
+        if !matches!(self, Self::Loading(loading) if *loading == key) {
+            return;
+        }
+        *self = match image {
+            Ok(handle) => Self::Shown(key, handle),
+            Err(reason) => Self::Placeholder(key, reason),
+        };
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::Hidden;
+    }
+
+    pub fn view(
+        &'_ self,
+        translations: &HashMap<String, String>,
+    ) -> Option<Element<'_, ReadMessage>> {
+        let (key, content): (_, Element<'_, ReadMessage>) = match self {
+            Self::Hidden => return None,
+            Self::Loading(key) => (key, text(tr!(translations, "preview_loading")).into()),
+            Self::Shown(key, handle) => (
+                key,
+                image(handle.clone())
+                    .content_fit(ContentFit::Contain)
+                    .width(Length::Fill)
+                    .height(Length::Fill)
+                    .into(),
+            ),
+            Self::Placeholder(key, reason) => (
+                key,
+                text(tr!(translations, *reason))
+                    .style(text::secondary)
+                    .align_x(Alignment::Center)
+                    .into(),
+            ),
+        };
+        let filename = Path::new(&key.path)
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        Some(
+            column![
+                container(content)
+                    .center(Length::Fixed(THUMBNAIL_SIZE))
+                    .style(container::rounded_box),
+                text(filename).size(13).style(text::secondary)
+            ]
+            .spacing(5)
+            .width(Length::Fixed(THUMBNAIL_SIZE))
+            .into(),
+        )
+    }
+
+    fn key(&self) -> Option<&ResultKey> {
+        match self {
+            Self::Hidden => None,
+            Self::Loading(key) | Self::Shown(key, _) | Self::Placeholder(key, _) => Some(key),
+        }
+    }
+
+    fn load(repository: &ListerRepository, key: &ResultKey) -> Result<image::Handle, &'static str> {
+        let drive_root = repository
+            .find_drive_root(&key.drive_name, &key.category_name)
+            .map_err(|_| "preview_unreadable")?;
+        match PreviewSource::locate(&key.path, drive_root.as_deref(), Path::exists) {
+            PreviewSource::Available(path) => {
+                Self::thumbnail(&path).map_err(|_| "preview_unreadable")
+            }
+            PreviewSource::Missing(_) => Err("preview_missing"),
+            PreviewSource::NoLocation => Err("preview_no_location"),
+            PreviewSource::NotAnImage => Err("preview_unreadable"),
+        }
+    }
+
+    /// Decodes the image at `path` down to the thumbnail size, upright as a viewer shows it.
+    ///
+    /// Only the thumbnail is kept, rather than the full resolution a photo or a scan comes in.
+    fn thumbnail(path: &Path) -> ImageResult<image::Handle> {
+        let mut decoder = ImageReader::open(path)?
+            .with_guessed_format()?
+            .into_decoder()?;
+        let orientation = decoder.orientation()?;
+        let size = THUMBNAIL_SIZE as u32;
+        let mut thumbnail = DynamicImage::from_decoder(decoder)?.thumbnail(size, size);
+        thumbnail.apply_orientation(orientation);
+        Ok(image::Handle::from_rgba(
+            thumbnail.width(),
+            thumbnail.height(),
+            thumbnail.into_rgba8().into_raw(),
+        ))
+    }
+}
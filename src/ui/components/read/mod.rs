@@ -1,4 +1,7 @@
-pub mod cache;
 pub mod file_list;
+pub mod image_preview;
 pub mod pagination;
+pub mod saved_searches;
 pub mod search;
+pub mod status_bar;
+pub mod tree_view;
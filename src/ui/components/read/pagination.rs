@@ -1,14 +1,18 @@
-use iced::widget::{button, row, text, text_input};
-use iced::{Alignment, Element, Length};
+use iced::widget::{button, pick_list, row, text, text_input};
+use iced::{Alignment, Element, Length, Theme};
 use std::collections::HashMap;
 
+use crate::domain::model::pagination::{self, PageInput, PAGE_SIZES};
 use crate::tr;
 use crate::ui::messages::read_message::ReadMessage;
 
 pub struct Pagination {
     pub total_count: u64,
+    /// Combined size of every result, across all pages.
+    pub total_size: u64,
     pub current_page_index: usize,
     pub page_input_value: String,
+    pub input_error: bool,
     pub items_per_page: usize,
 }
 
@@ -16,30 +20,60 @@ impl Pagination {
     pub const fn new(items_per_page: usize) -> Self {
         Self {
             total_count: 0,
+            total_size: 0,
             current_page_index: 0,
             page_input_value: String::new(),
+            input_error: false,
             items_per_page,
         }
     }
 
-    #[allow(clippy::cast_possible_truncation)]
     pub const fn total_pages(&self) -> usize {
-        if self.total_count == 0 {
-            1
-        } else {
-            self.total_count.div_ceil(self.items_per_page as u64) as usize
-        }
+        pagination::total_pages(self.total_count, self.items_per_page)
+    }
+
+    /// Updates the number of results, moving back to the last page if the current one is gone.
+    ///
+    /// Returns whether the current page changed and needs to be loaded again.
+    pub const fn set_total_count(&mut self, total_count: u64) -> bool {
+        self.total_count = total_count;
+        let page_index =
+            pagination::clamp_page_index(self.current_page_index, total_count, self.items_per_page);
+        let has_moved = page_index != self.current_page_index;
+        self.current_page_index = page_index;
+        has_moved
     }
 
     pub const fn navigate_to(&mut self, page_index: usize) -> Option<usize> {
         if page_index < self.total_pages() {
             self.current_page_index = page_index;
+            self.input_error = false;
             Some(page_index)
         } else {
             None
         }
     }
 
+    pub fn edit_page_input(&mut self, value: String) {
+        self.page_input_value = value;
+        self.input_error = false;
+    }
+
+    /// Navigates to the typed page, flagging the input when it is not a valid page number.
+    pub fn submit_page_input(&mut self) -> Option<usize> {
+        match pagination::parse_page_input(&self.page_input_value, self.total_pages()) {
+            PageInput::Empty => {
+                self.input_error = false;
+                None
+            }
+            PageInput::Page(page_index) => self.navigate_to(page_index),
+            PageInput::Invalid => {
+                self.input_error = true;
+                None
+            }
+        }
+    }
+
     pub const fn first_page(&mut self) -> Option<usize> {
         self.navigate_to(0)
     }
@@ -66,13 +100,21 @@ impl Pagination {
         }
     }
 
+    /// Changes the page size, going back to the first page as the current one no longer exists.
+    pub fn set_items_per_page(&mut self, items_per_page: usize) {
+        self.items_per_page = items_per_page;
+        self.reset();
+    }
+
     pub fn reset(&mut self) {
         self.current_page_index = 0;
         self.page_input_value.clear();
+        self.input_error = false;
     }
 
     pub fn clear(&mut self) {
         self.total_count = 0;
+        self.total_size = 0;
         self.reset();
     }
 
@@ -97,16 +139,12 @@ impl Pagination {
 
         let page_info = text(format!(
             "{:^5} / {:^5} - {:^7}",
-            if self.total_count == 0 {
+            if total_pages == 0 {
                 0
             } else {
                 self.current_page_index + 1
             },
-            if self.total_count == 0 {
-                0
-            } else {
-                total_pages
-            },
+            total_pages,
             self.total_count
         ))
         .size(14);
@@ -134,7 +172,34 @@ impl Pagination {
         .on_input(ReadMessage::PageInputChanged)
         .on_submit(ReadMessage::PageInputSubmit)
         .padding(8)
-        .width(Length::Fixed(100f32));
+        .width(Length::Fixed(100f32))
+        .style(if self.input_error {
+            Self::invalid_input_style
+        } else {
+            text_input::default
+        });
+
+        let input_error = self.input_error.then(|| {
+            text(tr!(
+                translations,
+                "page_input_invalid",
+                "total_pages" => &total_pages.to_string()
+            ))
+            .style(text::danger)
+            .size(14)
+        });
+
+        let page_size_list = row![
+            pick_list(
+                PAGE_SIZES,
+                Some(self.items_per_page),
+                ReadMessage::ItemsPerPageChanged
+            )
+            .padding(8),
+            text(tr!(translations, "items_per_page")).size(14),
+        ]
+        .spacing(5)
+        .align_y(Alignment::Center);
 
         row![
             first_button,
@@ -143,9 +208,19 @@ impl Pagination {
             next_button,
             last_button,
             page_input,
+            page_size_list,
+            input_error,
         ]
         .spacing(20)
         .align_y(Alignment::Center)
         .into()
     }
+
+    fn invalid_input_style(theme: &Theme, status: text_input::Status) -> text_input::Style {
+        let style = text_input::default(theme, status);
+        text_input::Style {
+            border: style.border.color(theme.palette().danger),
+            ..style
+        }
+    }
 }
@@ -0,0 +1,65 @@
+use crate::domain::model::saved_search::SavedSearch;
+use crate::tr;
+use crate::ui::messages::read_message::ReadMessage;
+use iced::widget::{button, pick_list, row, text, text_input};
+use iced::{Element, Length};
+use std::collections::HashMap;
+
+pub struct SavedSearches {
+    pub searches: Vec<SavedSearch>,
+    pub name: String,
+    pub selected: Option<String>,
+}
+
+impl SavedSearches {
+    pub const fn new() -> Self {
+        Self {
+            searches: Vec::new(),
+            name: String::new(),
+            selected: None,
+        }
+    }
+
+    pub fn find(&self, name: &str) -> Option<&SavedSearch> {
+        self.searches.iter().find(|search| search.name == name)
+    }
+
+    pub fn view(&'_ self, translations: &HashMap<String, String>) -> Element<'_, ReadMessage> {
+        let names: Vec<String> = self
+            .searches
+            .iter()
+            .map(|search| search.name.clone())
+            .collect();
+
+        let saved_list = pick_list(
+            names,
+            self.selected.clone(),
+            ReadMessage::SavedSearchSelected,
+        )
+        .placeholder(tr!(translations, "saved_searches_placeholder"))
+        .padding(10);
+
+        let delete_button = button(text(tr!(translations, "delete_saved_search_button")))
+            .on_press_maybe(self.selected.clone().map(ReadMessage::SavedSearchDeleted))
+            .padding(10);
+
+        let name_input = text_input(
+            &tr!(translations, "saved_search_name_placeholder"),
+            &self.name,
+        )
+        .on_input(ReadMessage::SavedSearchNameChanged)
+        .on_submit(ReadMessage::SaveSearchPressed)
+        .padding(10)
+        .width(Length::Fill);
+
+        let save_button = button(text(tr!(translations, "save_search_button")))
+            .on_press_maybe(
+                (!self.name.trim().is_empty()).then_some(ReadMessage::SaveSearchPressed),
+            )
+            .padding(10);
+
+        row![saved_list, delete_button, name_input, save_button]
+            .spacing(10)
+            .into()
+    }
+}
@@ -1,43 +1,299 @@
+use crate::domain::model::file_ownership::PermissionFilter;
+use crate::domain::model::indexed_within::IndexedWithin;
+use crate::domain::model::search_filters::{SearchFilters, LARGEST_FILES_COUNT};
+use crate::domain::model::search_history::{SearchHistory, SEARCH_HISTORY_LIMIT};
+use crate::domain::model::sort_key::SortKey;
 use crate::tr;
 use crate::ui::messages::read_message::ReadMessage;
-use iced::widget::operation::focus_next;
-use iced::widget::{button, column, row, text, text_input};
+use chrono::Local;
+use iced::advanced::widget;
+use iced::advanced::widget::operation::focusable;
+use iced::widget::{
+    button, column, container, operation, pick_list, row, space, text, text_input, toggler,
+    tooltip, Id,
+};
 use iced::{Element, Length, Task};
 use std::collections::HashMap;
 
 pub struct Search {
     pub query: String,
+    pub refinement: String,
+    pub filters: SearchFilters,
+    pub indexed_within: Option<IndexedWithin>,
+    pub extensions: Vec<String>,
+    pub tags: Vec<String>,
+    pub history: SearchHistory,
+    pub offer_history_clear: bool,
+    input_id: Id,
 }
 
 impl Search {
     pub fn new() -> (Self, Task<ReadMessage>) {
-        (
-            Self {
-                query: String::new(),
-            },
-            focus_next(),
-        )
+        let search = Self {
+            query: String::new(),
+            refinement: String::new(),
+            filters: SearchFilters::default(),
+            indexed_within: None,
+            extensions: Vec::new(),
+            tags: Vec::new(),
+            history: SearchHistory::new(SEARCH_HISTORY_LIMIT),
+            offer_history_clear: false,
+            input_id: Id::unique(),
+        };
+        let task = search.focus();
+        (search, task)
+    }
+
+    pub fn focus(&self) -> Task<ReadMessage> {
+        operation::focus(self.input_id.clone())
+    }
+
+    /// Returns the query sent to the database, with the refinement terms appended when there are any.
+    pub fn database_query(&self) -> String {
+        let refinement = self.refinement.trim();
+        if refinement.is_empty() {
+            self.query.clone()
+        } else {
+            format!("{} {refinement}", self.query)
+        }
+    }
+
+    /// Selects a recently indexed period, bounding the search from the current time.
+    pub fn set_indexed_within(&mut self, indexed_within: Option<IndexedWithin>) {
+        self.indexed_within = indexed_within;
+        self.filters.indexed_since =
+            indexed_within.map(|period| period.since(Local::now().naive_local()));
+    }
+
+    /// Empties the search box and moves the focus away from it.
+    pub fn leave(&mut self) -> Task<ReadMessage> {
+        self.query.clear();
+        self.refinement.clear();
+        widget::operate(focusable::unfocus())
     }
 
     pub fn clear(&mut self) {
         self.query.clear();
+        self.refinement.clear();
+        self.filters.extension = None;
+        self.filters.tag = None;
+        self.filters.permission = None;
+        self.set_indexed_within(None);
+        self.offer_history_clear = !self.history.is_empty();
     }
 
     pub fn view(&'_ self, translations: &HashMap<String, String>) -> Element<'_, ReadMessage> {
         let search_input = text_input(&tr!(translations, "search_placeholder"), &self.query)
+            .id(self.input_id.clone())
             .on_input(ReadMessage::ContentChanged)
             .on_submit(ReadMessage::SearchSubmit)
             .padding(10)
-            .width(Length::Fill);
+            .width(Length::FillPortion(2));
 
-        let search_button = button(text(tr!(translations, "search_button")))
-            .on_press(ReadMessage::SearchSubmit)
-            .padding(10);
+        let refinement_input = text_input(
+            &tr!(translations, "refinement_placeholder"),
+            &self.refinement,
+        )
+        .on_input(ReadMessage::RefinementChanged)
+        .on_submit(ReadMessage::SearchSubmit)
+        .padding(10)
+        .width(Length::FillPortion(1));
+
+        let history_list = (!self.history.is_empty()).then(|| {
+            pick_list(
+                self.history.entries(),
+                None::<String>,
+                ReadMessage::HistorySelected,
+            )
+            .placeholder(tr!(translations, "search_history_placeholder"))
+            .padding(10)
+        });
+
+        let extension_list = pick_list(
+            self.extensions.as_slice(),
+            self.filters.extension.clone(),
+            ReadMessage::ExtensionSelected,
+        )
+        .placeholder(tr!(translations, "extension_placeholder"))
+        .padding(10);
+
+        let tag_list = (!self.tags.is_empty()).then(|| {
+            pick_list(
+                self.tags.as_slice(),
+                self.filters.tag.clone(),
+                ReadMessage::TagSelected,
+            )
+            .placeholder(tr!(translations, "tag_placeholder"))
+            .padding(10)
+        });
+
+        let filename_only_toggle = toggler(self.filters.filename_only)
+            .label(tr!(translations, "filename_only_toggle"))
+            .on_toggle(ReadMessage::FilenameOnlyToggled);
+
+        let whole_segments_toggle = toggler(self.filters.whole_segments)
+            .label(tr!(translations, "whole_segments_toggle"))
+            .on_toggle(ReadMessage::WholeSegmentsToggled);
+
+        let fuzzy_toggle = toggler(self.filters.fuzzy)
+            .label(tr!(translations, "fuzzy_toggle"))
+            .on_toggle(ReadMessage::FuzzyToggled);
+
+        let search_button = tooltip(
+            button(text(tr!(translations, "search_button")))
+                .on_press(ReadMessage::SearchSubmit)
+                .padding(10),
+            container(text(tr!(translations, "search_shortcuts_help")))
+                .style(container::rounded_box)
+                .padding(10),
+            tooltip::Position::Bottom,
+        );
 
         let clear_button = button(text(tr!(translations, "clear_button")))
             .on_press(ReadMessage::SearchClear)
             .padding(10);
 
-        column![row![search_input, search_button, clear_button].spacing(10)].into()
+        let clear_history_button = self.offer_history_clear.then(|| {
+            button(text(tr!(translations, "clear_history_button")))
+                .on_press(ReadMessage::HistoryClear)
+                .padding(10)
+        });
+
+        column![
+            row![
+                search_input,
+                refinement_input,
+                history_list,
+                extension_list,
+                tag_list,
+                filename_only_toggle,
+                whole_segments_toggle,
+                fuzzy_toggle,
+                search_button,
+                clear_button,
+                clear_history_button
+            ]
+            .spacing(10),
+            row![
+                self.indexed_within_control(translations),
+                // Owners and permissions are only recorded on Unix
+                cfg!(unix).then(|| self.permission_control(translations)),
+                space::horizontal(),
+                self.sort_control(translations)
+            ]
+            .spacing(10)
+        ]
+        .spacing(10)
+        .into()
+    }
+
+    fn indexed_within_control(
+        &'_ self,
+        translations: &HashMap<String, String>,
+    ) -> Element<'_, ReadMessage> {
+        let any_time = self.period_button(tr!(translations, "indexed_any_time"), None);
+        let periods = IndexedWithin::ALL.into_iter().map(|period| {
+            self.period_button(
+                tr!(translations, "indexed_within_days", "days" => &period.days().to_string()),
+                Some(period),
+            )
+        });
+
+        row(std::iter::once(any_time).chain(periods))
+            .spacing(2)
+            .into()
+    }
+
+    fn permission_control(
+        &'_ self,
+        translations: &HashMap<String, String>,
+    ) -> Element<'_, ReadMessage> {
+        let any_permission = self.permission_button(tr!(translations, "permission_any"), None);
+        let permissions = PermissionFilter::ALL.into_iter().map(|permission| {
+            self.permission_button(permission_label(permission, translations), Some(permission))
+        });
+
+        row(std::iter::once(any_permission).chain(permissions))
+            .spacing(2)
+            .into()
+    }
+
+    fn sort_control(&'_ self, translations: &HashMap<String, String>) -> Element<'_, ReadMessage> {
+        let buttons = SortKey::ALL.into_iter().map(|sort| {
+            let label = match sort {
+                SortKey::Indexed => tr!(translations, "sort_indexed"),
+                SortKey::Modified => tr!(translations, "sort_modified"),
+                SortKey::Size => tr!(translations, "sort_size"),
+            };
+            button(text(label))
+                .on_press(ReadMessage::SortSelected(sort))
+                .padding([5, 10])
+                .style(if self.filters.sort == sort {
+                    button::primary
+                } else {
+                    button::secondary
+                })
+                .into()
+        });
+
+        let largest_files = button(text(tr!(
+            translations,
+            "largest_files_button",
+            "count" => &LARGEST_FILES_COUNT.to_string()
+        )))
+        .on_press(ReadMessage::LargestFilesToggled)
+        .padding([5, 10])
+        .style(if self.filters.top.is_some() {
+            button::primary
+        } else {
+            button::secondary
+        });
+
+        row![row(buttons).spacing(2), largest_files]
+            .spacing(10)
+            .into()
+    }
+
+    fn period_button(
+        &'_ self,
+        label: String,
+        period: Option<IndexedWithin>,
+    ) -> Element<'_, ReadMessage> {
+        button(text(label))
+            .on_press(ReadMessage::IndexedWithinSelected(period))
+            .padding([5, 10])
+            .style(if self.indexed_within == period {
+                button::primary
+            } else {
+                button::secondary
+            })
+            .into()
+    }
+
+    fn permission_button(
+        &'_ self,
+        label: String,
+        permission: Option<PermissionFilter>,
+    ) -> Element<'_, ReadMessage> {
+        button(text(label))
+            .on_press(ReadMessage::PermissionSelected(permission))
+            .padding([5, 10])
+            .style(if self.filters.permission == permission {
+                button::primary
+            } else {
+                button::secondary
+            })
+            .into()
+    }
+}
+
+pub fn permission_label(
+    permission: PermissionFilter,
+    translations: &HashMap<String, String>,
+) -> String {
+    match permission {
+        PermissionFilter::WorldWritable => tr!(translations, "permission_world_writable"),
+        PermissionFilter::Executable => tr!(translations, "permission_executable"),
+        PermissionFilter::SetId => tr!(translations, "permission_set_id"),
     }
 }
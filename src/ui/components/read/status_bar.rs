@@ -0,0 +1,63 @@
+use crate::domain::model::search_filters::SearchFilters;
+use crate::domain::model::search_status::{status_parts, StatusPart};
+use crate::tr;
+use crate::ui::components::read::search::permission_label;
+use crate::ui::messages::read_message::ReadMessage;
+use humansize::{format_size, DECIMAL};
+use iced::widget::text;
+use iced::Element;
+use std::collections::HashMap;
+
+/// One-line summary of the active search, shown under the results.
+pub struct StatusBar<'a> {
+    pub drive: Option<&'a str>,
+    pub query: &'a str,
+    pub filters: &'a SearchFilters,
+    pub total_count: u64,
+    pub total_size: u64,
+}
+
+impl StatusBar<'_> {
+    pub fn view<'b>(&self, translations: &HashMap<String, String>) -> Element<'b, ReadMessage> {
+        let summary = status_parts(
+            self.drive,
+            self.query,
+            self.filters,
+            self.total_count,
+            self.total_size,
+        )
+        .iter()
+        .map(|part| Self::part_label(part, translations))
+        .collect::<Vec<_>>()
+        .join(" | ");
+
+        text(summary).size(13).style(text::secondary).into()
+    }
+
+    fn part_label(part: &StatusPart, translations: &HashMap<String, String>) -> String {
+        match part {
+            StatusPart::Drive(drive) => tr!(translations, "status_drive", "drive" => drive),
+            StatusPart::AllDrives => tr!(translations, "status_all_drives"),
+            StatusPart::Query(query) => tr!(translations, "status_query", "query" => query),
+            StatusPart::Extension(extension) => {
+                tr!(translations, "status_extension", "extension" => extension)
+            }
+            StatusPart::Tag(tag) => tr!(translations, "status_tag", "tag" => tag),
+            StatusPart::Permission(permission) => permission_label(*permission, translations),
+            StatusPart::FilenameOnly => tr!(translations, "filename_only_toggle"),
+            StatusPart::WholeSegments => tr!(translations, "whole_segments_toggle"),
+            StatusPart::Fuzzy => tr!(translations, "fuzzy_toggle"),
+            StatusPart::Results(1) => tr!(translations, "status_one_result"),
+            StatusPart::Results(count) => {
+                tr!(translations, "status_results", "count" => &count.to_string())
+            }
+            StatusPart::Top { shown, total } => tr!(
+                translations,
+                "status_top",
+                "shown" => &shown.to_string(),
+                "count" => &total.to_string()
+            ),
+            StatusPart::TotalSize(total_size) => format_size(*total_size, DECIMAL),
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use crate::domain::model::file_entry::FileWithMetadata;
+use crate::domain::model::hidden_results::HiddenResults;
+use crate::domain::model::path_tree::PathNode;
+use crate::tr;
+use crate::ui::messages::read_message::ReadMessage;
+use humansize::{format_size, DECIMAL};
+use iced::widget::{button, container, row, text};
+use iced::{padding, Element, Length};
+use std::collections::{HashMap, HashSet};
+
+/// Indentation added at each level of the tree.
+const INDENT: f32 = 20.0;
+
+/// Results shown as an expandable tree of drives and folders.
+pub struct TreeView {
+    nodes: Option<Vec<PathNode>>,
+    expanded: HashSet<(String, String)>,
+}
+
+impl TreeView {
+    pub fn new() -> Self {
+        Self {
+            nodes: None,
+            expanded: HashSet::new(),
+        }
+    }
+
+    /// Sets the tree of the whole dataset, or `None` when it is not cached.
+    pub fn set_nodes(&mut self, nodes: Option<Vec<PathNode>>) {
+        self.nodes = nodes;
+    }
+
+    pub const fn is_available(&self) -> bool {
+        self.nodes.is_some()
+    }
+
+    pub fn toggle(&mut self, drive_name: String, path: String) {
+        let key = (drive_name, path);
+        if !self.expanded.remove(&key) {
+            self.expanded.insert(key);
+        }
+    }
+
+    /// Renders the tree down to the expanded nodes, files being rendered by `file_row`.
+    pub fn rows<'a>(
+        &'a self,
+        hidden: &'a HiddenResults,
+        translations: &HashMap<String, String>,
+        file_row: &dyn Fn(&'a FileWithMetadata) -> Element<'a, ReadMessage>,
+    ) -> Vec<Element<'a, ReadMessage>> {
+        let mut rows = vec![];
+        for node in self.nodes.iter().flatten() {
+            self.push_node_rows(node, 0, hidden, translations, file_row, &mut rows);
+        }
+        rows
+    }
+
+    fn push_node_rows<'a>(
+        &'a self,
+        node: &'a PathNode,
+        depth: u16,
+        hidden: &'a HiddenResults,
+        translations: &HashMap<String, String>,
+        file_row: &dyn Fn(&'a FileWithMetadata) -> Element<'a, ReadMessage>,
+        rows: &mut Vec<Element<'a, ReadMessage>>,
+    ) {
+        let is_expanded = self
+            .expanded
+            .contains(&(node.drive_name.clone(), node.path.clone()));
+        let marker = if is_expanded { "▾" } else { "▸" };
+        let header = button(row![
+            text(format!("{marker} {}", node.name)).width(Length::FillPortion(12)),
+            text(tr!(translations, "group_file_count", "nb_files" => &node.file_count.to_string()))
+                .width(Length::FillPortion(2)),
+            text(format_size(node.total_size, DECIMAL)).width(Length::FillPortion(1))
+        ])
+        .on_press(ReadMessage::TreeNodeToggled(
+            node.drive_name.clone(),
+            node.path.clone(),
+        ))
+        .style(if depth == 0 {
+            button::primary
+        } else {
+            button::secondary
+        })
+        .width(Length::Fill);
+        rows.push(indented(header, depth));
+
+        if !is_expanded {
+            return;
+        }
+        for child in &node.children {
+            self.push_node_rows(child, depth + 1, hidden, translations, file_row, rows);
+        }
+        rows.extend(
+            hidden
+                .visible(&node.files)
+                .map(|file| indented(file_row(file), depth + 1)),
+        );
+    }
+}
+
+fn indented<'a>(
+    content: impl Into<Element<'a, ReadMessage>>,
+    depth: u16,
+) -> Element<'a, ReadMessage> {
+    container(content)
+        .padding(padding::left(INDENT * f32::from(depth)))
+        .into()
+}
@@ -0,0 +1,44 @@
+use crate::application::shortcut::SHORTCUT_GROUPS;
+use crate::tr;
+use crate::ui::messages::app_message::AppMessage;
+use iced::widget::{
+    button, center, column, container, opaque, row, rule, scrollable, text, Column,
+};
+use iced::{Color, Element, Length};
+use std::collections::HashMap;
+
+/// Modal listing every keyboard shortcut, grouped by where they apply.
+pub fn view<'a>(translations: &HashMap<String, String>) -> Element<'a, AppMessage> {
+    let groups = SHORTCUT_GROUPS.iter().map(|group| {
+        let shortcuts = group.shortcuts.iter().map(|shortcut| {
+            row![
+                text(shortcut.keys).width(Length::FillPortion(2)),
+                text(tr!(translations, shortcut.description_key)).width(Length::FillPortion(3)),
+            ]
+            .spacing(10)
+            .into()
+        });
+        column![
+            text(tr!(translations, group.title_key))
+                .size(18)
+                .style(text::primary),
+            Column::with_children(shortcuts).spacing(5),
+        ]
+        .spacing(10)
+        .into()
+    });
+
+    let dialog = column![
+        text(tr!(translations, "shortcuts_title")).size(24),
+        rule::horizontal(1),
+        scrollable(Column::with_children(groups).spacing(20)).height(Length::Shrink),
+        button(text(tr!(translations, "close_menu"))).on_press(AppMessage::ToggleHelp),
+    ]
+    .spacing(15)
+    .width(Length::Fixed(600.0));
+
+    opaque(
+        center(container(dialog).style(container::rounded_box).padding(20))
+            .style(|_theme| container::background(Color::from_rgba(0.0, 0.0, 0.0, 0.6))),
+    )
+}
@@ -1,14 +1,44 @@
+use crate::domain::model::scan_diff::ScanDiff;
+
 #[derive(Eq, PartialEq)]
 pub enum IndexingState {
     Ready,
-    CleaningDatabase,
+    CheckingExisting,
+    ConfirmingOverwrite {
+        files_to_replace: u64,
+    },
+    Previewing,
+    /// Scanning to compare the directories with the files stored for the drive.
+    Diffing,
+    ReviewingChanges {
+        diff: ScanDiff,
+    },
+    CleaningDatabase {
+        files_to_remove: u64,
+    },
     Scanning,
-    Saving,
-    Completed { files_indexed: usize },
+    Saving {
+        files_saved: usize,
+    },
+    Completed {
+        files_indexed: usize,
+    },
+    Failed {
+        /// Translation key of what went wrong.
+        message_key: &'static str,
+    },
 }
 
 impl IndexingState {
-    pub const fn is_indexing(&self) -> bool {
-        matches!(self, Self::CleaningDatabase | Self::Scanning | Self::Saving)
+    pub const fn is_busy(&self) -> bool {
+        matches!(
+            self,
+            Self::CheckingExisting
+                | Self::Previewing
+                | Self::Diffing
+                | Self::CleaningDatabase { .. }
+                | Self::Scanning
+                | Self::Saving { .. }
+        )
     }
 }
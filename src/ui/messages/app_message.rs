@@ -1,22 +1,44 @@
+use crate::application::shortcut::KeyPress;
+use crate::domain::model::compaction_report::CompactionReport;
 use crate::domain::model::language::Language;
+use crate::infrastructure::updater::app_updater::{AvailableUpdate, DownloadProgress};
 use crate::ui::app::PageKind;
+use crate::ui::messages::browse_message::BrowseMessage;
+use crate::ui::messages::compare_message::CompareMessage;
 use crate::ui::messages::delete_message::DeleteMessage;
+use crate::ui::messages::manage_message::ManageMessage;
 use crate::ui::messages::read_message::ReadMessage;
+use crate::ui::messages::settings_message::SettingsMessage;
+use crate::ui::messages::stats_message::StatsMessage;
 use crate::ui::messages::toaster_message::ToasterMessage;
 use crate::ui::messages::write_message::WriteMessage;
 use std::collections::HashMap;
+use std::path::PathBuf;
 
 #[derive(Clone, Debug)]
 pub enum AppMessage {
+    Browse(BrowseMessage),
     ChangeLanguage(Language),
     ChangePage(PageKind),
     ChangePageNext,
+    CloseHelp,
+    CheckForUpdates,
     CompactDatabase,
-    DatabaseCompacted(u64),
+    Compare(CompareMessage),
+    DatabaseCompacted(Option<CompactionReport>),
     Delete(DeleteMessage),
+    /// A path dropped on the window from the file manager.
+    FileDropped(PathBuf),
     LanguageChanged(Language, HashMap<String, String>),
+    Manage(ManageMessage),
     Read(ReadMessage),
-    TabPressed { shift: bool },
+    Settings(SettingsMessage),
+    ShortcutPressed(KeyPress),
+    Stats(StatsMessage),
     ToastMessage(ToasterMessage),
+    ToggleHelp,
+    UpdateAccepted(AvailableUpdate),
+    UpdateFinished(bool),
+    UpdateProgressed(DownloadProgress),
     Write(WriteMessage),
 }
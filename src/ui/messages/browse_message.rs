@@ -0,0 +1,13 @@
+use crate::domain::model::directory_listing::DirectoryListing;
+use crate::ui::messages::drive_combo_box::DriveComboBoxMessage;
+
+#[derive(Clone, Debug)]
+pub enum BrowseMessage {
+    Drive(DriveComboBoxMessage),
+    FolderOpened(String),
+    ChildrenListed {
+        drive: String,
+        prefix: String,
+        listing: Option<DirectoryListing>,
+    },
+}
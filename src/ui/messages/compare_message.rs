@@ -0,0 +1,13 @@
+use crate::domain::model::drive_diff::DriveDiff;
+use crate::ui::messages::drive_combo_box::DriveComboBoxMessage;
+
+#[derive(Clone, Debug)]
+pub enum CompareMessage {
+    DriveA(DriveComboBoxMessage),
+    DriveB(DriveComboBoxMessage),
+    DrivesCompared {
+        drive_a: String,
+        drive_b: String,
+        diff: Option<DriveDiff>,
+    },
+}
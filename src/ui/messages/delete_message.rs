@@ -1,10 +1,23 @@
+use crate::application::presence_check::{PresenceCheckError, PresenceReport};
+use crate::infrastructure::database::drive_snapshot::DriveSnapshot;
 use crate::ui::messages::drive_combo_box::DriveComboBoxMessage;
+use std::sync::Arc;
 
 #[derive(Clone, Debug)]
 pub enum DeleteMessage {
     CategoriesFetched(Vec<String>),
     CategorySelected(String),
     DriveComboBox(DriveComboBoxMessage),
-    EndDelete,
+    EndDelete(Option<Arc<DriveSnapshot>>),
+    EndPrune(usize),
+    EndUndo(usize),
+    EndVerify(Option<Result<PresenceReport, PresenceCheckError>>),
     StartDelete,
+    StartPrune {
+        confirm_title: String,
+        confirm_details: String,
+    },
+    StartVerify,
+    Undo,
+    UndoExpired,
 }
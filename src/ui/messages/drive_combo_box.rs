@@ -1,5 +1,7 @@
+use crate::domain::model::drive_info::DriveInfo;
+
 #[derive(Debug, Clone)]
 pub enum DriveComboBoxMessage {
-    DrivesFetched(Vec<String>),
+    DrivesFetched(Vec<DriveInfo>),
     DriveSelected(String),
-}
\ No newline at end of file
+}
@@ -0,0 +1,28 @@
+use crate::domain::model::category_stats::CategoryStats;
+use crate::domain::model::drive_space::DriveSpace;
+use std::collections::BTreeMap;
+
+#[derive(Clone, Debug)]
+pub enum ManageMessage {
+    AddTag(usize),
+    AvailableSpaceChanged(usize, String),
+    AvailableSpaceSaved,
+    CategoriesFetched(Vec<CategoryStats>),
+    CategoriesMerged,
+    DrivesFetched(Vec<DriveSpace>),
+    DriveTagsFetched(BTreeMap<String, Vec<String>>),
+    DriveTagsSaved,
+    MergeCategory {
+        from: String,
+        into: String,
+        confirm_title: String,
+        confirm_details: String,
+    },
+    MergeTargetSelected(usize, String),
+    RemoveTag {
+        drive_name: String,
+        tag: String,
+    },
+    SaveAvailableSpace(usize),
+    TagInputChanged(usize, String),
+}
@@ -1,6 +1,11 @@
 pub mod app_message;
+pub mod browse_message;
+pub mod compare_message;
 pub mod delete_message;
 pub mod drive_combo_box;
+pub mod manage_message;
 pub mod read_message;
+pub mod settings_message;
+pub mod stats_message;
 pub mod toaster_message;
 pub mod write_message;
@@ -1,26 +1,103 @@
+use crate::domain::model::file_entry::FileWithMetadata;
+use crate::domain::model::file_ownership::PermissionFilter;
+use crate::domain::model::hidden_results::ResultKey;
+use crate::domain::model::indexed_within::IndexedWithin;
 use crate::domain::model::pagination::PaginatedResult;
+use crate::domain::model::result_columns::{ResultColumn, ResultColumns};
+use crate::domain::model::row_density::RowDensity;
+use crate::domain::model::saved_search::SavedSearch;
+use crate::domain::model::search_constraint::SearchConstraint;
+use crate::domain::model::search_history::SearchHistory;
+use crate::domain::model::sort_key::SortKey;
 use crate::ui::messages::drive_combo_box::DriveComboBoxMessage;
+use iced::widget::image;
+use iced::widget::scrollable::Viewport;
+use std::time::Instant;
 
 #[derive(Clone, Debug)]
 pub enum ReadMessage {
-    ArrowDownPressed { shift: bool },
-    ArrowLeftPressed { shift: bool },
+    ArrowDownPressed {
+        shift: bool,
+    },
+    ArrowLeftPressed {
+        shift: bool,
+    },
     ArrowNavigationReleased,
-    ArrowRightPressed { shift: bool },
-    ArrowUpPressed { shift: bool },
+    ArrowRightPressed {
+        shift: bool,
+    },
+    ArrowUpPressed {
+        shift: bool,
+    },
+    CacheWarmBatchLoaded(u64, Vec<FileWithMetadata>),
+    CacheWarmFinished(u64),
+    ColumnToggled(ResultColumn, bool),
+    ColumnsMenuToggled,
+    ConstraintLifted(SearchConstraint),
     ContentChanged(String),
+    CopyMarkdownTable,
+    CopyToClipboard(String),
     DriveComboBox(DriveComboBoxMessage),
     EndPressed,
-    FilesLoaded(PaginatedResult),
+    EnterPressed,
+    EscapePressed,
+    ExtensionSelected(String),
+    ExtensionsFetched(Vec<String>),
+    TagSelected(String),
+    TagsFetched(Vec<String>),
+    FileListScrolled(Viewport),
+    FilenameOnlyToggled(bool),
+    FuzzyToggled(bool),
+    PermissionSelected(Option<PermissionFilter>),
+    LargestFilesToggled,
+    WholeSegmentsToggled(bool),
+    FilesLoaded(u64, PaginatedResult),
     FirstPage,
+    FocusSearch,
+    DriveGroupToggled(String),
+    GroupByDriveToggled(bool),
+    GroupByFolderToggled(bool),
+    GroupToggled(String, String),
+    HistoryClear,
+    HistoryFetched(SearchHistory),
+    HistorySelected(String),
     HomePressed,
+    IndexedWithinSelected(Option<IndexedWithin>),
+    ItemsPerPageChanged(usize),
+    ItemsPerPageFetched(usize),
     LastPage,
+    LiveSearchDue(u64),
+    ManifestPressed {
+        dialog_title: String,
+    },
     NextPage,
+    OpenFile {
+        key: ResultKey,
+        no_location: String,
+        not_found: String,
+    },
     PageDownPressed,
     PageInputChanged(String),
     PageInputSubmit,
     PageUpPressed,
+    PreviewLoaded(ResultKey, Result<image::Handle, &'static str>),
     PrevPage,
+    RecentFilesFetched(Vec<FileWithMetadata>),
+    RefinementChanged(String),
+    Refresh,
+    ResultColumnsFetched(ResultColumns),
+    ResultHidden(ResultKey),
+    RowDensityFetched(RowDensity),
+    RowMenuToggled(ResultKey),
+    SaveSearchPressed,
+    SavedSearchDeleted(String),
+    SavedSearchNameChanged(String),
+    SavedSearchSelected(String),
+    SavedSearchesFetched(Vec<SavedSearch>),
     SearchClear,
     SearchSubmit,
+    SearchTimerTicked(Instant),
+    SortSelected(SortKey),
+    TreeNodeToggled(String, String),
+    TreeViewToggled(bool),
 }
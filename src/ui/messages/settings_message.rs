@@ -0,0 +1,11 @@
+use crate::domain::model::row_density::RowDensity;
+
+#[derive(Clone, Debug)]
+pub enum SettingsMessage {
+    AutoUpdateFetched(bool),
+    AutoUpdateToggled(bool),
+    IndexHiddenFilesFetched(bool),
+    IndexHiddenFilesToggled(bool),
+    RowDensityFetched(RowDensity),
+    RowDensitySelected(RowDensity),
+}
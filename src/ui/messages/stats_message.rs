@@ -0,0 +1,10 @@
+use crate::domain::model::category_overview::CategoryOverview;
+use crate::domain::model::drive_stats::DriveStats;
+use crate::domain::model::index_run::IndexRun;
+
+#[derive(Clone, Debug)]
+pub enum StatsMessage {
+    CategoryOverviewLoaded(Vec<CategoryOverview>),
+    IndexRunsFetched(Vec<IndexRun>),
+    StatisticsFetched(Vec<DriveStats>),
+}
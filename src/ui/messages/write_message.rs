@@ -1,15 +1,48 @@
 use crate::domain::model::directory::DirectoryData;
 use crate::domain::model::file_entry::FileEntry;
+use crate::domain::model::mounted_disk::MountedDisk;
+use crate::domain::model::scan_diff::ScanDiff;
+use crate::domain::model::scan_summary::ScanSummary;
+use std::path::PathBuf;
 
 #[derive(Clone, Debug)]
 pub enum WriteMessage {
     CategoryChanged(String),
-    DatabaseCleaned,
-    DirectoryPressed { dialog_title: String },
+    ChangesApplied,
+    ChangesDiscarded,
+    ChangesScanned(ScanSummary, Vec<FileEntry>, ScanDiff),
+    DirectoryAddPressed {
+        dialog_title: String,
+    },
+    DirectoryAdded(Option<PathBuf>),
+    DirectoryPressed {
+        dialog_title: String,
+    },
     DirectoryChanged(Option<DirectoryData>),
+    DirectoryDropped(PathBuf),
+    DirectoryRemoved(usize),
     DiskChanged(String),
-    InsertInDatabaseFinished(usize),
+    DiskNotFound(DirectoryData, String),
+    DiskPicked(MountedDisk),
+    DisksLoaded(Vec<MountedDisk>),
+    DisksRequested,
+    DropRejected(PathBuf),
+    ExistingDriveChecked {
+        category: String,
+        drive: String,
+        exists: bool,
+    },
+    FilesSaved(usize),
+    /// Carries the translation key of the error.
+    IndexingFailed(&'static str),
+    InsertInDatabaseFinished(ScanSummary),
+    OverwriteCancelled,
+    OverwriteChecked(u64),
+    OverwriteConfirmed,
+    PreviewFinished(ScanSummary, Vec<FileEntry>),
+    PreviewPressed,
     ResetForm,
-    ScanDirectoryFinished(Vec<FileEntry>),
+    ReviewChangesPressed,
+    RetryIndexing,
     WriteSubmit,
 }
@@ -0,0 +1,205 @@
+use crate::domain::model::directory_listing::{breadcrumbs, DirectoryListing};
+use crate::infrastructure::database::repository::ListerRepository;
+use crate::tr;
+use crate::ui::components::drive_combo_box::DriveComboBox;
+use crate::ui::messages::browse_message::BrowseMessage;
+use crate::ui::messages::drive_combo_box::DriveComboBoxMessage;
+use crate::utils::blocking::run_blocking;
+use crate::utils::dialogs::popup_error;
+use humansize::{format_size, DECIMAL};
+use iced::widget::{button, column, container, row, rule, text, Scrollable};
+use iced::{Alignment, Element, Length, Task};
+use iced_aw::Spinner;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub struct BrowsePage {
+    repository: Arc<ListerRepository>,
+    drive_combo_box: DriveComboBox,
+    /// Folder being browsed, empty for the top of the drive.
+    prefix: String,
+    listing: Option<DirectoryListing>,
+    is_listing: bool,
+}
+
+impl BrowsePage {
+    pub fn new(repository: Arc<ListerRepository>) -> (Self, Task<BrowseMessage>) {
+        let (drive_combo_box, task) = DriveComboBox::new(repository.clone());
+        (
+            Self {
+                repository,
+                drive_combo_box,
+                prefix: String::new(),
+                listing: None,
+                is_listing: false,
+            },
+            task.map(BrowseMessage::Drive),
+        )
+    }
+
+    pub fn title(translations: &HashMap<String, String>) -> String {
+        tr!(translations, "browse_page_title")
+    }
+
+    pub fn view(&'_ self, translations: &HashMap<String, String>) -> Element<'_, BrowseMessage> {
+        let picker = row![
+            self.drive_combo_box
+                .view(translations)
+                .map(BrowseMessage::Drive),
+            self.is_listing.then(|| Spinner::new()
+                .width(Length::from(30))
+                .height(Length::from(30))),
+        ]
+        .spacing(10)
+        .align_y(Alignment::Center);
+
+        let content: Element<'_, BrowseMessage> =
+            match (&self.drive_combo_box.selected_drive, &self.listing) {
+                (Some(drive), Some(listing)) => column![
+                    self.breadcrumb_bar(drive),
+                    rule::horizontal(1),
+                    Self::listing_view(listing, translations),
+                ]
+                .spacing(10)
+                .into(),
+                _ => container(text(tr!(translations, "browse_pick_drive")).style(text::secondary))
+                    .center(Length::Fill)
+                    .into(),
+            };
+
+        column![picker, rule::horizontal(1), content]
+            .spacing(20)
+            .padding(20)
+            .into()
+    }
+
+    /// Path to the browsed folder, each folder leading to it being clickable.
+    fn breadcrumb_bar<'a>(&self, drive: &'a str) -> Element<'a, BrowseMessage> {
+        let crumbs = breadcrumbs(&self.prefix);
+        let mut bar = row![Self::crumb_button(drive, String::new(), crumbs.is_empty())]
+            .spacing(5)
+            .align_y(Alignment::Center);
+        let last = crumbs.len().saturating_sub(1);
+        for (index, crumb) in crumbs.into_iter().enumerate() {
+            bar = bar
+                .push(text("/").style(text::secondary))
+                .push(Self::crumb_button(crumb.name, crumb.path, index == last));
+        }
+        bar.wrap().into()
+    }
+
+    fn crumb_button<'a>(
+        name: impl text::IntoFragment<'a>,
+        path: String,
+        is_current: bool,
+    ) -> Element<'a, BrowseMessage> {
+        button(text(name))
+            .on_press_maybe((!is_current).then_some(BrowseMessage::FolderOpened(path)))
+            .style(if is_current {
+                button::primary
+            } else {
+                button::text
+            })
+            .into()
+    }
+
+    fn listing_view<'a>(
+        listing: &'a DirectoryListing,
+        translations: &HashMap<String, String>,
+    ) -> Element<'a, BrowseMessage> {
+        if listing.folders.is_empty() && listing.files.is_empty() {
+            return container(
+                text(tr!(translations, "browse_empty_folder")).style(text::secondary),
+            )
+            .center(Length::Fill)
+            .into();
+        }
+
+        let folders = listing.folders.iter().map(|folder| {
+            let file_count = folder.file_count.to_string();
+            button(
+                row![
+                    text(format!("{}/", folder.name)).width(Length::FillPortion(4)),
+                    text(tr!(translations, "browse_folder_files", "nb_files" => &file_count))
+                        .style(text::secondary)
+                        .width(Length::FillPortion(1)),
+                    text(format_size(folder.total_size, DECIMAL)).width(Length::FillPortion(1)),
+                ]
+                .spacing(10),
+            )
+            .on_press(BrowseMessage::FolderOpened(folder.path.clone()))
+            .style(button::text)
+            .width(Length::Fill)
+            .into()
+        });
+        let files = listing.files.iter().map(|file| {
+            row![
+                text(file.filename()).width(Length::FillPortion(4)),
+                text("").width(Length::FillPortion(1)),
+                text(format_size(file.size_bytes, DECIMAL)).width(Length::FillPortion(1)),
+            ]
+            .spacing(10)
+            .padding([5, 16])
+            .into()
+        });
+
+        Scrollable::new(column(
+            folders
+                .chain(files)
+                .collect::<Vec<Element<'a, BrowseMessage>>>(),
+        ))
+        .height(Length::Fill)
+        .into()
+    }
+
+    pub fn update(&mut self, message: BrowseMessage) -> Task<BrowseMessage> {
+        match message {
+            BrowseMessage::Drive(DriveComboBoxMessage::DrivesFetched(drives)) => {
+                self.drive_combo_box.drives = drives;
+                Task::none()
+            }
+            BrowseMessage::Drive(DriveComboBoxMessage::DriveSelected(drive)) => {
+                self.drive_combo_box.selected_drive = Some(drive);
+                self.list_children(String::new())
+            }
+            BrowseMessage::FolderOpened(prefix) => self.list_children(prefix),
+            BrowseMessage::ChildrenListed {
+                drive,
+                prefix,
+                listing,
+            } => {
+                // A listing finishing after another folder was opened is outdated
+                if self.drive_combo_box.selected_drive.as_ref() == Some(&drive)
+                    && self.prefix == prefix
+                {
+                    self.is_listing = false;
+                    if listing.is_some() {
+                        self.listing = listing;
+                    }
+                }
+                Task::none()
+            }
+        }
+    }
+
+    /// Lists the content of the folder at `prefix` on the selected drive.
+    fn list_children(&mut self, prefix: String) -> Task<BrowseMessage> {
+        let Some(drive) = self.drive_combo_box.selected_drive.clone() else {
+            return Task::none();
+        };
+        self.prefix.clone_from(&prefix);
+        self.is_listing = true;
+        let repository = self.repository.clone();
+        Task::future(run_blocking(move || {
+            let listing = repository
+                .list_immediate_children(&drive, &prefix)
+                .map_err(popup_error)
+                .ok();
+            BrowseMessage::ChildrenListed {
+                drive,
+                prefix,
+                listing,
+            }
+        }))
+    }
+}
@@ -0,0 +1,205 @@
+use crate::domain::model::drive_diff::DriveDiff;
+use crate::infrastructure::database::repository::ListerRepository;
+use crate::tr;
+use crate::ui::components::drive_combo_box::DriveComboBox;
+use crate::ui::messages::compare_message::CompareMessage;
+use crate::ui::messages::drive_combo_box::DriveComboBoxMessage;
+use crate::utils::blocking::run_blocking;
+use crate::utils::dialogs::popup_error;
+use humansize::{format_size, DECIMAL};
+use iced::widget::{column, container, row, rule, text, Scrollable};
+use iced::{Element, Length, Task};
+use iced_aw::Spinner;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Paths shown per list, so that comparing unrelated drives keeps the page responsive.
+const DISPLAYED_PATHS_LIMIT: usize = 1000;
+
+pub struct ComparePage {
+    repository: Arc<ListerRepository>,
+    drive_a: DriveComboBox,
+    drive_b: DriveComboBox,
+    diff: Option<DriveDiff>,
+    is_comparing: bool,
+}
+
+impl ComparePage {
+    pub fn new(repository: Arc<ListerRepository>) -> (Self, Task<CompareMessage>) {
+        let (drive_a, task_a) = DriveComboBox::new(repository.clone());
+        let (drive_b, task_b) = DriveComboBox::new(repository.clone());
+        (
+            Self {
+                repository,
+                drive_a,
+                drive_b,
+                diff: None,
+                is_comparing: false,
+            },
+            Task::batch([
+                task_a.map(CompareMessage::DriveA),
+                task_b.map(CompareMessage::DriveB),
+            ]),
+        )
+    }
+
+    pub fn title(translations: &HashMap<String, String>) -> String {
+        tr!(translations, "compare_page_title")
+    }
+
+    pub fn view(&'_ self, translations: &HashMap<String, String>) -> Element<'_, CompareMessage> {
+        let pickers = row![
+            self.drive_a.view(translations).map(CompareMessage::DriveA),
+            text(tr!(translations, "compare_with")),
+            self.drive_b.view(translations).map(CompareMessage::DriveB),
+            self.is_comparing.then(|| Spinner::new()
+                .width(Length::from(30))
+                .height(Length::from(30))),
+        ]
+        .spacing(10)
+        .align_y(iced::Alignment::Center);
+
+        let result: Element<'_, CompareMessage> = match &self.diff {
+            None => {
+                container(text(tr!(translations, "compare_pick_drives")).style(text::secondary))
+                    .center(Length::Fill)
+                    .into()
+            }
+            Some(diff) if diff.is_identical() => container(text(
+                tr!(translations, "compare_identical", "nb_files" => &diff.in_both.to_string()),
+            ))
+            .center(Length::Fill)
+            .into(),
+            Some(diff) => Self::diff_view(diff, translations),
+        };
+
+        column![pickers, rule::horizontal(1), result]
+            .spacing(20)
+            .padding(20)
+            .into()
+    }
+
+    fn diff_view<'a>(
+        diff: &'a DriveDiff,
+        translations: &HashMap<String, String>,
+    ) -> Element<'a, CompareMessage> {
+        let mismatches = diff.size_mismatches.iter().map(|mismatch| {
+            format!(
+                "{} ({} / {})",
+                mismatch.path,
+                format_size(mismatch.size_a, DECIMAL),
+                format_size(mismatch.size_b, DECIMAL)
+            )
+        });
+
+        column![
+            text(tr!(translations, "compare_in_both", "nb_files" => &diff.in_both.to_string())),
+            row![
+                Self::path_list(
+                    tr!(translations, "compare_only_in_a", "nb_files" => &diff.only_in_a.len().to_string()),
+                    diff.only_in_a.iter().cloned(),
+                    translations,
+                ),
+                Self::path_list(
+                    tr!(translations, "compare_only_in_b", "nb_files" => &diff.only_in_b.len().to_string()),
+                    diff.only_in_b.iter().cloned(),
+                    translations,
+                ),
+                Self::path_list(
+                    tr!(translations, "compare_size_mismatches", "nb_files" => &diff.size_mismatches.len().to_string()),
+                    mismatches,
+                    translations,
+                ),
+            ]
+            .spacing(20)
+            .height(Length::Fill),
+        ]
+        .spacing(10)
+        .into()
+    }
+
+    fn path_list<'a>(
+        title: String,
+        paths: impl ExactSizeIterator<Item = String>,
+        translations: &HashMap<String, String>,
+    ) -> Element<'a, CompareMessage> {
+        let hidden = paths.len().saturating_sub(DISPLAYED_PATHS_LIMIT);
+        let mut rows: Vec<Element<'a, CompareMessage>> = paths
+            .take(DISPLAYED_PATHS_LIMIT)
+            .map(|path| text(path).into())
+            .collect();
+        if hidden > 0 {
+            rows.push(
+                text(tr!(translations, "compare_more_paths", "nb_files" => &hidden.to_string()))
+                    .style(text::secondary)
+                    .into(),
+            );
+        }
+
+        column![
+            text(title).size(16),
+            rule::horizontal(1),
+            Scrollable::new(column(rows)).height(Length::Fill),
+        ]
+        .spacing(5)
+        .width(Length::FillPortion(1))
+        .into()
+    }
+
+    pub fn update(&mut self, message: CompareMessage) -> Task<CompareMessage> {
+        match message {
+            CompareMessage::DriveA(msg) => {
+                Self::update_combo_box(&mut self.drive_a, msg);
+                self.compare()
+            }
+            CompareMessage::DriveB(msg) => {
+                Self::update_combo_box(&mut self.drive_b, msg);
+                self.compare()
+            }
+            CompareMessage::DrivesCompared {
+                drive_a,
+                drive_b,
+                diff,
+            } => {
+                // A comparison finishing after another drive was picked is outdated
+                if self.drive_a.selected_drive.as_ref() == Some(&drive_a)
+                    && self.drive_b.selected_drive.as_ref() == Some(&drive_b)
+                {
+                    self.is_comparing = false;
+                    self.diff = diff;
+                }
+                Task::none()
+            }
+        }
+    }
+
+    fn update_combo_box(combo_box: &mut DriveComboBox, message: DriveComboBoxMessage) {
+        match message {
+            DriveComboBoxMessage::DrivesFetched(drives) => combo_box.drives = drives,
+            DriveComboBoxMessage::DriveSelected(drive) => combo_box.selected_drive = Some(drive),
+        }
+    }
+
+    /// Compares the two selected drives, once both are picked.
+    fn compare(&mut self) -> Task<CompareMessage> {
+        let (Some(drive_a), Some(drive_b)) = (
+            self.drive_a.selected_drive.clone(),
+            self.drive_b.selected_drive.clone(),
+        ) else {
+            return Task::none();
+        };
+        self.is_comparing = true;
+        let repository = self.repository.clone();
+        Task::future(run_blocking(move || {
+            let diff = repository
+                .diff_drives(&drive_a, &drive_b)
+                .map_err(popup_error)
+                .ok();
+            CompareMessage::DrivesCompared {
+                drive_a,
+                drive_b,
+                diff,
+            }
+        }))
+    }
+}
@@ -1,13 +1,21 @@
+use crate::application::presence_check::{check_presence, PresenceCheckError, PresenceReport};
+use crate::application::undo_manager::{UndoManager, UNDO_WINDOW};
+use crate::infrastructure::database::drive_snapshot::DriveSnapshot;
 use crate::infrastructure::database::repository::ListerRepository;
 use crate::tr;
 use crate::ui::components::drive_combo_box::DriveComboBox;
 use crate::ui::messages::delete_message::DeleteMessage;
 use crate::ui::messages::drive_combo_box::DriveComboBoxMessage;
-use crate::utils::dialogs::popup_error;
+use crate::utils::blocking::run_blocking;
+use crate::utils::dialogs::{confirm, popup_error};
 use iced::widget::{button, column, container, pick_list, row, rule, text};
-use iced::{Element, Length, Task};
+use iced::{padding, Alignment, Element, Length, Task};
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Instant;
+
+/// Number of missing files listed after a verification, the others being only counted.
+const MISSING_PATHS_SHOWN: usize = 10;
 
 pub struct DeletePage {
     repository: Arc<ListerRepository>,
@@ -15,6 +23,12 @@ pub struct DeletePage {
     categories_per_drive: Vec<String>,
     selected_category: Option<String>,
     is_deleted: bool,
+    undo: UndoManager<Arc<DriveSnapshot>>,
+    restored_files: Option<usize>,
+    is_verifying: bool,
+    /// Outcome of the last check that the cataloged files are still on the drive.
+    verification: Option<Result<PresenceReport, PresenceCheckError>>,
+    pruned_files: Option<usize>,
 }
 
 impl DeletePage {
@@ -27,6 +41,11 @@ impl DeletePage {
                 categories_per_drive: vec![],
                 selected_category: None,
                 is_deleted: false,
+                undo: UndoManager::default(),
+                restored_files: None,
+                is_verifying: false,
+                verification: None,
+                pruned_files: None,
             },
             combo_box_task.map(DeleteMessage::DriveComboBox),
         )
@@ -40,6 +59,7 @@ impl DeletePage {
         let drive_combo_box = self.drive_combo_box.view(translations);
         let category_combo_box = self.category_combo_box(translations);
         let action_section = self.action_section(translations);
+        let verify_section = self.verify_section(translations);
 
         container(
             column![
@@ -48,7 +68,8 @@ impl DeletePage {
                     category_combo_box
                 ]
                 .spacing(20),
-                action_section
+                action_section,
+                verify_section
             ]
             .padding(20)
             .spacing(20),
@@ -65,6 +86,7 @@ impl DeletePage {
             }
             DeleteMessage::CategorySelected(category) => {
                 self.selected_category = Some(category);
+                self.clear_verification();
                 Task::none()
             }
             DeleteMessage::DriveComboBox(msg) => match msg {
@@ -74,21 +96,72 @@ impl DeletePage {
                 }
                 DriveComboBoxMessage::DriveSelected(drive) => {
                     self.drive_combo_box.selected_drive = Some(drive.clone());
+                    self.selected_category = None;
+                    self.clear_verification();
                     self.find_categories_for_drive(drive)
                 }
             },
-            DeleteMessage::EndDelete => {
+            DeleteMessage::EndDelete(snapshot) => {
                 self.is_deleted = true;
+                self.clear_verification();
                 self.drive_combo_box.selected_drive = None;
                 self.selected_category = None;
+                let refresh = self
+                    .drive_combo_box
+                    .find_drives()
+                    .map(DeleteMessage::DriveComboBox);
+                match snapshot {
+                    Some(snapshot) => {
+                        self.undo.record(snapshot, Instant::now());
+                        Task::batch([
+                            refresh,
+                            Task::perform(tokio::time::sleep(UNDO_WINDOW), |()| {
+                                DeleteMessage::UndoExpired
+                            }),
+                        ])
+                    }
+                    None => refresh,
+                }
+            }
+            DeleteMessage::EndPrune(pruned_files) => {
+                self.verification = None;
+                self.pruned_files = Some(pruned_files);
+                Task::none()
+            }
+            DeleteMessage::EndUndo(restored_files) => {
+                self.restored_files = Some(restored_files);
                 self.drive_combo_box
                     .find_drives()
                     .map(DeleteMessage::DriveComboBox)
             }
+            DeleteMessage::EndVerify(verification) => {
+                self.is_verifying = false;
+                self.verification = verification;
+                Task::none()
+            }
             DeleteMessage::StartDelete => {
                 self.is_deleted = false;
+                self.restored_files = None;
+                self.undo.clear();
                 self.delete()
             }
+            DeleteMessage::StartPrune {
+                confirm_title,
+                confirm_details,
+            } => self.prune_missing(confirm_title, confirm_details),
+            DeleteMessage::StartVerify => {
+                self.clear_verification();
+                self.is_verifying = true;
+                self.verify()
+            }
+            DeleteMessage::Undo => {
+                self.is_deleted = false;
+                self.undo_delete()
+            }
+            DeleteMessage::UndoExpired => {
+                self.undo.expire(Instant::now());
+                Task::none()
+            }
         }
     }
 
@@ -112,19 +185,32 @@ impl DeletePage {
         let submit_button = self.submit_button(translations);
 
         let requirements_text = if self.can_submit() {
-            if self.is_deleted {
-                text(tr!(translations, "delete_completed"))
-            } else {
-                text("")
-            }
+            text("")
+        } else if self.is_deleted {
+            text(tr!(translations, "delete_completed"))
+        } else if let Some(restored_files) = self.restored_files {
+            text(tr!(translations, "undo_completed", "nb_files" => &restored_files.to_string()))
         } else {
             text(tr!(translations, "delete_select_drive")).style(text::danger)
         }
         .width(Length::Fill);
 
-        column![rule::horizontal(1), row![requirements_text, submit_button]]
-            .spacing(20)
-            .into()
+        let undo_button = (self.is_deleted && self.undo.can_undo(Instant::now())).then(|| {
+            button(text(tr!(translations, "undo_delete")))
+                .on_press(DeleteMessage::Undo)
+                .padding(15)
+                .style(button::secondary)
+        });
+
+        column![
+            rule::horizontal(1),
+            row![requirements_text]
+                .push(undo_button)
+                .push(submit_button)
+                .spacing(10)
+        ]
+        .spacing(20)
+        .into()
     }
 
     fn submit_button(
@@ -147,21 +233,130 @@ impl DeletePage {
             .into()
     }
 
+    /// Checks that the files of the selected drive and category are still on the drive, and
+    /// offers to remove from the index those that are not.
+    fn verify_section(
+        &'_ self,
+        translations: &HashMap<String, String>,
+    ) -> Element<'_, DeleteMessage> {
+        let can_verify = self.can_verify();
+        let verify_button = button(text(tr!(translations, "verify_button")))
+            .on_press_maybe(can_verify.then_some(DeleteMessage::StartVerify))
+            .padding(15)
+            .style(button::secondary);
+
+        let status = if self.is_verifying {
+            text(tr!(translations, "verify_running"))
+        } else if let Some(pruned_files) = self.pruned_files {
+            text(tr!(translations, "prune_completed", "nb_files" => &pruned_files.to_string()))
+        } else {
+            match &self.verification {
+                Some(Ok(report)) if report.missing.is_empty() => text(tr!(
+                    translations,
+                    "verify_all_present",
+                    "nb_files" => &report.checked.to_string()
+                )),
+                Some(Ok(report)) => text(tr!(
+                    translations,
+                    "verify_missing",
+                    "nb_missing" => &report.missing.len().to_string(),
+                    "nb_files" => &report.checked.to_string()
+                )),
+                Some(Err(PresenceCheckError::UnknownLocation)) => {
+                    text(tr!(translations, "verify_no_location")).style(text::danger)
+                }
+                Some(Err(PresenceCheckError::NotMounted(root))) => text(tr!(
+                    translations,
+                    "verify_not_mounted",
+                    "path" => &root.display().to_string()
+                ))
+                .style(text::danger),
+                None if can_verify => text(""),
+                None => text(tr!(translations, "verify_select_category")),
+            }
+        }
+        .width(Length::Fill);
+
+        let missing = self
+            .verification
+            .as_ref()
+            .and_then(|verification| verification.as_ref().ok())
+            .filter(|report| !report.missing.is_empty())
+            .map(|report| Self::missing_files(report, translations));
+
+        column![
+            rule::horizontal(1),
+            row![status, verify_button]
+                .spacing(10)
+                .align_y(Alignment::Center),
+            missing
+        ]
+        .spacing(20)
+        .into()
+    }
+
+    /// Lists the first missing files, then offers to remove all of them from the index.
+    fn missing_files<'a>(
+        report: &PresenceReport,
+        translations: &HashMap<String, String>,
+    ) -> Element<'a, DeleteMessage> {
+        let hidden = report.missing.len().saturating_sub(MISSING_PATHS_SHOWN);
+        let shown = report
+            .missing
+            .iter()
+            .take(MISSING_PATHS_SHOWN)
+            .map(|file| text(file.path.clone()).size(12).into());
+        let more = (hidden > 0).then(|| {
+            text(tr!(translations, "skipped_entries_more", "nb_entries" => &hidden.to_string()))
+                .size(12)
+        });
+        let nb_missing = report.missing.len().to_string();
+        let prune_button = button(text(
+            tr!(translations, "prune_button", "nb_files" => &nb_missing),
+        ))
+        .on_press(DeleteMessage::StartPrune {
+            confirm_title: tr!(translations, "prune_confirm_title"),
+            confirm_details: tr!(translations, "prune_confirm_details", "nb_files" => &nb_missing),
+        })
+        .padding(15)
+        .style(button::danger);
+
+        column![
+            column(shown).padding(padding::left(10)).spacing(5),
+            more,
+            prune_button
+        ]
+        .spacing(10)
+        .into()
+    }
+
     const fn can_submit(&self) -> bool {
         self.drive_combo_box.selected_drive.is_some()
     }
 
+    /// Files are checked for one category at a time, the drive being indexed from a folder in each.
+    const fn can_verify(&self) -> bool {
+        self.drive_combo_box.selected_drive.is_some()
+            && self.selected_category.is_some()
+            && !self.is_verifying
+    }
+
+    fn clear_verification(&mut self) {
+        self.verification = None;
+        self.pruned_files = None;
+    }
+
     fn find_categories_for_drive(&self, drive: String) -> Task<DeleteMessage> {
         let repository = self.repository.clone();
         Task::perform(
-            async move {
+            run_blocking(move || {
                 repository
                     .find_all_category_names_for_drive(&drive)
                     .unwrap_or_else(|err| {
                         popup_error(err);
                         vec![]
                     })
-            },
+            }),
             DeleteMessage::CategoriesFetched,
         )
     }
@@ -171,12 +366,90 @@ impl DeletePage {
         let drive = self.drive_combo_box.selected_drive.clone().unwrap();
         let category = self.selected_category.clone();
         Task::perform(
-            async move {
-                command_repository
-                    .delete(&drive, category.as_deref())
-                    .unwrap_or_else(popup_error);
-            },
-            |()| DeleteMessage::EndDelete,
+            run_blocking(move || {
+                // Without a snapshot the drive is still deleted, only it cannot be undone
+                let snapshot = command_repository
+                    .snapshot(&drive, category.as_deref())
+                    .map_err(popup_error)
+                    .ok();
+                match command_repository.delete(&drive, category.as_deref()) {
+                    Ok(()) => snapshot.map(Arc::new),
+                    Err(err) => {
+                        popup_error(err);
+                        None
+                    }
+                }
+            }),
+            DeleteMessage::EndDelete,
+        )
+    }
+
+    fn verify(&self) -> Task<DeleteMessage> {
+        let repository = self.repository.clone();
+        let (Some(drive), Some(category)) = (
+            self.drive_combo_box.selected_drive.clone(),
+            self.selected_category.clone(),
+        ) else {
+            return Task::none();
+        };
+        Task::perform(
+            run_blocking(move || {
+                let root = repository
+                    .find_drive_root(&drive, &category)
+                    .map_err(popup_error)
+                    .ok()?;
+                let Some(root) = root else {
+                    return Some(Err(PresenceCheckError::UnknownLocation));
+                };
+                let files = repository
+                    .files_for(&category, &drive)
+                    .map_err(popup_error)
+                    .ok()?;
+                Some(check_presence(&root, files))
+            }),
+            DeleteMessage::EndVerify,
+        )
+    }
+
+    fn prune_missing(&self, confirm_title: String, confirm_details: String) -> Task<DeleteMessage> {
+        let (Some(drive), Some(category), Some(Ok(report))) = (
+            self.drive_combo_box.selected_drive.clone(),
+            self.selected_category.clone(),
+            self.verification.as_ref(),
+        ) else {
+            return Task::none();
+        };
+        let paths: Vec<String> = report
+            .missing
+            .iter()
+            .map(|file| file.path.clone())
+            .collect();
+        let repository = self.repository.clone();
+        Task::future(run_blocking(move || {
+            if !confirm(confirm_title, confirm_details) {
+                return None;
+            }
+            repository
+                .remove_files(&category, &drive, &paths)
+                .map_err(popup_error)
+                .ok()
+        }))
+        .and_then(|pruned_files| Task::done(DeleteMessage::EndPrune(pruned_files)))
+    }
+
+    fn undo_delete(&mut self) -> Task<DeleteMessage> {
+        let Some(snapshot) = self.undo.take(Instant::now()) else {
+            return Task::none();
+        };
+        let command_repository = self.repository.clone();
+        Task::perform(
+            run_blocking(move || {
+                command_repository.restore(&snapshot).unwrap_or_else(|err| {
+                    popup_error(err);
+                    0
+                })
+            }),
+            DeleteMessage::EndUndo,
         )
     }
 }
@@ -0,0 +1,369 @@
+use crate::domain::model::byte_size::parse_size;
+use crate::domain::model::category_stats::CategoryStats;
+use crate::domain::model::drive_tag::normalize_tag;
+use crate::infrastructure::database::repository::ListerRepository;
+use crate::tr;
+use crate::ui::messages::manage_message::ManageMessage;
+use crate::utils::blocking::run_blocking;
+use crate::utils::dialogs::{confirm, popup_error};
+use humansize::{format_size, DECIMAL};
+use iced::widget::{
+    button, column, container, pick_list, row, rule, text, text_input, Scrollable, Space,
+};
+use iced::{Alignment, Element, Length, Task};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+struct DriveSpaceInput {
+    drive_name: String,
+    available_space: String,
+    new_tag: String,
+}
+
+/// A category with the one chosen to merge it into.
+struct CategoryMerge {
+    category: CategoryStats,
+    merge_target: Option<String>,
+}
+
+pub struct ManagePage {
+    repository: Arc<ListerRepository>,
+    drives: Vec<DriveSpaceInput>,
+    /// Tags of each tagged drive, by drive name.
+    drive_tags: BTreeMap<String, Vec<String>>,
+    categories: Vec<CategoryMerge>,
+}
+
+impl ManagePage {
+    pub fn new(repository: Arc<ListerRepository>) -> (Self, Task<ManageMessage>) {
+        let task = Task::batch([
+            Self::find_drives(repository.clone()),
+            Self::find_drive_tags(repository.clone()),
+            Self::find_categories(repository.clone()),
+        ]);
+        (
+            Self {
+                repository,
+                drives: vec![],
+                drive_tags: BTreeMap::new(),
+                categories: vec![],
+            },
+            task,
+        )
+    }
+
+    pub fn title(translations: &HashMap<String, String>) -> String {
+        tr!(translations, "manage_page_title")
+    }
+
+    pub fn view(&'_ self, translations: &HashMap<String, String>) -> Element<'_, ManageMessage> {
+        if self.drives.is_empty() {
+            return container(text(tr!(translations, "no_drives_indexed")).style(text::secondary))
+                .center(Length::Fill)
+                .into();
+        }
+
+        let header = row![
+            text(tr!(translations, "drive_column")).width(Length::FillPortion(2)),
+            text(tr!(translations, "available_space_column")).width(Length::FillPortion(2)),
+            Space::new().width(Length::FillPortion(1)),
+            text(tr!(translations, "tags_column")).width(Length::FillPortion(4)),
+        ]
+        .padding(3);
+
+        let drive_rows: Vec<Element<'_, ManageMessage>> = self
+            .drives
+            .iter()
+            .enumerate()
+            .map(|(index, drive)| {
+                row![
+                    text(&drive.drive_name).width(Length::FillPortion(2)),
+                    text_input("500 GB", &drive.available_space)
+                        .on_input(move |input| ManageMessage::AvailableSpaceChanged(index, input))
+                        .on_submit(ManageMessage::SaveAvailableSpace(index))
+                        .width(Length::FillPortion(2)),
+                    button(text(tr!(translations, "save_button")))
+                        .on_press_maybe(
+                            parse_size(&drive.available_space)
+                                .map(|_| ManageMessage::SaveAvailableSpace(index)),
+                        )
+                        .width(Length::FillPortion(1)),
+                    self.tag_editor(index, drive, translations),
+                ]
+                .spacing(10)
+                .padding(3)
+                .align_y(Alignment::Center)
+                .into()
+            })
+            .collect();
+
+        column![
+            header,
+            rule::horizontal(1),
+            Scrollable::new(column(drive_rows)).height(Length::FillPortion(3)),
+            rule::horizontal(1),
+            self.categories_section(translations),
+        ]
+        .spacing(10)
+        .padding(20)
+        .into()
+    }
+
+    /// Lists the tags of a drive, each removed by pressing it, then an input to add one.
+    fn tag_editor(
+        &'_ self,
+        index: usize,
+        drive: &'_ DriveSpaceInput,
+        translations: &HashMap<String, String>,
+    ) -> Element<'_, ManageMessage> {
+        let tags = self
+            .drive_tags
+            .get(&drive.drive_name)
+            .into_iter()
+            .flatten()
+            .map(|tag| {
+                button(text(format!("{tag} ×")).size(13))
+                    .on_press(ManageMessage::RemoveTag {
+                        drive_name: drive.drive_name.clone(),
+                        tag: tag.clone(),
+                    })
+                    .padding([2, 6])
+                    .style(button::secondary)
+                    .into()
+            });
+
+        row![
+            row(tags).spacing(4),
+            text_input(&tr!(translations, "tag_input_placeholder"), &drive.new_tag)
+                .on_input(move |input| ManageMessage::TagInputChanged(index, input))
+                .on_submit(ManageMessage::AddTag(index))
+                .width(Length::Fixed(120.0)),
+        ]
+        .spacing(6)
+        .align_y(Alignment::Center)
+        .width(Length::FillPortion(4))
+        .into()
+    }
+
+    fn categories_section(
+        &'_ self,
+        translations: &HashMap<String, String>,
+    ) -> Element<'_, ManageMessage> {
+        let header = row![
+            text(tr!(translations, "category_column")).width(Length::FillPortion(2)),
+            text(tr!(translations, "file_count_column")).width(Length::FillPortion(1)),
+            text(tr!(translations, "merge_into_column")).width(Length::FillPortion(2)),
+            Space::new().width(Length::FillPortion(1)),
+        ]
+        .padding(3);
+
+        let category_rows: Vec<Element<'_, ManageMessage>> = self
+            .categories
+            .iter()
+            .enumerate()
+            .map(|(index, merge)| {
+                let targets: Vec<String> = self
+                    .categories
+                    .iter()
+                    .map(|other| other.category.name.clone())
+                    .filter(|name| name != &merge.category.name)
+                    .collect();
+                row![
+                    text(&merge.category.name).width(Length::FillPortion(2)),
+                    text(merge.category.file_count.to_string()).width(Length::FillPortion(1)),
+                    pick_list(targets, merge.merge_target.clone(), move |target| {
+                        ManageMessage::MergeTargetSelected(index, target)
+                    })
+                    .placeholder(tr!(translations, "merge_into_placeholder"))
+                    .width(Length::FillPortion(2)),
+                    button(text(tr!(translations, "merge_button")))
+                        .on_press_maybe(merge.merge_target.as_ref().map(|target| {
+                            ManageMessage::MergeCategory {
+                                from: merge.category.name.clone(),
+                                into: target.clone(),
+                                confirm_title: tr!(translations, "merge_confirm_title"),
+                                confirm_details: tr!(translations, "merge_confirm_details",
+                                    "from" => &merge.category.name, "into" => target),
+                            }
+                        }))
+                        .style(button::danger)
+                        .width(Length::FillPortion(1)),
+                ]
+                .spacing(10)
+                .padding(3)
+                .align_y(Alignment::Center)
+                .into()
+            })
+            .collect();
+
+        column![
+            text(tr!(translations, "categories_title")).size(18),
+            header,
+            rule::horizontal(1),
+            Scrollable::new(column(category_rows)).height(Length::FillPortion(2)),
+            rule::horizontal(1),
+        ]
+        .spacing(10)
+        .into()
+    }
+
+    pub fn update(&mut self, message: ManageMessage) -> Task<ManageMessage> {
+        match message {
+            ManageMessage::AddTag(index) => {
+                let Some((drive_name, tag)) = self.drives.get_mut(index).and_then(|drive| {
+                    let tag = normalize_tag(&drive.new_tag)?;
+                    drive.new_tag.clear();
+                    Some((drive.drive_name.clone(), tag))
+                }) else {
+                    return Task::none();
+                };
+                let repository = self.repository.clone();
+                Task::perform(
+                    run_blocking(move || {
+                        repository
+                            .drive_tags()
+                            .add(&drive_name, &tag)
+                            .unwrap_or_else(popup_error);
+                    }),
+                    |()| ManageMessage::DriveTagsSaved,
+                )
+            }
+            ManageMessage::AvailableSpaceChanged(index, input) => {
+                if let Some(drive) = self.drives.get_mut(index) {
+                    drive.available_space = input;
+                }
+                Task::none()
+            }
+            ManageMessage::AvailableSpaceSaved => Self::find_drives(self.repository.clone()),
+            ManageMessage::CategoriesFetched(categories) => {
+                self.categories = categories
+                    .into_iter()
+                    .map(|category| CategoryMerge {
+                        category,
+                        merge_target: None,
+                    })
+                    .collect();
+                Task::none()
+            }
+            ManageMessage::CategoriesMerged => Task::batch([
+                Self::find_drives(self.repository.clone()),
+                Self::find_categories(self.repository.clone()),
+            ]),
+            ManageMessage::DrivesFetched(drives) => {
+                self.drives = drives
+                    .into_iter()
+                    .map(|drive| DriveSpaceInput {
+                        drive_name: drive.drive_name,
+                        available_space: format_size(drive.available_space, DECIMAL),
+                        new_tag: String::new(),
+                    })
+                    .collect();
+                Task::none()
+            }
+            ManageMessage::DriveTagsFetched(drive_tags) => {
+                self.drive_tags = drive_tags;
+                Task::none()
+            }
+            ManageMessage::DriveTagsSaved => Self::find_drive_tags(self.repository.clone()),
+            ManageMessage::MergeCategory {
+                from,
+                into,
+                confirm_title,
+                confirm_details,
+            } => {
+                let repository = self.repository.clone();
+                Task::perform(
+                    run_blocking(move || {
+                        if confirm(confirm_title, confirm_details) {
+                            repository
+                                .merge_categories(&from, &into)
+                                .unwrap_or_else(popup_error);
+                        }
+                    }),
+                    |()| ManageMessage::CategoriesMerged,
+                )
+            }
+            ManageMessage::MergeTargetSelected(index, target) => {
+                if let Some(merge) = self.categories.get_mut(index) {
+                    merge.merge_target = Some(target);
+                }
+                Task::none()
+            }
+            ManageMessage::RemoveTag { drive_name, tag } => {
+                let repository = self.repository.clone();
+                Task::perform(
+                    run_blocking(move || {
+                        repository
+                            .drive_tags()
+                            .remove(&drive_name, &tag)
+                            .unwrap_or_else(popup_error);
+                    }),
+                    |()| ManageMessage::DriveTagsSaved,
+                )
+            }
+            ManageMessage::SaveAvailableSpace(index) => {
+                let Some((drive_name, available_space)) =
+                    self.drives.get(index).and_then(|drive| {
+                        parse_size(&drive.available_space)
+                            .map(|size| (drive.drive_name.clone(), size))
+                    })
+                else {
+                    return Task::none();
+                };
+                let repository = self.repository.clone();
+                Task::perform(
+                    run_blocking(move || {
+                        repository
+                            .set_drive_available_space(&drive_name, available_space)
+                            .unwrap_or_else(popup_error);
+                    }),
+                    |()| ManageMessage::AvailableSpaceSaved,
+                )
+            }
+            ManageMessage::TagInputChanged(index, input) => {
+                if let Some(drive) = self.drives.get_mut(index) {
+                    drive.new_tag = input;
+                }
+                Task::none()
+            }
+        }
+    }
+
+    fn find_categories(repository: Arc<ListerRepository>) -> Task<ManageMessage> {
+        Task::perform(
+            run_blocking(move || {
+                repository.find_all_categories().unwrap_or_else(|err| {
+                    popup_error(err);
+                    vec![]
+                })
+            }),
+            ManageMessage::CategoriesFetched,
+        )
+    }
+
+    fn find_drive_tags(repository: Arc<ListerRepository>) -> Task<ManageMessage> {
+        Task::perform(
+            run_blocking(move || {
+                repository.drive_tags().find_all().unwrap_or_else(|err| {
+                    popup_error(err);
+                    BTreeMap::new()
+                })
+            }),
+            ManageMessage::DriveTagsFetched,
+        )
+    }
+
+    fn find_drives(repository: Arc<ListerRepository>) -> Task<ManageMessage> {
+        Task::perform(
+            run_blocking(move || {
+                repository
+                    .find_all_drives_available_space()
+                    .unwrap_or_else(|err| {
+                        popup_error(err);
+                        vec![]
+                    })
+            }),
+            ManageMessage::DrivesFetched,
+        )
+    }
+}
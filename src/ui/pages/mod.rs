@@ -1,3 +1,8 @@
+pub mod browse_page;
+pub mod compare_page;
 pub mod delete_page;
+pub mod manage_page;
 pub mod read_page;
+pub mod settings_page;
+pub mod stats_page;
 pub mod write_page;
@@ -1,52 +1,150 @@
 use std::collections::HashMap;
+use std::fs;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
+use crate::application::cache::{Cache, DEFAULT_CACHED_DATASETS, DEFAULT_CACHED_ROWS};
+use crate::application::latest_request::LatestRequest;
+use crate::application::search_timer::SearchTimer;
+use crate::domain::model::directory_group::group_by_parent_directory;
+use crate::domain::model::drive_group::group_by_drive;
 use crate::domain::model::file_entry::FileWithMetadata;
+use crate::domain::model::fuzzy_match::rank_by_filename;
+use crate::domain::model::hidden_results::ResultKey;
 use crate::domain::model::language::Language;
-use crate::domain::model::pagination::PaginatedResult;
+use crate::domain::model::manifest::format_manifest;
+use crate::domain::model::pagination::{PaginatedResult, DEFAULT_PAGE_SIZE};
+use crate::domain::model::path_tree::build_path_tree;
+use crate::domain::model::result_columns::ResultColumns;
+use crate::domain::model::result_table::format_markdown_table;
+use crate::domain::model::row_density::RowDensity;
+use crate::domain::model::saved_search::SavedSearch;
+use crate::domain::model::search_constraint::SearchConstraint;
+use crate::domain::model::search_filters::SearchFilters;
+use crate::domain::model::search_history::{SearchHistory, SEARCH_HISTORY_LIMIT};
+use crate::domain::model::stored_path::decode_path;
+use crate::infrastructure::database::pool::InfrastructureError;
 use crate::infrastructure::database::repository::ListerRepository;
 use crate::tr;
 use crate::ui::components::drive_combo_box::DriveComboBox;
-use crate::ui::components::read::cache::Cache;
-use crate::ui::components::read::file_list::FileList;
+use crate::ui::components::read::file_list::{FileList, PageKey};
+use crate::ui::components::read::image_preview::ImagePreview;
 use crate::ui::components::read::pagination::Pagination;
+use crate::ui::components::read::saved_searches::SavedSearches;
 use crate::ui::components::read::search::Search;
+use crate::ui::components::read::status_bar::StatusBar;
 use crate::ui::messages::drive_combo_box::DriveComboBoxMessage;
 use crate::ui::messages::read_message::ReadMessage;
-use crate::utils::dialogs::popup_error;
+use crate::utils::blocking::run_blocking;
+use crate::utils::dialogs::{popup_error, popup_warning};
+use crate::utils::os_open::open_path;
+use iced::futures::channel::mpsc;
 use iced::keyboard::key::Named;
-use iced::widget::{column, row};
-use iced::{event, keyboard, Element, Event, Subscription, Task};
+use iced::widget::{button, column, container, row, text, toggler, tooltip};
+use iced::{event, keyboard, Alignment, Element, Event, Length, Subscription, Task};
+use iced_aw::Spinner;
 
-const ITEMS_PER_PAGE: usize = 100;
 const CACHED_SIZE: u64 = 10000;
+const WARM_BATCH_SIZE: u64 = 1000;
+/// Number of recently indexed files shown before any search.
+const RECENT_FILES_LIMIT: u64 = 20;
+/// Pause in typing after which the query is searched.
+const SEARCH_DEBOUNCE: Duration = Duration::from_millis(300);
+/// Interval at which a running search is checked for slowness.
+const SEARCH_TIMER_TICK: Duration = Duration::from_millis(250);
+
+/// Full dataset being streamed in the background before it is cached.
+struct CacheWarm {
+    generation: u64,
+    drive: Option<String>,
+    query: String,
+    filters: SearchFilters,
+    rows: Vec<FileWithMetadata>,
+}
 
 pub struct ReadPage {
     repository: Arc<ListerRepository>,
     drive_combo_box: DriveComboBox,
     search: Search,
+    saved_searches: SavedSearches,
     pagination: Pagination,
     file_list: FileList,
+    preview: ImagePreview,
     cache: Cache,
-    is_cache_warming: bool,
+    cache_warm: Option<CacheWarm>,
+    warm_load: LatestRequest,
+    /// Search scheduled while typing, replaced by every keystroke.
+    live_search: LatestRequest,
+    /// Page being loaded, whose results are dropped when another load starts meanwhile.
+    page_load: LatestRequest,
+    is_loading: bool,
+    search_timer: SearchTimer,
 }
 
 impl ReadPage {
     pub fn new(repository: Arc<ListerRepository>) -> (Self, Task<ReadMessage>) {
         let (drive_combo_box, combo_box_task) = DriveComboBox::new(repository.clone());
         let (search, search_task) = Search::new();
+        let history_repository = repository.clone();
+        let history_task = Task::perform(
+            run_blocking(move || {
+                history_repository
+                    .get_search_history()
+                    .unwrap_or_else(|error| {
+                        popup_error(error);
+                        SearchHistory::new(SEARCH_HISTORY_LIMIT)
+                    })
+            }),
+            ReadMessage::HistoryFetched,
+        );
+        let saved_searches_task = Self::fetch_saved_searches(repository.clone());
+        let items_per_page_task = Self::fetch_items_per_page(repository.clone());
+        let row_density_task = Self::fetch_row_density(repository.clone());
+        let columns_task = Self::fetch_result_columns(repository.clone());
+        let extensions_task = Task::perform(
+            run_blocking({
+                let repository = repository.clone();
+                move || {
+                    repository.list_extensions(None).unwrap_or_else(|error| {
+                        popup_error(error);
+                        vec![]
+                    })
+                }
+            }),
+            ReadMessage::ExtensionsFetched,
+        );
+        let recent_files_task = Self::fetch_recent_files(repository.clone());
+        let tags_task = Self::fetch_tags(repository.clone());
         let page = Self {
             repository,
             drive_combo_box,
             search,
-            pagination: Pagination::new(ITEMS_PER_PAGE),
-            file_list: FileList::new(),
-            cache: Cache::new(),
-            is_cache_warming: false,
+            saved_searches: SavedSearches::new(),
+            pagination: Pagination::new(DEFAULT_PAGE_SIZE),
+            file_list: FileList::new(RowDensity::default(), ResultColumns::default()),
+            preview: ImagePreview::default(),
+            cache: Cache::new(DEFAULT_CACHED_DATASETS, DEFAULT_CACHED_ROWS),
+            cache_warm: None,
+            warm_load: LatestRequest::default(),
+            live_search: LatestRequest::default(),
+            page_load: LatestRequest::default(),
+            is_loading: false,
+            search_timer: SearchTimer::default(),
         };
         (
             page,
-            Task::batch([combo_box_task.map(ReadMessage::DriveComboBox), search_task]),
+            Task::batch([
+                combo_box_task.map(ReadMessage::DriveComboBox),
+                search_task,
+                history_task,
+                saved_searches_task,
+                extensions_task,
+                recent_files_task,
+                tags_task,
+                columns_task,
+                row_density_task,
+                items_per_page_task,
+            ]),
         )
     }
 
@@ -64,13 +162,118 @@ impl ReadPage {
             .view(translations)
             .map(ReadMessage::DriveComboBox);
         let search_section = self.search.view(translations);
-        let files = self.file_list.view(language);
-        let pagination_section = self.pagination.view(translations);
+        let saved_searches_section = self.saved_searches.view(translations);
+        let files = self.file_list.view(translations, language);
+        // The largest files come on a single page
+        let pagination_section = self
+            .search
+            .filters
+            .top
+            .is_none()
+            .then(|| self.pagination.view(translations));
+        let status_bar = StatusBar {
+            drive: self.drive_combo_box.selected_drive.as_deref(),
+            query: &self.search.database_query(),
+            filters: &self.search.filters,
+            total_count: self.pagination.total_count,
+            total_size: self.pagination.total_size,
+        }
+        .view(translations);
+        let group_toggle = toggler(self.file_list.group_by_folder)
+            .label(tr!(translations, "group_by_folder_toggle"))
+            .on_toggle_maybe(
+                self.file_list
+                    .can_group()
+                    .then_some(ReadMessage::GroupByFolderToggled),
+            );
+        let drive_group_toggle = toggler(self.file_list.group_by_drive)
+            .label(tr!(translations, "group_by_drive_toggle"))
+            .on_toggle_maybe(
+                self.file_list
+                    .can_group()
+                    .then_some(ReadMessage::GroupByDriveToggled),
+            );
+        let tree_toggle = toggler(self.file_list.tree_view)
+            .label(tr!(translations, "tree_view_toggle"))
+            .on_toggle_maybe(
+                self.file_list
+                    .can_group()
+                    .then_some(ReadMessage::TreeViewToggled),
+            );
+        let group_toggles = row![group_toggle, drive_group_toggle, tree_toggle].spacing(20);
+        let group_section: Element<'_, ReadMessage> = if self.file_list.can_group() {
+            group_toggles.into()
+        } else {
+            tooltip(
+                group_toggles,
+                container(text(tr!(translations, "group_unavailable")))
+                    .style(container::rounded_box)
+                    .padding(10),
+                tooltip::Position::Top,
+            )
+            .into()
+        };
+        let columns_button = button(text(tr!(translations, "columns_menu")))
+            .on_press(ReadMessage::ColumnsMenuToggled)
+            .style(if self.file_list.is_columns_menu_open {
+                button::primary
+            } else {
+                button::secondary
+            });
+        let manifest_button = button(text(tr!(translations, "manifest_button"))).on_press_maybe(
+            self.drive_combo_box
+                .selected_drive
+                .is_some()
+                .then(|| ReadMessage::ManifestPressed {
+                    dialog_title: tr!(translations, "manifest_dialog_title"),
+                }),
+        );
+        let markdown_button = button(text(tr!(translations, "copy_markdown_table")))
+            .on_press_maybe(
+                self.file_list
+                    .visible_files()
+                    .next()
+                    .is_some()
+                    .then_some(ReadMessage::CopyMarkdownTable),
+            );
+        let columns_menu = self
+            .file_list
+            .is_columns_menu_open
+            .then(|| self.file_list.columns_menu(translations));
+        let loading_indicator = self.is_loading.then(|| {
+            Spinner::new()
+                .width(Length::from(40))
+                .height(Length::from(40))
+        });
+        let slow_search_notice = self.search_timer.is_slow().then(|| {
+            let notice = if self.drive_combo_box.selected_drive.is_none() {
+                format!(
+                    "{} {}",
+                    tr!(translations, "slow_search_notice"),
+                    tr!(translations, "slow_search_hint")
+                )
+            } else {
+                tr!(translations, "slow_search_notice")
+            };
+            text(notice).style(text::secondary)
+        });
 
         column![
-            row![drive_combo_box, search_section].spacing(10),
-            files,
-            pagination_section
+            row![drive_combo_box, search_section, loading_indicator].spacing(10),
+            saved_searches_section,
+            slow_search_notice,
+            row![files, self.preview.view(translations)].spacing(10),
+            row![
+                pagination_section,
+                group_section,
+                columns_button,
+                manifest_button,
+                markdown_button
+            ]
+            .spacing(20)
+            .align_y(Alignment::Center),
+            columns_menu,
+            status_bar
         ]
         .spacing(20)
         .padding(20)
@@ -84,9 +287,33 @@ impl ReadPage {
             ReadMessage::ArrowNavigationReleased => self.load_current_page(),
             ReadMessage::ArrowRightPressed { shift } => self.handle_right(shift),
             ReadMessage::ArrowUpPressed { shift } => self.arrow_up_pressed(shift),
+            ReadMessage::CacheWarmBatchLoaded(generation, files) => {
+                self.append_warm_batch(generation, files);
+                Task::none()
+            }
+            ReadMessage::CacheWarmFinished(generation) => self.finish_cache_warm(generation),
+            ReadMessage::ColumnToggled(column, is_shown) => {
+                self.file_list.columns.set_shown(column, is_shown);
+                self.save_columns()
+            }
+            ReadMessage::ColumnsMenuToggled => {
+                self.file_list.is_columns_menu_open = !self.file_list.is_columns_menu_open;
+                Task::none()
+            }
+            ReadMessage::ConstraintLifted(constraint) => self.lift_constraint(constraint),
             ReadMessage::ContentChanged(content) => {
                 self.search.query = content;
-                Task::none()
+                self.search.offer_history_clear = false;
+                self.schedule_live_search()
+            }
+            ReadMessage::CopyMarkdownTable => {
+                let files: Vec<FileWithMetadata> =
+                    self.file_list.visible_files().cloned().collect();
+                iced::clipboard::write(format_markdown_table(&files))
+            }
+            ReadMessage::CopyToClipboard(content) => {
+                self.file_list.close_row_menu();
+                iced::clipboard::write(content)
             }
             ReadMessage::DriveComboBox(msg) => match msg {
                 DriveComboBoxMessage::DrivesFetched(drives) => {
@@ -95,138 +322,465 @@ impl ReadPage {
                 }
                 DriveComboBoxMessage::DriveSelected(drive) => {
                     self.drive_combo_box.selected_drive = Some(drive);
-                    self.process_new_search()
+                    Task::batch([self.process_new_search(), self.fetch_extensions()])
                 }
             },
             ReadMessage::EndPressed => self.file_list.snap_to_bottom(),
-            ReadMessage::FilesLoaded(result) => self.handle_files_loaded(result),
+            ReadMessage::EnterPressed => {
+                self.file_list.toggle_selected_row_menu();
+                Task::none()
+            }
+            ReadMessage::EscapePressed => self.search.leave(),
+            ReadMessage::ExtensionSelected(extension) => {
+                self.search.filters.extension = Some(extension);
+                self.process_new_search()
+            }
+            ReadMessage::ExtensionsFetched(extensions) => {
+                self.search.extensions = extensions;
+                Task::none()
+            }
+            ReadMessage::TagSelected(tag) => {
+                self.search.filters.tag = Some(tag);
+                self.process_new_search()
+            }
+            ReadMessage::TagsFetched(tags) => {
+                self.search.tags = tags;
+                Task::none()
+            }
+            ReadMessage::FileListScrolled(viewport) => {
+                self.file_list.scrolled(&viewport);
+                Task::none()
+            }
+            ReadMessage::FilenameOnlyToggled(filename_only) => {
+                self.search.filters.filename_only = filename_only;
+                self.process_new_search()
+            }
+            ReadMessage::PermissionSelected(permission) => {
+                self.search.filters.permission = permission;
+                self.process_new_search()
+            }
+            ReadMessage::FuzzyToggled(fuzzy) => {
+                self.search.filters.fuzzy = fuzzy;
+                self.process_new_search()
+            }
+            ReadMessage::WholeSegmentsToggled(whole_segments) => {
+                self.search.filters.whole_segments = whole_segments;
+                self.process_new_search()
+            }
+            ReadMessage::LargestFilesToggled => {
+                self.search.filters.toggle_largest_files();
+                self.process_new_search()
+            }
+            ReadMessage::FocusSearch => self.search.focus(),
+            ReadMessage::IndexedWithinSelected(indexed_within) => {
+                self.search.set_indexed_within(indexed_within);
+                self.process_new_search()
+            }
+            ReadMessage::ItemsPerPageChanged(items_per_page) => {
+                self.change_items_per_page(items_per_page)
+            }
+            ReadMessage::ItemsPerPageFetched(items_per_page) => {
+                self.show_items_per_page(items_per_page)
+            }
+            ReadMessage::FilesLoaded(generation, result) => {
+                self.handle_files_loaded(generation, result)
+            }
             ReadMessage::FirstPage => self.navigate_to_page(0),
+            ReadMessage::DriveGroupToggled(drive_name) => {
+                self.file_list.toggle_drive_group(drive_name);
+                Task::none()
+            }
+            ReadMessage::GroupByDriveToggled(group_by_drive) => {
+                self.file_list.group_by_drive = group_by_drive;
+                if group_by_drive {
+                    self.file_list.group_by_folder = false;
+                    self.file_list.tree_view = false;
+                }
+                Task::none()
+            }
+            ReadMessage::GroupByFolderToggled(group_by_folder) => {
+                self.file_list.group_by_folder = group_by_folder;
+                if group_by_folder {
+                    self.file_list.group_by_drive = false;
+                    self.file_list.tree_view = false;
+                }
+                Task::none()
+            }
+            ReadMessage::GroupToggled(drive_name, directory) => {
+                self.file_list.toggle_group(drive_name, directory);
+                Task::none()
+            }
+            ReadMessage::HistoryClear => self.clear_history(),
+            ReadMessage::RowDensityFetched(density) => {
+                self.file_list.density = density;
+                Task::none()
+            }
+            ReadMessage::ResultColumnsFetched(columns) => {
+                self.file_list.columns = columns;
+                Task::none()
+            }
+            ReadMessage::HistoryFetched(history) => {
+                self.search.history = history;
+                Task::none()
+            }
+            ReadMessage::HistorySelected(query) => {
+                self.search.query = query;
+                self.process_new_search()
+            }
             ReadMessage::HomePressed => self.file_list.snap_to_top(),
             ReadMessage::LastPage => {
                 self.navigate_to_page(self.pagination.total_pages().saturating_sub(1))
             }
+            ReadMessage::ManifestPressed { dialog_title } => self.generate_manifest(dialog_title),
             ReadMessage::NextPage => self.next_page(),
+            ReadMessage::OpenFile {
+                key,
+                no_location,
+                not_found,
+            } => {
+                self.file_list.close_row_menu();
+                self.open_file(key, no_location, not_found)
+            }
             ReadMessage::PageDownPressed => self.arrow_done_pressed(true),
             ReadMessage::PageInputChanged(page_number) => {
-                self.pagination.page_input_value = page_number;
+                self.pagination.edit_page_input(page_number);
                 Task::none()
             }
             ReadMessage::PageInputSubmit => self.process_page_input(),
             ReadMessage::PageUpPressed => self.arrow_up_pressed(true),
             ReadMessage::PrevPage => self.previous_page(),
+            ReadMessage::RecentFilesFetched(files) => {
+                self.file_list.set_recent_files(files);
+                Task::none()
+            }
+            ReadMessage::RefinementChanged(refinement) => self.refine_results(refinement),
+            ReadMessage::Refresh => self.refresh(),
+            ReadMessage::ResultHidden(key) => {
+                self.file_list.hide(key);
+                Task::none()
+            }
+            ReadMessage::RowMenuToggled(key) => {
+                self.file_list.toggle_row_menu(key);
+                self.follow_focus()
+            }
+            ReadMessage::PreviewLoaded(key, image) => {
+                self.preview.loaded(key, image);
+                Task::none()
+            }
+            ReadMessage::SaveSearchPressed => self.save_search(),
+            ReadMessage::SavedSearchDeleted(name) => self.delete_saved_search(name),
+            ReadMessage::SavedSearchNameChanged(name) => {
+                self.saved_searches.name = name;
+                Task::none()
+            }
+            ReadMessage::SavedSearchSelected(name) => self.restore_saved_search(&name),
+            ReadMessage::SavedSearchesFetched(searches) => {
+                self.saved_searches.searches = searches;
+                Task::none()
+            }
             ReadMessage::SearchClear => self.clear_search(),
+            ReadMessage::LiveSearchDue(generation) => {
+                if self.live_search.is_latest(generation) {
+                    self.run_new_search()
+                } else {
+                    Task::none()
+                }
+            }
             ReadMessage::SearchSubmit => self.process_new_search(),
+            ReadMessage::SearchTimerTicked(now) => {
+                self.search_timer.tick(now);
+                Task::none()
+            }
+            ReadMessage::SortSelected(sort) => {
+                self.search.filters.sort = sort;
+                self.search.filters.top = None;
+                self.process_new_search()
+            }
+            ReadMessage::TreeNodeToggled(drive_name, path) => {
+                self.file_list.toggle_tree_node(drive_name, path);
+                Task::none()
+            }
+            ReadMessage::TreeViewToggled(tree_view) => {
+                self.file_list.tree_view = tree_view;
+                if tree_view {
+                    self.file_list.group_by_folder = false;
+                    self.file_list.group_by_drive = false;
+                }
+                Task::none()
+            }
         }
     }
 
-    pub fn subscription() -> Subscription<ReadMessage> {
-        Subscription::batch([event::listen_with(|event, _status, _window| match event {
-            Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. }) => {
-                let keyboard::Key::Named(key) = key else {
-                    return None;
-                };
-                match (key, modifiers) {
-                    (Named::ArrowLeft, _) => Some(ReadMessage::ArrowLeftPressed {
-                        shift: modifiers.shift(),
-                    }),
-                    (Named::ArrowRight, _) => Some(ReadMessage::ArrowRightPressed {
-                        shift: modifiers.shift(),
-                    }),
-                    (Named::ArrowUp, _) => Some(ReadMessage::ArrowUpPressed {
-                        shift: modifiers.shift(),
-                    }),
-                    (Named::ArrowDown, _) => Some(ReadMessage::ArrowDownPressed {
-                        shift: modifiers.shift(),
-                    }),
-                    (Named::PageUp, _) => Some(ReadMessage::PageUpPressed),
-                    (Named::PageDown, _) => Some(ReadMessage::PageDownPressed),
-                    (Named::Home, _) => Some(ReadMessage::HomePressed),
-                    (Named::End, _) => Some(ReadMessage::EndPressed),
-                    _ => None,
+    pub fn subscription(&self) -> Subscription<ReadMessage> {
+        // Ticks only while a search is running and not yet reported as slow
+        let search_timer = if self.search_timer.needs_ticks() {
+            iced::time::every(SEARCH_TIMER_TICK).map(ReadMessage::SearchTimerTicked)
+        } else {
+            Subscription::none()
+        };
+
+        Subscription::batch([
+            search_timer,
+            event::listen_with(|event, status, _window| match event {
+                Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. }) => {
+                    let key = match key {
+                        keyboard::Key::Named(key) => key,
+                        keyboard::Key::Character(c) if modifiers.command() => {
+                            return match c.as_str() {
+                                "f" => Some(ReadMessage::FocusSearch),
+                                "l" => Some(ReadMessage::SearchClear),
+                                "r" => Some(ReadMessage::Refresh),
+                                _ => None,
+                            };
+                        }
+                        _ => return None,
+                    };
+                    match (key, modifiers) {
+                        (Named::ArrowLeft, _) => Some(ReadMessage::ArrowLeftPressed {
+                            shift: modifiers.shift(),
+                        }),
+                        (Named::ArrowRight, _) => Some(ReadMessage::ArrowRightPressed {
+                            shift: modifiers.shift(),
+                        }),
+                        (Named::ArrowUp, _) => Some(ReadMessage::ArrowUpPressed {
+                            shift: modifiers.shift(),
+                        }),
+                        (Named::ArrowDown, _) => Some(ReadMessage::ArrowDownPressed {
+                            shift: modifiers.shift(),
+                        }),
+                        (Named::PageUp, _) => Some(ReadMessage::PageUpPressed),
+                        (Named::PageDown, _) => Some(ReadMessage::PageDownPressed),
+                        (Named::Home, _) => Some(ReadMessage::HomePressed),
+                        (Named::End, _) => Some(ReadMessage::EndPressed),
+                        (Named::Escape, _) => Some(ReadMessage::EscapePressed),
+                        // Enter in the search field submits the search instead
+                        (Named::Enter, _) => {
+                            (status == event::Status::Ignored).then_some(ReadMessage::EnterPressed)
+                        }
+                        (Named::F5, _) => Some(ReadMessage::Refresh),
+                        _ => None,
+                    }
                 }
-            }
-            Event::Keyboard(keyboard::Event::KeyReleased { key, .. }) => {
-                let keyboard::Key::Named(key) = key else {
-                    return None;
-                };
-                match key {
-                    Named::ArrowLeft | Named::ArrowRight => {
-                        Some(ReadMessage::ArrowNavigationReleased)
+                Event::Keyboard(keyboard::Event::KeyReleased { key, .. }) => {
+                    let keyboard::Key::Named(key) = key else {
+                        return None;
+                    };
+                    match key {
+                        Named::ArrowLeft | Named::ArrowRight => {
+                            Some(ReadMessage::ArrowNavigationReleased)
+                        }
+                        _ => None,
                     }
-                    _ => None,
                 }
-            }
-            _ => None,
-        })])
+                _ => None,
+            }),
+        ])
     }
 
     fn load_current_page(&mut self) -> Task<ReadMessage> {
+        // Whatever was loading before is outdated, even when this page comes from the cache
+        let generation = self.page_load.start();
+        self.preview.clear();
+        self.is_loading = false;
+        self.search_timer.stop();
+        let (cached_query, refinement) = self.cache_lookup();
         if let Some(files) = self.cache.get_page(
             self.drive_combo_box.selected_drive.as_ref(),
-            &self.search.query,
+            &cached_query,
+            &self.search.filters,
+            &refinement,
             self.pagination.current_page_index,
-            ITEMS_PER_PAGE,
+            self.pagination.items_per_page,
         ) {
-            self.file_list.set_files(files);
-            return self.file_list.snap_to_top();
+            if let Some(count) = self.cache.count(
+                self.drive_combo_box.selected_drive.as_ref(),
+                &cached_query,
+                &self.search.filters,
+                &refinement,
+            ) {
+                // The page was past the last one, as happens after results were removed
+                if self.pagination.set_total_count(count) {
+                    return self.load_current_page();
+                }
+            }
+            if let Some(total_size) = self.cache.total_size(
+                self.drive_combo_box.selected_drive.as_ref(),
+                &cached_query,
+                &self.search.filters,
+                &refinement,
+            ) {
+                self.pagination.total_size = total_size;
+            }
+            self.refresh_groups();
+            return self.file_list.show(files, self.page_key());
         }
 
-        if !self.cache.is_valid_for(
-            self.drive_combo_box.selected_drive.as_ref(),
-            &self.search.query,
-        ) {
-            self.cache.clear();
+        if let Some(files) = self.warmed_page() {
+            return self.show_page(files);
         }
 
+        let database_query = self.search.database_query();
+        if self.search.filters.fuzzy && !database_query.trim().is_empty() {
+            return self.load_fuzzy_results(generation, database_query);
+        }
         let selected_drive = self.drive_combo_box.selected_drive.clone();
-        let search_query = if self.search.query.is_empty() {
+        let search_query = if database_query.is_empty() {
             None
         } else {
-            Some(self.search.query.clone())
+            Some(database_query)
         };
+        let filters = self.search.filters.clone();
         let query_repository = self.repository.clone();
         let page = self.pagination.current_page_index;
-        let ipp = self.pagination.items_per_page;
+        let ipp = self
+            .search
+            .filters
+            .top
+            .map_or(self.pagination.items_per_page, |top| top as usize);
+        self.is_loading = true;
+        self.search_timer.start(Instant::now());
 
         Task::perform(
-            async move {
+            run_blocking(move || {
                 let count = query_repository
-                    .count_search_results(selected_drive.as_deref(), search_query.as_deref())
+                    .count_search_results(
+                        selected_drive.as_deref(),
+                        search_query.as_deref(),
+                        &filters,
+                    )
+                    .unwrap_or(0);
+                let total_size = query_repository
+                    .sum_search_results_size(
+                        selected_drive.as_deref(),
+                        search_query.as_deref(),
+                        &filters,
+                    )
                     .unwrap_or(0);
-                let files = if count <= CACHED_SIZE {
+                let files = query_repository
+                    .search_files_paginated(
+                        selected_drive.as_deref(),
+                        search_query.as_deref(),
+                        &filters,
+                        page as u64,
+                        ipp as u64,
+                    )
+                    .unwrap_or_else(|err| {
+                        popup_error(err);
+                        vec![]
+                    });
+                let is_drive_missing = count == 0
+                    && selected_drive
+                        .as_deref()
+                        .is_some_and(|drive| !query_repository.drive_exists(drive).unwrap_or(true));
+                let lifted_constraints = if count == 0 && !is_drive_missing {
                     query_repository
-                        .search_files_paginated(
+                        .diagnose_empty(
                             selected_drive.as_deref(),
                             search_query.as_deref(),
-                            0,
-                            count,
+                            &filters,
                         )
-                        .unwrap_or_else(|err| {
-                            popup_error(err);
-                            vec![]
-                        })
+                        .unwrap_or_default()
                 } else {
-                    query_repository
-                        .search_files_paginated(
-                            selected_drive.as_deref(),
-                            search_query.as_deref(),
-                            page as u64,
-                            ipp as u64,
-                        )
-                        .unwrap_or_else(|err| {
-                            popup_error(err);
-                            vec![]
-                        })
+                    Vec::new()
                 };
                 PaginatedResult {
                     items: files,
                     total_count: count,
+                    total_size,
+                    is_drive_missing,
+                    exceeds_fuzzy_limit: false,
+                    lifted_constraints,
                 }
-            },
-            ReadMessage::FilesLoaded,
+            }),
+            move |result| ReadMessage::FilesLoaded(generation, result),
+        )
+    }
+
+    /// Loads every file left by the drive and filters, then ranks them in memory by how closely
+    /// their filename matches the query.
+    ///
+    /// The ranked results come back whole, so they are cached like any small dataset. Above
+    /// [`CACHED_SIZE`] files nothing is loaded, and the search has to be narrowed first.
+    fn load_fuzzy_results(&mut self, generation: u64, query: String) -> Task<ReadMessage> {
+        let selected_drive = self.drive_combo_box.selected_drive.clone();
+        let filters = self.search.filters.clone();
+        let query_repository = self.repository.clone();
+        self.is_loading = true;
+        self.search_timer.start(Instant::now());
+
+        Task::perform(
+            run_blocking(move || {
+                let count = query_repository
+                    .count_search_results(selected_drive.as_deref(), None, &filters)
+                    .unwrap_or(0);
+                if count > CACHED_SIZE {
+                    return PaginatedResult {
+                        items: vec![],
+                        total_count: 0,
+                        total_size: 0,
+                        is_drive_missing: false,
+                        exceeds_fuzzy_limit: true,
+                        lifted_constraints: Vec::new(),
+                    };
+                }
+                let files = query_repository
+                    .search_files_paginated(selected_drive.as_deref(), None, &filters, 0, count)
+                    .unwrap_or_else(|err| {
+                        popup_error(err);
+                        vec![]
+                    });
+                let mut ranked = rank_by_filename(files, &query);
+                if let Some(top) = filters.top {
+                    ranked.truncate(usize::try_from(top).unwrap_or(usize::MAX));
+                }
+                let is_drive_missing = count == 0
+                    && selected_drive
+                        .as_deref()
+                        .is_some_and(|drive| !query_repository.drive_exists(drive).unwrap_or(true));
+                PaginatedResult {
+                    total_count: ranked.len() as u64,
+                    total_size: ranked.iter().map(|file| file.size_bytes).sum(),
+                    items: ranked,
+                    is_drive_missing,
+                    exceeds_fuzzy_limit: false,
+                    lifted_constraints: Vec::new(),
+                }
+            }),
+            move |result| ReadMessage::FilesLoaded(generation, result),
         )
     }
 
+    /// Narrows the cached full dataset in memory, or falls back to the combined database query.
+    fn cache_lookup(&self) -> (String, String) {
+        if self.cache.is_valid_for(
+            self.drive_combo_box.selected_drive.as_ref(),
+            &self.search.query,
+            &self.search.filters,
+        ) {
+            (
+                self.search.query.clone(),
+                self.search.refinement.trim().to_string(),
+            )
+        } else {
+            (self.search.database_query(), String::new())
+        }
+    }
+
+    fn refine_results(&mut self, refinement: String) -> Task<ReadMessage> {
+        self.search.refinement = refinement;
+        let is_fully_cached = self.cache.is_valid_for(
+            self.drive_combo_box.selected_drive.as_ref(),
+            &self.search.query,
+            &self.search.filters,
+        );
+        if is_fully_cached || self.search.refinement.trim().is_empty() {
+            self.pagination.reset();
+            self.load_current_page()
+        } else {
+            Task::none()
+        }
+    }
+
     fn previous_page(&mut self) -> Task<ReadMessage> {
         if self.pagination.prev().is_some() {
             self.load_current_page()
@@ -243,6 +797,52 @@ impl ReadPage {
         }
     }
 
+    /// Searches again without one of the constraints that kept the results empty.
+    fn lift_constraint(&mut self, constraint: SearchConstraint) -> Task<ReadMessage> {
+        match constraint {
+            SearchConstraint::Drive => self.drive_combo_box.selected_drive = None,
+            SearchConstraint::Query => {
+                self.search.query.clear();
+                self.search.refinement.clear();
+            }
+            SearchConstraint::Extension => self.search.filters.extension = None,
+            SearchConstraint::FilenameOnly => self.search.filters.filename_only = false,
+            SearchConstraint::WholeSegments => self.search.filters.whole_segments = false,
+            SearchConstraint::IndexedSince => self.search.set_indexed_within(None),
+            SearchConstraint::Tag => self.search.filters.tag = None,
+            SearchConstraint::Permission => self.search.filters.permission = None,
+        }
+        Task::batch([self.process_new_search(), self.fetch_extensions()])
+    }
+
+    /// Shows pages of another size from the first one, and keeps the size for next time.
+    fn change_items_per_page(&mut self, items_per_page: usize) -> Task<ReadMessage> {
+        if items_per_page == self.pagination.items_per_page {
+            return Task::none();
+        }
+        let repository = self.repository.clone();
+        let save = Task::future(run_blocking(move || {
+            if let Err(error) = repository.set_items_per_page(items_per_page) {
+                popup_error(error);
+            }
+        }))
+        .discard();
+        Task::batch([save, self.show_items_per_page(items_per_page)])
+    }
+
+    /// Shows pages of another size, from the first one when there are results already.
+    fn show_items_per_page(&mut self, items_per_page: usize) -> Task<ReadMessage> {
+        if items_per_page == self.pagination.items_per_page {
+            return Task::none();
+        }
+        self.pagination.set_items_per_page(items_per_page);
+        // Without results there is nothing to show again
+        if self.pagination.total_count == 0 {
+            return Task::none();
+        }
+        self.load_current_page()
+    }
+
     fn navigate_to_page(&mut self, page_index: usize) -> Task<ReadMessage> {
         if self.pagination.navigate_to(page_index).is_some() {
             self.load_current_page()
@@ -251,50 +851,353 @@ impl ReadPage {
         }
     }
 
+    /// Runs the search from its first page and records the query in the history.
     fn process_new_search(&mut self) -> Task<ReadMessage> {
+        let history_task = if self.search.history.record(&self.search.query) {
+            self.save_history()
+        } else {
+            Task::none()
+        };
+        Task::batch([self.run_new_search(), history_task])
+    }
+
+    /// Runs the search from its first page, replacing any search scheduled while typing.
+    fn run_new_search(&mut self) -> Task<ReadMessage> {
+        self.live_search.cancel();
         self.pagination.reset();
+        self.file_list.clear_hidden();
+        self.search.offer_history_clear = false;
         self.load_current_page()
     }
 
+    /// Searches the query once typing pauses, without recording it in the history.
+    fn schedule_live_search(&mut self) -> Task<ReadMessage> {
+        let generation = self.live_search.start();
+        Task::future(async move {
+            tokio::time::sleep(SEARCH_DEBOUNCE).await;
+            ReadMessage::LiveSearchDue(generation)
+        })
+    }
+
+    /// Opens a result with its default application, once its drive root is known and the
+    /// file is still where it was indexed.
+    fn open_file(
+        &self,
+        key: ResultKey,
+        no_location: String,
+        not_found: String,
+    ) -> Task<ReadMessage> {
+        let repository = self.repository.clone();
+        Task::future(run_blocking(move || {
+            match repository.find_drive_root(&key.drive_name, &key.category_name) {
+                Ok(Some(root)) => {
+                    // The catalog may be stale, or the drive unplugged
+                    let path = root.join(decode_path(&key.path));
+                    if !path.exists() {
+                        popup_warning(format!("{not_found}\n\n{}", path.display()));
+                    } else if let Err(error) = open_path(&path) {
+                        popup_error(error);
+                    }
+                }
+                Ok(None) => popup_warning(no_location),
+                Err(error) => popup_error(error),
+            }
+        }))
+        .discard()
+    }
+
+    fn fetch_saved_searches(repository: Arc<ListerRepository>) -> Task<ReadMessage> {
+        Task::perform(
+            run_blocking(move || {
+                repository
+                    .saved_searches()
+                    .find_all()
+                    .unwrap_or_else(|error| {
+                        popup_error(error);
+                        vec![]
+                    })
+            }),
+            ReadMessage::SavedSearchesFetched,
+        )
+    }
+
+    fn fetch_recent_files(repository: Arc<ListerRepository>) -> Task<ReadMessage> {
+        Task::perform(
+            run_blocking(move || {
+                repository
+                    .recent_files(RECENT_FILES_LIMIT)
+                    .unwrap_or_else(|error| {
+                        popup_error(error);
+                        vec![]
+                    })
+            }),
+            ReadMessage::RecentFilesFetched,
+        )
+    }
+
+    /// Reads the number of results per page kept from last time.
+    fn fetch_items_per_page(repository: Arc<ListerRepository>) -> Task<ReadMessage> {
+        Task::perform(
+            run_blocking(move || {
+                repository.get_items_per_page().unwrap_or_else(|error| {
+                    popup_error(error);
+                    DEFAULT_PAGE_SIZE
+                })
+            }),
+            ReadMessage::ItemsPerPageFetched,
+        )
+    }
+
+    /// Reads the row density kept from last time.
+    fn fetch_row_density(repository: Arc<ListerRepository>) -> Task<ReadMessage> {
+        Task::perform(
+            run_blocking(move || {
+                repository.get_row_density().unwrap_or_else(|error| {
+                    popup_error(error);
+                    RowDensity::default()
+                })
+            }),
+            ReadMessage::RowDensityFetched,
+        )
+    }
+
+    /// Reads the result columns kept from last time.
+    fn fetch_result_columns(repository: Arc<ListerRepository>) -> Task<ReadMessage> {
+        Task::perform(
+            run_blocking(move || {
+                repository.get_result_columns().unwrap_or_else(|error| {
+                    popup_error(error);
+                    ResultColumns::default()
+                })
+            }),
+            ReadMessage::ResultColumnsFetched,
+        )
+    }
+
+    fn fetch_tags(repository: Arc<ListerRepository>) -> Task<ReadMessage> {
+        Task::perform(
+            run_blocking(move || {
+                repository
+                    .drive_tags()
+                    .find_all_tags()
+                    .unwrap_or_else(|error| {
+                        popup_error(error);
+                        vec![]
+                    })
+            }),
+            ReadMessage::TagsFetched,
+        )
+    }
+
+    fn fetch_extensions(&self) -> Task<ReadMessage> {
+        let selected_drive = self.drive_combo_box.selected_drive.clone();
+        let query_repository = self.repository.clone();
+        Task::perform(
+            run_blocking(move || {
+                query_repository
+                    .list_extensions(selected_drive.as_deref())
+                    .unwrap_or_else(|error| {
+                        popup_error(error);
+                        vec![]
+                    })
+            }),
+            ReadMessage::ExtensionsFetched,
+        )
+    }
+
+    fn save_search(&mut self) -> Task<ReadMessage> {
+        let name = self.saved_searches.name.trim().to_string();
+        if name.is_empty() {
+            return Task::none();
+        }
+        let search = SavedSearch {
+            name: name.clone(),
+            drive: self.drive_combo_box.selected_drive.clone(),
+            query: self.search.query.clone(),
+            filters: self.search.filters.clone(),
+        };
+        self.saved_searches.name.clear();
+        self.saved_searches.selected = Some(name);
+        let command_repository = self.repository.clone();
+        Task::future(run_blocking(move || {
+            command_repository
+                .saved_searches()
+                .save(&search)
+                .unwrap_or_else(popup_error);
+            command_repository
+        }))
+        .then(Self::fetch_saved_searches)
+    }
+
+    fn delete_saved_search(&mut self, name: String) -> Task<ReadMessage> {
+        self.saved_searches.selected = None;
+        let command_repository = self.repository.clone();
+        Task::future(run_blocking(move || {
+            command_repository
+                .saved_searches()
+                .delete(&name)
+                .unwrap_or_else(popup_error);
+            command_repository
+        }))
+        .then(Self::fetch_saved_searches)
+    }
+
+    fn restore_saved_search(&mut self, name: &str) -> Task<ReadMessage> {
+        let Some(search) = self.saved_searches.find(name).cloned() else {
+            return Task::none();
+        };
+        self.saved_searches.selected = Some(search.name);
+        self.drive_combo_box.selected_drive = search.drive;
+        self.search.query = search.query;
+        self.search.filters = search.filters;
+        self.search.indexed_within = None;
+        Task::batch([self.process_new_search(), self.fetch_extensions()])
+    }
+
+    fn clear_history(&mut self) -> Task<ReadMessage> {
+        self.search.history.clear();
+        self.search.offer_history_clear = false;
+        self.save_history()
+    }
+
+    fn save_history(&self) -> Task<ReadMessage> {
+        let history = self.search.history.clone();
+        let command_repository = self.repository.clone();
+        Task::future(run_blocking(move || {
+            command_repository
+                .set_search_history(&history)
+                .unwrap_or_else(popup_error);
+        }))
+        .discard()
+    }
+
+    /// Writes the manifest of the selected drive to the file picked in a save dialog.
+    fn generate_manifest(&self, dialog_title: String) -> Task<ReadMessage> {
+        let Some(drive) = self.drive_combo_box.selected_drive.clone() else {
+            return Task::none();
+        };
+        let repository = self.repository.clone();
+        Task::future(async move {
+            let Some(path) = rfd::FileDialog::new()
+                .set_title(&dialog_title)
+                .set_file_name(format!("{drive}.txt"))
+                .add_filter("Text", &["txt"])
+                .save_file()
+            else {
+                return;
+            };
+            run_blocking(move || {
+                let filters = SearchFilters::default();
+                let count = repository.count_search_results(Some(&drive), None, &filters)?;
+                let files =
+                    repository.search_files_paginated(Some(&drive), None, &filters, 0, count)?;
+                fs::write(path, format_manifest(&drive, &files))?;
+                Ok(())
+            })
+            .await
+            .unwrap_or_else(|error: InfrastructureError| popup_error(error));
+        })
+        .discard()
+    }
+
+    fn save_columns(&self) -> Task<ReadMessage> {
+        let columns = self.file_list.columns.clone();
+        let command_repository = self.repository.clone();
+        Task::future(run_blocking(move || {
+            command_repository
+                .set_result_columns(&columns)
+                .unwrap_or_else(popup_error);
+        }))
+        .discard()
+    }
+
+    fn refresh(&mut self) -> Task<ReadMessage> {
+        // Drop cached rows so files indexed since the last query show up
+        self.cache.clear();
+        self.cache_warm = None;
+        Task::batch([
+            self.load_current_page(),
+            self.drive_combo_box
+                .find_drives()
+                .map(ReadMessage::DriveComboBox),
+            self.fetch_extensions(),
+            Self::fetch_recent_files(self.repository.clone()),
+            Self::fetch_tags(self.repository.clone()),
+        ])
+    }
+
     fn clear_search(&mut self) -> Task<ReadMessage> {
         self.drive_combo_box.selected_drive = None;
         self.search.clear();
-        self.cache.clear();
         self.file_list.clear();
         self.pagination.clear();
-        Task::none()
+        self.live_search.cancel();
+        self.page_load.cancel();
+        self.is_loading = false;
+        self.search_timer.stop();
+        Task::batch([
+            self.fetch_extensions(),
+            Self::fetch_recent_files(self.repository.clone()),
+        ])
     }
 
     fn process_page_input(&mut self) -> Task<ReadMessage> {
-        if let Ok(page) = self.pagination.page_input_value.parse::<usize>() {
-            if page > 0 && page <= self.pagination.total_pages() {
-                self.navigate_to_page(page - 1)
-            } else {
-                Task::none()
-            }
+        if self.pagination.submit_page_input().is_some() {
+            self.load_current_page()
         } else {
             Task::none()
         }
     }
 
-    fn handle_files_loaded(&mut self, result: PaginatedResult) -> Task<ReadMessage> {
-        self.update_total_count(&result);
+    fn handle_files_loaded(
+        &mut self,
+        generation: u64,
+        result: PaginatedResult,
+    ) -> Task<ReadMessage> {
+        // A slow query for an earlier text must not replace the results of a newer one
+        if !self.page_load.is_latest(generation) {
+            return Task::none();
+        }
+        self.is_loading = false;
+        self.search_timer.stop();
+        let has_page_moved = self.update_totals(&result);
+
+        if result.is_drive_missing {
+            self.file_list.show_missing_drive();
+            return Task::none();
+        }
+        if result.exceeds_fuzzy_limit {
+            self.file_list.show_fuzzy_limit_exceeded(CACHED_SIZE);
+            return Task::none();
+        }
+        // The requested page was past the last one, as happens after results were removed
+        if has_page_moved {
+            return self.load_current_page();
+        }
 
         if self.should_warm_cache(&result) {
             self.handle_small_dataset(result)
         } else {
-            self.show_page(result.items)
+            let task = self.show_page(result.items);
+            self.file_list
+                .set_lifted_constraints(result.lifted_constraints);
+            task
         }
     }
 
-    const fn update_total_count(&mut self, result: &PaginatedResult) {
-        self.pagination.total_count = result.total_count;
+    /// Returns whether the current page no longer existed and was moved back.
+    const fn update_totals(&mut self, result: &PaginatedResult) -> bool {
+        self.pagination.total_size = result.total_size;
+        self.pagination.set_total_count(result.total_count)
     }
 
     const fn should_warm_cache(&self, result: &PaginatedResult) -> bool {
-        result.total_count > 0
+        // The cache holds every result, not only the first ones. Fuzzy results come whole
+        // whatever the page, since they are ranked in memory.
+        self.search.filters.top.is_none()
+            && result.total_count > 0
             && result.total_count <= CACHED_SIZE
-            && self.pagination.current_page_index == 0
+            && (self.pagination.current_page_index == 0 || self.search.filters.fuzzy)
     }
 
     fn handle_small_dataset(&mut self, result: PaginatedResult) -> Task<ReadMessage> {
@@ -304,7 +1207,7 @@ impl ReadPage {
         }
 
         // Case B: we only received a single page; start warming if not already warming
-        if self.is_cache_warming {
+        if self.is_warming_current_dataset() {
             self.show_page(result.items)
         } else {
             self.start_cache_warm(result.items)
@@ -313,74 +1216,197 @@ impl ReadPage {
 
     fn store_full_and_show_page(&mut self, full_items: Vec<FileWithMetadata>) -> Task<ReadMessage> {
         // store full dataset in cache
+        let database_query = self.search.database_query();
         self.cache.store(
             self.drive_combo_box.selected_drive.clone(),
-            self.search.query.clone(),
+            database_query.clone(),
+            self.search.filters.clone(),
             full_items,
         );
 
-        if let Some(page_files) = self.cache.get_page(
-            self.drive_combo_box.selected_drive.as_ref(),
-            &self.search.query,
-            self.pagination.current_page_index,
-            ITEMS_PER_PAGE,
-        ) {
-            self.file_list.set_files(page_files);
-        } else {
-            self.file_list.set_files(Vec::new());
-        }
+        let page_files = self
+            .cache
+            .get_page(
+                self.drive_combo_box.selected_drive.as_ref(),
+                &database_query,
+                &self.search.filters,
+                "",
+                self.pagination.current_page_index,
+                self.pagination.items_per_page,
+            )
+            .unwrap_or_default();
 
-        self.is_cache_warming = false;
-        self.file_list.snap_to_top()
+        self.cache_warm = None;
+        self.refresh_groups();
+        self.file_list.show(page_files, self.page_key())
     }
 
     fn start_cache_warm(&mut self, current_page_items: Vec<FileWithMetadata>) -> Task<ReadMessage> {
-        // mark warming and show current page immediately
-        self.is_cache_warming = true;
-        self.file_list.set_files(current_page_items);
-
+        // show current page immediately, then stream the whole dataset in the background
+        let generation = self.warm_load.start();
         let selected_drive = self.drive_combo_box.selected_drive.clone();
-        let search_query = if self.search.query.is_empty() {
+        let database_query = self.search.database_query();
+        let filters = self.search.filters.clone();
+        self.cache_warm = Some(CacheWarm {
+            generation,
+            drive: selected_drive.clone(),
+            query: database_query.clone(),
+            filters: filters.clone(),
+            rows: Vec::new(),
+        });
+
+        let search_query = if database_query.is_empty() {
             None
         } else {
-            Some(self.search.query.clone())
+            Some(database_query)
         };
         let query_repository = self.repository.clone();
-        let total = self.pagination.total_count;
+        let (batch_sender, batch_receiver) = mpsc::unbounded();
 
-        Task::perform(
-            async move {
-                let files = query_repository
-                    .search_files_paginated(
-                        selected_drive.as_deref(),
-                        search_query.as_deref(),
-                        0,
-                        total,
-                    )
-                    .unwrap_or_else(|error| {
+        let warm = Task::future(run_blocking(move || {
+            for batch in query_repository.search_files_in_batches(
+                selected_drive.as_deref(),
+                search_query.as_deref(),
+                &filters,
+                WARM_BATCH_SIZE,
+            ) {
+                match batch {
+                    Ok(files) => {
+                        if batch_sender.unbounded_send(files).is_err() {
+                            break;
+                        }
+                    }
+                    Err(error) => {
                         popup_error(error);
-                        vec![]
-                    });
-                PaginatedResult {
-                    items: files,
-                    total_count: total,
+                        break;
+                    }
                 }
-            },
-            ReadMessage::FilesLoaded,
-        )
+            }
+        }))
+        .discard();
+
+        Task::batch([
+            self.show_page(current_page_items),
+            warm,
+            Task::stream(batch_receiver)
+                .map(move |files| ReadMessage::CacheWarmBatchLoaded(generation, files))
+                .chain(Task::done(ReadMessage::CacheWarmFinished(generation))),
+        ])
+    }
+
+    fn append_warm_batch(&mut self, generation: u64, files: Vec<FileWithMetadata>) {
+        if let Some(warm) = self
+            .cache_warm
+            .as_mut()
+            .filter(|warm| warm.generation == generation)
+        {
+            warm.rows.extend(files);
+        }
+    }
+
+    fn finish_cache_warm(&mut self, generation: u64) -> Task<ReadMessage> {
+        let Some(warm) = self
+            .cache_warm
+            .take_if(|warm| warm.generation == generation)
+        else {
+            return Task::none();
+        };
+        if !self.is_current_dataset(&warm) {
+            return Task::none();
+        }
+        // An interrupted stream leaves a partial dataset, which must not be cached
+        if warm.rows.len() as u64 != self.pagination.total_count {
+            return Task::none();
+        }
+        self.store_full_and_show_page(warm.rows)
+    }
+
+    fn is_current_dataset(&self, warm: &CacheWarm) -> bool {
+        warm.drive == self.drive_combo_box.selected_drive
+            && warm.query == self.search.database_query()
+            && warm.filters == self.search.filters
+    }
+
+    fn is_warming_current_dataset(&self) -> bool {
+        self.cache_warm
+            .as_ref()
+            .is_some_and(|warm| self.is_current_dataset(warm))
+    }
+
+    /// Returns the current page from the rows streamed so far, once they cover it.
+    fn warmed_page(&self) -> Option<Vec<FileWithMetadata>> {
+        let warm = self
+            .cache_warm
+            .as_ref()
+            .filter(|warm| self.is_current_dataset(warm))?;
+        let start = self.pagination.current_page_index * self.pagination.items_per_page;
+        let end = start + self.pagination.items_per_page;
+        let is_last_page = end as u64 >= self.pagination.total_count;
+        if warm.rows.len() >= end
+            || (is_last_page && warm.rows.len() as u64 == self.pagination.total_count)
+        {
+            Some(warm.rows[start.min(warm.rows.len())..end.min(warm.rows.len())].to_vec())
+        } else {
+            None
+        }
     }
 
     fn show_page(&mut self, items: Vec<FileWithMetadata>) -> Task<ReadMessage> {
-        self.file_list.set_files(items);
-        self.file_list.snap_to_top()
+        self.refresh_groups();
+        self.file_list.show(items, self.page_key())
+    }
+
+    /// Groups the whole cached dataset by folder, by drive and as a tree; grouping is unavailable when it is not cached.
+    fn refresh_groups(&mut self) {
+        let (cached_query, refinement) = self.cache_lookup();
+        let files = self.cache.get_page(
+            self.drive_combo_box.selected_drive.as_ref(),
+            &cached_query,
+            &self.search.filters,
+            &refinement,
+            0,
+            usize::MAX,
+        );
+        self.file_list
+            .set_groups(files.as_deref().map(group_by_parent_directory));
+        self.file_list
+            .set_drive_groups(files.as_deref().map(group_by_drive));
+        self.file_list
+            .set_tree(files.as_deref().map(build_path_tree));
     }
 
-    fn arrow_done_pressed(&self, shift: bool) -> Task<ReadMessage> {
-        self.file_list.scroll(30., shift)
+    fn page_key(&self) -> PageKey {
+        PageKey {
+            drive: self.drive_combo_box.selected_drive.clone(),
+            query: self.search.database_query(),
+            filters: self.search.filters.clone(),
+            page_index: self.pagination.current_page_index,
+            items_per_page: self.pagination.items_per_page,
+        }
+    }
+
+    /// Moves the selected row down, or scrolls when Shift is held or the rows are grouped.
+    fn arrow_done_pressed(&mut self, shift: bool) -> Task<ReadMessage> {
+        if shift || self.file_list.is_grouped() {
+            self.file_list.scroll(30., shift)
+        } else {
+            Task::batch([self.file_list.move_selection(1), self.follow_focus()])
+        }
+    }
+
+    /// Moves the selected row up, or scrolls when Shift is held or the rows are grouped.
+    fn arrow_up_pressed(&mut self, shift: bool) -> Task<ReadMessage> {
+        if shift || self.file_list.is_grouped() {
+            self.file_list.scroll(-30., shift)
+        } else {
+            Task::batch([self.file_list.move_selection(-1), self.follow_focus()])
+        }
     }
 
-    fn arrow_up_pressed(&self, shift: bool) -> Task<ReadMessage> {
-        self.file_list.scroll(-30., shift)
+    /// Previews the result in focus when it is an image.
+    fn follow_focus(&mut self) -> Task<ReadMessage> {
+        self.preview
+            .follow(self.file_list.focused_file(), &self.repository)
     }
 
     fn handle_left(&mut self, shift: bool) -> Task<ReadMessage> {
@@ -0,0 +1,171 @@
+use crate::domain::model::row_density::RowDensity;
+use crate::infrastructure::database::repository::ListerRepository;
+use crate::tr;
+use crate::ui::messages::settings_message::SettingsMessage;
+use crate::utils::blocking::run_blocking;
+use crate::utils::dialogs::popup_error;
+use iced::widget::{button, column, container, row, text, toggler};
+use iced::{Alignment, Element, Length, Task};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub struct SettingsPage {
+    repository: Arc<ListerRepository>,
+    auto_update: bool,
+    index_hidden_files: bool,
+    row_density: RowDensity,
+}
+
+impl SettingsPage {
+    pub fn new(repository: Arc<ListerRepository>) -> (Self, Task<SettingsMessage>) {
+        let task = Task::batch([
+            Self::find_auto_update(repository.clone()),
+            Self::find_index_hidden_files(repository.clone()),
+            Self::find_row_density(repository.clone()),
+        ]);
+        (
+            Self {
+                repository,
+                auto_update: true,
+                index_hidden_files: true,
+                row_density: RowDensity::default(),
+            },
+            task,
+        )
+    }
+
+    pub fn title(translations: &HashMap<String, String>) -> String {
+        tr!(translations, "settings_page_title")
+    }
+
+    pub fn view(&'_ self, translations: &HashMap<String, String>) -> Element<'_, SettingsMessage> {
+        container(
+            column![
+                toggler(self.auto_update)
+                    .label(tr!(translations, "auto_update_on_launch"))
+                    .on_toggle(SettingsMessage::AutoUpdateToggled),
+                toggler(self.index_hidden_files)
+                    .label(tr!(translations, "index_hidden_files"))
+                    .on_toggle(SettingsMessage::IndexHiddenFilesToggled),
+                self.density_control(translations)
+            ]
+            .padding(20)
+            .spacing(20),
+        )
+        .center_y(Length::Fill)
+        .into()
+    }
+
+    pub fn update(&mut self, message: SettingsMessage) -> Task<SettingsMessage> {
+        match message {
+            SettingsMessage::AutoUpdateFetched(enabled) => {
+                self.auto_update = enabled;
+                Task::none()
+            }
+            SettingsMessage::AutoUpdateToggled(enabled) => {
+                self.auto_update = enabled;
+                let repository = self.repository.clone();
+                Task::future(run_blocking(move || {
+                    repository
+                        .set_auto_update(enabled)
+                        .unwrap_or_else(popup_error);
+                }))
+                .discard()
+            }
+            SettingsMessage::IndexHiddenFilesFetched(enabled) => {
+                self.index_hidden_files = enabled;
+                Task::none()
+            }
+            SettingsMessage::IndexHiddenFilesToggled(enabled) => {
+                self.index_hidden_files = enabled;
+                let repository = self.repository.clone();
+                Task::future(run_blocking(move || {
+                    repository
+                        .set_index_hidden_files(enabled)
+                        .unwrap_or_else(popup_error);
+                }))
+                .discard()
+            }
+            SettingsMessage::RowDensityFetched(density) => {
+                self.row_density = density;
+                Task::none()
+            }
+            SettingsMessage::RowDensitySelected(density) => {
+                self.row_density = density;
+                let repository = self.repository.clone();
+                Task::future(run_blocking(move || {
+                    repository
+                        .set_row_density(density)
+                        .unwrap_or_else(popup_error);
+                }))
+                .discard()
+            }
+        }
+    }
+
+    fn density_control(
+        &'_ self,
+        translations: &HashMap<String, String>,
+    ) -> Element<'_, SettingsMessage> {
+        let buttons = RowDensity::ALL.into_iter().map(|density| {
+            let label = match density {
+                RowDensity::Compact => tr!(translations, "density_compact"),
+                RowDensity::Comfortable => tr!(translations, "density_comfortable"),
+                RowDensity::Spacious => tr!(translations, "density_spacious"),
+            };
+            button(text(label))
+                .on_press(SettingsMessage::RowDensitySelected(density))
+                .padding([5, 10])
+                .style(if self.row_density == density {
+                    button::primary
+                } else {
+                    button::secondary
+                })
+                .into()
+        });
+
+        row![
+            text(tr!(translations, "row_density")),
+            row(buttons).spacing(2)
+        ]
+        .spacing(10)
+        .align_y(Alignment::Center)
+        .into()
+    }
+
+    fn find_auto_update(repository: Arc<ListerRepository>) -> Task<SettingsMessage> {
+        Task::perform(
+            run_blocking(move || {
+                repository.get_auto_update().unwrap_or_else(|err| {
+                    popup_error(err);
+                    true
+                })
+            }),
+            SettingsMessage::AutoUpdateFetched,
+        )
+    }
+
+    fn find_index_hidden_files(repository: Arc<ListerRepository>) -> Task<SettingsMessage> {
+        Task::perform(
+            run_blocking(move || {
+                repository.get_index_hidden_files().unwrap_or_else(|err| {
+                    popup_error(err);
+                    true
+                })
+            }),
+            SettingsMessage::IndexHiddenFilesFetched,
+        )
+    }
+
+    fn find_row_density(repository: Arc<ListerRepository>) -> Task<SettingsMessage> {
+        Task::perform(
+            run_blocking(move || {
+                repository.get_row_density().unwrap_or_else(|err| {
+                    popup_error(err);
+                    RowDensity::default()
+                })
+            }),
+            SettingsMessage::RowDensityFetched,
+        )
+    }
+}
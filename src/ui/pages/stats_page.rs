@@ -0,0 +1,281 @@
+use crate::domain::model::category_overview::CategoryOverview;
+use crate::domain::model::drive_stats::DriveStats;
+use crate::domain::model::index_run::IndexRun;
+use crate::domain::model::language::Language;
+use crate::infrastructure::database::repository::ListerRepository;
+use crate::tr;
+use crate::ui::messages::stats_message::StatsMessage;
+use crate::ui::utils::format_date_time::format_date_time;
+use crate::utils::blocking::run_blocking;
+use crate::utils::dialogs::popup_error;
+use humansize::{format_size, DECIMAL};
+use iced::widget::{column, container, progress_bar, row, rule, stack, text, Scrollable};
+use iced::{Color, Element, Length, Task};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub struct StatsPage {
+    category_overview: Vec<CategoryOverview>,
+    drive_stats: Vec<DriveStats>,
+    index_runs: Vec<IndexRun>,
+}
+
+impl StatsPage {
+    pub fn new(repository: Arc<ListerRepository>) -> (Self, Task<StatsMessage>) {
+        (
+            Self {
+                category_overview: vec![],
+                drive_stats: vec![],
+                index_runs: vec![],
+            },
+            Task::batch([
+                Self::find_category_overview(repository.clone()),
+                Self::find_statistics(repository.clone()),
+                Self::find_index_runs(repository),
+            ]),
+        )
+    }
+
+    pub fn title(translations: &HashMap<String, String>) -> String {
+        tr!(translations, "stats_page_title")
+    }
+
+    pub fn view(
+        &'_ self,
+        translations: &HashMap<String, String>,
+        language: &Language,
+    ) -> Element<'_, StatsMessage> {
+        if self.drive_stats.is_empty() {
+            return container(text(tr!(translations, "no_drives_indexed")).style(text::secondary))
+                .center(Length::Fill)
+                .into();
+        }
+
+        let header = row![
+            text(tr!(translations, "drive_column")).width(Length::FillPortion(2)),
+            text(tr!(translations, "category_column")).width(Length::FillPortion(2)),
+            text(tr!(translations, "file_count_column")).width(Length::FillPortion(1)),
+            text(tr!(translations, "total_size_column")).width(Length::FillPortion(1)),
+            text(tr!(translations, "available_space_column")).width(Length::FillPortion(1)),
+            text(tr!(translations, "fullness_column")).width(Length::FillPortion(2)),
+            text(tr!(translations, "last_indexed_column")).width(Length::FillPortion(2)),
+        ]
+        .padding(3);
+
+        let stats_rows: Vec<Element<'_, StatsMessage>> = self
+            .drive_stats
+            .iter()
+            .map(|stats| {
+                row![
+                    text(&stats.drive_name).width(Length::FillPortion(2)),
+                    text(&stats.category_name).width(Length::FillPortion(2)),
+                    text(stats.file_count).width(Length::FillPortion(1)),
+                    text(format_size(stats.total_size, DECIMAL)).width(Length::FillPortion(1)),
+                    text(format_size(stats.available_space, DECIMAL)).width(Length::FillPortion(1)),
+                    container(Self::fullness_gauge(stats, translations))
+                        .width(Length::FillPortion(2)),
+                    text(format_date_time(stats.last_indexed, language))
+                        .width(Length::FillPortion(2)),
+                ]
+                .padding(3)
+                .into()
+            })
+            .collect();
+
+        column![
+            self.category_section(translations),
+            rule::horizontal(1),
+            header,
+            rule::horizontal(1),
+            Scrollable::new(column(stats_rows)).height(Length::FillPortion(2)),
+            rule::horizontal(1),
+            self.history_section(translations, language),
+        ]
+        .spacing(10)
+        .padding(20)
+        .into()
+    }
+
+    /// Sums up each category over all its drives.
+    fn category_section(
+        &'_ self,
+        translations: &HashMap<String, String>,
+    ) -> Element<'_, StatsMessage> {
+        let header = row![
+            text(tr!(translations, "category_column")).width(Length::FillPortion(2)),
+            text(tr!(translations, "drive_count_column")).width(Length::FillPortion(1)),
+            text(tr!(translations, "file_count_column")).width(Length::FillPortion(1)),
+            text(tr!(translations, "total_size_column")).width(Length::FillPortion(1)),
+        ]
+        .padding(3);
+
+        let category_rows: Vec<Element<'_, StatsMessage>> = self
+            .category_overview
+            .iter()
+            .map(|category| {
+                row![
+                    text(&category.name).width(Length::FillPortion(2)),
+                    text(category.drive_count).width(Length::FillPortion(1)),
+                    text(category.file_count).width(Length::FillPortion(1)),
+                    text(format_size(category.total_size, DECIMAL)).width(Length::FillPortion(1)),
+                ]
+                .padding(3)
+                .into()
+            })
+            .collect();
+
+        column![
+            text(tr!(translations, "categories_title")).size(18),
+            header,
+            rule::horizontal(1),
+            Scrollable::new(column(category_rows)).height(Length::Fill),
+        ]
+        .spacing(5)
+        .height(Length::FillPortion(1))
+        .into()
+    }
+
+    /// Shows the used space of a drive with its indexed files on top, out of its total space.
+    fn fullness_gauge<'a>(
+        stats: &DriveStats,
+        translations: &HashMap<String, String>,
+    ) -> Element<'a, StatsMessage> {
+        let Some(fullness) = stats.capacity().fullness(stats.total_size) else {
+            return text(tr!(translations, "fullness_unknown"))
+                .style(text::secondary)
+                .into();
+        };
+        let used = format!("{:.0}", fullness.used_fraction * 100.0);
+        let indexed = format!("{:.0}", fullness.indexed_fraction * 100.0);
+        let total = format_size(stats.total_space, DECIMAL);
+        let label = tr!(
+            translations,
+            "fullness_value",
+            "used" => &used,
+            "indexed" => &indexed,
+            "total" => &total
+        );
+
+        column![
+            stack![
+                progress_bar(0.0..=1.0, fullness.used_fraction)
+                    .girth(8)
+                    .style(progress_bar::secondary),
+                progress_bar(0.0..=1.0, fullness.indexed_fraction)
+                    .girth(8)
+                    .style(|theme| progress_bar::Style {
+                        background: Color::TRANSPARENT.into(),
+                        ..progress_bar::primary(theme)
+                    }),
+            ],
+            text(label).size(12),
+        ]
+        .spacing(2)
+        .into()
+    }
+
+    /// Lists the indexing runs, most recent first.
+    fn history_section(
+        &'_ self,
+        translations: &HashMap<String, String>,
+        language: &Language,
+    ) -> Element<'_, StatsMessage> {
+        let header = row![
+            text(tr!(translations, "indexed_at_column")).width(Length::FillPortion(2)),
+            text(tr!(translations, "drive_column")).width(Length::FillPortion(2)),
+            text(tr!(translations, "category_column")).width(Length::FillPortion(2)),
+            text(tr!(translations, "directories_column")).width(Length::FillPortion(4)),
+            text(tr!(translations, "file_count_column")).width(Length::FillPortion(1)),
+            text(tr!(translations, "duration_column")).width(Length::FillPortion(1)),
+        ]
+        .padding(3);
+
+        let run_rows: Vec<Element<'_, StatsMessage>> = self
+            .index_runs
+            .iter()
+            .map(|run| {
+                let directories = run
+                    .directories
+                    .iter()
+                    .map(|directory| directory.to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                row![
+                    text(format_date_time(run.indexed_at, language)).width(Length::FillPortion(2)),
+                    text(&run.drive).width(Length::FillPortion(2)),
+                    text(&run.category).width(Length::FillPortion(2)),
+                    text(directories).width(Length::FillPortion(4)),
+                    text(run.file_count).width(Length::FillPortion(1)),
+                    text(tr!(translations, "duration_seconds",
+                        "seconds" => &format!("{:.1}", run.duration.as_secs_f64())))
+                    .width(Length::FillPortion(1)),
+                ]
+                .padding(3)
+                .into()
+            })
+            .collect();
+
+        column![
+            text(tr!(translations, "index_history_title")).size(18),
+            header,
+            rule::horizontal(1),
+            Scrollable::new(column(run_rows)).height(Length::Fill),
+        ]
+        .spacing(5)
+        .height(Length::FillPortion(1))
+        .into()
+    }
+
+    pub fn update(&mut self, message: StatsMessage) -> Task<StatsMessage> {
+        match message {
+            StatsMessage::CategoryOverviewLoaded(category_overview) => {
+                self.category_overview = category_overview;
+                Task::none()
+            }
+            StatsMessage::IndexRunsFetched(index_runs) => {
+                self.index_runs = index_runs;
+                Task::none()
+            }
+            StatsMessage::StatisticsFetched(drive_stats) => {
+                self.drive_stats = drive_stats;
+                Task::none()
+            }
+        }
+    }
+
+    fn find_category_overview(repository: Arc<ListerRepository>) -> Task<StatsMessage> {
+        Task::perform(
+            run_blocking(move || {
+                repository.category_overview().unwrap_or_else(|err| {
+                    popup_error(err);
+                    vec![]
+                })
+            }),
+            StatsMessage::CategoryOverviewLoaded,
+        )
+    }
+
+    fn find_statistics(repository: Arc<ListerRepository>) -> Task<StatsMessage> {
+        Task::perform(
+            run_blocking(move || {
+                repository.drive_statistics().unwrap_or_else(|err| {
+                    popup_error(err);
+                    vec![]
+                })
+            }),
+            StatsMessage::StatisticsFetched,
+        )
+    }
+
+    fn find_index_runs(repository: Arc<ListerRepository>) -> Task<StatsMessage> {
+        Task::perform(
+            run_blocking(move || {
+                repository.index_runs().list_runs().unwrap_or_else(|err| {
+                    popup_error(err);
+                    vec![]
+                })
+            }),
+            StatsMessage::IndexRunsFetched,
+        )
+    }
+}
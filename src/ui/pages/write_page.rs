@@ -1,29 +1,51 @@
-use crate::application::directory_scanner;
+use crate::application::directory_scanner::{self, ErrorPolicy, SkippedEntries};
+use crate::domain::disk_lister::removable_disks;
+use crate::domain::model::directory::DirectoryData;
+use crate::domain::model::drive_capacity::DriveCapacity;
 use crate::domain::model::file_entry::FileEntry;
+use crate::domain::model::mounted_disk::MountedDisk;
+use crate::domain::model::scan_diff::ScanDiff;
+use crate::domain::model::scan_summary::{CapacityAdvice, ScanSummary};
+use crate::infrastructure::database::pool::InfrastructureError;
 use crate::infrastructure::database::repository::ListerRepository;
-use crate::infrastructure::filesystem::directory::directory_data;
+use crate::infrastructure::filesystem::directory::{
+    ensure_mounted, pick_directory, PickedDirectory, SystemDisks,
+};
 use crate::tr;
 use crate::ui::components::write::indexing::IndexingState;
 use crate::ui::messages::write_message::WriteMessage;
-use crate::utils::dialogs::{popup_error, popup_error_and_exit};
-use iced::widget::{button, column, container, row, rule, text, text_input};
-use iced::{Alignment, Element, Length, Task};
+use crate::utils::blocking::run_blocking;
+use crate::utils::dialogs::{popup_error, popup_warning};
+use humansize::{format_size, DECIMAL};
+use iced::futures::channel::mpsc;
+use iced::widget::{button, column, container, pick_list, row, rule, text, text_input, Column};
+use iced::{padding, Alignment, Element, Length, Task};
 use iced_aw::Spinner;
 use std::collections::HashMap;
+use std::fmt;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Instant;
+
+/// Number of skipped paths listed under the warning, the others being only counted.
+const SKIPPED_PATHS_SHOWN: usize = 5;
+/// Number of paths listed for each kind of change, the others being only counted.
+const CHANGED_PATHS_SHOWN: usize = 10;
 
 #[derive(Default)]
 struct WriteData {
     category: String,
-    directory: Option<PathBuf>,
+    /// Directories indexed together under the same category and drive.
+    directories: Vec<PathBuf>,
     drive: String,
-    drive_available_space: u64,
+    drive_capacity: DriveCapacity,
+    /// Mount point of the removable disk picked to be indexed whole, checked while scanning.
+    disk: Option<PathBuf>,
 }
 
 impl WriteData {
     const fn is_complete(&self) -> bool {
-        self.directory.is_some() && !self.category.is_empty() && !self.drive.is_empty()
+        !self.directories.is_empty() && !self.category.is_empty() && !self.drive.is_empty()
     }
 }
 
@@ -31,16 +53,61 @@ pub struct WritePage {
     command_repository: Arc<ListerRepository>,
     state: IndexingState,
     write_data: WriteData,
+    preview: Option<(ScanSummary, Vec<FileEntry>)>,
+    /// Why the disk of the picked directory could not be detected, until another is picked.
+    disk_warning: Option<String>,
+    /// Last path dropped on the window that was not a folder, until a directory is picked.
+    rejected_drop: Option<PathBuf>,
+    /// Whether the category and drive were filled in from the picked directory.
+    has_suggestions: bool,
+    /// Whether the entered drive is already indexed under the entered category.
+    is_existing_drive: bool,
+    /// Advisory about the size of the last scan compared to the drive available space.
+    capacity_advice: Option<CapacityAdvice>,
+    /// Entries the last scan could not read, left out of the index.
+    skipped_entries: Vec<PathBuf>,
+    /// Removable disks mounted when the list was last opened.
+    removable_disks: Vec<MountedDisk>,
+    /// Whether scans include hidden files and folders, as set on the settings page.
+    include_hidden: bool,
+}
+
+/// Pick list entry showing a removable disk along with its free space.
+#[derive(Clone, PartialEq)]
+struct DiskOption {
+    disk: MountedDisk,
+    label: String,
+}
+
+impl fmt::Display for DiskOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.label)
+    }
 }
 
 impl WritePage {
     pub fn new(command_repository: Arc<ListerRepository>) -> (Self, Task<WriteMessage>) {
+        let include_hidden = command_repository
+            .get_index_hidden_files()
+            .unwrap_or_else(|error| {
+                popup_error(error);
+                true
+            });
         let page = Self {
             command_repository,
             state: IndexingState::Ready,
             write_data: WriteData::default(),
+            preview: None,
+            disk_warning: None,
+            rejected_drop: None,
+            has_suggestions: false,
+            is_existing_drive: false,
+            capacity_advice: None,
+            skipped_entries: Vec::new(),
+            removable_disks: Vec::new(),
+            include_hidden,
         };
-        (page, Task::none())
+        (page, Self::load_disks())
     }
 
     pub fn title(translations: &HashMap<String, String>) -> String {
@@ -65,48 +132,255 @@ impl WritePage {
         match message {
             WriteMessage::CategoryChanged(value) => {
                 self.write_data.category = value;
-                Task::none()
+                self.check_existing_drive()
             }
-            WriteMessage::DatabaseCleaned => self.start_indexing(),
-            WriteMessage::DirectoryPressed { dialog_title } => Task::perform(
-                async move {
+            WriteMessage::DirectoryAddPressed { dialog_title } => Task::future(async move {
+                WriteMessage::DirectoryAdded(
                     rfd::FileDialog::new()
                         .set_title(&dialog_title)
-                        .pick_folder()
-                        .map(|handle| directory_data(handle.as_path()))
-                },
-                WriteMessage::DirectoryChanged,
-            ),
-            WriteMessage::DirectoryChanged(selected_data) => {
-                if let Some(data) = selected_data {
-                    self.write_data = WriteData {
-                        category: data.last_folder_name(),
-                        directory: Some(data.directory),
-                        drive: data.drive_name,
-                        drive_available_space: data.drive_available_space,
-                    }
+                        .pick_folder(),
+                )
+            }),
+            WriteMessage::DirectoryAdded(directory) => {
+                if let Some(directory) = directory
+                    && !self.write_data.directories.contains(&directory)
+                {
+                    self.write_data.directories.push(directory);
+                    self.clear_scan();
                 }
                 Task::none()
             }
+            WriteMessage::DirectoryRemoved(index) => {
+                if index < self.write_data.directories.len() {
+                    self.write_data.directories.remove(index);
+                    self.clear_scan();
+                }
+                Task::none()
+            }
+            WriteMessage::DirectoryPressed { dialog_title } => Task::future(async move {
+                let Some(directory) = rfd::FileDialog::new()
+                    .set_title(&dialog_title)
+                    .pick_folder()
+                else {
+                    return WriteMessage::DirectoryChanged(None);
+                };
+                Self::picked_message(
+                    run_blocking(move || pick_directory(&SystemDisks, &directory)).await,
+                )
+            }),
+            WriteMessage::DirectoryDropped(path) => {
+                // Keep the form of a running indexing as it is
+                if self.state.is_busy() {
+                    return Task::none();
+                }
+                Task::perform(
+                    run_blocking(move || pick_directory(&SystemDisks, &path)),
+                    Self::picked_message,
+                )
+            }
+            WriteMessage::DirectoryChanged(selected_data) => {
+                let Some(data) = selected_data else {
+                    return Task::none();
+                };
+                self.select_directory(data);
+                self.disk_warning = None;
+                self.check_existing_drive()
+            }
             WriteMessage::DiskChanged(value) => {
                 self.write_data.drive = value;
+                self.check_existing_drive()
+            }
+            WriteMessage::DiskNotFound(fallback, error) => {
+                self.select_directory(fallback);
+                self.disk_warning = Some(error);
+                self.check_existing_drive()
+            }
+            WriteMessage::DiskPicked(disk) => {
+                if self.state.is_busy() {
+                    return Task::none();
+                }
+                self.select_directory(disk.directory_data());
+                self.write_data.disk = Some(disk.mount_point);
+                self.disk_warning = None;
+                self.check_existing_drive()
+            }
+            WriteMessage::DisksLoaded(disks) => {
+                self.removable_disks = disks;
                 Task::none()
             }
-            WriteMessage::InsertInDatabaseFinished(count) => {
+            WriteMessage::DisksRequested => Self::load_disks(),
+            WriteMessage::DropRejected(path) => {
+                self.rejected_drop = Some(path);
+                Task::none()
+            }
+            WriteMessage::ExistingDriveChecked {
+                category,
+                drive,
+                exists,
+            } => {
+                // Ignore answers about values edited in the meantime
+                if category == self.write_data.category && drive == self.write_data.drive {
+                    self.is_existing_drive = exists;
+                }
+                Task::none()
+            }
+            WriteMessage::FilesSaved(files_saved) => {
+                if matches!(
+                    self.state,
+                    IndexingState::CleaningDatabase { .. }
+                        | IndexingState::Scanning
+                        | IndexingState::Saving { .. }
+                ) {
+                    self.state = IndexingState::Saving { files_saved };
+                }
+                Task::none()
+            }
+            WriteMessage::IndexingFailed(message_key) => {
+                if self.state.is_busy() {
+                    self.state = IndexingState::Failed { message_key };
+                }
+                Task::none()
+            }
+            WriteMessage::InsertInDatabaseFinished(summary) => {
                 self.state = IndexingState::Completed {
-                    files_indexed: count,
+                    files_indexed: summary.file_count,
+                };
+                self.check_scan(&summary);
+                Task::none()
+            }
+            WriteMessage::PreviewFinished(summary, files) => {
+                if self.state == IndexingState::Previewing {
+                    self.state = IndexingState::Ready;
+                    self.check_scan(&summary);
+                    self.preview = Some((summary, files));
+                }
+                Task::none()
+            }
+            WriteMessage::PreviewPressed => self.preview_directory(),
+            WriteMessage::ReviewChangesPressed => self.diff_scan(),
+            WriteMessage::ChangesScanned(summary, files, diff) => {
+                if self.state == IndexingState::Diffing {
+                    self.state = IndexingState::ReviewingChanges { diff };
+                    self.check_scan(&summary);
+                    self.preview = Some((summary, files));
+                }
+                Task::none()
+            }
+            WriteMessage::ChangesApplied => {
+                let IndexingState::ReviewingChanges { diff } = &self.state else {
+                    return Task::none();
                 };
+                let files_to_replace = diff.stored_count();
+                self.state = IndexingState::Ready;
+                self.start_indexing(files_to_replace)
+            }
+            WriteMessage::ChangesDiscarded => {
+                if matches!(self.state, IndexingState::ReviewingChanges { .. }) {
+                    self.state = IndexingState::Ready;
+                }
                 Task::none()
             }
             WriteMessage::ResetForm => {
                 self.state = IndexingState::Ready;
+                self.clear_scan();
                 Task::none()
             }
-            WriteMessage::ScanDirectoryFinished(scanned_files) => {
-                self.insert_in_database(scanned_files)
+            WriteMessage::RetryIndexing => {
+                self.state = IndexingState::Ready;
+                self.start_indexing(0)
             }
-            WriteMessage::WriteSubmit => self.clean_database(),
+            WriteMessage::OverwriteCancelled => {
+                if matches!(self.state, IndexingState::ConfirmingOverwrite { .. }) {
+                    self.state = IndexingState::Ready;
+                }
+                Task::none()
+            }
+            WriteMessage::OverwriteChecked(files_to_replace) => {
+                if self.state != IndexingState::CheckingExisting {
+                    return Task::none();
+                }
+                if files_to_replace == 0 {
+                    self.state = IndexingState::Ready;
+                    self.start_indexing(0)
+                } else {
+                    self.state = IndexingState::ConfirmingOverwrite { files_to_replace };
+                    Task::none()
+                }
+            }
+            WriteMessage::OverwriteConfirmed => {
+                let IndexingState::ConfirmingOverwrite { files_to_replace } = self.state else {
+                    return Task::none();
+                };
+                self.state = IndexingState::Ready;
+                self.start_indexing(files_to_replace)
+            }
+            WriteMessage::WriteSubmit => self.check_overwrite(),
+        }
+    }
+
+    fn load_disks() -> Task<WriteMessage> {
+        Task::perform(
+            run_blocking(|| removable_disks(&SystemDisks)),
+            WriteMessage::DisksLoaded,
+        )
+    }
+
+    /// Fills the form from a picked or dropped path, as choosing it in the dialog would.
+    fn picked_message(picked: PickedDirectory) -> WriteMessage {
+        match picked {
+            PickedDirectory::Found(data) => WriteMessage::DirectoryChanged(Some(data)),
+            PickedDirectory::WithoutDisk(fallback, error) => {
+                WriteMessage::DiskNotFound(fallback, error.to_string())
+            }
+            PickedDirectory::NotADirectory(path) => WriteMessage::DropRejected(path),
+        }
+    }
+
+    fn select_directory(&mut self, data: DirectoryData) {
+        let drive_capacity = data.drive_capacity();
+        self.write_data = WriteData {
+            category: data.last_folder_name(),
+            directories: vec![data.directory],
+            drive: data.drive_name,
+            drive_capacity,
+            disk: None,
+        };
+        self.has_suggestions = true;
+        self.rejected_drop = None;
+        self.clear_scan();
+    }
+
+    /// Drops the preview and advisories of a scan made before the directories changed.
+    fn clear_scan(&mut self) {
+        self.preview = None;
+        self.capacity_advice = None;
+        self.skipped_entries.clear();
+    }
+
+    fn check_scan(&mut self, summary: &ScanSummary) {
+        self.capacity_advice =
+            summary.capacity_advice(self.write_data.drive_capacity.available_space);
+        self.skipped_entries.clone_from(&summary.skipped);
+    }
+
+    fn check_existing_drive(&mut self) -> Task<WriteMessage> {
+        self.is_existing_drive = false;
+        if self.write_data.category.is_empty() || self.write_data.drive.is_empty() {
+            return Task::none();
         }
+        let command_repository = self.command_repository.clone();
+        let category = self.write_data.category.clone();
+        let drive = self.write_data.drive.clone();
+        Task::future(run_blocking(move || {
+            let exists = command_repository
+                .drive_exists_in_category(&drive, &category)
+                .unwrap_or(false);
+            WriteMessage::ExistingDriveChecked {
+                category,
+                drive,
+                exists,
+            }
+        }))
     }
 
     fn form_section(&'_ self, translations: &HashMap<String, String>) -> Element<'_, WriteMessage> {
@@ -134,12 +408,38 @@ impl WritePage {
                 .style(text::primary),
             rule::horizontal(1),
             directory_section,
+            self.rejected_drop.as_ref().map(|path| {
+                text(tr!(translations, "drop_not_a_folder", "path" => &path.display().to_string()))
+                    .style(text::secondary)
+                    .size(14)
+            }),
             column![
                 text(tr!(translations, "category_label")).size(16),
                 category_input,
             ]
             .spacing(5),
-            column![text(tr!(translations, "drive_label")).size(16), drive_input,].spacing(5),
+            column![
+                text(tr!(translations, "drive_label")).size(16),
+                drive_input,
+                self.disk_warning.as_ref().map(|error| {
+                    text(tr!(translations, "disk_not_found_warning", "error" => error))
+                        .style(text::warning)
+                        .size(14)
+                }),
+            ]
+            .spacing(5),
+            self.has_suggestions.then(|| {
+                text(tr!(translations, "detected_values_note"))
+                    .style(text::secondary)
+                    .size(14)
+            }),
+            self.is_existing_drive.then(|| {
+                text(tr!(translations, "existing_drive_warning",
+                    "drive" => &self.write_data.drive,
+                    "category" => &self.write_data.category))
+                .style(text::warning)
+                .size(14)
+            }),
         ]
         .spacing(15)
         .into()
@@ -151,11 +451,31 @@ impl WritePage {
     ) -> Element<'_, WriteMessage> {
         let directory_label = text(tr!(translations, "directory_label")).size(16);
 
-        let directory_display = self.write_data.directory.as_ref()
-            .map_or_else(|| text(tr!(translations, "no_directory_selected")).style(text::secondary), |dir|
-                text(tr!(translations, "selected_directory", "dir" => &dir.display().to_string()))
-                    .style(text::success))
-            .width(Length::Fill);
+        let directory_display: Element<'_, WriteMessage> = if self.write_data.directories.is_empty()
+        {
+            text(tr!(translations, "no_directory_selected"))
+                .style(text::secondary)
+                .width(Length::Fill)
+                .into()
+        } else {
+            column(self.write_data.directories.iter().enumerate().map(|(index, dir)| {
+                row![
+                    text(tr!(translations, "selected_directory", "dir" => &dir.display().to_string()))
+                        .style(text::success)
+                        .width(Length::Fill),
+                    button(text(tr!(translations, "remove_directory")))
+                        .on_press(WriteMessage::DirectoryRemoved(index))
+                        .padding([2, 8])
+                        .style(button::text),
+                ]
+                .spacing(10)
+                .align_y(Alignment::Center)
+                .into()
+            }))
+            .spacing(5)
+            .width(Length::Fill)
+            .into()
+        };
 
         let browse_button = button(text(tr!(translations, "browse_directory")))
             .on_press(WriteMessage::DirectoryPressed {
@@ -163,12 +483,39 @@ impl WritePage {
             })
             .padding(10)
             .style(button::secondary);
+        let add_button = (!self.write_data.directories.is_empty()).then(|| {
+            button(text(tr!(translations, "add_directory")))
+                .on_press(WriteMessage::DirectoryAddPressed {
+                    dialog_title: tr!(translations, "browse_file_dialog"),
+                })
+                .padding(10)
+                .style(button::secondary)
+        });
+
+        let disk_options: Vec<DiskOption> = self
+            .removable_disks
+            .iter()
+            .map(|disk| DiskOption {
+                disk: disk.clone(),
+                label: tr!(translations, "removable_disk_option",
+                    "name" => &disk.label(),
+                    "available" => &format_size(disk.available_space, DECIMAL),
+                    "total" => &format_size(disk.total_space, DECIMAL)),
+            })
+            .collect();
+        let disk_picker = pick_list(disk_options, None::<DiskOption>, |option| {
+            WriteMessage::DiskPicked(option.disk)
+        })
+        .placeholder(tr!(translations, "removable_disk_placeholder"))
+        .on_open(WriteMessage::DisksRequested)
+        .padding(10);
 
         column![
             directory_label,
-            row![directory_display, browse_button]
+            row![directory_display, browse_button, add_button]
                 .spacing(10)
                 .align_y(Alignment::Center),
+            disk_picker,
         ]
         .spacing(5)
         .into()
@@ -181,22 +528,80 @@ impl WritePage {
         let submit_button = self.submit_button(translations);
 
         let requirements_text = if self.write_data.is_complete() {
-            text("")
+            self.preview.as_ref().map_or_else(
+                || text(""),
+                |(summary, _)| {
+                    text(tr!(translations, "preview_summary",
+                        "nb_files" => &summary.file_count.to_string(),
+                        "total_size" => &format_size(summary.total_size, DECIMAL)))
+                },
+            )
         } else {
             text(tr!(translations, "fill_all_fields")).style(text::danger)
         }
         .width(Length::Fill);
 
-        column![rule::horizontal(1), row![requirements_text, submit_button]]
-            .spacing(10)
-            .into()
+        let capacity_advice = self.capacity_advice.map(|advice| {
+            let key = match advice {
+                CapacityAdvice::ExceedsAvailableSpace => "capacity_exceeded_warning",
+                CapacityAdvice::NearlyFull => "drive_nearly_full_warning",
+            };
+            let available_space =
+                format_size(self.write_data.drive_capacity.available_space, DECIMAL);
+            text(tr!(translations, key, "available_space" => &available_space))
+                .style(text::warning)
+                .size(14)
+        });
+
+        column![
+            rule::horizontal(1),
+            row![requirements_text, submit_button],
+            capacity_advice,
+            self.skipped_entries_warning(translations)
+        ]
+        .spacing(10)
+        .into()
+    }
+
+    fn skipped_entries_warning(
+        &'_ self,
+        translations: &HashMap<String, String>,
+    ) -> Option<Element<'_, WriteMessage>> {
+        if self.skipped_entries.is_empty() {
+            return None;
+        }
+        let hidden = self
+            .skipped_entries
+            .len()
+            .saturating_sub(SKIPPED_PATHS_SHOWN);
+        let paths = self
+            .skipped_entries
+            .iter()
+            .take(SKIPPED_PATHS_SHOWN)
+            .map(|path| text(path.display().to_string()).size(12).into());
+        let more = (hidden > 0).then(|| {
+            text(tr!(translations, "skipped_entries_more", "nb_entries" => &hidden.to_string()))
+                .size(12)
+        });
+        Some(
+            column![
+                text(tr!(translations, "skipped_entries_warning",
+                    "nb_entries" => &self.skipped_entries.len().to_string()))
+                .style(text::warning)
+                .size(14),
+                column(paths).padding(padding::left(10)),
+                more
+            ]
+            .spacing(5)
+            .into(),
+        )
     }
 
     fn submit_button(
         &'_ self,
         translations: &HashMap<String, String>,
     ) -> Element<'_, WriteMessage> {
-        if self.state.is_indexing() {
+        if self.state.is_busy() {
             container(
                 Spinner::new()
                     .height(Length::from(45))
@@ -206,7 +611,20 @@ impl WritePage {
             .into()
         } else {
             let can_submit = self.write_data.is_complete() && self.state == IndexingState::Ready;
-            button(text(tr!(translations, "start_indexing")))
+            let can_preview =
+                !self.write_data.directories.is_empty() && self.state == IndexingState::Ready;
+            let preview_button = button(text(tr!(translations, "preview_button")))
+                .on_press_maybe(can_preview.then_some(WriteMessage::PreviewPressed))
+                .padding(15)
+                .style(button::secondary);
+            // Only a drive already indexed under the category has changes to review
+            let review_button = (can_submit && self.is_existing_drive).then(|| {
+                button(text(tr!(translations, "review_changes_button")))
+                    .on_press(WriteMessage::ReviewChangesPressed)
+                    .padding(15)
+                    .style(button::secondary)
+            });
+            let start_button = button(text(tr!(translations, "start_indexing")))
                 .on_press_maybe(if can_submit {
                     Some(WriteMessage::WriteSubmit)
                 } else {
@@ -217,7 +635,9 @@ impl WritePage {
                     button::primary
                 } else {
                     button::text
-                })
+                });
+            row![preview_button, review_button, start_button]
+                .spacing(10)
                 .into()
         }
     }
@@ -226,13 +646,45 @@ impl WritePage {
         &'_ self,
         translations: &HashMap<String, String>,
     ) -> Element<'_, WriteMessage> {
-        match self.state {
-            IndexingState::Ready => column![],
-            IndexingState::CleaningDatabase => column![
-                text(tr!(translations, "clean_status"))
+        match &self.state {
+            IndexingState::Ready | IndexingState::CheckingExisting => column![],
+            IndexingState::ConfirmingOverwrite { files_to_replace } => column![
+                text(tr!(translations, "overwrite_confirm_status"))
+                    .size(18)
+                    .style(text::warning),
+                text(tr!(translations, "overwrite_confirm_details",
+                    "nb_files" => &files_to_replace.to_string(),
+                    "drive" => &self.write_data.drive,
+                    "category" => &self.write_data.category))
+                .size(14),
+                row![
+                    button(text(tr!(translations, "overwrite_confirm_button")))
+                        .on_press(WriteMessage::OverwriteConfirmed)
+                        .padding(10)
+                        .style(button::danger),
+                    button(text(tr!(translations, "cancel_button")))
+                        .on_press(WriteMessage::OverwriteCancelled)
+                        .padding(10)
+                        .style(button::secondary),
+                ]
+                .spacing(10),
+            ]
+            .spacing(10),
+            IndexingState::ReviewingChanges { diff } => self.changes_review(diff, translations),
+            IndexingState::Previewing | IndexingState::Diffing => column![
+                text(tr!(translations, "scan_status"))
+                    .size(18)
+                    .style(text::primary),
+                text(tr!(translations, "scan_details"))
+                    .style(text::secondary)
+                    .size(14),
+            ]
+            .spacing(10),
+            IndexingState::CleaningDatabase { files_to_remove } => column![
+                text(tr!(translations, "cleaning_status"))
                     .size(18)
                     .style(text::primary),
-                text(tr!(translations, "clean_details"))
+                text(tr!(translations, "cleaning_details", "nb_files" => &files_to_remove.to_string()))
                     .style(text::secondary)
                     .size(14),
             ]
@@ -246,15 +698,39 @@ impl WritePage {
                     .size(14),
             ]
             .spacing(10),
-            IndexingState::Saving => column![
+            IndexingState::Saving { files_saved } => column![
                 text(tr!(translations, "save_status"))
                     .size(18)
                     .style(text::primary),
-                text(tr!(translations, "save_details"))
+                text(tr!(translations, "save_details", "nb_files" => &files_saved.to_string()))
                     .style(text::secondary)
                     .size(14),
             ]
             .spacing(10),
+            IndexingState::Failed { message_key } => column![
+                text(tr!(translations, "failed_status"))
+                    .size(18)
+                    .style(text::danger),
+                text(tr!(translations, *message_key))
+                    .style(text::danger)
+                    .size(14),
+                row![
+                    button(text(tr!(translations, "retry_button")))
+                        .on_press_maybe(
+                            self.write_data
+                                .is_complete()
+                                .then_some(WriteMessage::RetryIndexing)
+                        )
+                        .padding(10)
+                        .style(button::primary),
+                    button(text(tr!(translations, "start_new_indexing")))
+                        .on_press(WriteMessage::ResetForm)
+                        .padding(10)
+                        .style(button::secondary),
+                ]
+                .spacing(10),
+            ]
+            .spacing(10),
             IndexingState::Completed { files_indexed } => {
                 column![
                     iced::widget::column![
@@ -277,66 +753,307 @@ impl WritePage {
         .into()
     }
 
-    fn clean_database(&mut self) -> Task<WriteMessage> {
+    fn changes_review<'a>(
+        &self,
+        diff: &ScanDiff,
+        translations: &HashMap<String, String>,
+    ) -> Column<'a, WriteMessage> {
+        let buttons = row![
+            button(text(tr!(translations, "apply_changes_button")))
+                .on_press(WriteMessage::ChangesApplied)
+                .padding(10)
+                .style(button::primary),
+            button(text(tr!(translations, "cancel_button")))
+                .on_press(WriteMessage::ChangesDiscarded)
+                .padding(10)
+                .style(button::secondary),
+        ]
+        .spacing(10);
+        if diff.is_empty() {
+            return column![
+                text(tr!(translations, "no_changes_status"))
+                    .size(18)
+                    .style(text::success),
+                buttons
+            ]
+            .spacing(10);
+        }
+
+        let resized = diff.resized.iter().map(|mismatch| {
+            format!(
+                "{} ({} → {})",
+                mismatch.path,
+                format_size(mismatch.size_a, DECIMAL),
+                format_size(mismatch.size_b, DECIMAL)
+            )
+        });
+        column![
+            text(tr!(translations, "changes_status",
+                "drive" => &self.write_data.drive,
+                "category" => &self.write_data.category))
+            .size(18)
+            .style(text::primary),
+            changed_paths(
+                tr!(translations, "added_files", "nb_files" => &diff.added.len().to_string()),
+                diff.added.iter().cloned(),
+                translations
+            ),
+            changed_paths(
+                tr!(translations, "removed_files", "nb_files" => &diff.removed.len().to_string()),
+                diff.removed.iter().cloned(),
+                translations
+            ),
+            changed_paths(
+                tr!(translations, "resized_files", "nb_files" => &diff.resized.len().to_string()),
+                resized,
+                translations
+            ),
+            text(tr!(translations, "unchanged_files", "nb_files" => &diff.unchanged.to_string()))
+                .style(text::secondary)
+                .size(14),
+            buttons
+        ]
+        .spacing(10)
+    }
+
+    /// Counts the files a re-index would replace, to have them confirmed before indexing.
+    fn check_overwrite(&mut self) -> Task<WriteMessage> {
         if self.state != IndexingState::Ready {
             return Task::none();
         }
-        self.state = IndexingState::CleaningDatabase;
+        self.state = IndexingState::CheckingExisting;
 
-        let indexing_use_case = self.command_repository.clone();
+        let command_repository = self.command_repository.clone();
         let category = self.write_data.category.clone();
         let drive = self.write_data.drive.clone();
-
         Task::perform(
-            async move {
-                indexing_use_case
-                    .remove_duplicates(&category, &drive)
-                    .unwrap_or_else(|error| popup_error_and_exit(error));
+            run_blocking(move || {
+                command_repository
+                    .count_for(&category, &drive)
+                    .map_err(|error| error.message_key())
+            }),
+            |result| match result {
+                Ok(files_to_replace) => WriteMessage::OverwriteChecked(files_to_replace),
+                Err(message_key) => WriteMessage::IndexingFailed(message_key),
             },
-            |()| WriteMessage::DatabaseCleaned,
         )
     }
 
-    fn start_indexing(&mut self) -> Task<WriteMessage> {
-        if self.state != IndexingState::CleaningDatabase {
+    fn start_indexing(&mut self, files_to_replace: u64) -> Task<WriteMessage> {
+        if self.state != IndexingState::Ready {
             return Task::none();
         }
-        self.state = IndexingState::Scanning;
+        // Old entries are removed before the first batch is saved
+        self.state = if files_to_replace == 0 {
+            IndexingState::Scanning
+        } else {
+            IndexingState::CleaningDatabase {
+                files_to_remove: files_to_replace,
+            }
+        };
 
-        self.write_data
-            .directory
-            .clone()
-            .map_or_else(Task::none, |directory| {
-                Task::perform(
-                    async move {
-                        directory_scanner::scan_directory(&directory).unwrap_or_else(|error| {
-                            popup_error(error);
-                            vec![]
-                        })
-                    },
-                    WriteMessage::ScanDirectoryFinished,
-                )
-            })
+        if self.write_data.directories.is_empty() {
+            return Task::none();
+        }
+        let directories = self.write_data.directories.clone();
+        let command_repository = self.command_repository.clone();
+        let category = self.write_data.category.clone();
+        let drive = self.write_data.drive.clone();
+        let drive_capacity = self.write_data.drive_capacity;
+        let root_path = directory_scanner::common_root(&directories);
+        let disk = self.write_data.disk.clone();
+        let include_hidden = self.include_hidden;
+        let preview = self.preview.take();
+        let (progress_sender, progress_receiver) = mpsc::unbounded();
+
+        let save = Task::perform(
+            run_blocking(move || {
+                let started = Instant::now();
+                let skipped = SkippedEntries::default();
+                let batches: Box<dyn Iterator<Item = _>> = match &preview {
+                    Some((_, files)) => Box::new(
+                        files
+                            .chunks(directory_scanner::SCAN_BATCH_SIZE)
+                            .map(|chunk| Ok(chunk.to_vec())),
+                    ),
+                    None => Box::new(
+                        directory_scanner::scan_directories_in_batches(
+                            &directories,
+                            directory_scanner::SCAN_BATCH_SIZE,
+                            false,
+                            include_hidden,
+                            ErrorPolicy::SkipErrors(skipped.clone()),
+                        )
+                        .map(|batch| {
+                            let files = batch
+                                .map_err(|error| InfrastructureError::Scan(error.to_string()))?;
+                            // Entries of an unplugged disk read as skipped, so the rest of
+                            // the scan would otherwise replace the index with a partial one
+                            if let Some(disk) = &disk {
+                                ensure_mounted(&SystemDisks, disk)?;
+                            }
+                            Ok(files)
+                        }),
+                    ),
+                };
+                let mut total_size = 0;
+                let batches = batches.inspect(|batch| {
+                    if let Ok(files) = batch {
+                        total_size += files.iter().map(|file| file.size_bytes).sum::<u64>();
+                    }
+                });
+
+                let file_count = command_repository
+                    .reindex(
+                        &category,
+                        &drive,
+                        drive_capacity,
+                        root_path.as_deref(),
+                        batches,
+                        |files_saved| {
+                            let _ = progress_sender.unbounded_send(files_saved);
+                        },
+                    )
+                    .map_err(|error| error.message_key())?;
+                // The files are saved either way, a missing history entry is only worth a warning
+                if let Err(error) = command_repository.index_runs().record_run(
+                    &directories,
+                    &category,
+                    &drive,
+                    file_count,
+                    started.elapsed(),
+                ) {
+                    popup_warning(error);
+                }
+                Ok(ScanSummary {
+                    file_count,
+                    total_size,
+                    // Files from a preview were already scanned, along with their skipped entries
+                    skipped: preview
+                        .map_or_else(|| skipped.paths(), |(summary, _)| summary.skipped),
+                })
+            }),
+            |result| match result {
+                Ok(summary) => WriteMessage::InsertInDatabaseFinished(summary),
+                Err(message_key) => WriteMessage::IndexingFailed(message_key),
+            },
+        );
+
+        Task::batch([
+            Task::stream(progress_receiver).map(WriteMessage::FilesSaved),
+            save,
+        ])
     }
 
-    fn insert_in_database(&mut self, files: Vec<FileEntry>) -> Task<WriteMessage> {
-        if self.state != IndexingState::Scanning {
+    /// Scans the directories and compares them with the files stored for the category and
+    /// drive, so the changes can be reviewed before they replace the index.
+    fn diff_scan(&mut self) -> Task<WriteMessage> {
+        if self.state != IndexingState::Ready || !self.write_data.is_complete() {
             return Task::none();
         }
-        self.state = IndexingState::Saving;
-
+        let directories = self.write_data.directories.clone();
+        let disk = self.write_data.disk.clone();
+        let include_hidden = self.include_hidden;
         let command_repository = self.command_repository.clone();
         let category = self.write_data.category.clone();
         let drive = self.write_data.drive.clone();
-        let drive_available_space = self.write_data.drive_available_space;
+        self.state = IndexingState::Diffing;
 
         Task::perform(
-            async move {
-                command_repository
-                    .save(&category, &drive, drive_available_space, &files)
-                    .unwrap_or(0)
+            run_blocking(move || {
+                let skipped = SkippedEntries::default();
+                let policy = ErrorPolicy::SkipErrors(skipped.clone());
+                let files = directory_scanner::scan_directories(
+                    &directories,
+                    false,
+                    include_hidden,
+                    policy,
+                )
+                .map_err(|error| error.message_key())?;
+                if let Some(disk) = &disk {
+                    ensure_mounted(&SystemDisks, disk).map_err(|error| error.message_key())?;
+                }
+                let stored = command_repository
+                    .files_for(&category, &drive)
+                    .map_err(|error| error.message_key())?;
+                let diff = ScanDiff::between(&stored, &files);
+                let summary = ScanSummary {
+                    skipped: skipped.paths(),
+                    ..ScanSummary::from_files(&files)
+                };
+                Ok((summary, files, diff))
+            }),
+            |result| match result {
+                Ok((summary, files, diff)) => WriteMessage::ChangesScanned(summary, files, diff),
+                Err(message_key) => WriteMessage::IndexingFailed(message_key),
+            },
+        )
+    }
+
+    fn preview_directory(&mut self) -> Task<WriteMessage> {
+        if self.state != IndexingState::Ready {
+            return Task::none();
+        }
+        if self.write_data.directories.is_empty() {
+            return Task::none();
+        }
+        let directories = self.write_data.directories.clone();
+        let disk = self.write_data.disk.clone();
+        let include_hidden = self.include_hidden;
+        self.state = IndexingState::Previewing;
+
+        Task::perform(
+            run_blocking(move || {
+                let skipped = SkippedEntries::default();
+                let policy = ErrorPolicy::SkipErrors(skipped.clone());
+                let files = directory_scanner::scan_directories(
+                    &directories,
+                    false,
+                    include_hidden,
+                    policy,
+                )
+                .map_err(|error| error.message_key())?;
+                if let Some(disk) = &disk {
+                    ensure_mounted(&SystemDisks, disk).map_err(|error| error.message_key())?;
+                }
+                let summary = ScanSummary {
+                    skipped: skipped.paths(),
+                    ..ScanSummary::from_files(&files)
+                };
+                Ok((summary, files))
+            }),
+            |result| match result {
+                Ok((summary, files)) => WriteMessage::PreviewFinished(summary, files),
+                Err(message_key) => WriteMessage::IndexingFailed(message_key),
             },
-            WriteMessage::InsertInDatabaseFinished,
         )
     }
 }
+
+/// Lists the first changed paths under their title, the others being only counted.
+fn changed_paths<'a>(
+    title: String,
+    paths: impl ExactSizeIterator<Item = String>,
+    translations: &HashMap<String, String>,
+) -> Option<Element<'a, WriteMessage>> {
+    if paths.len() == 0 {
+        return None;
+    }
+    let hidden = paths.len().saturating_sub(CHANGED_PATHS_SHOWN);
+    let shown = paths
+        .take(CHANGED_PATHS_SHOWN)
+        .map(|path| text(path).size(12).into());
+    let more = (hidden > 0).then(|| {
+        text(tr!(translations, "skipped_entries_more", "nb_entries" => &hidden.to_string()))
+            .size(12)
+    });
+    Some(
+        column![
+            text(title).size(14),
+            column(shown).padding(padding::left(10)),
+            more
+        ]
+        .spacing(5)
+        .into(),
+    )
+}
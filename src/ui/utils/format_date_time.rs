@@ -1,11 +1,41 @@
+use crate::domain::model::elapsed_time::ElapsedTime;
+use crate::domain::model::file_age::FileAge;
 use crate::domain::model::language::Language;
+use crate::tr;
 use chrono::NaiveDateTime;
+use std::collections::HashMap;
 
 pub fn format_date_time(date_time: NaiveDateTime, language: &Language) -> String {
     date_time
         .format(match language {
             Language::English => "%Y-%m-%d %H:%M:%S",
-            Language::French => "%d/%m/%Y %H:%M:%S",
+            Language::French | Language::Spanish => "%d/%m/%Y %H:%M:%S",
+            Language::German => "%d.%m.%Y %H:%M:%S",
         })
         .to_string()
 }
+
+pub fn format_indexed_ago(elapsed: ElapsedTime, translations: &HashMap<String, String>) -> String {
+    let (key, count) = match elapsed {
+        ElapsedTime::JustNow => return tr!(translations, "indexed_just_now"),
+        ElapsedTime::Minutes(1) => ("indexed_one_minute_ago", 1),
+        ElapsedTime::Minutes(count) => ("indexed_minutes_ago", count),
+        ElapsedTime::Hours(1) => ("indexed_one_hour_ago", 1),
+        ElapsedTime::Hours(count) => ("indexed_hours_ago", count),
+        ElapsedTime::Days(1) => ("indexed_one_day_ago", 1),
+        ElapsedTime::Days(count) => ("indexed_days_ago", count),
+    };
+    tr!(translations, key, "count" => &count.to_string())
+}
+
+/// Formats how long ago a file was modified, `None` standing for an unknown time.
+pub fn format_file_age(age: Option<FileAge>, translations: &HashMap<String, String>) -> String {
+    let (key, count) = match age {
+        None => return tr!(translations, "modified_unknown"),
+        Some(FileAge::Today) => return tr!(translations, "modified_today"),
+        Some(FileAge::Days(count)) => ("modified_days_ago", count),
+        Some(FileAge::Months(count)) => ("modified_months_ago", count),
+        Some(FileAge::Years(count)) => ("modified_years_ago", count),
+    };
+    tr!(translations, key, "count" => &count.to_string())
+}
@@ -0,0 +1,17 @@
+use std::panic::resume_unwind;
+use tokio::task::spawn_blocking;
+
+/// Runs a blocking operation, such as a database query or a directory walk, on the runtime's
+/// blocking thread pool.
+///
+/// Awaiting the returned future never stalls the executor driving the UI tasks. A panic in
+/// `operation` is propagated to the caller.
+pub async fn run_blocking<T, F>(operation: F) -> T
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    spawn_blocking(operation)
+        .await
+        .unwrap_or_else(|error| resume_unwind(error.into_panic()))
+}
@@ -1,12 +1,45 @@
-use rfd::{MessageButtons, MessageDialog, MessageLevel};
+use crate::tr;
+use crate::utils::localized_error::LocalizedError;
+use rfd::{MessageButtons, MessageDialog, MessageDialogResult, MessageLevel};
+use std::collections::HashMap;
 use std::fmt::Display;
 use std::process::exit;
+use std::sync::{PoisonError, RwLock};
 
-pub fn popup_error(error: impl Display) {
+/// Translations used by error dialogs, which can pop up from anywhere, away from the views.
+static DIALOG_TRANSLATIONS: RwLock<Option<HashMap<String, String>>> = RwLock::new(None);
+
+/// Sets the language of the error dialogs shown from now on.
+pub fn use_dialog_translations(translations: HashMap<String, String>) {
+    *DIALOG_TRANSLATIONS
+        .write()
+        .unwrap_or_else(PoisonError::into_inner) = Some(translations);
+}
+
+/// Shows an error, explained in the user's language when it is a known one.
+///
+/// The raw detail goes to the standard error output, for bug reports.
+pub fn popup_error(error: impl LocalizedError) {
+    eprintln!("{error}");
+    let translations = DIALOG_TRANSLATIONS
+        .read()
+        .unwrap_or_else(PoisonError::into_inner);
+    let translations = translations.as_ref();
+    let (title, description) = translations.map_or_else(
+        || ("An error happened".to_string(), error.to_string()),
+        |translations| {
+            (
+                tr!(translations, "error_title"),
+                error
+                    .message_key()
+                    .map_or_else(|| error.to_string(), |key| tr!(translations, key)),
+            )
+        },
+    );
     MessageDialog::new()
         .set_level(MessageLevel::Error)
-        .set_title("An error happened")
-        .set_description(error.to_string())
+        .set_title(title)
+        .set_description(description)
         .set_buttons(MessageButtons::Ok)
         .show();
 }
@@ -20,7 +53,26 @@ pub fn popup_info(info: impl Display) {
         .show();
 }
 
-pub fn popup_error_and_exit(error: impl Display) -> ! {
+pub fn popup_warning(warning: impl Display) {
+    MessageDialog::new()
+        .set_level(MessageLevel::Warning)
+        .set_title("Warning")
+        .set_description(warning.to_string())
+        .set_buttons(MessageButtons::Ok)
+        .show();
+}
+
+pub fn confirm(title: impl Display, description: impl Display) -> bool {
+    MessageDialog::new()
+        .set_level(MessageLevel::Info)
+        .set_title(title.to_string())
+        .set_description(description.to_string())
+        .set_buttons(MessageButtons::YesNo)
+        .show()
+        == MessageDialogResult::Yes
+}
+
+pub fn popup_error_and_exit(error: impl LocalizedError) -> ! {
     popup_error(error);
     exit(1)
 }
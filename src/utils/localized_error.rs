@@ -0,0 +1,37 @@
+use std::fmt::Display;
+
+/// An error that can be explained to the user in their language.
+pub trait LocalizedError: Display {
+    /// Translation key of the explanation, or `None` to show the error as it is.
+    fn message_key(&self) -> Option<&'static str> {
+        None
+    }
+}
+
+impl LocalizedError for String {}
+
+impl LocalizedError for &str {}
+
+impl LocalizedError for std::io::Error {
+    fn message_key(&self) -> Option<&'static str> {
+        Some(io_message_key(self))
+    }
+}
+
+impl LocalizedError for iced::window::icon::Error {}
+
+impl<T: LocalizedError + ?Sized> LocalizedError for &T {
+    fn message_key(&self) -> Option<&'static str> {
+        (**self).message_key()
+    }
+}
+
+/// Translation key of a message explaining a failed file system operation.
+#[must_use]
+pub fn io_message_key(error: &std::io::Error) -> &'static str {
+    match error.kind() {
+        std::io::ErrorKind::NotFound => "error_path_not_found",
+        std::io::ErrorKind::PermissionDenied => "error_permission_denied",
+        _ => "error_io",
+    }
+}
@@ -1 +1,4 @@
+pub mod blocking;
 pub mod dialogs;
+pub mod localized_error;
+pub mod os_open;
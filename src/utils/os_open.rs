@@ -0,0 +1,51 @@
+use std::io;
+use std::path::Path;
+use std::process::Command;
+
+/// Opens a file or folder with the application the operating system associates with it.
+///
+/// The launcher is started without waiting for the opened application to exit.
+///
+/// # Errors
+///
+/// Returns an [`io::Error`] if the platform launcher cannot be started.
+pub fn open_path(path: &Path) -> io::Result<()> {
+    launcher(path).spawn().map(|_| ())
+}
+
+/// Builds the platform launcher command opening `path`.
+///
+/// The path is handed over as a single argument and never goes through a shell, so characters
+/// such as `&`, `|` or `%` in a cataloged file name cannot run commands.
+#[cfg(target_os = "windows")]
+#[must_use]
+pub fn launcher(path: &Path) -> Command {
+    // Explorer opens the path with ShellExecute, unlike `cmd /C start` which parses it
+    let mut command = Command::new("explorer.exe");
+    command.arg(path);
+    command
+}
+
+/// Builds the platform launcher command opening `path`.
+///
+/// The path is handed over as a single argument and never goes through a shell, so characters
+/// such as `&`, `|` or `%` in a cataloged file name cannot run commands.
+#[cfg(target_os = "macos")]
+#[must_use]
+pub fn launcher(path: &Path) -> Command {
+    let mut command = Command::new("open");
+    command.arg(path);
+    command
+}
+
+/// Builds the platform launcher command opening `path`.
+///
+/// The path is handed over as a single argument and never goes through a shell, so characters
+/// such as `&`, `|` or `%` in a cataloged file name cannot run commands.
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+#[must_use]
+pub fn launcher(path: &Path) -> Command {
+    let mut command = Command::new("xdg-open");
+    command.arg(path);
+    command
+}
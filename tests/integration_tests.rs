@@ -1,15 +1,233 @@
-use chrono::Local;
-use lister::domain::model::file_entry::FileEntry;
+use chrono::{Local, NaiveDate, NaiveDateTime};
+use diesel::result::{DatabaseErrorKind, Error as DieselError};
+use diesel_migrations::MigrationHarness;
+use lister::application::cache::Cache;
+use lister::application::directory_scanner::{
+    DirectoryScannerError, ErrorPolicy, SkippedEntries, common_root, scan_directories,
+    scan_directories_in_batches, scan_directory, scan_directory_in_batches,
+};
+use lister::application::latest_request::LatestRequest;
+use lister::application::presence_check::{PresenceCheckError, check_presence};
+use lister::application::search_timer::{SLOW_SEARCH_THRESHOLD, SearchTimer};
+use lister::application::shortcut::{KeyPress, SHORTCUT_GROUPS, Shortcut, ShortcutKey};
+use lister::application::undo_manager::{UNDO_WINDOW, UndoManager};
+use lister::cli::args::CliArgs;
+use lister::domain::clock::{Clock, SystemClock};
+use lister::domain::disk_lister::{DiskInfoProvider, DiskLister, disk_holding, removable_disks};
+use lister::domain::model::byte_size::parse_size;
+use lister::domain::model::category_overview::CategoryOverview;
+use lister::domain::model::category_stats::CategoryStats;
+use lister::domain::model::directory::DirectoryData;
+use lister::domain::model::directory_group::group_by_parent_directory;
+use lister::domain::model::directory_listing::{DirectoryListing, FolderEntry, breadcrumbs};
+use lister::domain::model::drive_capacity::{DriveCapacity, DriveFullness};
+use lister::domain::model::drive_diff::{DriveDiff, SizeMismatch};
+use lister::domain::model::drive_group::group_by_drive;
+use lister::domain::model::drive_info::DriveInfo;
+use lister::domain::model::drive_tag::normalize_tag;
+use lister::domain::model::elapsed_time::ElapsedTime;
+use lister::domain::model::file_age::FileAge;
+use lister::domain::model::file_entry::{FileEntry, FileWithMetadata};
+use lister::domain::model::file_kind::FileKind;
+use lister::domain::model::file_ownership::{FileOwnership, PermissionFilter};
+use lister::domain::model::fuzzy_match::rank_by_filename;
+use lister::domain::model::hidden_results::{HiddenResults, ResultKey};
+use lister::domain::model::image_preview::{PreviewSource, is_previewable};
+use lister::domain::model::index_run::IndexRun;
+use lister::domain::model::indexed_within::IndexedWithin;
 use lister::domain::model::language::Language;
-use lister::infrastructure::database::pool::SqliteRepositoryPool;
+use lister::domain::model::manifest::format_manifest;
+use lister::domain::model::mounted_disk::MountedDisk;
+use lister::domain::model::pagination::{
+    DEFAULT_PAGE_SIZE, PAGE_SIZES, PageInput, clamp_page_index, parse_page_input, total_pages,
+};
+use lister::domain::model::path_tree::{PathNode, build_path_tree};
+use lister::domain::model::result_columns::{ResultColumn, ResultColumns};
+use lister::domain::model::result_table::format_markdown_table;
+use lister::domain::model::row_density::RowDensity;
+use lister::domain::model::row_selection::{move_selection, reveal_offset};
+use lister::domain::model::saved_search::SavedSearch;
+use lister::domain::model::scan_diff::ScanDiff;
+use lister::domain::model::scan_summary::{
+    CapacityAdvice, EXCESS_SIZE_FACTOR, NEARLY_FULL_SPACE, ScanSummary,
+};
+use lister::domain::model::search_constraint::{LiftedConstraint, SearchConstraint};
+use lister::domain::model::search_filters::{LARGEST_FILES_COUNT, SearchFilters};
+use lister::domain::model::search_history::{SEARCH_HISTORY_LIMIT, SearchHistory};
+use lister::domain::model::search_status::{StatusPart, status_parts};
+use lister::domain::model::search_terms::SearchTerms;
+use lister::domain::model::sort_key::SortKey;
+use lister::domain::model::stored_path::{decode_path, encode_path};
+use lister::infrastructure::database::pool::{
+    BUSY_MAX_ATTEMPTS, InfrastructureError, MIGRATIONS, PoolConfig, SqliteRepositoryPool,
+};
 use lister::infrastructure::database::repository::ListerRepository;
-use lister::infrastructure::i18n::json_translation_loader::load_translations;
+use lister::infrastructure::filesystem::directory::{
+    PickedDirectory, directory_data, ensure_mounted, pick_directory,
+};
+use lister::infrastructure::i18n::json_translation_loader::{
+    load_translations, load_translations_from,
+};
+use lister::infrastructure::updater::app_updater::{
+    AvailableUpdate, DownloadProgress, GitHubUpdater, UPDATE_SOURCE, UpdateError, UpdateSource,
+    Updater, is_compatible_version, is_newer_version, look_up_quietly, verify_checksum,
+};
+use lister::utils::blocking::run_blocking;
 use lister::utils::dialogs::popup_error_and_exit;
-use std::collections::HashSet;
+use lister::utils::os_open::launcher;
+use std::collections::{BTreeMap, HashSet};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
 use std::time::Instant;
 use tempfile::TempDir;
 
 // Test helpers and fixtures
+
+/// Clock stuck at a given time until it is moved explicitly, so the tests are deterministic.
+#[derive(Debug)]
+struct FixedClock {
+    now: Mutex<NaiveDateTime>,
+}
+
+impl FixedClock {
+    #[must_use]
+    const fn new(now: NaiveDateTime) -> Self {
+        Self {
+            now: Mutex::new(now),
+        }
+    }
+
+    fn set(&self, now: NaiveDateTime) {
+        *self
+            .now
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = now;
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> NaiveDateTime {
+        *self
+            .now
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Fixed list of disks, standing in for the mounted ones.
+#[derive(Debug, Default)]
+struct FixedDisks(Vec<MountedDisk>);
+
+impl DiskLister for FixedDisks {
+    fn mounted_disks(&self) -> Vec<MountedDisk> {
+        self.0.clone()
+    }
+}
+
+impl DiskInfoProvider for FixedDisks {
+    fn disk_info(&self, path: &Path) -> Option<MountedDisk> {
+        disk_holding(self.mounted_disks(), path)
+    }
+}
+
+/// Updater answering from a single canned release instead of going online.
+#[derive(Debug, Default)]
+struct FakeUpdater {
+    latest: Option<AvailableUpdate>,
+    is_offline: bool,
+    applied: Mutex<Vec<String>>,
+}
+
+impl FakeUpdater {
+    /// Size reported while pretending to download a release.
+    const DOWNLOAD_SIZE: u64 = 1024;
+
+    /// Updater whose latest release is the running version.
+    #[must_use]
+    fn up_to_date() -> Self {
+        Self::default()
+    }
+
+    /// Updater whose latest release is `version`.
+    #[must_use]
+    fn available(version: &str, release_notes: &str) -> Self {
+        Self {
+            latest: Some(AvailableUpdate {
+                version: version.to_string(),
+                release_notes: release_notes.to_string(),
+            }),
+            ..Self::default()
+        }
+    }
+
+    /// Updater failing every request as if the network were down.
+    #[must_use]
+    fn offline() -> Self {
+        Self {
+            is_offline: true,
+            ..Self::default()
+        }
+    }
+
+    /// Versions installed so far, in order.
+    #[must_use]
+    fn applied(&self) -> Vec<String> {
+        self.applied
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    fn latest(&self) -> Result<Option<&AvailableUpdate>, UpdateError> {
+        if self.is_offline {
+            return Err(io::Error::from(io::ErrorKind::NotConnected).into());
+        }
+        Ok(self
+            .latest
+            .as_ref()
+            .filter(|update| is_newer_version(env!("CARGO_PKG_VERSION"), &update.version)))
+    }
+}
+
+impl Updater for FakeUpdater {
+    fn is_enabled(&self) -> bool {
+        true
+    }
+
+    fn check(&self) -> Result<Option<AvailableUpdate>, UpdateError> {
+        Ok(self.latest()?.cloned())
+    }
+
+    fn check_compatible(&self) -> Result<Option<AvailableUpdate>, UpdateError> {
+        Ok(self
+            .latest()?
+            .filter(|update| is_compatible_version(env!("CARGO_PKG_VERSION"), &update.version))
+            .cloned())
+    }
+
+    fn apply(
+        &self,
+        update: &AvailableUpdate,
+        on_progress: &mut dyn FnMut(DownloadProgress),
+    ) -> Result<(), UpdateError> {
+        self.latest()?;
+        for downloaded in [0, Self::DOWNLOAD_SIZE / 2, Self::DOWNLOAD_SIZE] {
+            on_progress(DownloadProgress {
+                downloaded,
+                total: Some(Self::DOWNLOAD_SIZE),
+            });
+        }
+        self.applied
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .push(update.version.clone());
+        Ok(())
+    }
+}
+
 struct TestFixture {
     _temp_dir: TempDir, // Store it to prevent its disposal
     repository: ListerRepository,
@@ -17,6 +235,10 @@ struct TestFixture {
 
 impl TestFixture {
     fn new() -> Self {
+        Self::with_clock(Arc::new(SystemClock))
+    }
+
+    fn with_clock(clock: Arc<dyn Clock>) -> Self {
         let temp_dir = TempDir::new().expect("Failed to create temp directory");
         let db_path = temp_dir.path().join("test.db");
         let db_url = format!("sqlite://{}", db_path.display());
@@ -26,7 +248,7 @@ impl TestFixture {
 
         Self {
             _temp_dir: temp_dir,
-            repository: ListerRepository::new(pool),
+            repository: ListerRepository::new(pool, clock),
         }
     }
 
@@ -35,18 +257,26 @@ impl TestFixture {
             FileEntry {
                 path: "documents/report.pdf".to_string(),
                 size_bytes: 1024,
+                modified_time: None,
+                ownership: None,
             },
             FileEntry {
                 path: "images/photo.jpg".to_string(),
                 size_bytes: 2048,
+                modified_time: None,
+                ownership: None,
             },
             FileEntry {
                 path: "code/main.rs".to_string(),
                 size_bytes: 512,
+                modified_time: None,
+                ownership: None,
             },
             FileEntry {
                 path: "documents/invoice.pdf".to_string(),
                 size_bytes: 768,
+                modified_time: None,
+                ownership: None,
             },
         ]
     }
@@ -64,7 +294,10 @@ fn test_complete_file_indexing_workflow() {
     assert_eq!(result.unwrap(), 4);
 
     // Verify files were indexed
-    let query_result = fixture.repository.search_files_paginated(None, None, 0, 10);
+    let query_result =
+        fixture
+            .repository
+            .search_files_paginated(None, None, &SearchFilters::default(), 0, 10);
     assert!(query_result.is_ok());
 
     let actual = query_result.unwrap();
@@ -79,6 +312,109 @@ fn test_complete_file_indexing_workflow() {
     assert!(files.iter().any(|f| f.path == first_file.path));
 }
 
+#[test]
+fn test_save_more_files_than_one_statement_holds() {
+    let fixture = TestFixture::new();
+    // Enough files for a single statement to bind more variables than the bundled `SQLite` accepts
+    let files: Vec<FileEntry> = (0..40_000)
+        .map(|index| FileEntry {
+            path: format!("photos/{index}.jpg"),
+            size_bytes: 1,
+            modified_time: None,
+            ownership: Some(FileOwnership {
+                uid: 1000,
+                gid: 1000,
+                mode: 0o100_644,
+            }),
+        })
+        .collect();
+
+    let saved = fixture.repository.save("Work", "Laptop", 1024, &files);
+    assert_eq!(saved.unwrap(), files.len());
+    assert_eq!(
+        fixture.repository.count_for("Work", "Laptop").unwrap(),
+        40_000
+    );
+
+    let reindexed = fixture.repository.reindex(
+        "Work",
+        "Laptop",
+        DriveCapacity::available_only(1024),
+        None,
+        [Ok(files.clone()), Ok(files[..10].to_vec())],
+        |_| {},
+    );
+    assert_eq!(reindexed.unwrap(), 40_010);
+}
+
+#[test]
+fn test_diagnose_empty_search() {
+    let fixture = TestFixture::new();
+    let files = TestFixture::create_test_files();
+    fixture
+        .repository
+        .save("Work", "Laptop", 1024, &files)
+        .unwrap();
+    fixture
+        .repository
+        .save("Home", "Desktop", 1024, &files[..1])
+        .unwrap();
+    let future = NaiveDate::from_ymd_opt(2100, 1, 1)
+        .unwrap()
+        .and_hms_opt(0, 0, 0);
+
+    // Only the indexing period empties the search, lifting anything else keeps it empty
+    let filters = SearchFilters {
+        extension: Some("pdf".to_string()),
+        indexed_since: future,
+        ..SearchFilters::default()
+    };
+    let query = Some("report");
+    assert_eq!(
+        fixture
+            .repository
+            .count_search_results(Some("Laptop"), query, &filters)
+            .unwrap(),
+        0
+    );
+    assert_eq!(
+        fixture
+            .repository
+            .diagnose_empty(Some("Laptop"), query, &filters)
+            .unwrap(),
+        [LiftedConstraint {
+            constraint: SearchConstraint::IndexedSince,
+            result_count: 1,
+        }]
+    );
+
+    // No Rust file is in the documents or on the desktop, only the extension is too tight
+    let filters = SearchFilters {
+        extension: Some("rs".to_string()),
+        ..SearchFilters::default()
+    };
+    assert_eq!(
+        fixture
+            .repository
+            .diagnose_empty(Some("Desktop"), Some("documents"), &filters)
+            .unwrap(),
+        [LiftedConstraint {
+            constraint: SearchConstraint::Extension,
+            result_count: 1,
+        }]
+    );
+
+    // Matching file names only does not narrow a search without a query
+    let filters = SearchFilters {
+        filename_only: true,
+        ..SearchFilters::default()
+    };
+    assert_eq!(
+        SearchConstraint::active(Some("Laptop"), Some("  "), &filters),
+        [SearchConstraint::Drive]
+    );
+}
+
 #[test]
 fn test_duplicate_removal_workflow() {
     let fixture = TestFixture::new();
@@ -92,12 +428,12 @@ fn test_duplicate_removal_workflow() {
 
     // Remove duplicates
     let remove_result = fixture.repository.remove_duplicates("Work", "Laptop");
-    assert!(remove_result.is_ok());
+    assert_eq!(remove_result.unwrap(), files.len());
 
     // Verify files were removed
     let query_result = fixture
         .repository
-        .search_files_paginated(None, None, 0, 10)
+        .search_files_paginated(None, None, &SearchFilters::default(), 0, 10)
         .unwrap();
     assert_eq!(query_result.len(), 0);
 
@@ -109,11 +445,222 @@ fn test_duplicate_removal_workflow() {
 
     let final_result = fixture
         .repository
-        .search_files_paginated(None, None, 0, 10)
+        .search_files_paginated(None, None, &SearchFilters::default(), 0, 10)
         .unwrap();
     assert_eq!(final_result.len(), 4);
 }
 
+#[test]
+fn test_undo_duplicate_removal() {
+    let fixture = TestFixture::new();
+    let modified = NaiveDate::from_ymd_opt(2024, 5, 1)
+        .unwrap()
+        .and_hms_opt(8, 30, 0);
+    let mut files = TestFixture::create_test_files();
+    files[0].modified_time = modified;
+    fixture
+        .repository
+        .save("Work", "Laptop", 1024, &files)
+        .unwrap();
+    fixture
+        .repository
+        .save("Personal", "Laptop", 1024, &files[..1])
+        .unwrap();
+    let all_files = || {
+        let mut found = fixture
+            .repository
+            .search_files_paginated(None, None, &SearchFilters::default(), 0, 100)
+            .unwrap();
+        // Restored rows come back in another storage order
+        found.sort_by(|a, b| (&a.category_name, &a.path).cmp(&(&b.category_name, &b.path)));
+        found
+    };
+    let before = all_files();
+
+    let snapshot = fixture.repository.snapshot("Laptop", Some("Work")).unwrap();
+    assert_eq!(snapshot.drive_count(), 1);
+    assert_eq!(snapshot.file_count(), files.len());
+    let mut undo = UndoManager::default();
+    let deleted_at = Instant::now();
+    undo.record(snapshot, deleted_at);
+    fixture
+        .repository
+        .remove_duplicates("Work", "Laptop")
+        .unwrap();
+    assert_eq!(all_files().len(), 1);
+
+    let snapshot = undo.take(deleted_at + Duration::from_secs(1)).unwrap();
+    assert_eq!(fixture.repository.restore(&snapshot).unwrap(), files.len());
+    // Same files, sizes, times, and drive details as before, and nothing more
+    assert_eq!(all_files(), before);
+    assert_eq!(fixture.repository.count_for("Work", "Laptop").unwrap(), 4);
+    // The operation is undone only once
+    assert!(undo.take(deleted_at + Duration::from_secs(2)).is_none());
+}
+
+#[test]
+fn test_undo_deleted_drive() {
+    let fixture = TestFixture::new();
+    let files = TestFixture::create_test_files();
+    fixture
+        .repository
+        .save("Work", "Laptop", 1024, &files)
+        .unwrap();
+    let tags = fixture.repository.drive_tags();
+    tags.add("Laptop", "offsite").unwrap();
+    tags.add("Laptop", "2024").unwrap();
+
+    let snapshot = fixture.repository.snapshot("Laptop", None).unwrap();
+    fixture.repository.delete("Laptop", None).unwrap();
+    assert!(fixture.repository.find_all_categories().unwrap().is_empty());
+    assert!(tags.find_all().unwrap().is_empty());
+
+    // The emptied category comes back along with the drive and its tags
+    assert_eq!(fixture.repository.restore(&snapshot).unwrap(), files.len());
+    assert_eq!(fixture.repository.count_for("Work", "Laptop").unwrap(), 4);
+    assert_eq!(
+        tags.find_all().unwrap(),
+        BTreeMap::from([(
+            "Laptop".to_string(),
+            vec!["2024".to_string(), "offsite".to_string()]
+        )])
+    );
+    // Restoring again replaces the drive instead of duplicating it
+    assert_eq!(fixture.repository.restore(&snapshot).unwrap(), files.len());
+    assert_eq!(fixture.repository.count_for("Work", "Laptop").unwrap(), 4);
+    assert!(
+        fixture
+            .repository
+            .snapshot("Desktop", None)
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_shortcuts_while_typing() {
+    let press = |key, command, shift| KeyPress {
+        key,
+        command,
+        shift,
+    };
+    let tab = press(ShortcutKey::Tab, false, false);
+    let shift_tab = press(ShortcutKey::Tab, false, true);
+    let ctrl_tab = press(ShortcutKey::Tab, true, false);
+    let ctrl_1 = press(ShortcutKey::Digit(1), true, false);
+    let ctrl_2 = press(ShortcutKey::Digit(2), true, false);
+
+    for is_typing in [false, true] {
+        assert_eq!(tab.shortcut(is_typing), Some(Shortcut::FocusNext));
+        assert_eq!(shift_tab.shortcut(is_typing), Some(Shortcut::FocusPrevious));
+        assert_eq!(ctrl_1.shortcut(is_typing), Some(Shortcut::ReadPage));
+        assert_eq!(ctrl_2.shortcut(is_typing), Some(Shortcut::WritePage));
+    }
+
+    // Cycling pages would leave the text being typed
+    assert_eq!(ctrl_tab.shortcut(false), Some(Shortcut::NextPage));
+    assert_eq!(ctrl_tab.shortcut(true), None);
+
+    // Typed digits and other combinations are left to the pages
+    assert_eq!(
+        press(ShortcutKey::Digit(1), false, false).shortcut(false),
+        None
+    );
+    assert_eq!(
+        press(ShortcutKey::Digit(3), true, false).shortcut(false),
+        None
+    );
+    assert_eq!(press(ShortcutKey::Tab, true, true).shortcut(false), None);
+
+    // The help opens from anywhere with F1, and with ? only when it is not typed as text
+    assert_eq!(
+        press(ShortcutKey::F1, false, false).shortcut(true),
+        Some(Shortcut::ToggleHelp)
+    );
+    assert_eq!(
+        press(ShortcutKey::QuestionMark, false, true).shortcut(false),
+        Some(Shortcut::ToggleHelp)
+    );
+    assert_eq!(
+        press(ShortcutKey::QuestionMark, false, true).shortcut(true),
+        None
+    );
+    assert_eq!(
+        press(ShortcutKey::Escape, false, false).shortcut(true),
+        Some(Shortcut::CloseHelp)
+    );
+}
+
+#[test]
+fn test_shortcut_help_lists_every_shortcut() {
+    let keys: Vec<&str> = SHORTCUT_GROUPS
+        .iter()
+        .flat_map(|group| group.shortcuts)
+        .map(|shortcut| shortcut.keys)
+        .collect();
+    for expected in [
+        "Ctrl+Tab",
+        "Ctrl+1",
+        "Ctrl+2",
+        "Tab / Shift+Tab",
+        "F1 / ?",
+        "Ctrl+F",
+        "Ctrl+L",
+        "Ctrl+R / F5",
+        "Up / Down",
+        "Page Up / Page Down",
+        "Home / End",
+        "Enter",
+        "Left / Right",
+        "Shift+Left / Shift+Right",
+    ] {
+        assert!(keys.contains(&expected), "{expected} is missing");
+    }
+
+    for language in Language::ALL {
+        let translations = load_translations(&language).unwrap();
+        assert!(translations.contains_key("shortcuts_title"));
+        for group in SHORTCUT_GROUPS {
+            assert!(
+                translations.contains_key(group.title_key),
+                "{} in {language:?}",
+                group.title_key
+            );
+            for shortcut in group.shortcuts {
+                assert!(
+                    translations.contains_key(shortcut.description_key),
+                    "{} in {language:?}",
+                    shortcut.description_key
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_undo_window() {
+    let now = Instant::now();
+    let mut undo = UndoManager::default();
+    assert!(!undo.can_undo(now));
+
+    undo.record("first", now);
+    undo.record("second", now);
+    assert!(undo.can_undo(now + UNDO_WINDOW - Duration::from_secs(1)));
+    // Only the most recent operation is kept
+    assert_eq!(undo.take(now), Some("second"));
+    assert_eq!(undo.take(now), None);
+
+    undo.record("late", now);
+    undo.expire(now);
+    assert!(undo.can_undo(now));
+    assert!(!undo.can_undo(now + UNDO_WINDOW));
+    assert_eq!(undo.take(now + UNDO_WINDOW), None);
+
+    undo.record("expired", now);
+    undo.expire(now + UNDO_WINDOW);
+    assert!(!undo.can_undo(now));
+}
+
 #[test]
 fn test_file_search_functionality() {
     let fixture = TestFixture::new();
@@ -128,7 +675,7 @@ fn test_file_search_functionality() {
     // Test search by extension
     let pdf_results = fixture
         .repository
-        .search_files_paginated(None, Some(".pdf"), 0, 10)
+        .search_files_paginated(None, Some(".pdf"), &SearchFilters::default(), 0, 10)
         .unwrap();
     assert_eq!(pdf_results.len(), 2);
     assert!(pdf_results.iter().all(|f| f.path.contains(".pdf")));
@@ -136,7 +683,7 @@ fn test_file_search_functionality() {
     // Test search by directory
     let doc_results = fixture
         .repository
-        .search_files_paginated(None, Some("documents"), 0, 10)
+        .search_files_paginated(None, Some("documents"), &SearchFilters::default(), 0, 10)
         .unwrap();
     assert_eq!(doc_results.len(), 2);
     assert!(doc_results.iter().all(|f| f.path.contains("documents")));
@@ -144,7 +691,7 @@ fn test_file_search_functionality() {
     // Test search by filename
     let main_results = fixture
         .repository
-        .search_files_paginated(None, Some("main"), 0, 10)
+        .search_files_paginated(None, Some("main"), &SearchFilters::default(), 0, 10)
         .unwrap();
     assert_eq!(main_results.len(), 1);
     assert_eq!(main_results[0].path, "code/main.rs");
@@ -152,7 +699,7 @@ fn test_file_search_functionality() {
     // Test empty search returns all files
     let all_results = fixture
         .repository
-        .search_files_paginated(None, Some(""), 0, 10)
+        .search_files_paginated(None, Some(""), &SearchFilters::default(), 0, 10)
         .unwrap();
     assert_eq!(all_results.len(), 4);
 }
@@ -181,21 +728,21 @@ fn test_selected_drive_basic_functionality() {
     // Test selecting specific drive
     let laptop_results = fixture
         .repository
-        .search_files_paginated(Some("Laptop"), None, 0, 20)
+        .search_files_paginated(Some("Laptop"), None, &SearchFilters::default(), 0, 20)
         .unwrap();
     assert_eq!(laptop_results.len(), 4);
     assert!(laptop_results.iter().all(|f| f.drive_name == "Laptop"));
 
     let desktop_results = fixture
         .repository
-        .search_files_paginated(Some("Desktop"), None, 0, 20)
+        .search_files_paginated(Some("Desktop"), None, &SearchFilters::default(), 0, 20)
         .unwrap();
     assert_eq!(desktop_results.len(), 4);
     assert!(desktop_results.iter().all(|f| f.drive_name == "Desktop"));
 
     let server_results = fixture
         .repository
-        .search_files_paginated(Some("Server"), None, 0, 20)
+        .search_files_paginated(Some("Server"), None, &SearchFilters::default(), 0, 20)
         .unwrap();
     assert_eq!(server_results.len(), 4);
     assert!(server_results.iter().all(|f| f.drive_name == "Server"));
@@ -203,7 +750,7 @@ fn test_selected_drive_basic_functionality() {
     // Test no drive selection (should return all)
     let all_results = fixture
         .repository
-        .search_files_paginated(None, None, 0, 20)
+        .search_files_paginated(None, None, &SearchFilters::default(), 0, 20)
         .unwrap();
     assert_eq!(all_results.len(), 12); // 4 files × 3 drives
 }
@@ -227,7 +774,13 @@ fn test_selected_drive_with_search_query() {
     // Test combining drive selection with search query
     let laptop_pdf_results = fixture
         .repository
-        .search_files_paginated(Some("Laptop"), Some(".pdf"), 0, 10)
+        .search_files_paginated(
+            Some("Laptop"),
+            Some(".pdf"),
+            &SearchFilters::default(),
+            0,
+            10,
+        )
         .unwrap();
     assert_eq!(laptop_pdf_results.len(), 2);
     assert!(
@@ -238,7 +791,13 @@ fn test_selected_drive_with_search_query() {
 
     let desktop_pdf_results = fixture
         .repository
-        .search_files_paginated(Some("Desktop"), Some(".pdf"), 0, 10)
+        .search_files_paginated(
+            Some("Desktop"),
+            Some(".pdf"),
+            &SearchFilters::default(),
+            0,
+            10,
+        )
         .unwrap();
     assert_eq!(desktop_pdf_results.len(), 2);
     assert!(
@@ -250,14 +809,20 @@ fn test_selected_drive_with_search_query() {
     // Test search query without drive selection (should find PDFs on both drives)
     let all_pdf_results = fixture
         .repository
-        .search_files_paginated(None, Some(".pdf"), 0, 10)
+        .search_files_paginated(None, Some(".pdf"), &SearchFilters::default(), 0, 10)
         .unwrap();
     assert_eq!(all_pdf_results.len(), 4); // 2 PDFs × 2 drives
 
     // Test drive selection with search query that has no matches
     let laptop_nonexistent_results = fixture
         .repository
-        .search_files_paginated(Some("Laptop"), Some("nonexistent"), 0, 10)
+        .search_files_paginated(
+            Some("Laptop"),
+            Some("nonexistent"),
+            &SearchFilters::default(),
+            0,
+            10,
+        )
         .unwrap();
     assert_eq!(laptop_nonexistent_results.len(), 0);
 }
@@ -276,7 +841,13 @@ fn test_selected_drive_nonexistent_drive() {
     // Test selecting a nonexistent drive
     let nonexistent_results = fixture
         .repository
-        .search_files_paginated(Some("NonexistentDrive"), None, 0, 10)
+        .search_files_paginated(
+            Some("NonexistentDrive"),
+            None,
+            &SearchFilters::default(),
+            0,
+            10,
+        )
         .unwrap();
     assert_eq!(nonexistent_results.len(), 0);
     assert_eq!(nonexistent_results.len(), 0);
@@ -284,7 +855,13 @@ fn test_selected_drive_nonexistent_drive() {
     // Test selecting a nonexistent drive with search query
     let nonexistent_with_query = fixture
         .repository
-        .search_files_paginated(Some("NonexistentDrive"), Some(".pdf"), 0, 10)
+        .search_files_paginated(
+            Some("NonexistentDrive"),
+            Some(".pdf"),
+            &SearchFilters::default(),
+            0,
+            10,
+        )
         .unwrap();
     assert_eq!(nonexistent_with_query.len(), 0);
     assert_eq!(nonexistent_with_query.len(), 0);
@@ -300,6 +877,8 @@ fn test_selected_drive_with_pagination() {
         many_files.push(FileEntry {
             path: format!("file_{i:03}.txt"),
             size_bytes: i * 10,
+            modified_time: None,
+            ownership: None,
         });
     }
 
@@ -317,13 +896,13 @@ fn test_selected_drive_with_pagination() {
     // Test pagination with drive selection
     let drive1_page0 = fixture
         .repository
-        .search_files_paginated(Some("Drive1"), None, 0, 100)
+        .search_files_paginated(Some("Drive1"), None, &SearchFilters::default(), 0, 100)
         .unwrap();
     assert_eq!(drive1_page0.len(), 100);
 
     let count = fixture
         .repository
-        .count_search_results(Some("Drive1"), None)
+        .count_search_results(Some("Drive1"), None, &SearchFilters::default())
         .unwrap();
 
     assert_eq!(count, 150);
@@ -331,13 +910,13 @@ fn test_selected_drive_with_pagination() {
 
     let drive1_page1 = fixture
         .repository
-        .search_files_paginated(Some("Drive1"), None, 1, 100)
+        .search_files_paginated(Some("Drive1"), None, &SearchFilters::default(), 1, 100)
         .unwrap();
     assert_eq!(drive1_page1.len(), 50);
 
     let count = fixture
         .repository
-        .count_search_results(Some("Drive1"), None)
+        .count_search_results(Some("Drive1"), None, &SearchFilters::default())
         .unwrap();
 
     assert_eq!(count, 150);
@@ -346,21 +925,27 @@ fn test_selected_drive_with_pagination() {
     // Test pagination without drive selection (should see all files)
     let all_page0 = fixture
         .repository
-        .search_files_paginated(None, None, 0, 100)
+        .search_files_paginated(None, None, &SearchFilters::default(), 0, 100)
         .unwrap();
     assert_eq!(all_page0.len(), 100);
 
-    let count = fixture.repository.count_search_results(None, None).unwrap();
+    let count = fixture
+        .repository
+        .count_search_results(None, None, &SearchFilters::default())
+        .unwrap();
 
     assert_eq!(count, 300); // 150 files × 2 drives
 
     let all_page2 = fixture
         .repository
-        .search_files_paginated(None, None, 2, 100)
+        .search_files_paginated(None, None, &SearchFilters::default(), 2, 100)
         .unwrap();
     assert_eq!(all_page2.len(), 100);
 
-    let count = fixture.repository.count_search_results(None, None).unwrap();
+    let count = fixture
+        .repository
+        .count_search_results(None, None, &SearchFilters::default())
+        .unwrap();
 
     assert_eq!(count, 300);
 }
@@ -375,6 +960,8 @@ fn test_pagination_behavior() {
         many_files.push(FileEntry {
             path: format!("file_{i:03}.txt"),
             size_bytes: i * 10,
+            modified_time: None,
+            ownership: None,
         });
     }
 
@@ -386,32 +973,35 @@ fn test_pagination_behavior() {
     // Test first page
     let page_0 = fixture
         .repository
-        .search_files_paginated(None, None, 0, 100)
+        .search_files_paginated(None, None, &SearchFilters::default(), 0, 100)
         .unwrap();
     assert_eq!(page_0.len(), 100);
 
     // Test second page
     let page_1 = fixture
         .repository
-        .search_files_paginated(None, None, 1, 100)
+        .search_files_paginated(None, None, &SearchFilters::default(), 1, 100)
         .unwrap();
     assert_eq!(page_1.len(), 100);
 
     // Test last page
     let page_2 = fixture
         .repository
-        .search_files_paginated(None, None, 2, 100)
+        .search_files_paginated(None, None, &SearchFilters::default(), 2, 100)
         .unwrap();
     assert_eq!(page_2.len(), 50);
 
     // Test beyond last page
     let page_3 = fixture
         .repository
-        .search_files_paginated(None, None, 3, 100)
+        .search_files_paginated(None, None, &SearchFilters::default(), 3, 100)
         .unwrap();
     assert_eq!(page_3.len(), 0);
 
-    let count = fixture.repository.count_search_results(None, None).unwrap();
+    let count = fixture
+        .repository
+        .count_search_results(None, None, &SearchFilters::default())
+        .unwrap();
 
     assert_eq!(
         count,
@@ -433,7 +1023,7 @@ fn test_search_result_count_accuracy() {
     let search_query = ".pdf";
     let search_results = fixture
         .repository
-        .search_files_paginated(None, Some(search_query), 0, 100)
+        .search_files_paginated(None, Some(search_query), &SearchFilters::default(), 0, 100)
         .unwrap();
 
     // Should find exactly 2 PDF files
@@ -448,9 +1038,13 @@ fn test_search_result_count_accuracy() {
 fn test_language_management_workflow() {
     let fixture = TestFixture::new();
 
-    // Test default language
-    let default_lang = fixture.repository.get_language().unwrap();
-    assert_eq!(default_lang, Language::English);
+    // Test default language is detected once, then stored
+    let detected_lang = fixture
+        .repository
+        .get_language_or_detect(|| Language::German)
+        .unwrap();
+    assert_eq!(detected_lang, Language::German);
+    assert_eq!(fixture.repository.get_language().unwrap(), Language::German);
 
     // Test language change
     let french = Language::French;
@@ -462,13 +1056,114 @@ fn test_language_management_workflow() {
     let current_lang = fixture.repository.get_language().unwrap();
     assert_eq!(current_lang, Language::French);
 
+    // A stored language always wins over detection
+    let stored_lang = fixture
+        .repository
+        .get_language_or_detect(|| Language::Spanish)
+        .unwrap();
+    assert_eq!(stored_lang, Language::French);
+
+    // English chosen explicitly is kept as well
+    fixture.repository.set_language(&Language::English).unwrap();
+    let english_lang = fixture
+        .repository
+        .get_language_or_detect(|| Language::Spanish)
+        .unwrap();
+    assert_eq!(english_lang, Language::English);
+
     // Test translation loading
     let translations = load_translations(&current_lang).unwrap();
     assert!(!translations.is_empty());
 
-    // Test language toggle
-    let toggled = current_lang.toggle();
-    assert_eq!(toggled, Language::English);
+    // Test language codes round trip
+    for language in Language::ALL {
+        assert_eq!(Language::new(language.code()), language);
+    }
+    assert_eq!(Language::new("DE"), Language::German);
+    assert_eq!(Language::new("unknown"), Language::English);
+}
+
+#[test]
+fn test_language_from_locale() {
+    assert_eq!(Language::from_locale("en-US"), Language::English);
+    assert_eq!(Language::from_locale("fr-FR"), Language::French);
+    assert_eq!(Language::from_locale("fr_CA.UTF-8"), Language::French);
+    assert_eq!(Language::from_locale("de-AT"), Language::German);
+    assert_eq!(Language::from_locale("es"), Language::Spanish);
+    assert_eq!(Language::from_locale("ES-419"), Language::Spanish);
+
+    // Unsupported or missing locales fall back to English
+    assert_eq!(Language::from_locale("ja-JP"), Language::English);
+    assert_eq!(Language::from_locale("C"), Language::English);
+    assert_eq!(Language::from_locale(""), Language::English);
+}
+
+#[test]
+fn test_translation_files_have_every_english_key() {
+    let english = load_translations(&Language::English).unwrap();
+
+    for language in Language::ALL {
+        let translations = load_translations(&language).unwrap();
+        let missing: Vec<_> = english
+            .keys()
+            .filter(|key| !translations.contains_key(*key))
+            .collect();
+        assert!(missing.is_empty(), "{language:?} is missing {missing:?}");
+    }
+}
+
+#[test]
+fn test_external_translations_override_embedded_ones() {
+    let temp_dir = TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("de.json"),
+        r#"{"search_button": "Finden", "custom_key": "Eigener Text"}"#,
+    )
+    .unwrap();
+
+    let embedded = load_translations_from(&Language::German, None).unwrap();
+    let translations = load_translations_from(&Language::German, Some(temp_dir.path())).unwrap();
+
+    // Overridden and added keys come from the external file
+    assert_eq!(translations["search_button"], "Finden");
+    assert_eq!(translations["custom_key"], "Eigener Text");
+
+    // Every other key falls back to the embedded translation
+    assert_eq!(translations["read_page"], embedded["read_page"]);
+    assert_eq!(translations.len(), embedded.len() + 1);
+
+    // Languages without an external file are unaffected
+    let french = load_translations_from(&Language::French, Some(temp_dir.path())).unwrap();
+    assert_eq!(
+        french,
+        load_translations_from(&Language::French, None).unwrap()
+    );
+}
+
+#[test]
+fn test_malformed_external_translations_are_reported() {
+    let temp_dir = TempDir::new().unwrap();
+    std::fs::write(temp_dir.path().join("es.json"), "{ not json").unwrap();
+
+    assert!(load_translations_from(&Language::Spanish, Some(temp_dir.path())).is_err());
+}
+
+#[test]
+fn test_auto_update_setting() {
+    let fixture = TestFixture::new();
+    fixture.repository.set_language(&Language::French).unwrap();
+
+    // Updates are checked on launch unless the user opted out
+    assert!(fixture.repository.get_auto_update().unwrap());
+
+    fixture.repository.set_auto_update(false).unwrap();
+    assert!(!fixture.repository.get_auto_update().unwrap());
+
+    fixture.repository.set_auto_update(true).unwrap();
+    assert!(fixture.repository.get_auto_update().unwrap());
+
+    // The language setting is stored independently
+    assert_eq!(fixture.repository.get_language().unwrap(), Language::French);
 }
 
 #[test]
@@ -495,7 +1190,7 @@ fn test_multiple_categories_and_drives() {
     // Verify all files are indexed
     let all_files = fixture
         .repository
-        .search_files_paginated(None, None, 0, 20)
+        .search_files_paginated(None, None, &SearchFilters::default(), 0, 20)
         .unwrap();
     assert_eq!(all_files.len(), 12); // 4 files × 3 locations
 
@@ -518,28 +1213,28 @@ fn test_edge_cases_and_error_handling() {
     // Test empty search
     let empty_result = fixture
         .repository
-        .search_files_paginated(None, Some(""), 0, 10)
+        .search_files_paginated(None, Some(""), &SearchFilters::default(), 0, 10)
         .unwrap();
     assert_eq!(empty_result.len(), 0);
 
     // Test search with no matches
     let no_matches = fixture
         .repository
-        .search_files_paginated(None, Some("nonexistent"), 0, 10)
+        .search_files_paginated(None, Some("nonexistent"), &SearchFilters::default(), 0, 10)
         .unwrap();
     assert_eq!(no_matches.len(), 0);
 
     // Test pagination with no data
     let no_data = fixture
         .repository
-        .search_files_paginated(None, None, 5, 10)
+        .search_files_paginated(None, None, &SearchFilters::default(), 5, 10)
         .unwrap();
     assert_eq!(no_data.len(), 0);
     assert_eq!(no_data.len(), 0);
 
     // Test remove duplicates with no data
     let remove_empty = fixture.repository.remove_duplicates("NonExistent", "Drive");
-    assert!(remove_empty.is_ok());
+    assert_eq!(remove_empty.unwrap(), 0);
 
     // Test indexing empty file list
     let empty_index = fixture.repository.save("Empty", "Drive", 0, &[]);
@@ -549,7 +1244,7 @@ fn test_edge_cases_and_error_handling() {
     // Test selected drive edge cases
     let empty_drive_name = fixture
         .repository
-        .search_files_paginated(Some(""), None, 0, 10)
+        .search_files_paginated(Some(""), None, &SearchFilters::default(), 0, 10)
         .unwrap();
     assert_eq!(empty_drive_name.len(), 0);
 }
@@ -570,6 +1265,8 @@ fn test_search_performance_with_large_dataset() {
                 i
             ),
             size_bytes: i,
+            modified_time: None,
+            ownership: None,
         });
     }
 
@@ -583,7 +1280,7 @@ fn test_search_performance_with_large_dataset() {
     // Test search performance
     let search_result = fixture
         .repository
-        .search_files_paginated(None, Some("category_5"), 0, 100)
+        .search_files_paginated(None, Some("category_5"), &SearchFilters::default(), 0, 100)
         .unwrap();
 
     let elapsed = start.elapsed();
@@ -596,7 +1293,7 @@ fn test_search_performance_with_large_dataset() {
     let start = Instant::now();
     let page_result = fixture
         .repository
-        .search_files_paginated(None, None, 50, 100)
+        .search_files_paginated(None, None, &SearchFilters::default(), 50, 100)
         .unwrap();
     let elapsed = start.elapsed();
 
@@ -617,17 +1314,115 @@ fn test_delete_entire_drive() {
 
     let result = fixture
         .repository
-        .search_files_paginated(Some("Laptop"), None, 0, 10)
+        .search_files_paginated(Some("Laptop"), None, &SearchFilters::default(), 0, 10)
         .unwrap();
     assert!(result.is_empty());
 
     let count = fixture
         .repository
-        .count_search_results(Some("Laptop"), None)
+        .count_search_results(Some("Laptop"), None, &SearchFilters::default())
         .unwrap();
     assert_eq!(count, 0);
 }
 
+#[test]
+fn test_drive_exists() {
+    let fixture = TestFixture::new();
+    let files = TestFixture::create_test_files();
+    fixture
+        .repository
+        .save("Work", "Laptop", 1024, &files)
+        .unwrap();
+
+    assert!(fixture.repository.drive_exists("Laptop").unwrap());
+    assert!(!fixture.repository.drive_exists("Desktop").unwrap());
+    assert!(!fixture.repository.drive_exists("").unwrap());
+
+    // An existing drive without matches is still reported as indexed
+    assert_eq!(
+        fixture
+            .repository
+            .count_search_results(
+                Some("Laptop"),
+                Some("no-such-file"),
+                &SearchFilters::default()
+            )
+            .unwrap(),
+        0
+    );
+    assert!(fixture.repository.drive_exists("Laptop").unwrap());
+
+    fixture.repository.delete("Laptop", None).unwrap();
+    assert!(!fixture.repository.drive_exists("Laptop").unwrap());
+}
+
+#[test]
+fn test_drive_exists_in_category() {
+    let fixture = TestFixture::new();
+    let files = TestFixture::create_test_files();
+    fixture
+        .repository
+        .save("Work", "Laptop", 1024, &files)
+        .unwrap();
+    fixture
+        .repository
+        .save("Personal", "Desktop", 1024, &files)
+        .unwrap();
+
+    assert!(
+        fixture
+            .repository
+            .drive_exists_in_category("Laptop", "Work")
+            .unwrap()
+    );
+    // The same drive name under another category is a different entry
+    assert!(
+        !fixture
+            .repository
+            .drive_exists_in_category("Laptop", "Personal")
+            .unwrap()
+    );
+    assert!(
+        !fixture
+            .repository
+            .drive_exists_in_category("Tablet", "Work")
+            .unwrap()
+    );
+
+    fixture.repository.delete("Laptop", Some("Work")).unwrap();
+    assert!(
+        !fixture
+            .repository
+            .drive_exists_in_category("Laptop", "Work")
+            .unwrap()
+    );
+}
+
+#[test]
+fn test_count_for_category_and_drive() {
+    let fixture = TestFixture::new();
+    let files = TestFixture::create_test_files();
+    fixture
+        .repository
+        .save("Work", "Laptop", 1024, &files)
+        .unwrap();
+    fixture
+        .repository
+        .save("Personal", "Laptop", 1024, &files[..1])
+        .unwrap();
+
+    assert_eq!(
+        fixture.repository.count_for("Work", "Laptop").unwrap(),
+        files.len() as u64
+    );
+    assert_eq!(
+        fixture.repository.count_for("Personal", "Laptop").unwrap(),
+        1
+    );
+    assert_eq!(fixture.repository.count_for("Work", "Desktop").unwrap(), 0);
+    assert_eq!(fixture.repository.count_for("Music", "Laptop").unwrap(), 0);
+}
+
 #[test]
 fn test_delete_specific_category() {
     let fixture = TestFixture::new();
@@ -647,13 +1442,13 @@ fn test_delete_specific_category() {
 
     let result = fixture
         .repository
-        .search_files_paginated(Some("Laptop"), None, 0, 10)
+        .search_files_paginated(Some("Laptop"), None, &SearchFilters::default(), 0, 10)
         .unwrap();
     assert!(result.iter().all(|f| f.category_name != "Work"));
     assert!(result.iter().any(|f| f.category_name == "Personal"));
     let count = fixture
         .repository
-        .count_search_results(Some("Laptop"), None)
+        .count_search_results(Some("Laptop"), None, &SearchFilters::default())
         .unwrap();
     assert_eq!(count, files.len() as u64); // only Personal category files remain
 }
@@ -695,7 +1490,7 @@ fn test_delete_entire_drive_removes_stale_categories() {
     // Drive files should be gone
     let result = fixture
         .repository
-        .search_files_paginated(Some("Laptop"), None, 0, 10)
+        .search_files_paginated(Some("Laptop"), None, &SearchFilters::default(), 0, 10)
         .unwrap();
     assert!(result.is_empty());
 
@@ -730,7 +1525,7 @@ fn test_delete_specific_category_removes_stale_category() {
     // Work files should be gone, Personal files remain
     let remaining_files = fixture
         .repository
-        .search_files_paginated(Some("Laptop"), None, 0, 10)
+        .search_files_paginated(Some("Laptop"), None, &SearchFilters::default(), 0, 10)
         .unwrap();
     assert!(remaining_files.iter().all(|f| f.category_name != "Work"));
     assert!(
@@ -769,7 +1564,7 @@ fn test_category_not_deleted_if_used_by_another_drive() {
     // Verify Laptop has no files
     let laptop_files = fixture
         .repository
-        .search_files_paginated(Some("Laptop"), None, 0, 10)
+        .search_files_paginated(Some("Laptop"), None, &SearchFilters::default(), 0, 10)
         .unwrap();
     assert!(laptop_files.is_empty());
 
@@ -787,3 +1582,4388 @@ fn test_category_not_deleted_if_used_by_another_drive() {
         .unwrap();
     assert!(!laptop_categories.contains(&"Work".to_string()));
 }
+
+#[test]
+fn test_drive_statistics() {
+    let fixture = TestFixture::new();
+    let files = TestFixture::create_test_files();
+
+    fixture
+        .repository
+        .save("Work", "Laptop", 1024, &files)
+        .unwrap();
+    fixture
+        .repository
+        .save("Personal", "Laptop", 1024, &files[..2])
+        .unwrap();
+    fixture
+        .repository
+        .save("Work", "Desktop", 2048, &files[..1])
+        .unwrap();
+
+    let stats = fixture.repository.drive_statistics().unwrap();
+    assert_eq!(stats.len(), 3);
+
+    // Sorted by drive name, then category name
+    assert_eq!(stats[0].drive_name, "Desktop");
+    assert_eq!(stats[0].category_name, "Work");
+    assert_eq!(stats[0].file_count, 1);
+    assert_eq!(stats[0].total_size, 1024);
+    assert_eq!(stats[0].available_space, 2048);
+
+    assert_eq!(stats[1].drive_name, "Laptop");
+    assert_eq!(stats[1].category_name, "Personal");
+    assert_eq!(stats[1].file_count, 2);
+    assert_eq!(stats[1].total_size, 1024 + 2048);
+
+    assert_eq!(stats[2].drive_name, "Laptop");
+    assert_eq!(stats[2].category_name, "Work");
+    assert_eq!(stats[2].file_count, 4);
+    assert_eq!(stats[2].total_size, 1024 + 2048 + 512 + 768);
+    assert!(stats[2].last_indexed <= Local::now().naive_local());
+}
+
+#[test]
+fn test_category_overview() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let db_url = format!("sqlite://{}", temp_dir.path().join("test.db").display());
+    // Merges can leave a category without drives
+    let pool = SqliteRepositoryPool::new(&db_url).unwrap();
+    diesel::RunQueryDsl::execute(
+        diesel::sql_query(
+            "INSERT INTO file_categories (id, name) VALUES (randomblob(16), 'Archive')",
+        ),
+        &mut pool.get_connection().unwrap(),
+    )
+    .unwrap();
+    let repository = ListerRepository::new(pool, Arc::new(SystemClock));
+    let files = TestFixture::create_test_files();
+
+    repository.save("Work", "Laptop", 1024, &files).unwrap();
+    repository
+        .save("Work", "Desktop", 2048, &files[..1])
+        .unwrap();
+    repository
+        .save("Personal", "Laptop", 1024, &files[..2])
+        .unwrap();
+    repository.save("Personal", "Phone", 512, &[]).unwrap();
+
+    let overview = repository.category_overview().unwrap();
+    assert_eq!(
+        overview,
+        [
+            CategoryOverview {
+                name: "Archive".to_string(),
+                drive_count: 0,
+                file_count: 0,
+                total_size: 0,
+            },
+            CategoryOverview {
+                name: "Personal".to_string(),
+                drive_count: 2,
+                file_count: 2,
+                total_size: 1024 + 2048,
+            },
+            CategoryOverview {
+                name: "Work".to_string(),
+                drive_count: 2,
+                file_count: 5,
+                total_size: 2 * 1024 + 2048 + 512 + 768,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_drive_statistics_includes_empty_drives() {
+    let fixture = TestFixture::new();
+
+    fixture.repository.save("Empty", "Drive", 512, &[]).unwrap();
+
+    let stats = fixture.repository.drive_statistics().unwrap();
+    assert_eq!(stats.len(), 1);
+    assert_eq!(stats[0].file_count, 0);
+    assert_eq!(stats[0].total_size, 0);
+    assert_eq!(stats[0].available_space, 512);
+}
+
+#[test]
+fn test_drive_statistics_fullness() {
+    let fixture = TestFixture::new();
+    let files = TestFixture::create_test_files();
+    let capacity = DriveCapacity {
+        available_space: 8192,
+        total_space: 16384,
+    };
+
+    fixture
+        .repository
+        .reindex("Work", "Laptop", capacity, None, vec![Ok(files)], |_| {})
+        .unwrap();
+    fixture
+        .repository
+        .save("Work", "Desktop", 2048, &[])
+        .unwrap();
+
+    let stats = fixture.repository.drive_statistics().unwrap();
+    // Drives saved without a known total space have no gauge
+    assert_eq!(stats[0].drive_name, "Desktop");
+    assert_eq!(stats[0].total_space, 0);
+    assert_eq!(stats[0].capacity().fullness(stats[0].total_size), None);
+
+    assert_eq!(stats[1].drive_name, "Laptop");
+    assert_eq!(stats[1].capacity(), capacity);
+    assert_eq!(
+        stats[1].capacity().fullness(stats[1].total_size),
+        Some(DriveFullness {
+            used_fraction: 0.5,
+            indexed_fraction: 4352.0 / 16384.0,
+        })
+    );
+
+    // Re-indexing records the latest total space of the drive
+    let capacity = DriveCapacity {
+        available_space: 8192,
+        total_space: 32768,
+    };
+    fixture
+        .repository
+        .reindex("Work", "Laptop", capacity, None, vec![Ok(vec![])], |_| {})
+        .unwrap();
+    let stats = fixture.repository.drive_statistics().unwrap();
+    assert_eq!(stats[1].total_space, 32768);
+}
+
+#[test]
+fn test_drive_capacity_fullness() {
+    let capacity = DriveCapacity {
+        available_space: 768,
+        total_space: 1024,
+    };
+    assert_eq!(capacity.used_space(), Some(256));
+    assert_eq!(
+        capacity.fullness(128),
+        Some(DriveFullness {
+            used_fraction: 0.25,
+            indexed_fraction: 0.125,
+        })
+    );
+
+    // Indexed files deleted since never count above the used space
+    assert_eq!(
+        capacity.fullness(4096),
+        Some(DriveFullness {
+            used_fraction: 0.25,
+            indexed_fraction: 0.25,
+        })
+    );
+
+    // An available space above the total, as reported by some network shares, means empty
+    let capacity = DriveCapacity {
+        available_space: 2048,
+        total_space: 1024,
+    };
+    assert_eq!(capacity.used_space(), Some(0));
+
+    let unknown = DriveCapacity::available_only(1024);
+    assert_eq!(unknown.used_space(), None);
+    assert_eq!(unknown.fullness(128), None);
+}
+
+#[test]
+fn test_total_pages_boundaries() {
+    // An empty dataset has no pages to navigate to
+    assert_eq!(total_pages(0, 100), 0);
+
+    assert_eq!(total_pages(1, 100), 1);
+    assert_eq!(total_pages(99, 100), 1);
+    assert_eq!(total_pages(100, 100), 1);
+    assert_eq!(total_pages(101, 100), 2);
+    assert_eq!(total_pages(250, 100), 3);
+
+    // A zero page size never divides by zero
+    assert_eq!(total_pages(10, 0), 0);
+}
+
+#[test]
+fn test_page_size_change_mid_session() {
+    let mut cache = Cache::new(1, 1000);
+    cache.store(
+        None,
+        "file".to_string(),
+        SearchFilters::default(),
+        cached_files(260),
+    );
+
+    // The same results spread over as many pages as the picked size needs
+    let pages: Vec<usize> = PAGE_SIZES
+        .iter()
+        .map(|&size| total_pages(260, size))
+        .collect();
+    assert_eq!(pages, [11, 6, 3, 2, 1]);
+
+    // Pages of the cached dataset follow the new size, the last one holding the rest
+    let mut page = |index, size| {
+        cache
+            .get_page(None, "file", &SearchFilters::default(), "", index, size)
+            .map(|files| files.len())
+    };
+    assert_eq!(page(2, 100), Some(60));
+    assert_eq!(page(0, 250), Some(250));
+    assert_eq!(page(1, 250), Some(10));
+    assert_eq!(page(2, 250), None);
+    assert_eq!(page(10, 25), Some(10));
+}
+
+#[test]
+fn test_items_per_page_setting() {
+    let fixture = TestFixture::new();
+    assert_eq!(
+        fixture.repository.get_items_per_page().unwrap(),
+        DEFAULT_PAGE_SIZE
+    );
+
+    fixture.repository.set_items_per_page(250).unwrap();
+    assert_eq!(fixture.repository.get_items_per_page().unwrap(), 250);
+
+    // A size that is not offered falls back to the default
+    fixture.repository.set_items_per_page(7).unwrap();
+    assert_eq!(
+        fixture.repository.get_items_per_page().unwrap(),
+        DEFAULT_PAGE_SIZE
+    );
+}
+
+#[test]
+fn test_row_density() {
+    let layout = |density: RowDensity| {
+        (
+            density.row_padding(),
+            density.text_size(),
+            density.row_height(),
+        )
+    };
+    assert_eq!(layout(RowDensity::Compact), (1, 13.0, 20.0));
+    assert_eq!(layout(RowDensity::Comfortable), (3, 16.0, 28.0));
+    assert_eq!(layout(RowDensity::Spacious), (6, 19.0, 38.0));
+
+    let fixture = TestFixture::new();
+    assert_eq!(
+        fixture.repository.get_row_density().unwrap(),
+        RowDensity::Comfortable
+    );
+    fixture
+        .repository
+        .set_row_density(RowDensity::Compact)
+        .unwrap();
+    assert_eq!(
+        fixture.repository.get_row_density().unwrap(),
+        RowDensity::Compact
+    );
+    assert_eq!(RowDensity::new("unknown"), RowDensity::Comfortable);
+}
+
+#[test]
+fn test_result_columns() {
+    let mut columns = ResultColumns::default();
+    assert_eq!(columns.shown().collect::<Vec<_>>(), ResultColumn::ALL);
+    assert_eq!(columns.to_setting(), "");
+
+    columns.set_shown(ResultColumn::Size, false);
+    columns.set_shown(ResultColumn::Category, false);
+    columns.set_shown(ResultColumn::Category, false);
+    assert_eq!(columns.to_setting(), "category,size");
+    assert_eq!(ResultColumns::from_setting("category,size"), columns);
+    assert_eq!(
+        ResultColumns::from_setting("size, unknown,category"),
+        columns
+    );
+
+    columns.set_shown(ResultColumn::Size, true);
+    assert!(columns.is_shown(ResultColumn::Size));
+    assert_eq!(columns.to_setting(), "category");
+
+    // Hiding everything still leaves the filename
+    for column in ResultColumn::ALL {
+        columns.set_shown(column, false);
+    }
+    assert_eq!(
+        columns.shown().collect::<Vec<_>>(),
+        vec![ResultColumn::Filename]
+    );
+    assert_eq!(
+        ResultColumns::from_setting("filename")
+            .shown()
+            .collect::<Vec<_>>(),
+        ResultColumn::ALL
+    );
+
+    let fixture = TestFixture::new();
+    assert_eq!(
+        fixture.repository.get_result_columns().unwrap(),
+        ResultColumns::default()
+    );
+    fixture.repository.set_result_columns(&columns).unwrap();
+    assert_eq!(fixture.repository.get_result_columns().unwrap(), columns);
+}
+
+#[test]
+fn test_parse_page_input() {
+    // Typed pages are one-based, navigation indices zero-based
+    assert_eq!(parse_page_input("1", 3), PageInput::Page(0));
+    assert_eq!(parse_page_input(" 3 ", 3), PageInput::Page(2));
+
+    // Empty input is a no-op rather than an error
+    assert_eq!(parse_page_input("", 3), PageInput::Empty);
+    assert_eq!(parse_page_input("   ", 3), PageInput::Empty);
+
+    // Out of range
+    assert_eq!(parse_page_input("0", 3), PageInput::Invalid);
+    assert_eq!(parse_page_input("4", 3), PageInput::Invalid);
+    assert_eq!(parse_page_input("1", 0), PageInput::Invalid);
+
+    // Not a page number
+    assert_eq!(parse_page_input("abc", 3), PageInput::Invalid);
+    assert_eq!(parse_page_input("-1", 3), PageInput::Invalid);
+    assert_eq!(parse_page_input("2.5", 3), PageInput::Invalid);
+}
+
+#[test]
+fn test_clamp_page_index() {
+    // Page 50 of 100 results per page stays while it exists
+    assert_eq!(clamp_page_index(49, 5_000, 100), 49);
+    assert_eq!(clamp_page_index(49, 4_901, 100), 49);
+
+    // Removing results snaps back to the last page left
+    assert_eq!(clamp_page_index(49, 4_900, 100), 48);
+    assert_eq!(clamp_page_index(49, 250, 100), 2);
+    assert_eq!(clamp_page_index(49, 1, 100), 0);
+
+    // Nothing left shows the first page
+    assert_eq!(clamp_page_index(49, 0, 100), 0);
+    assert_eq!(clamp_page_index(0, 0, 100), 0);
+}
+
+#[test]
+fn test_is_newer_version() {
+    assert!(is_newer_version("1.9.0", "1.10.0"));
+    assert!(is_newer_version("1.2.3", "2.0.0"));
+    assert!(!is_newer_version("1.10.0", "1.9.0"));
+    assert!(!is_newer_version("1.2.3", "1.2.3"));
+
+    // Unparseable release tags are never offered as updates
+    assert!(!is_newer_version("1.2.3", "nightly"));
+}
+
+#[test]
+fn test_verify_checksum() {
+    // Known SHA-256 vectors
+    verify_checksum(
+        b"",
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+    )
+    .unwrap();
+    verify_checksum(
+        b"abc",
+        "BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD  lister-x86_64-unknown-linux-gnu\n",
+    )
+    .unwrap();
+
+    assert!(
+        verify_checksum(
+            b"abd",
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        )
+        .is_err()
+    );
+    assert!(verify_checksum(b"abc", "").is_err());
+}
+
+#[test]
+fn test_download_progress_fraction() {
+    let progress = |downloaded, total| DownloadProgress { downloaded, total };
+    assert_eq!(progress(0, Some(200)).fraction(), Some(0.0));
+    assert_eq!(progress(50, Some(200)).fraction(), Some(0.25));
+    assert_eq!(progress(200, Some(200)).fraction(), Some(1.0));
+
+    // A server announcing less than it sends never goes past complete
+    assert_eq!(progress(300, Some(200)).fraction(), Some(1.0));
+
+    // Without a known size there is no percentage to show
+    assert_eq!(progress(50, None).fraction(), None);
+    assert_eq!(progress(0, Some(0)).fraction(), None);
+}
+
+#[test]
+fn test_unconfigured_update_source_skips_lookup() {
+    let no_owner = UpdateSource {
+        repo_owner: "",
+        ..UPDATE_SOURCE
+    };
+    let no_name = UpdateSource {
+        repo_name: "",
+        ..UPDATE_SOURCE
+    };
+    for source in [no_owner, no_name] {
+        assert!(!source.is_configured());
+        let updater = GitHubUpdater::new(source);
+        assert!(!updater.is_enabled());
+        // A lookup would fail offline or find no release for an empty repo, never answer `None`
+        assert!(matches!(updater.check(), Ok(None)));
+        assert!(matches!(updater.check_compatible(), Ok(None)));
+    }
+}
+
+#[test]
+fn test_quiet_update_lookup_survives_network_errors() {
+    // Startup is not held back while the lookup waits on the network, then gives up
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let (update, startup_finished, lookup_finished) = runtime.block_on(async {
+        let lookup = run_blocking(|| {
+            let update = look_up_quietly(|| {
+                thread::sleep(Duration::from_millis(300));
+                Err(UpdateError::Io(std::io::ErrorKind::TimedOut.into()))
+            });
+            (update, Instant::now())
+        });
+        let startup = async { Instant::now() };
+
+        let ((update, lookup_finished), startup_finished) = tokio::join!(lookup, startup);
+        (update, startup_finished, lookup_finished)
+    });
+
+    assert!(update.is_none());
+    assert!(startup_finished < lookup_finished);
+
+    // A successful lookup is passed through
+    let updater = FakeUpdater::available("9.9.9", "");
+    let update = look_up_quietly(|| updater.check());
+    assert_eq!(
+        update.map(|update| update.version).as_deref(),
+        Some("9.9.9")
+    );
+    assert!(look_up_quietly(|| FakeUpdater::offline().check()).is_none());
+}
+
+#[test]
+fn test_update_available() {
+    let current = env!("CARGO_PKG_VERSION");
+    let compatible = "1.999.0";
+    let breaking = "999.0.0";
+    assert!(is_compatible_version(current, compatible));
+    assert!(!is_compatible_version(current, breaking));
+
+    let updater = FakeUpdater::available(compatible, "Faster scans");
+    let update = updater.check().unwrap().unwrap();
+    assert_eq!(update.version, compatible);
+    assert_eq!(update.release_notes, "Faster scans");
+    assert_eq!(
+        updater.check_compatible().unwrap().unwrap().version,
+        compatible
+    );
+
+    let mut progress = Vec::new();
+    updater
+        .apply(&update, &mut |download| progress.push(download))
+        .unwrap();
+    assert_eq!(updater.applied(), [compatible]);
+    assert_eq!(
+        progress.last().and_then(DownloadProgress::fraction),
+        Some(1.0)
+    );
+
+    // A breaking release is offered on a manual check, never installed on startup
+    let updater = FakeUpdater::available(breaking, "");
+    assert!(updater.check().unwrap().is_some());
+    assert!(updater.check_compatible().unwrap().is_none());
+}
+
+#[test]
+fn test_update_up_to_date() {
+    let updater = FakeUpdater::up_to_date();
+    assert!(updater.check().unwrap().is_none());
+    assert!(updater.check_compatible().unwrap().is_none());
+
+    // The running version, or an older one, is not an update
+    for version in [env!("CARGO_PKG_VERSION"), "0.0.1"] {
+        let updater = FakeUpdater::available(version, "");
+        assert!(updater.check().unwrap().is_none());
+        assert!(updater.check_compatible().unwrap().is_none());
+    }
+}
+
+#[test]
+fn test_update_error() {
+    let updater = FakeUpdater::offline();
+    let error = updater.check().unwrap_err();
+    assert!(matches!(error, UpdateError::Io(_)));
+    assert!(updater.check_compatible().is_err());
+
+    let update = FakeUpdater::available("1.999.0", "")
+        .check()
+        .unwrap()
+        .unwrap();
+    let mut progress = Vec::new();
+    assert!(
+        updater
+            .apply(&update, &mut |download| progress.push(download))
+            .is_err()
+    );
+    assert!(progress.is_empty());
+    assert!(updater.applied().is_empty());
+}
+
+fn fixed_disk(name: &str, mount_point: &str, available_space: u64) -> MountedDisk {
+    MountedDisk {
+        name: name.to_string(),
+        mount_point: PathBuf::from(mount_point),
+        available_space,
+        total_space: available_space * 10,
+        is_removable: false,
+    }
+}
+
+#[test]
+fn test_directory_data_picks_deepest_mount_point() {
+    let disks = FixedDisks(vec![
+        fixed_disk("root", "/", 10),
+        fixed_disk("home", "/home", 20),
+    ]);
+    let data = directory_data(&disks, Path::new("/home/user/photos")).unwrap();
+    assert_eq!(data.drive_name, "home");
+    assert_eq!(
+        data.drive_capacity(),
+        DriveCapacity {
+            available_space: 20,
+            total_space: 200,
+        }
+    );
+    assert_eq!(data.directory, Path::new("/home/user/photos"));
+}
+
+#[test]
+fn test_directory_data_without_matching_disk() {
+    let directory = Path::new("/mnt/share/photos");
+    let disks = FixedDisks(vec![fixed_disk("data", "/data", 10)]);
+    let error = directory_data(&disks, directory).unwrap_err();
+    assert!(matches!(&error, InfrastructureError::DiskNotFound(path) if path == directory));
+
+    // The fallback still lets the directory be indexed, under a name derived from the path
+    let fallback = DirectoryData::without_disk(directory);
+    assert_eq!(fallback.drive_name, "mnt");
+    assert_eq!(fallback.drive_available_space, 0);
+    assert_eq!(fallback.drive_total_space, 0);
+    assert_eq!(fallback.last_folder_name(), "photos");
+}
+
+#[test]
+fn test_directory_data_on_network_share() {
+    // Shares reached by their network path are on none of the local disks
+    let directory = Path::new("//nas/photos/2024");
+    let disks = FixedDisks(vec![fixed_disk("data", "/data", 10)]);
+    let error = directory_data(&disks, directory).unwrap_err();
+    assert!(matches!(&error, InfrastructureError::DiskNotFound(path) if path == directory));
+
+    let fallback = DirectoryData::without_disk(directory);
+    assert_eq!(fallback.drive_name, "nas");
+    assert_eq!(fallback.drive_capacity(), DriveCapacity::default());
+    assert_eq!(fallback.last_folder_name(), "2024");
+
+    // A share mounted locally is found on its mount point instead
+    let mounted = FixedDisks(vec![
+        fixed_disk("root", "/", 10),
+        fixed_disk("nas", "/mnt/nas", 30),
+    ]);
+    let data = directory_data(&mounted, Path::new("/mnt/nas/photos")).unwrap();
+    assert_eq!(data.drive_name, "nas");
+    assert_eq!(data.drive_available_space, 30);
+}
+
+#[test]
+fn test_removable_disks() {
+    let disk = |name: &str, mount_point: &str, total_space, is_removable| MountedDisk {
+        name: name.to_string(),
+        mount_point: PathBuf::from(mount_point),
+        available_space: total_space / 2,
+        total_space,
+        is_removable,
+    };
+    let disks = FixedDisks(vec![
+        disk("system", "/", 500, false),
+        disk("USB", "/media/user/USB", 64, true),
+        disk("", "/media/user/card", 32, true),
+        disk("reader", "/media/user/reader", 0, true),
+        disk("Backup", "/media/user/Backup", 1000, true),
+    ]);
+
+    // Fixed and empty disks are not offered, unlabelled ones show their mount point
+    let removable = removable_disks(&disks);
+    let labels: Vec<String> = removable.iter().map(MountedDisk::label).collect();
+    assert_eq!(labels, ["/media/user/card", "Backup", "USB"]);
+
+    // A picked disk is indexed whole, under its label
+    let data = removable[1].directory_data();
+    assert_eq!(data.directory, Path::new("/media/user/Backup"));
+    assert_eq!(data.drive_name, "Backup");
+    assert_eq!(data.last_folder_name(), "Backup");
+    assert_eq!(data.drive_available_space, 500);
+    assert_eq!(data.drive_total_space, 1000);
+
+    // Unplugging the disk mid-scan is detected from its mount point
+    assert!(ensure_mounted(&disks, Path::new("/media/user/USB")).is_ok());
+    let unplugged = FixedDisks(vec![disk("system", "/", 500, false)]);
+    let error = ensure_mounted(&unplugged, Path::new("/media/user/USB")).unwrap_err();
+    assert!(
+        matches!(error, InfrastructureError::DiskUnmounted(path) if path == Path::new("/media/user/USB"))
+    );
+}
+
+#[test]
+fn test_pick_dropped_directory() {
+    let temp_dir = TempDir::new().unwrap();
+    let folder = temp_dir.path().join("photos");
+    std::fs::create_dir(&folder).unwrap();
+    let file = temp_dir.path().join("notes.txt");
+    std::fs::write(&file, "notes").unwrap();
+    let disks = FixedDisks(vec![fixed_disk("root", "/", 10)]);
+
+    // A dropped folder fills the form as picking it in the dialog does
+    let PickedDirectory::Found(data) = pick_directory(&disks, &folder) else {
+        panic!("the folder should be found on its disk");
+    };
+    assert_eq!(data.directory, folder);
+    assert_eq!(data.drive_name, "root");
+    assert_eq!(data.last_folder_name(), "photos");
+
+    let PickedDirectory::WithoutDisk(fallback, error) =
+        pick_directory(&FixedDisks::default(), &folder)
+    else {
+        panic!("the folder should be kept without its disk");
+    };
+    assert_eq!(fallback.directory, folder);
+    assert!(matches!(error, InfrastructureError::DiskNotFound(path) if path == folder));
+
+    // Files and missing paths are not folders to index
+    for path in [file, temp_dir.path().join("missing")] {
+        assert!(matches!(
+            pick_directory(&disks, &path),
+            PickedDirectory::NotADirectory(rejected) if rejected == path
+        ));
+    }
+}
+
+#[test]
+fn test_total_space_shared_by_same_drives() {
+    let fixture = TestFixture::new();
+    let capacity = |total_space| DriveCapacity {
+        available_space: 1024,
+        total_space,
+    };
+
+    fixture
+        .repository
+        .reindex(
+            "Work",
+            "Backup",
+            capacity(4096),
+            None,
+            vec![Ok(vec![])],
+            |_| {},
+        )
+        .unwrap();
+    fixture
+        .repository
+        .reindex(
+            "Personal",
+            "Backup",
+            capacity(8192),
+            None,
+            vec![Ok(vec![])],
+            |_| {},
+        )
+        .unwrap();
+    fixture
+        .repository
+        .save("Work", "Laptop", 2048, &[])
+        .unwrap();
+
+    // The latest total space applies to every entry sharing the drive name
+    let total_spaces = |fixture: &TestFixture| {
+        fixture
+            .repository
+            .drive_statistics()
+            .unwrap()
+            .into_iter()
+            .map(|stats| (stats.category_name, stats.drive_name, stats.total_space))
+            .collect::<Vec<_>>()
+    };
+    let expected = vec![
+        ("Personal".to_string(), "Backup".to_string(), 8192),
+        ("Work".to_string(), "Backup".to_string(), 8192),
+        ("Work".to_string(), "Laptop".to_string(), 0),
+    ];
+    assert_eq!(total_spaces(&fixture), expected);
+
+    // Neither an unknown total space nor a manual available space erases the known one
+    fixture
+        .repository
+        .reindex(
+            "Work",
+            "Backup",
+            capacity(0),
+            None,
+            vec![Ok(vec![])],
+            |_| {},
+        )
+        .unwrap();
+    fixture
+        .repository
+        .set_drive_available_space("Backup", 512)
+        .unwrap();
+    assert_eq!(total_spaces(&fixture), expected);
+}
+
+#[test]
+fn test_set_drive_available_space() {
+    let fixture = TestFixture::new();
+    let files = TestFixture::create_test_files();
+
+    fixture
+        .repository
+        .save("Work", "Backup", 1024, &files)
+        .unwrap();
+    fixture
+        .repository
+        .save("Personal", "Backup", 1024, &files)
+        .unwrap();
+    fixture
+        .repository
+        .save("Work", "Laptop", 2048, &files)
+        .unwrap();
+
+    fixture
+        .repository
+        .set_drive_available_space("Backup", 500_000_000_000)
+        .unwrap();
+
+    // Every entry sharing the drive name is updated, other drives are untouched
+    let drives = fixture
+        .repository
+        .find_all_drives_available_space()
+        .unwrap();
+    assert_eq!(drives.len(), 2);
+    assert_eq!(drives[0].drive_name, "Backup");
+    assert_eq!(drives[0].available_space, 500_000_000_000);
+    assert_eq!(drives[1].drive_name, "Laptop");
+    assert_eq!(drives[1].available_space, 2048);
+
+    let backup_files = fixture
+        .repository
+        .search_files_paginated(Some("Backup"), None, &SearchFilters::default(), 0, 100)
+        .unwrap();
+    assert_eq!(backup_files.len(), files.len() * 2);
+    assert!(
+        backup_files
+            .iter()
+            .all(|file| file.drive_available_space == 500_000_000_000)
+    );
+}
+
+#[test]
+fn test_parse_size() {
+    assert_eq!(parse_size("1024"), Some(1024));
+    assert_eq!(parse_size("512 B"), Some(512));
+    assert_eq!(parse_size("500 GB"), Some(500_000_000_000));
+    assert_eq!(parse_size("1.5TB"), Some(1_500_000_000_000));
+    assert_eq!(parse_size(" 2 kb "), Some(2_000));
+    assert_eq!(parse_size("256 MiB"), Some(256 * 1024 * 1024));
+    assert_eq!(parse_size("1 GiB"), Some(1 << 30));
+
+    assert_eq!(parse_size(""), None);
+    assert_eq!(parse_size("GB"), None);
+    assert_eq!(parse_size("12 parsecs"), None);
+    assert_eq!(parse_size("-5 GB"), None);
+}
+
+#[test]
+fn test_blocking_queries_do_not_stall_the_executor() {
+    let fixture = TestFixture::new();
+    fixture
+        .repository
+        .save("Work", "Laptop", 1024, &TestFixture::create_test_files())
+        .unwrap();
+    let repository = Arc::new(fixture.repository);
+
+    // A single-threaded runtime: a query blocking the executor would delay every other task
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+
+    let (slow_finished, fast_finished) = runtime.block_on(async {
+        let slow_repository = repository.clone();
+        let slow = run_blocking(move || {
+            thread::sleep(Duration::from_millis(300));
+            let files = slow_repository
+                .search_files_paginated(None, None, &SearchFilters::default(), 0, 100)
+                .unwrap();
+            (files.len(), Instant::now())
+        });
+
+        let fast = async {
+            for _ in 0..5 {
+                let fast_repository = repository.clone();
+                let count = run_blocking(move || {
+                    fast_repository.count_search_results(None, None, &SearchFilters::default())
+                })
+                .await
+                .unwrap();
+                assert_eq!(count, 4);
+            }
+            Instant::now()
+        };
+
+        tokio::join!(slow, fast)
+    });
+
+    assert_eq!(slow_finished.0, 4);
+    assert!(fast_finished < slow_finished.1);
+}
+
+#[test]
+fn test_save_scanned_directory_in_batches() {
+    let fixture = TestFixture::new();
+    let scanned_dir = TempDir::new().unwrap();
+    std::fs::create_dir(scanned_dir.path().join("nested")).unwrap();
+    for index in 0..25 {
+        let parent = if index % 2 == 0 { "" } else { "nested" };
+        std::fs::write(
+            scanned_dir
+                .path()
+                .join(parent)
+                .join(format!("file_{index}.txt")),
+            "x".repeat(index),
+        )
+        .unwrap();
+    }
+
+    let batches: Vec<_> =
+        scan_directory_in_batches(scanned_dir.path(), 10, false, true, ErrorPolicy::FailFast)
+            .map(Result::unwrap)
+            .collect();
+    assert_eq!(
+        batches.iter().map(Vec::len).collect::<Vec<_>>(),
+        vec![10, 10, 5]
+    );
+
+    let mut progress = vec![];
+    let saved = fixture
+        .repository
+        .save_in_batches(
+            "Work",
+            "Laptop",
+            1024,
+            scan_directory_in_batches(scanned_dir.path(), 10, false, true, ErrorPolicy::FailFast)
+                .map(|batch| batch.map_err(|error| InfrastructureError::Scan(error.to_string()))),
+            |files_saved| progress.push(files_saved),
+        )
+        .unwrap();
+
+    // The returned count matches every inserted file
+    assert_eq!(saved, 25);
+    assert_eq!(progress, vec![10, 20, 25]);
+    assert_eq!(
+        fixture
+            .repository
+            .count_search_results(None, None, &SearchFilters::default())
+            .unwrap(),
+        25
+    );
+    assert_eq!(
+        scan_directory(scanned_dir.path(), false, true, ErrorPolicy::FailFast)
+            .unwrap()
+            .len(),
+        25
+    );
+}
+
+#[test]
+fn test_save_in_batches_rolls_back_on_error() {
+    let fixture = TestFixture::new();
+    let files = TestFixture::create_test_files();
+
+    let result = fixture.repository.save_in_batches(
+        "Work",
+        "Laptop",
+        1024,
+        vec![
+            Ok(files.clone()),
+            Err(InfrastructureError::Scan("unreadable file".to_string())),
+        ],
+        |_| {},
+    );
+
+    assert!(result.is_err());
+    assert_eq!(
+        fixture
+            .repository
+            .count_search_results(None, None, &SearchFilters::default())
+            .unwrap(),
+        0
+    );
+    assert!(fixture.repository.find_all_drives().unwrap().is_empty());
+}
+
+#[test]
+fn test_index_two_directories_into_one_drive() {
+    let fixture = TestFixture::new();
+    let roots = TempDir::new().unwrap();
+    let photos = roots.path().join("Photos");
+    let videos = roots.path().join("Videos");
+    std::fs::create_dir_all(photos.join("2024")).unwrap();
+    std::fs::create_dir_all(&videos).unwrap();
+    std::fs::write(photos.join("2024").join("beach.jpg"), "jpg").unwrap();
+    // Same relative path in both roots
+    std::fs::write(photos.join("notes.txt"), "a").unwrap();
+    std::fs::write(videos.join("notes.txt"), "bb").unwrap();
+    let directories = vec![photos.clone(), videos.clone()];
+
+    let saved = fixture
+        .repository
+        .reindex(
+            "Media",
+            "External",
+            DriveCapacity::available_only(1024),
+            common_root(&directories).as_deref(),
+            scan_directories_in_batches(&directories, 2, false, true, ErrorPolicy::FailFast)
+                .map(|batch| batch.map_err(|error| InfrastructureError::Scan(error.to_string()))),
+            |_| {},
+        )
+        .unwrap();
+    assert_eq!(saved, 3);
+
+    let mut paths: Vec<String> = fixture
+        .repository
+        .search_files_paginated(Some("External"), None, &SearchFilters::default(), 0, 10)
+        .unwrap()
+        .into_iter()
+        .map(|file| file.path.replace('\\', "/"))
+        .collect();
+    paths.sort();
+    assert_eq!(
+        paths,
+        [
+            "Photos/2024/beach.jpg",
+            "Photos/notes.txt",
+            "Videos/notes.txt"
+        ]
+    );
+
+    // A single directory keeps its paths unprefixed
+    let single = scan_directories(
+        std::slice::from_ref(&videos),
+        false,
+        true,
+        ErrorPolicy::FailFast,
+    )
+    .unwrap();
+    assert_eq!(single.len(), 1);
+    assert_eq!(single[0].path, "notes.txt");
+
+    // Both directories share a parent, so the stored paths resolve against it
+    let root = fixture
+        .repository
+        .find_drive_root("External", "Media")
+        .unwrap()
+        .unwrap();
+    assert_eq!(root, roots.path());
+    assert!(root.join("Photos").join("2024").join("beach.jpg").exists());
+}
+
+#[test]
+fn test_diff_drives() {
+    let fixture = TestFixture::new();
+    let files = |entries: &[(&str, u64)]| -> Vec<FileEntry> {
+        entries
+            .iter()
+            .map(|(path, size_bytes)| FileEntry {
+                path: (*path).to_string(),
+                size_bytes: *size_bytes,
+                modified_time: None,
+                ownership: None,
+            })
+            .collect()
+    };
+    fixture
+        .repository
+        .save(
+            "Work",
+            "Primary",
+            0,
+            &files(&[
+                ("docs/a.txt", 10),
+                ("docs/b.txt", 20),
+                ("photos/c.jpg", 30),
+                ("only_primary.txt", 1),
+            ]),
+        )
+        .unwrap();
+    // The backup is split across two categories, it is still compared as one drive
+    fixture
+        .repository
+        .save(
+            "Work",
+            "Backup",
+            0,
+            &files(&[("docs/a.txt", 10), ("docs/b.txt", 25)]),
+        )
+        .unwrap();
+    fixture
+        .repository
+        .save(
+            "Archive",
+            "Backup",
+            0,
+            &files(&[("photos/c.jpg", 30), ("only_backup.txt", 2)]),
+        )
+        .unwrap();
+    fixture
+        .repository
+        .save("Work", "Other", 0, &files(&[("unrelated.txt", 5)]))
+        .unwrap();
+
+    let diff = fixture.repository.diff_drives("Primary", "Backup").unwrap();
+    assert_eq!(
+        diff,
+        DriveDiff {
+            only_in_a: vec!["only_primary.txt".to_string()],
+            only_in_b: vec!["only_backup.txt".to_string()],
+            size_mismatches: vec![SizeMismatch {
+                path: "docs/b.txt".to_string(),
+                size_a: 20,
+                size_b: 25,
+            }],
+            in_both: 3,
+        }
+    );
+    assert!(!diff.is_identical());
+
+    // Disjoint drives share nothing
+    let disjoint = fixture.repository.diff_drives("Primary", "Other").unwrap();
+    assert_eq!(disjoint.only_in_a.len(), 4);
+    assert_eq!(disjoint.only_in_b, ["unrelated.txt"]);
+    assert_eq!(disjoint.in_both, 0);
+
+    // A drive compared with itself, or two unknown drives, are identical
+    let same = fixture.repository.diff_drives("Backup", "Backup").unwrap();
+    assert!(same.is_identical());
+    assert_eq!(same.in_both, 4);
+    assert_eq!(
+        fixture.repository.diff_drives("Missing", "Gone").unwrap(),
+        DriveDiff::default()
+    );
+}
+
+#[test]
+fn test_directory_listing_at_root() {
+    let files = vec![
+        ("photos/2024/a.jpg".to_string(), 10),
+        ("photos/b.jpg".to_string(), 20),
+        ("photos2/c.jpg".to_string(), 30),
+        ("notes.txt".to_string(), 5),
+    ];
+
+    let listing = DirectoryListing::children_of("", files);
+    assert_eq!(
+        listing.folders,
+        vec![
+            FolderEntry {
+                name: "photos".to_string(),
+                path: "photos".to_string(),
+                file_count: 2,
+                total_size: 30,
+            },
+            FolderEntry {
+                name: "photos2".to_string(),
+                path: "photos2".to_string(),
+                file_count: 1,
+                total_size: 30,
+            },
+        ]
+    );
+    assert_eq!(
+        listing.files,
+        vec![FileEntry {
+            path: "notes.txt".to_string(),
+            size_bytes: 5,
+            modified_time: None,
+            ownership: None,
+        }]
+    );
+}
+
+#[test]
+fn test_directory_listing_nested_prefix() {
+    let files = || {
+        vec![
+            ("photos/2024/a.jpg".to_string(), 10),
+            ("photos/2024/trip/b.jpg".to_string(), 20),
+            ("photos/c.jpg".to_string(), 30),
+            ("photos2/d.jpg".to_string(), 40),
+            ("photos\\2023\\e.jpg".to_string(), 50),
+        ]
+    };
+
+    // A sibling sharing the beginning of the name is not inside the prefix
+    let listing = DirectoryListing::children_of("photos", files());
+    let folder_paths: Vec<&str> = listing.folders.iter().map(|f| f.path.as_str()).collect();
+    assert_eq!(folder_paths, vec!["photos\\2023", "photos/2024"]);
+    assert_eq!(listing.files.len(), 1);
+    assert_eq!(listing.files[0].path, "photos/c.jpg");
+
+    // Trailing separators of the prefix are ignored
+    assert_eq!(DirectoryListing::children_of("photos/", files()), listing);
+    assert_eq!(DirectoryListing::children_of("photos\\", files()), listing);
+
+    let listing = DirectoryListing::children_of("photos/2024/", files());
+    assert_eq!(listing.folders.len(), 1);
+    assert_eq!(listing.folders[0].name, "trip");
+    assert_eq!(listing.folders[0].path, "photos/2024/trip");
+    assert_eq!(listing.files[0].path, "photos/2024/a.jpg");
+
+    assert_eq!(
+        DirectoryListing::children_of("missing", files()),
+        DirectoryListing::default()
+    );
+}
+
+#[test]
+fn test_breadcrumbs() {
+    assert!(breadcrumbs("").is_empty());
+    assert!(breadcrumbs("/").is_empty());
+
+    let crumbs = breadcrumbs("photos/2024\\trip/");
+    let crumbs: Vec<(&str, &str)> = crumbs
+        .iter()
+        .map(|crumb| (crumb.name.as_str(), crumb.path.as_str()))
+        .collect();
+    assert_eq!(
+        crumbs,
+        vec![
+            ("photos", "photos"),
+            ("2024", "photos/2024"),
+            ("trip", "photos/2024\\trip"),
+        ]
+    );
+}
+
+#[test]
+fn test_list_immediate_children() {
+    let fixture = TestFixture::new();
+    let files = |paths: &[&str]| -> Vec<FileEntry> {
+        paths
+            .iter()
+            .map(|path| FileEntry {
+                path: (*path).to_string(),
+                size_bytes: 10,
+                modified_time: None,
+                ownership: None,
+            })
+            .collect()
+    };
+    fixture
+        .repository
+        .save(
+            "Work",
+            "Laptop",
+            0,
+            &files(&[
+                "docs/a.txt",
+                "docs/sub/b.txt",
+                "Docs/c.txt",
+                "docs_old/d.txt",
+            ]),
+        )
+        .unwrap();
+    // Categories of the same drive are browsed together, other drives are left out
+    fixture
+        .repository
+        .save("Personal", "Laptop", 0, &files(&["docs/e.txt"]))
+        .unwrap();
+    fixture
+        .repository
+        .save("Work", "Desktop", 0, &files(&["docs/f.txt"]))
+        .unwrap();
+
+    let root = fixture
+        .repository
+        .list_immediate_children("Laptop", "")
+        .unwrap();
+    let names: Vec<&str> = root.folders.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(names, vec!["Docs", "docs", "docs_old"]);
+    assert!(root.files.is_empty());
+
+    // The prefix is matched with its case, and `_` is not a wildcard
+    let docs = fixture
+        .repository
+        .list_immediate_children("Laptop", "docs/")
+        .unwrap();
+    assert_eq!(docs.folders.len(), 1);
+    assert_eq!(docs.folders[0].path, "docs/sub");
+    let paths: Vec<&str> = docs.files.iter().map(|f| f.path.as_str()).collect();
+    assert_eq!(paths, vec!["docs/a.txt", "docs/e.txt"]);
+
+    assert_eq!(
+        fixture
+            .repository
+            .list_immediate_children("Laptop", "do_s")
+            .unwrap(),
+        DirectoryListing::default()
+    );
+}
+
+#[test]
+fn test_record_and_list_index_runs() {
+    let first_run = NaiveDate::from_ymd_opt(2026, 3, 1)
+        .unwrap()
+        .and_hms_opt(9, 0, 0)
+        .unwrap();
+    let clock = Arc::new(FixedClock::new(first_run));
+    let fixture = TestFixture::with_clock(clock.clone());
+    let runs = fixture.repository.index_runs();
+    assert!(runs.list_runs().unwrap().is_empty());
+
+    let photos = vec![
+        PathBuf::from("/media/disk/Photos"),
+        PathBuf::from("/media/disk/Videos"),
+    ];
+    let recorded = runs
+        .record_run(
+            &photos,
+            "Media",
+            "External",
+            1200,
+            Duration::from_millis(3500),
+        )
+        .unwrap();
+    assert_eq!(
+        recorded,
+        IndexRun {
+            indexed_at: first_run,
+            directories: photos,
+            category: "Media".to_string(),
+            drive: "External".to_string(),
+            file_count: 1200,
+            duration: Duration::from_millis(3500),
+        }
+    );
+
+    // A later run and one recorded at the same instant are both listed before the first one
+    let second_run = first_run + chrono::Duration::days(1);
+    clock.set(second_run);
+    runs.record_run(
+        &[PathBuf::from("/home/user")],
+        "Work",
+        "Laptop",
+        10,
+        Duration::from_secs(1),
+    )
+    .unwrap();
+    runs.record_run(
+        &[PathBuf::from("/home/user/docs")],
+        "Work",
+        "Laptop",
+        5,
+        Duration::ZERO,
+    )
+    .unwrap();
+
+    let listed = runs.list_runs().unwrap();
+    let summary: Vec<(NaiveDateTime, u64)> = listed
+        .iter()
+        .map(|run| (run.indexed_at, run.file_count))
+        .collect();
+    assert_eq!(
+        summary,
+        [(second_run, 5), (second_run, 10), (first_run, 1200)]
+    );
+    assert_eq!(listed[2], recorded);
+}
+
+#[test]
+fn test_common_root() {
+    let roots = TempDir::new().unwrap();
+    let photos = roots.path().join("Photos");
+    let videos = roots.path().join("Videos");
+    let other = roots.path().join("nested").join("Photos");
+
+    assert_eq!(common_root(&[]), None);
+    assert_eq!(
+        common_root(std::slice::from_ref(&photos)),
+        Some(photos.clone())
+    );
+    assert_eq!(
+        common_root(&[photos.clone(), videos]),
+        Some(roots.path().to_path_buf())
+    );
+    // Different parents, or a name renamed to stay unique, leave no common root
+    assert_eq!(common_root(&[photos.clone(), other.clone()]), None);
+    assert_eq!(common_root(&[photos.clone(), photos]), None);
+    assert_eq!(
+        common_root(&[other.parent().unwrap().to_path_buf(), other]),
+        None
+    );
+}
+
+#[test]
+fn test_find_drive_root() {
+    let fixture = TestFixture::new();
+    let root = Path::new("/media/backup");
+    fixture
+        .repository
+        .reindex(
+            "Work",
+            "Laptop",
+            DriveCapacity::available_only(1024),
+            Some(root),
+            vec![Ok(vec![])],
+            |_| {},
+        )
+        .unwrap();
+    assert_eq!(
+        fixture
+            .repository
+            .find_drive_root("Laptop", "Work")
+            .unwrap(),
+        Some(root.to_path_buf())
+    );
+
+    // Indexing again from several unrelated directories forgets the root
+    fixture
+        .repository
+        .reindex(
+            "Work",
+            "Laptop",
+            DriveCapacity::available_only(1024),
+            None,
+            vec![Ok(vec![])],
+            |_| {},
+        )
+        .unwrap();
+    assert_eq!(
+        fixture
+            .repository
+            .find_drive_root("Laptop", "Work")
+            .unwrap(),
+        None
+    );
+    assert_eq!(
+        fixture
+            .repository
+            .find_drive_root("Missing", "Work")
+            .unwrap(),
+        None
+    );
+}
+
+#[test]
+fn test_scan_directories_with_same_name() {
+    let roots = TempDir::new().unwrap();
+    let first = roots.path().join("a").join("Docs");
+    let second = roots.path().join("b").join("Docs");
+    std::fs::create_dir_all(&first).unwrap();
+    std::fs::create_dir_all(&second).unwrap();
+    std::fs::write(first.join("file.txt"), "1").unwrap();
+    std::fs::write(second.join("file.txt"), "2").unwrap();
+
+    let paths: Vec<String> = scan_directories(&[first, second], false, true, ErrorPolicy::FailFast)
+        .unwrap()
+        .into_iter()
+        .map(|file| file.path.replace('\\', "/"))
+        .collect();
+    assert_eq!(paths, ["Docs/file.txt", "Docs_2/file.txt"]);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_scan_directory_with_symlink_loop() {
+    use std::os::unix::fs::symlink;
+
+    let root = TempDir::new().unwrap();
+    let photos = root.path().join("photos");
+    let nested = photos.join("2024");
+    std::fs::create_dir_all(&nested).unwrap();
+    std::fs::write(photos.join("a.jpg"), "a").unwrap();
+    std::fs::write(nested.join("b.jpg"), "bb").unwrap();
+    // A link back to the scanned directory, another path to a folder, and one to a file
+    symlink(root.path(), nested.join("loop")).unwrap();
+    symlink(&photos, root.path().join("photos_link")).unwrap();
+    symlink(photos.join("a.jpg"), root.path().join("a_link.jpg")).unwrap();
+
+    // Links are skipped by default
+    let paths: Vec<String> = scan_directory(root.path(), false, true, ErrorPolicy::FailFast)
+        .unwrap()
+        .into_iter()
+        .map(|file| file.path)
+        .collect();
+    assert_eq!(paths, ["photos/2024/b.jpg", "photos/a.jpg"]);
+
+    // Followed links end the scan, with each real file counted once
+    let files = scan_directory(root.path(), true, true, ErrorPolicy::FailFast).unwrap();
+    assert_eq!(files.len(), 2);
+    let real_paths: HashSet<PathBuf> = files
+        .iter()
+        .map(|file| root.path().join(&file.path).canonicalize().unwrap())
+        .collect();
+    assert_eq!(
+        real_paths,
+        HashSet::from([
+            photos.join("a.jpg").canonicalize().unwrap(),
+            nested.join("b.jpg").canonicalize().unwrap(),
+        ])
+    );
+    assert_eq!(files.iter().map(|file| file.size_bytes).sum::<u64>(), 3);
+}
+
+/// Directory with a readable file next to links that cannot be followed.
+#[cfg(unix)]
+fn directory_with_broken_links() -> TempDir {
+    use std::os::unix::fs::symlink;
+
+    let root = TempDir::new().unwrap();
+    std::fs::write(root.path().join("a.txt"), "a").unwrap();
+    symlink("missing", root.path().join("dangling")).unwrap();
+    symlink("itself", root.path().join("itself")).unwrap();
+    root
+}
+
+#[cfg(unix)]
+#[test]
+fn test_directory_scanner_error_message_keys() {
+    let root = directory_with_broken_links();
+    let error = scan_directory(root.path(), true, true, ErrorPolicy::FailFast).unwrap_err();
+    // Either the dangling link or the one pointing at itself fails first
+    assert!(
+        ["error_path_not_found", "error_scan", "error_scan_loop"].contains(&error.message_key())
+    );
+
+    let dangling = TempDir::new().unwrap();
+    std::os::unix::fs::symlink("missing", dangling.path().join("dangling")).unwrap();
+    let error = scan_directory(dangling.path(), true, true, ErrorPolicy::FailFast).unwrap_err();
+    assert_eq!(error.message_key(), "error_path_not_found");
+
+    let error =
+        DirectoryScannerError::RelativePath(Path::new("/a").strip_prefix("/b").unwrap_err());
+    assert_eq!(error.message_key(), "error_scan");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_scan_directory_skipping_unreadable_entries() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let root = directory_with_broken_links();
+    let skipped = SkippedEntries::default();
+
+    let files = scan_directory(
+        root.path(),
+        true,
+        true,
+        ErrorPolicy::SkipErrors(skipped.clone()),
+    );
+    let paths: Vec<String> = files.unwrap().into_iter().map(|file| file.path).collect();
+    assert_eq!(paths, ["a.txt"]);
+    assert_eq!(
+        skipped.paths().into_iter().collect::<HashSet<_>>(),
+        HashSet::from([root.path().join("dangling"), root.path().join("itself")])
+    );
+
+    // A folder without read permission, which only holds when not running as root
+    let locked = root.path().join("locked");
+    std::fs::create_dir(&locked).unwrap();
+    std::fs::write(locked.join("b.txt"), "b").unwrap();
+    std::fs::set_permissions(&locked, std::fs::Permissions::from_mode(0o000)).unwrap();
+    if std::fs::read_dir(&locked).is_err() {
+        let skipped = SkippedEntries::default();
+        let files = scan_directory(
+            root.path(),
+            false,
+            true,
+            ErrorPolicy::SkipErrors(skipped.clone()),
+        );
+        assert_eq!(files.unwrap().len(), 1);
+        assert_eq!(skipped.paths(), std::slice::from_ref(&locked));
+    }
+    std::fs::set_permissions(&locked, std::fs::Permissions::from_mode(0o755)).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn test_scan_directory_failing_fast_on_unreadable_entries() {
+    let root = directory_with_broken_links();
+
+    let error = scan_directory(root.path(), true, true, ErrorPolicy::FailFast).unwrap_err();
+    assert!(matches!(error, DirectoryScannerError::FileMetadata(_)));
+
+    // The batches stop at the error, the file read before it being handed over first
+    let batches: Vec<_> =
+        scan_directory_in_batches(root.path(), 10, true, true, ErrorPolicy::FailFast).collect();
+    assert!(batches.iter().any(Result::is_err));
+
+    // Without following links, none of them is read
+    let files = scan_directory(root.path(), false, true, ErrorPolicy::FailFast).unwrap();
+    assert_eq!(files.len(), 1);
+}
+
+#[test]
+fn test_scan_directory_with_hidden_files() {
+    let root = TempDir::new().unwrap();
+    std::fs::create_dir_all(root.path().join(".git/objects")).unwrap();
+    std::fs::create_dir_all(root.path().join("docs")).unwrap();
+    std::fs::write(root.path().join(".git/objects/pack"), "abc").unwrap();
+    std::fs::write(root.path().join(".env"), "abc").unwrap();
+    std::fs::write(root.path().join("docs/.draft.md"), "abc").unwrap();
+    std::fs::write(root.path().join("docs/report.md"), "abc").unwrap();
+    let scanned_paths = |include_hidden| {
+        let mut paths: Vec<String> =
+            scan_directory(root.path(), false, include_hidden, ErrorPolicy::FailFast)
+                .unwrap()
+                .into_iter()
+                .map(|file| file.path.replace('\\', "/"))
+                .collect();
+        paths.sort();
+        paths
+    };
+
+    assert_eq!(
+        scanned_paths(true),
+        [
+            ".env",
+            ".git/objects/pack",
+            "docs/.draft.md",
+            "docs/report.md"
+        ]
+    );
+    assert_eq!(scanned_paths(false), ["docs/report.md"]);
+
+    // A hidden folder picked on purpose is still scanned
+    let files = scan_directory(
+        &root.path().join(".git"),
+        false,
+        false,
+        ErrorPolicy::FailFast,
+    )
+    .unwrap();
+    assert_eq!(files.len(), 1);
+
+    let fixture = TestFixture::new();
+    assert!(fixture.repository.get_index_hidden_files().unwrap());
+    fixture.repository.set_index_hidden_files(false).unwrap();
+    assert!(!fixture.repository.get_index_hidden_files().unwrap());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_scan_and_search_by_permissions() {
+    use std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+    let root = TempDir::new().unwrap();
+    let modes = [
+        ("shared.txt", 0o666),
+        ("script.sh", 0o755),
+        ("notes.txt", 0o644),
+    ];
+    for (name, mode) in modes {
+        let path = root.path().join(name);
+        std::fs::write(&path, "abc").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(mode)).unwrap();
+    }
+    let owner = std::fs::metadata(root.path().join("notes.txt")).unwrap();
+
+    let mut files = scan_directory(root.path(), false, true, ErrorPolicy::FailFast).unwrap();
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    let ownership = files[0].ownership.unwrap();
+    assert_eq!(ownership.uid, owner.uid());
+    assert_eq!(ownership.gid, owner.gid());
+    let permissions: Vec<(&str, u32)> = files
+        .iter()
+        .map(|file| (file.path.as_str(), file.ownership.unwrap().mode & 0o7777))
+        .collect();
+    assert_eq!(
+        permissions,
+        [
+            ("notes.txt", 0o644),
+            ("script.sh", 0o755),
+            ("shared.txt", 0o666)
+        ]
+    );
+
+    // Files indexed without ownership never match a permission filter
+    files.push(FileEntry {
+        path: "legacy.txt".to_string(),
+        size_bytes: 3,
+        modified_time: None,
+        ownership: None,
+    });
+    let fixture = TestFixture::new();
+    fixture
+        .repository
+        .save("Work", "Server", 1024, &files)
+        .unwrap();
+    let stored = fixture.repository.files_for("Work", "Server").unwrap();
+    assert_eq!(stored[0].path, "legacy.txt");
+    assert_eq!(stored[0].ownership, None);
+    assert_eq!(stored[1].ownership, files[0].ownership);
+
+    let matching = |permission| {
+        let filters = SearchFilters {
+            permission: Some(permission),
+            ..SearchFilters::default()
+        };
+        let mut paths: Vec<String> = fixture
+            .repository
+            .search_files_paginated(None, None, &filters, 0, 100)
+            .unwrap()
+            .into_iter()
+            .map(|file| file.path)
+            .collect();
+        paths.sort();
+        paths
+    };
+    assert_eq!(matching(PermissionFilter::WorldWritable), ["shared.txt"]);
+    assert_eq!(matching(PermissionFilter::Executable), ["script.sh"]);
+    assert!(matching(PermissionFilter::SetId).is_empty());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_scan_directory_with_non_utf8_name() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let root = TempDir::new().unwrap();
+    let name = OsStr::from_bytes(b"caf\xe9 100%.txt");
+    std::fs::write(root.path().join(name), "abc").unwrap();
+
+    let files = scan_directory(root.path(), false, true, ErrorPolicy::FailFast).unwrap();
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].path, "caf%E9 100%.txt");
+    assert_eq!(files[0].size_bytes, 3);
+
+    // The stored path leads back to the very same file
+    let path = root.path().join(decode_path(&files[0].path));
+    assert_eq!(path.file_name(), Some(name));
+    assert!(path.exists());
+
+    // Literal percent signs only get escaped where they would read as an escape
+    for raw in [&b"50%E9%25 \xff%"[..], b"%\xc3", b"plain/na\xefve.txt"] {
+        let original = Path::new(OsStr::from_bytes(raw));
+        assert_eq!(decode_path(&encode_path(original)), original);
+    }
+    assert_eq!(encode_path(Path::new("50%E9%25")), "50%25E9%2525");
+    assert_eq!(encode_path(Path::new("café/100%.txt")), "café/100%.txt");
+}
+
+#[test]
+fn test_scan_summary() {
+    let files = TestFixture::create_test_files();
+
+    let summary = ScanSummary::from_files(&files);
+    assert_eq!(summary.file_count, 4);
+    assert_eq!(summary.total_size, 1024 + 2048 + 512 + 768);
+
+    assert_eq!(ScanSummary::from_files(&[]), ScanSummary::default());
+}
+
+#[test]
+fn test_capacity_advice_when_size_exceeds_available_space() {
+    let summary = ScanSummary {
+        file_count: 10,
+        total_size: NEARLY_FULL_SPACE * EXCESS_SIZE_FACTOR * 2,
+        skipped: Vec::new(),
+    };
+
+    assert_eq!(
+        summary.capacity_advice(NEARLY_FULL_SPACE),
+        Some(CapacityAdvice::ExceedsAvailableSpace)
+    );
+    assert_eq!(
+        summary.capacity_advice(NEARLY_FULL_SPACE / 2),
+        Some(CapacityAdvice::ExceedsAvailableSpace)
+    );
+}
+
+#[test]
+fn test_capacity_advice_for_normal_and_nearly_full_drives() {
+    let summary = ScanSummary::from_files(&TestFixture::create_test_files());
+
+    assert_eq!(summary.capacity_advice(NEARLY_FULL_SPACE * 10), None);
+    assert_eq!(
+        summary.capacity_advice(NEARLY_FULL_SPACE - 1),
+        Some(CapacityAdvice::NearlyFull)
+    );
+    // Unknown available space never raises an advisory
+    assert_eq!(summary.capacity_advice(0), None);
+}
+
+fn busy_error() -> InfrastructureError {
+    InfrastructureError::Database(DieselError::DatabaseError(
+        DatabaseErrorKind::Unknown,
+        Box::new("database is locked".to_string()),
+    ))
+}
+
+fn create_test_pool() -> (TempDir, SqliteRepositoryPool) {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let db_url = format!("sqlite://{}", temp_dir.path().join("test.db").display());
+    let pool = SqliteRepositoryPool::new(&db_url).unwrap();
+    (temp_dir, pool)
+}
+
+#[test]
+fn test_busy_database_is_retried() {
+    let (_temp_dir, pool) = create_test_pool();
+    assert!(busy_error().is_database_busy());
+
+    let mut attempts = 0;
+    let result = pool.execute_db_operation(|_| {
+        attempts += 1;
+        if attempts == 1 {
+            Err(busy_error())
+        } else {
+            Ok(42)
+        }
+    });
+    assert_eq!(result.unwrap(), 42);
+    assert_eq!(attempts, 2);
+}
+
+#[test]
+fn test_busy_database_gives_up_after_max_attempts() {
+    let (_temp_dir, pool) = create_test_pool();
+
+    let mut attempts = 0;
+    let result: Result<(), _> = pool.execute_db_operation(|_| {
+        attempts += 1;
+        Err(busy_error())
+    });
+    assert!(result.unwrap_err().is_database_busy());
+    assert_eq!(attempts, BUSY_MAX_ATTEMPTS);
+}
+
+#[test]
+fn test_infrastructure_error_message_keys() {
+    let database_error = |message: &str| {
+        InfrastructureError::Database(DieselError::DatabaseError(
+            DatabaseErrorKind::Unknown,
+            Box::new(message.to_string()),
+        ))
+    };
+    let io_error = |kind| InfrastructureError::IoError(std::io::Error::from(kind));
+    let pool_error = diesel::r2d2::Pool::builder()
+        .connection_timeout(Duration::from_millis(10))
+        .build(
+            diesel::r2d2::ConnectionManager::<diesel::SqliteConnection>::new(
+                "/missing/directory/app.db",
+            ),
+        )
+        .unwrap_err();
+
+    let cases = [
+        (busy_error(), "error_database_busy"),
+        (
+            database_error("disk I/O error"),
+            "error_database_unavailable",
+        ),
+        (
+            database_error("unable to open database file"),
+            "error_database_unavailable",
+        ),
+        (
+            InfrastructureError::Database(DieselError::NotFound),
+            "error_database",
+        ),
+        (database_error("malformed"), "error_database"),
+        (
+            InfrastructureError::ConnectionPool(pool_error),
+            "error_database_unavailable",
+        ),
+        (
+            InfrastructureError::Migration("failed".to_string()),
+            "error_migration",
+        ),
+        (
+            io_error(std::io::ErrorKind::NotFound),
+            "error_path_not_found",
+        ),
+        (
+            io_error(std::io::ErrorKind::PermissionDenied),
+            "error_permission_denied",
+        ),
+        (io_error(std::io::ErrorKind::Other), "error_io"),
+        (
+            InfrastructureError::DeserializeError(
+                serde_json::from_str::<u8>("not json").unwrap_err(),
+            ),
+            "error_corrupted_data",
+        ),
+        (
+            InfrastructureError::Scan("failed".to_string()),
+            "error_scan",
+        ),
+        (
+            InfrastructureError::DiskNotFound(PathBuf::from("/mnt/usb")),
+            "error_disk_not_found",
+        ),
+        (
+            InfrastructureError::DiskUnmounted(PathBuf::from("/mnt/usb")),
+            "error_disk_unmounted",
+        ),
+    ];
+    for (error, key) in cases {
+        assert_eq!(error.message_key(), key, "{error}");
+    }
+}
+
+#[test]
+fn test_update_error_message_keys() {
+    let cases = [
+        (
+            UpdateError::Io(std::io::Error::from(std::io::ErrorKind::PermissionDenied)),
+            "error_permission_denied",
+        ),
+        (
+            UpdateError::MissingAsset("lister.exe".to_string()),
+            "error_update",
+        ),
+        (
+            UpdateError::ChecksumMismatch {
+                expected: "a".to_string(),
+                actual: "b".to_string(),
+            },
+            "error_update",
+        ),
+    ];
+    for (error, key) in cases {
+        assert_eq!(error.message_key(), key, "{error}");
+    }
+}
+
+#[test]
+fn test_error_message_keys_are_translated() {
+    let keys = [
+        "error_title",
+        "error_database_busy",
+        "error_database_unavailable",
+        "error_database",
+        "error_migration",
+        "error_path_not_found",
+        "error_permission_denied",
+        "error_io",
+        "error_corrupted_data",
+        "error_scan",
+        "error_scan_loop",
+        "error_disk_not_found",
+        "error_disk_unmounted",
+        "error_update",
+        "error_update_download",
+    ];
+    for language in Language::ALL {
+        let translations = load_translations(&language).unwrap();
+        for key in keys {
+            assert!(translations.contains_key(key), "{key} in {language:?}");
+        }
+    }
+}
+
+#[test]
+fn test_other_database_errors_are_not_retried() {
+    let (_temp_dir, pool) = create_test_pool();
+
+    let mut attempts = 0;
+    let result: Result<(), _> = pool.execute_db_operation(|_| {
+        attempts += 1;
+        Err(InfrastructureError::Database(DieselError::NotFound))
+    });
+    assert!(!result.unwrap_err().is_database_busy());
+    assert_eq!(attempts, 1);
+
+    // A transaction that already ran is never replayed
+    let mut runs = 0;
+    let result: Result<(), _> = pool.execute_in_transaction(|_| {
+        runs += 1;
+        Err(busy_error())
+    });
+    assert!(result.is_err());
+    assert_eq!(runs, 1);
+}
+
+#[test]
+fn test_small_pool_with_busy_timeout_handles_concurrent_operations() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let db_url = format!("sqlite://{}", temp_dir.path().join("test.db").display());
+    let config = PoolConfig {
+        max_size: 2,
+        min_idle: Some(1),
+        busy_timeout: Duration::from_secs(2),
+    };
+    let repository = ListerRepository::new(
+        SqliteRepositoryPool::with_config(&db_url, config).unwrap(),
+        Arc::new(SystemClock),
+    );
+    let files = TestFixture::create_test_files();
+
+    thread::scope(|scope| {
+        let writers: Vec<_> = (0..4)
+            .map(|index| {
+                let (repository, files) = (&repository, &files);
+                scope.spawn(move || repository.save("Work", &format!("Drive{index}"), 1024, files))
+            })
+            .collect();
+        let readers: Vec<_> = (0..4)
+            .map(|_| {
+                let repository = &repository;
+                scope.spawn(move || {
+                    repository.count_search_results(None, None, &SearchFilters::default())
+                })
+            })
+            .collect();
+        for writer in writers {
+            assert!(writer.join().unwrap().is_ok());
+        }
+        for reader in readers {
+            assert!(reader.join().unwrap().is_ok());
+        }
+    });
+
+    assert_eq!(
+        repository
+            .count_search_results(None, None, &SearchFilters::default())
+            .unwrap(),
+        4 * files.len() as u64
+    );
+}
+
+#[test]
+fn test_cascade_on_every_pooled_connection() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let db_url = format!("sqlite://{}", temp_dir.path().join("test.db").display());
+    let repository = ListerRepository::new(
+        SqliteRepositoryPool::new(&db_url).unwrap(),
+        Arc::new(SystemClock),
+    );
+    let files = TestFixture::create_test_files();
+    repository.save("Work", "Laptop", 1024, &files).unwrap();
+    repository.save("Work", "Desktop", 1024, &files).unwrap();
+    drop(repository);
+
+    let config = PoolConfig {
+        max_size: 2,
+        min_idle: Some(0),
+        busy_timeout: Duration::from_secs(2),
+    };
+    let pool = SqliteRepositoryPool::with_config(&db_url, config).unwrap();
+    // Held together, the two connections are distinct, only one of them opened the database
+    let mut connections = [
+        pool.get_connection().unwrap(),
+        pool.get_connection().unwrap(),
+    ];
+
+    for (connection, (drive, remaining)) in connections
+        .iter_mut()
+        .zip([("Laptop", files.len()), ("Desktop", 0)])
+    {
+        diesel::RunQueryDsl::execute(
+            diesel::sql_query(format!("DELETE FROM drive_entries WHERE name = '{drive}'")),
+            connection,
+        )
+        .unwrap();
+        let remaining_files: i64 = diesel::RunQueryDsl::get_result(
+            diesel::QueryDsl::count(lister::infrastructure::database::schema::file_entries::table),
+            connection,
+        )
+        .unwrap();
+        assert_eq!(remaining_files, remaining as i64);
+    }
+}
+
+#[test]
+fn test_compact_after_partial_clear() {
+    let fixture = TestFixture::new();
+    let files: Vec<FileEntry> = (0..5000)
+        .map(|index| FileEntry {
+            path: format!("folder_{}/some/long/path/file_{index}.txt", index % 50),
+            size_bytes: index,
+            modified_time: None,
+            ownership: None,
+        })
+        .collect();
+    fixture
+        .repository
+        .save("Work", "Laptop", 1024, &files)
+        .unwrap();
+    fixture
+        .repository
+        .save("Work", "Backup", 1024, &files)
+        .unwrap();
+    fixture.repository.delete("Laptop", None).unwrap();
+
+    let report = fixture.repository.compact().unwrap();
+    assert!(report.size_before > 0);
+    assert!(report.size_after < report.size_before);
+    assert_eq!(report.freed_space(), report.size_before - report.size_after);
+
+    // The remaining drive is untouched
+    assert_eq!(
+        fixture
+            .repository
+            .count_search_results(Some("Backup"), None, &SearchFilters::default())
+            .unwrap(),
+        5000
+    );
+}
+
+#[test]
+fn test_backup_before_pending_migrations() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let db_url = format!("sqlite://{}", temp_dir.path().join("test.db").display());
+    let backups = || {
+        std::fs::read_dir(temp_dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .filter(|name| name.starts_with("test.db.bak-"))
+            .count()
+    };
+
+    // A new database and an up-to-date one are not backed up
+    drop(SqliteRepositoryPool::new(&db_url).unwrap());
+    drop(SqliteRepositoryPool::new(&db_url).unwrap());
+    assert_eq!(backups(), 0);
+
+    let pool = SqliteRepositoryPool::new(&db_url).unwrap();
+    pool.get_connection()
+        .unwrap()
+        .revert_last_migration(MIGRATIONS)
+        .unwrap();
+    drop(pool);
+
+    drop(SqliteRepositoryPool::new(&db_url).unwrap());
+    assert_eq!(backups(), 1);
+}
+
+#[test]
+fn test_merge_categories_reparents_drives() {
+    let fixture = TestFixture::new();
+    let files = TestFixture::create_test_files();
+    let file = |path: &str| FileEntry {
+        path: path.to_string(),
+        size_bytes: 1,
+        modified_time: None,
+        ownership: None,
+    };
+    fixture
+        .repository
+        .save("Work", "Laptop", 1024, &files)
+        .unwrap();
+    fixture
+        .repository
+        .save("Wrok", "Backup", 1024, &files)
+        .unwrap();
+    // A drive existing in both categories ends up as a single drive
+    fixture
+        .repository
+        .save("Wrok", "Laptop", 1024, &[file("extra.txt")])
+        .unwrap();
+
+    fixture.repository.merge_categories("Wrok", "Work").unwrap();
+
+    assert_eq!(
+        fixture.repository.find_all_categories().unwrap(),
+        vec![CategoryStats {
+            name: "Work".to_string(),
+            file_count: 2 * files.len() as u64 + 1,
+        }]
+    );
+    let drives: Vec<(String, String, u64)> = fixture
+        .repository
+        .drive_statistics()
+        .unwrap()
+        .into_iter()
+        .map(|stats| (stats.drive_name, stats.category_name, stats.file_count))
+        .collect();
+    assert_eq!(
+        drives,
+        [
+            ("Backup".to_string(), "Work".to_string(), files.len() as u64),
+            (
+                "Laptop".to_string(),
+                "Work".to_string(),
+                files.len() as u64 + 1
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_merge_categories_into_unknown_category_changes_nothing() {
+    let fixture = TestFixture::new();
+    let files = TestFixture::create_test_files();
+    fixture
+        .repository
+        .save("Work", "Laptop", 1024, &files)
+        .unwrap();
+
+    assert!(
+        fixture
+            .repository
+            .merge_categories("Work", "Missing")
+            .is_err()
+    );
+    assert_eq!(
+        fixture.repository.find_all_categories().unwrap(),
+        vec![CategoryStats {
+            name: "Work".to_string(),
+            file_count: files.len() as u64,
+        }]
+    );
+}
+
+#[test]
+fn test_reindex_replaces_files_atomically() {
+    let fixture = TestFixture::new();
+    let files = TestFixture::create_test_files();
+    fixture
+        .repository
+        .save("Work", "Laptop", 1024, &files)
+        .unwrap();
+
+    // A failure mid-save keeps the previous files
+    let new_files = vec![FileEntry {
+        path: "new/file.txt".to_string(),
+        size_bytes: 1,
+        modified_time: None,
+        ownership: None,
+    }];
+    let result = fixture.repository.reindex(
+        "Work",
+        "Laptop",
+        DriveCapacity::available_only(1024),
+        None,
+        vec![
+            Ok(new_files.clone()),
+            Err(InfrastructureError::Scan("interrupted".to_string())),
+        ],
+        |_| {},
+    );
+    assert!(result.is_err());
+    let remaining = fixture
+        .repository
+        .search_files_paginated(None, None, &SearchFilters::default(), 0, 100)
+        .unwrap();
+    assert_eq!(remaining.len(), files.len());
+    assert!(remaining.iter().all(|file| file.path != "new/file.txt"));
+
+    // A successful re-index replaces them
+    let saved = fixture
+        .repository
+        .reindex(
+            "Work",
+            "Laptop",
+            DriveCapacity::available_only(1024),
+            None,
+            vec![Ok(new_files)],
+            |_| {},
+        )
+        .unwrap();
+    assert_eq!(saved, 1);
+    let remaining = fixture
+        .repository
+        .search_files_paginated(None, None, &SearchFilters::default(), 0, 100)
+        .unwrap();
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].path, "new/file.txt");
+}
+
+#[test]
+fn test_search_history_deduplicates_and_orders() {
+    let mut history = SearchHistory::new(3);
+    assert!(history.record("photos"));
+    assert!(history.record("  music "));
+    assert!(!history.record("   "));
+    assert_eq!(history.entries(), ["music", "photos"]);
+
+    // A reused query moves to the front instead of being duplicated
+    assert!(history.record("photos"));
+    assert_eq!(history.entries(), ["photos", "music"]);
+
+    // The oldest query is dropped once the cap is reached
+    history.record("videos");
+    history.record("docs");
+    assert_eq!(history.entries(), ["docs", "videos", "photos"]);
+
+    let restored = SearchHistory::from_entries(["a", "", "b", "a", "c", "d"].map(String::from), 3);
+    assert_eq!(restored.entries(), ["a", "b", "c"]);
+
+    history.clear();
+    assert!(history.is_empty());
+}
+
+#[test]
+fn test_search_history_persistence() {
+    let fixture = TestFixture::new();
+    assert!(fixture.repository.get_search_history().unwrap().is_empty());
+
+    let mut history = SearchHistory::new(SEARCH_HISTORY_LIMIT);
+    history.record("report");
+    history.record("holiday photos");
+    fixture.repository.set_search_history(&history).unwrap();
+
+    assert_eq!(fixture.repository.get_search_history().unwrap(), history);
+}
+
+#[test]
+fn test_saved_search_crud() {
+    let fixture = TestFixture::new();
+    let saved_searches = fixture.repository.saved_searches();
+    assert!(saved_searches.find_all().unwrap().is_empty());
+
+    let videos = SavedSearch {
+        name: "Large videos on BackupA".to_string(),
+        drive: Some("BackupA".to_string()),
+        query: "holiday".to_string(),
+        filters: SearchFilters {
+            extension: Some("mkv".to_string()),
+            ..SearchFilters::default()
+        },
+    };
+    let reports = SavedSearch {
+        name: "All reports".to_string(),
+        drive: None,
+        query: "report".to_string(),
+        filters: SearchFilters {
+            filename_only: true,
+            whole_segments: true,
+            ..SearchFilters::default()
+        },
+    };
+    saved_searches.save(&videos).unwrap();
+    saved_searches.save(&reports).unwrap();
+
+    let all = saved_searches.find_all().unwrap();
+    assert_eq!(all, vec![reports.clone(), videos.clone()]);
+
+    // Saving under an existing name replaces the previous parameters
+    let updated = SavedSearch {
+        query: ".mp4".to_string(),
+        ..videos
+    };
+    saved_searches.save(&updated).unwrap();
+    assert_eq!(saved_searches.find_all().unwrap().len(), 2);
+
+    saved_searches.delete(&reports.name).unwrap();
+    assert_eq!(saved_searches.find_all().unwrap(), vec![updated]);
+    assert!(
+        saved_searches
+            .find_by_name(&reports.name)
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[test]
+fn test_restore_saved_search_parameters() {
+    let fixture = TestFixture::new();
+    fixture
+        .repository
+        .save("Work", "BackupA", 1024, &TestFixture::create_test_files())
+        .unwrap();
+    let search = SavedSearch {
+        name: "Reports on BackupA".to_string(),
+        drive: Some("BackupA".to_string()),
+        query: "report".to_string(),
+        filters: SearchFilters {
+            filename_only: true,
+            ..SearchFilters::default()
+        },
+    };
+    fixture.repository.saved_searches().save(&search).unwrap();
+
+    let restored = fixture
+        .repository
+        .saved_searches()
+        .find_by_name("Reports on BackupA")
+        .unwrap()
+        .unwrap();
+    assert_eq!(restored, search);
+
+    let results = fixture
+        .repository
+        .search_files_paginated(
+            restored.drive.as_deref(),
+            Some(restored.query.as_str()),
+            &restored.filters,
+            0,
+            100,
+        )
+        .unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].path, "documents/report.pdf");
+}
+
+#[test]
+fn test_drive_tag_crud() {
+    let fixture = TestFixture::new();
+    let files = TestFixture::create_test_files();
+    fixture
+        .repository
+        .save("Work", "BackupA", 1024, &files)
+        .unwrap();
+    fixture
+        .repository
+        .save("Work", "BackupB", 1024, &files)
+        .unwrap();
+    let tags = fixture.repository.drive_tags();
+    assert!(tags.find_all().unwrap().is_empty());
+
+    tags.add("BackupA", "offsite").unwrap();
+    tags.add("BackupA", "encrypted").unwrap();
+    tags.add("BackupA", "offsite").unwrap();
+    tags.add("BackupB", "to-verify").unwrap();
+    assert_eq!(
+        tags.find_all().unwrap(),
+        BTreeMap::from([
+            (
+                "BackupA".to_string(),
+                vec!["encrypted".to_string(), "offsite".to_string()]
+            ),
+            ("BackupB".to_string(), vec!["to-verify".to_string()]),
+        ])
+    );
+    assert_eq!(
+        tags.find_all_tags().unwrap(),
+        vec!["encrypted", "offsite", "to-verify"]
+    );
+
+    // A drive indexed under another category keeps its tags
+    fixture
+        .repository
+        .save("Photos", "BackupA", 1024, &files)
+        .unwrap();
+    fixture.repository.delete("BackupA", Some("Work")).unwrap();
+    assert_eq!(
+        tags.find_all().unwrap()["BackupA"],
+        vec!["encrypted", "offsite"]
+    );
+
+    tags.remove("BackupA", "offsite").unwrap();
+    assert_eq!(tags.find_all().unwrap()["BackupA"], vec!["encrypted"]);
+
+    // Deleting a drive drops its tags
+    fixture.repository.delete("BackupB", None).unwrap();
+    assert_eq!(tags.find_all_tags().unwrap(), vec!["encrypted"]);
+
+    assert_eq!(
+        normalize_tag("  To   Verify "),
+        Some("to verify".to_string())
+    );
+    assert_eq!(normalize_tag("   "), None);
+}
+
+#[test]
+fn test_filter_by_drive_tag() {
+    let fixture = TestFixture::new();
+    let files = TestFixture::create_test_files();
+    fixture
+        .repository
+        .save("Work", "BackupA", 1024, &files)
+        .unwrap();
+    fixture
+        .repository
+        .save("Work", "BackupB", 1024, &files)
+        .unwrap();
+    fixture
+        .repository
+        .save("Work", "Laptop", 1024, &files)
+        .unwrap();
+    let tags = fixture.repository.drive_tags();
+    tags.add("BackupA", "offsite").unwrap();
+    tags.add("Laptop", "offsite").unwrap();
+    tags.add("BackupB", "encrypted").unwrap();
+
+    assert_eq!(
+        tags.find_drives("offsite").unwrap(),
+        vec!["BackupA", "Laptop"]
+    );
+    assert!(tags.find_drives("missing").unwrap().is_empty());
+
+    let filters = SearchFilters {
+        tag: Some("offsite".to_string()),
+        ..SearchFilters::default()
+    };
+    let results = fixture
+        .repository
+        .search_files_paginated(None, Some("report"), &filters, 0, 100)
+        .unwrap();
+    let mut drives: Vec<&str> = results
+        .iter()
+        .map(|file| file.drive_name.as_str())
+        .collect();
+    drives.sort_unstable();
+    assert_eq!(drives, vec!["BackupA", "Laptop"]);
+    assert_eq!(
+        fixture
+            .repository
+            .count_search_results(None, Some("report"), &filters)
+            .unwrap(),
+        2
+    );
+
+    assert_eq!(
+        SearchConstraint::active(None, None, &filters),
+        vec![SearchConstraint::Tag]
+    );
+    let (_, _, lifted) = SearchConstraint::Tag.lift(None, None, &filters);
+    assert_eq!(lifted.tag, None);
+}
+
+#[test]
+fn test_whole_segment_search() {
+    let fixture = TestFixture::new();
+    let paths = [
+        "doc",
+        "doc/readme.txt",
+        "projects/doc",
+        "projects/doc/api.md",
+        "projects\\doc\\legacy.md",
+        "documents/report.pdf",
+        "docker/compose.yml",
+        "notes/my.doc",
+        "notes/DOC",
+        "notes/doc_old/index.md",
+    ];
+    let files: Vec<FileEntry> = paths
+        .iter()
+        .map(|path| FileEntry {
+            path: path.to_string(),
+            size_bytes: 1,
+            modified_time: None,
+            ownership: None,
+        })
+        .collect();
+    fixture
+        .repository
+        .save("Work", "Laptop", 1024, &files)
+        .unwrap();
+
+    let search = |query: &str, filename_only| {
+        let filters = SearchFilters {
+            whole_segments: true,
+            filename_only,
+            ..SearchFilters::default()
+        };
+        let mut matches: Vec<String> = fixture
+            .repository
+            .search_files_paginated(None, Some(query), &filters, 0, 100)
+            .unwrap()
+            .into_iter()
+            .map(|file| file.path)
+            .collect();
+        matches.sort();
+        matches
+    };
+
+    // Whole segments anywhere in the path, at its start, middle, or end, in any case
+    assert_eq!(
+        search("doc", false),
+        [
+            "doc",
+            "doc/readme.txt",
+            "notes/DOC",
+            "projects/doc",
+            "projects/doc/api.md",
+            "projects\\doc\\legacy.md",
+        ]
+    );
+    // Substring matching still finds the longer names
+    assert_eq!(
+        fixture
+            .repository
+            .count_search_results(None, Some("doc"), &SearchFilters::default())
+            .unwrap(),
+        paths.len() as u64
+    );
+    // Only file names equal to the term
+    assert_eq!(search("doc", true), ["doc", "notes/DOC", "projects/doc"]);
+    assert_eq!(search("my.doc", true), ["notes/my.doc"]);
+    // Several segments in a row, whichever separator is typed
+    assert_eq!(
+        search("projects/doc", false),
+        search("projects\\doc", false)
+    );
+    assert_eq!(search("projects/doc/", false).len(), 3);
+    // Wildcard characters are taken literally
+    assert!(search("doc%", false).is_empty());
+    assert!(search("do_", false).is_empty());
+    // Excluded terms drop only whole segments
+    assert_eq!(
+        search("notes -doc", false),
+        ["notes/doc_old/index.md", "notes/my.doc"]
+    );
+}
+
+#[test]
+fn test_filename_only_search_ignores_directories() {
+    let fixture = TestFixture::new();
+    let files = vec![
+        FileEntry {
+            path: "code/main.rs".to_string(),
+            size_bytes: 10,
+            modified_time: None,
+            ownership: None,
+        },
+        FileEntry {
+            path: "main/readme.txt".to_string(),
+            size_bytes: 20,
+            modified_time: None,
+            ownership: None,
+        },
+        FileEntry {
+            path: "main/domain/MAIN_notes.md".to_string(),
+            size_bytes: 30,
+            modified_time: None,
+            ownership: None,
+        },
+    ];
+    fixture
+        .repository
+        .save("Work", "Laptop", 1024, &files)
+        .unwrap();
+
+    let full_path = SearchFilters::default();
+    let filename_only = SearchFilters {
+        filename_only: true,
+        ..SearchFilters::default()
+    };
+
+    let mut matches: Vec<String> = fixture
+        .repository
+        .search_files_paginated(None, Some("main"), &full_path, 0, 10)
+        .unwrap()
+        .into_iter()
+        .map(|file| file.path)
+        .collect();
+    matches.sort();
+    assert_eq!(
+        matches,
+        [
+            "code/main.rs",
+            "main/domain/MAIN_notes.md",
+            "main/readme.txt"
+        ]
+    );
+
+    let mut matches: Vec<String> = fixture
+        .repository
+        .search_files_paginated(None, Some("main"), &filename_only, 0, 10)
+        .unwrap()
+        .into_iter()
+        .map(|file| file.path)
+        .collect();
+    matches.sort();
+    assert_eq!(matches, ["code/main.rs", "main/domain/MAIN_notes.md"]);
+    assert_eq!(
+        fixture
+            .repository
+            .count_search_results(None, Some("main"), &filename_only)
+            .unwrap(),
+        2
+    );
+
+    // A directory name alone never matches in filename-only mode
+    assert_eq!(
+        fixture
+            .repository
+            .count_search_results(None, Some("domain"), &filename_only)
+            .unwrap(),
+        0
+    );
+}
+
+#[test]
+fn test_extension_filter() {
+    let fixture = TestFixture::new();
+    let paths = [
+        "photos/beach.jpg",
+        "photos/CITY.JPG",
+        "docs/report.pdf",
+        "archive.tar.gz",
+        "README",
+        ".bashrc",
+    ];
+    let files: Vec<FileEntry> = paths
+        .iter()
+        .map(|path| FileEntry {
+            path: (*path).to_string(),
+            size_bytes: 1,
+            modified_time: None,
+            ownership: None,
+        })
+        .collect();
+    fixture
+        .repository
+        .save("Work", "Laptop", 1024, &files)
+        .unwrap();
+    fixture
+        .repository
+        .save(
+            "Work",
+            "Desktop",
+            1024,
+            &[FileEntry {
+                path: "music/song.mp3".to_string(),
+                size_bytes: 1,
+                modified_time: None,
+                ownership: None,
+            }],
+        )
+        .unwrap();
+
+    // Extensions are lowercase, deduplicated and files without one are left out
+    assert_eq!(
+        fixture.repository.list_extensions(Some("Laptop")).unwrap(),
+        ["gz", "jpg", "pdf"]
+    );
+    assert_eq!(
+        fixture.repository.list_extensions(None).unwrap(),
+        ["gz", "jpg", "mp3", "pdf"]
+    );
+
+    let jpg = SearchFilters {
+        extension: Some("jpg".to_string()),
+        ..SearchFilters::default()
+    };
+    let mut matches: Vec<String> = fixture
+        .repository
+        .search_files_paginated(None, None, &jpg, 0, 10)
+        .unwrap()
+        .into_iter()
+        .map(|file| file.path)
+        .collect();
+    matches.sort();
+    assert_eq!(matches, ["photos/CITY.JPG", "photos/beach.jpg"]);
+    assert_eq!(
+        fixture
+            .repository
+            .count_search_results(Some("Laptop"), Some("beach"), &jpg)
+            .unwrap(),
+        1
+    );
+    assert_eq!(
+        fixture
+            .repository
+            .count_search_results(Some("Desktop"), None, &jpg)
+            .unwrap(),
+        0
+    );
+}
+
+#[test]
+fn test_search_files_in_batches_matches_full_fetch() {
+    let fixture = TestFixture::new();
+    let files: Vec<FileEntry> = (0..25)
+        .map(|index| FileEntry {
+            path: format!("dir/file_{index:02}.txt"),
+            size_bytes: index,
+            modified_time: None,
+            ownership: None,
+        })
+        .collect();
+    fixture
+        .repository
+        .save("Work", "Laptop", 1024, &files)
+        .unwrap();
+
+    let filters = SearchFilters::default();
+    let batches: Vec<Vec<FileWithMetadata>> = fixture
+        .repository
+        .search_files_in_batches(Some("Laptop"), Some("file"), &filters, 10)
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(
+        batches.iter().map(Vec::len).collect::<Vec<_>>(),
+        [10, 10, 5]
+    );
+
+    // Same files in the same order, none repeated or skipped between batches
+    let streamed: Vec<String> = batches
+        .into_iter()
+        .flatten()
+        .map(|file| file.path)
+        .collect();
+    let fetched: Vec<String> = fixture
+        .repository
+        .search_files_paginated(Some("Laptop"), Some("file"), &filters, 0, 100)
+        .unwrap()
+        .into_iter()
+        .map(|file| file.path)
+        .collect();
+    assert_eq!(streamed, fetched);
+
+    // An exact multiple of the batch size ends without an empty trailing batch
+    assert_eq!(
+        fixture
+            .repository
+            .search_files_in_batches(Some("Laptop"), None, &filters, 5)
+            .count(),
+        5
+    );
+}
+
+#[test]
+fn test_indexed_since_filter() {
+    let fixture = TestFixture::new();
+    let before_indexing = Local::now().naive_local() - chrono::Duration::seconds(1);
+    fixture
+        .repository
+        .save("Work", "Laptop", 1024, &TestFixture::create_test_files())
+        .unwrap();
+
+    let since_before = SearchFilters {
+        indexed_since: Some(before_indexing),
+        ..SearchFilters::default()
+    };
+    let since_later = SearchFilters {
+        indexed_since: Some(Local::now().naive_local() + chrono::Duration::hours(1)),
+        ..SearchFilters::default()
+    };
+    assert_eq!(
+        fixture
+            .repository
+            .count_search_results(None, None, &since_before)
+            .unwrap(),
+        4
+    );
+    assert!(
+        fixture
+            .repository
+            .search_files_paginated(None, None, &since_later, 0, 10)
+            .unwrap()
+            .is_empty()
+    );
+
+    // The bound combines with the other filters
+    let pdf_since_before = SearchFilters {
+        extension: Some("pdf".to_string()),
+        ..since_before
+    };
+    assert_eq!(
+        fixture
+            .repository
+            .count_search_results(Some("Laptop"), None, &pdf_since_before)
+            .unwrap(),
+        2
+    );
+}
+
+#[test]
+fn test_indexed_within_since() {
+    let now = NaiveDate::from_ymd_opt(2026, 10, 16)
+        .unwrap()
+        .and_hms_opt(12, 0, 0)
+        .unwrap();
+    assert_eq!(
+        IndexedWithin::Week.since(now),
+        NaiveDate::from_ymd_opt(2026, 10, 9)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap()
+    );
+    assert_eq!(
+        IndexedWithin::Quarter.since(now).date(),
+        NaiveDate::from_ymd_opt(2026, 7, 18).unwrap()
+    );
+}
+
+#[test]
+fn test_insertion_time_comes_from_clock() {
+    let indexed_at = NaiveDate::from_ymd_opt(2026, 1, 2)
+        .unwrap()
+        .and_hms_opt(3, 4, 5)
+        .unwrap();
+    let fixture = TestFixture::with_clock(Arc::new(FixedClock::new(indexed_at)));
+    fixture
+        .repository
+        .save("Work", "Laptop", 1024, &TestFixture::create_test_files())
+        .unwrap();
+
+    let files = fixture
+        .repository
+        .search_files_paginated(Some("Laptop"), None, &SearchFilters::default(), 0, 10)
+        .unwrap();
+    assert!(!files.is_empty());
+    assert!(
+        files
+            .iter()
+            .all(|file| file.drive_insertion_time == indexed_at)
+    );
+}
+
+#[test]
+fn test_find_all_drives_keeps_latest_indexing_time() {
+    let first_indexed = NaiveDate::from_ymd_opt(2026, 3, 1)
+        .unwrap()
+        .and_hms_opt(8, 0, 0)
+        .unwrap();
+    let last_indexed = first_indexed + chrono::Duration::days(5);
+    let clock = Arc::new(FixedClock::new(first_indexed));
+    let fixture = TestFixture::with_clock(clock.clone());
+    let files = TestFixture::create_test_files();
+    fixture
+        .repository
+        .save("Work", "Laptop", 1024, &files)
+        .unwrap();
+    fixture
+        .repository
+        .save("Work", "Backup", 1024, &files)
+        .unwrap();
+    clock.set(last_indexed);
+    fixture
+        .repository
+        .save("Personal", "Laptop", 1024, &files)
+        .unwrap();
+
+    assert_eq!(
+        fixture.repository.find_all_drives().unwrap(),
+        vec![
+            DriveInfo {
+                name: "Backup".to_string(),
+                last_indexed: first_indexed,
+            },
+            DriveInfo {
+                name: "Laptop".to_string(),
+                last_indexed,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_recent_files() {
+    let first_indexed = NaiveDate::from_ymd_opt(2026, 3, 1)
+        .unwrap()
+        .and_hms_opt(8, 0, 0)
+        .unwrap();
+    let clock = Arc::new(FixedClock::new(first_indexed));
+    let fixture = TestFixture::with_clock(clock.clone());
+    let files = TestFixture::create_test_files();
+    fixture
+        .repository
+        .save("Work", "Backup", 1024, &files)
+        .unwrap();
+    clock.set(first_indexed + chrono::Duration::days(5));
+    fixture
+        .repository
+        .save("Personal", "Laptop", 1024, &files[..2])
+        .unwrap();
+
+    let recent = |limit| {
+        fixture
+            .repository
+            .recent_files(limit)
+            .unwrap()
+            .into_iter()
+            .map(|file| (file.drive_name, file.path))
+            .collect::<Vec<_>>()
+    };
+    let listed = |drive: &str, path: &str| (drive.to_string(), path.to_string());
+
+    assert_eq!(
+        recent(3),
+        vec![
+            listed("Laptop", "documents/report.pdf"),
+            listed("Laptop", "images/photo.jpg"),
+            listed("Backup", "code/main.rs"),
+        ]
+    );
+    assert_eq!(recent(10).len(), 6);
+    assert!(recent(0).is_empty());
+}
+
+#[test]
+fn test_elapsed_time_between() {
+    let now = NaiveDate::from_ymd_opt(2026, 10, 16)
+        .unwrap()
+        .and_hms_opt(12, 0, 0)
+        .unwrap();
+    let ago = |duration: chrono::Duration| ElapsedTime::between(now - duration, now);
+
+    assert_eq!(ago(chrono::Duration::seconds(30)), ElapsedTime::JustNow);
+    assert_eq!(ago(chrono::Duration::seconds(-90)), ElapsedTime::JustNow);
+    assert_eq!(ago(chrono::Duration::minutes(1)), ElapsedTime::Minutes(1));
+    assert_eq!(ago(chrono::Duration::minutes(59)), ElapsedTime::Minutes(59));
+    assert_eq!(ago(chrono::Duration::minutes(61)), ElapsedTime::Hours(1));
+    assert_eq!(ago(chrono::Duration::hours(23)), ElapsedTime::Hours(23));
+    assert_eq!(ago(chrono::Duration::hours(49)), ElapsedTime::Days(2));
+    assert_eq!(ago(chrono::Duration::days(400)), ElapsedTime::Days(400));
+}
+
+#[test]
+fn test_file_age_between() {
+    let now = NaiveDate::from_ymd_opt(2026, 10, 16)
+        .unwrap()
+        .and_hms_opt(12, 0, 0)
+        .unwrap();
+    let age = |duration: chrono::Duration| FileAge::between(now - duration, now);
+
+    assert_eq!(age(chrono::Duration::hours(23)), FileAge::Today);
+    assert_eq!(age(chrono::Duration::days(-3)), FileAge::Today);
+    assert_eq!(age(chrono::Duration::days(1)), FileAge::Days(1));
+    assert_eq!(age(chrono::Duration::days(29)), FileAge::Days(29));
+    assert_eq!(age(chrono::Duration::days(30)), FileAge::Months(1));
+    assert_eq!(age(chrono::Duration::days(364)), FileAge::Months(12));
+    assert_eq!(age(chrono::Duration::days(800)), FileAge::Years(2));
+}
+
+#[test]
+fn test_search_sorted_by_modified_time() {
+    let fixture = TestFixture::new();
+    let at = |year| {
+        NaiveDate::from_ymd_opt(year, 1, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+    };
+    let file = |path: &str, modified_time| FileEntry {
+        path: path.to_string(),
+        size_bytes: 1,
+        modified_time,
+        ownership: None,
+    };
+    let files = vec![
+        file("unknown_b.txt", None),
+        file("old.txt", at(2019)),
+        file("unknown_a.txt", None),
+        file("recent.txt", at(2026)),
+        file("middle.txt", at(2023)),
+    ];
+    fixture
+        .repository
+        .save("Work", "Laptop", 1024, &files)
+        .unwrap();
+
+    let search = |sort, page, page_size| {
+        let filters = SearchFilters {
+            sort,
+            ..SearchFilters::default()
+        };
+        fixture
+            .repository
+            .search_files_paginated(None, None, &filters, page, page_size)
+            .unwrap()
+            .into_iter()
+            .map(|file| file.path)
+            .collect::<Vec<_>>()
+    };
+
+    // Newest first, then the files without a time, whose ties are broken by path
+    let sorted = [
+        "recent.txt",
+        "middle.txt",
+        "old.txt",
+        "unknown_a.txt",
+        "unknown_b.txt",
+    ];
+    assert_eq!(search(SortKey::Modified, 0, 10), sorted);
+    // Pages follow the same order
+    assert_eq!(search(SortKey::Modified, 1, 2), sorted[2..4]);
+    // The stored times come back with the results
+    let found = fixture
+        .repository
+        .search_files_paginated(None, Some("old"), &SearchFilters::default(), 0, 10)
+        .unwrap();
+    assert_eq!(found[0].modified_time, at(2019));
+    assert_eq!(search(SortKey::Indexed, 0, 10).len(), files.len());
+}
+
+#[test]
+fn test_largest_files() {
+    let fixture = TestFixture::new();
+    let file = |path: &str, size_bytes| FileEntry {
+        path: path.to_string(),
+        size_bytes,
+        modified_time: None,
+        ownership: None,
+    };
+    let files: Vec<FileEntry> = (1..=250)
+        .map(|index| file(&format!("file_{index:03}.bin"), index * 10))
+        .chain([file("tie_b.iso", 5000), file("tie_a.iso", 5000)])
+        .collect();
+    fixture
+        .repository
+        .save("Work", "Laptop", 1024, &files)
+        .unwrap();
+
+    let mut filters = SearchFilters::default();
+    filters.toggle_largest_files();
+    assert_eq!(filters.sort, SortKey::Size);
+    assert_eq!(filters.top, Some(LARGEST_FILES_COUNT));
+
+    // A page larger than the cap stops at the cap, biggest first
+    let largest = fixture
+        .repository
+        .search_files_paginated(None, None, &filters, 0, 500)
+        .unwrap();
+    assert_eq!(largest.len(), 100);
+    let paths: Vec<&str> = largest.iter().map(|file| file.path.as_str()).collect();
+    // Equal sizes are ordered by path
+    assert_eq!(
+        paths[..4],
+        ["tie_a.iso", "tie_b.iso", "file_250.bin", "file_249.bin"]
+    );
+    assert_eq!(paths[99], "file_153.bin");
+    assert!(
+        largest
+            .windows(2)
+            .all(|pair| pair[0].size_bytes >= pair[1].size_bytes)
+    );
+
+    // Pages stop at the cap as well, while the count covers every match
+    let second_page = fixture
+        .repository
+        .search_files_paginated(None, None, &filters, 1, 60)
+        .unwrap();
+    assert_eq!(second_page.len(), 40);
+    assert_eq!(second_page[0].path, paths[60]);
+    assert!(
+        fixture
+            .repository
+            .search_files_paginated(None, None, &filters, 2, 60)
+            .unwrap()
+            .is_empty()
+    );
+    let total = fixture
+        .repository
+        .count_search_results(None, None, &filters)
+        .unwrap();
+    assert_eq!(total, 252);
+    assert_eq!(
+        status_parts(None, "", &filters, total, 0)[1],
+        StatusPart::Top {
+            shown: 100,
+            total: 252
+        }
+    );
+    // Fewer matches than the cap are all shown
+    assert_eq!(
+        status_parts(None, "", &filters, 12, 0)[1],
+        StatusPart::Top {
+            shown: 12,
+            total: 12
+        }
+    );
+
+    filters.toggle_largest_files();
+    assert_eq!(filters, SearchFilters::default());
+}
+
+#[test]
+fn test_scan_directory_reads_modified_time() {
+    let root = TempDir::new().unwrap();
+    std::fs::write(root.path().join("a.txt"), "a").unwrap();
+
+    let files = scan_directory(root.path(), false, true, ErrorPolicy::FailFast).unwrap();
+    let modified_time = files[0].modified_time.expect("modification time");
+    let age = Local::now().naive_local() - modified_time;
+    assert!(age < chrono::Duration::minutes(5), "{modified_time}");
+}
+
+#[test]
+fn test_indexed_within_with_mocked_clock() {
+    let now = NaiveDate::from_ymd_opt(2026, 10, 16)
+        .unwrap()
+        .and_hms_opt(12, 0, 0)
+        .unwrap();
+    let clock = Arc::new(FixedClock::new(now - chrono::Duration::days(60)));
+    let fixture = TestFixture::with_clock(clock.clone());
+    let file = |path: &str| FileEntry {
+        path: path.to_string(),
+        size_bytes: 1,
+        modified_time: None,
+        ownership: None,
+    };
+    fixture
+        .repository
+        .save("Work", "Old", 1024, &[file("old.txt")])
+        .unwrap();
+    clock.set(now - chrono::Duration::days(20));
+    fixture
+        .repository
+        .save("Work", "Recent", 1024, &[file("recent.txt")])
+        .unwrap();
+    clock.set(now - chrono::Duration::days(2));
+    fixture
+        .repository
+        .save("Work", "New", 1024, &[file("new.txt")])
+        .unwrap();
+
+    let drives_within = |period: Option<IndexedWithin>| {
+        let filters = SearchFilters {
+            indexed_since: period.map(|period| period.since(now)),
+            ..SearchFilters::default()
+        };
+        let mut drives: Vec<String> = fixture
+            .repository
+            .search_files_paginated(None, None, &filters, 0, 10)
+            .unwrap()
+            .into_iter()
+            .map(|file| file.drive_name)
+            .collect();
+        drives.sort();
+        drives
+    };
+    assert_eq!(drives_within(Some(IndexedWithin::Week)), ["New"]);
+    assert_eq!(drives_within(Some(IndexedWithin::Month)), ["New", "Recent"]);
+    assert_eq!(
+        drives_within(Some(IndexedWithin::Quarter)),
+        ["New", "Old", "Recent"]
+    );
+    assert_eq!(drives_within(None), ["New", "Old", "Recent"]);
+}
+
+#[test]
+fn test_parse_search_terms() {
+    let terms = SearchTerms::parse("  report -draft 2024\t-old ");
+    assert_eq!(terms.include, ["report", "2024"]);
+    assert_eq!(terms.exclude, ["draft", "old"]);
+
+    // A lone dash is a literal term
+    assert_eq!(SearchTerms::parse("a - b").include, ["a", "-", "b"]);
+    assert!(SearchTerms::parse("   ").is_empty());
+
+    assert!(terms.matches("docs/REPORT_2024.pdf"));
+    assert!(!terms.matches("docs/report_2024_draft.pdf"));
+    assert!(!terms.matches("docs/report.pdf"));
+}
+
+#[test]
+fn test_search_terms_quoted_phrases() {
+    let terms = SearchTerms::parse(r#""annual report" -"old draft" 2023 """#);
+    assert_eq!(terms.include, ["annual report", "2023"]);
+    assert_eq!(terms.exclude, ["old draft"]);
+
+    // An unterminated quote runs to the end of the query
+    assert_eq!(SearchTerms::parse(r#"a "b c"#).include, ["a", "b c"]);
+}
+
+#[test]
+fn test_multi_term_and_search() {
+    let fixture = TestFixture::new();
+    let paths = [
+        "finance/invoice_2023_march.pdf",
+        "finance/2023/invoice_april.pdf",
+        "finance/invoice_2024.pdf",
+        "archive/2023 taxes.pdf",
+        "archive/invoice 2023.txt",
+        "archive/my invoice.txt",
+    ];
+    let files: Vec<FileEntry> = paths
+        .iter()
+        .map(|path| FileEntry {
+            path: (*path).to_string(),
+            size_bytes: 1,
+            modified_time: None,
+            ownership: None,
+        })
+        .collect();
+    fixture
+        .repository
+        .save("Work", "Laptop", 1024, &files)
+        .unwrap();
+
+    let search = |query: &str| {
+        let filters = SearchFilters::default();
+        let mut matches: Vec<String> = fixture
+            .repository
+            .search_files_paginated(None, Some(query), &filters, 0, 100)
+            .unwrap()
+            .into_iter()
+            .map(|file| file.path)
+            .collect();
+        matches.sort();
+        assert_eq!(
+            fixture
+                .repository
+                .count_search_results(None, Some(query), &filters)
+                .unwrap(),
+            matches.len() as u64
+        );
+        matches
+    };
+
+    // Two terms, not necessarily adjacent nor in order
+    assert_eq!(
+        search("invoice 2023"),
+        [
+            "archive/invoice 2023.txt",
+            "finance/2023/invoice_april.pdf",
+            "finance/invoice_2023_march.pdf"
+        ]
+    );
+
+    // Three terms
+    assert_eq!(
+        search("2023 pdf invoice"),
+        [
+            "finance/2023/invoice_april.pdf",
+            "finance/invoice_2023_march.pdf"
+        ]
+    );
+
+    // A space is no longer a single-character wildcard, a quoted phrase matches as written
+    assert_eq!(search(r#""invoice 2023""#), ["archive/invoice 2023.txt"]);
+    assert_eq!(search(r#""my invoice""#), ["archive/my invoice.txt"]);
+}
+
+#[test]
+fn test_search_with_exclusion_terms() {
+    let fixture = TestFixture::new();
+    let paths = [
+        "work/report_final.pdf",
+        "work/report_draft.pdf",
+        "work/draft/notes.txt",
+        "home/report 2024.txt",
+        "home/100%_done.txt",
+        "home/100x done.txt",
+    ];
+    let files: Vec<FileEntry> = paths
+        .iter()
+        .map(|path| FileEntry {
+            path: (*path).to_string(),
+            size_bytes: 1,
+            modified_time: None,
+            ownership: None,
+        })
+        .collect();
+    fixture
+        .repository
+        .save("Work", "Laptop", 1024, &files)
+        .unwrap();
+
+    let search = |query: &str| {
+        let filters = SearchFilters::default();
+        let mut matches: Vec<String> = fixture
+            .repository
+            .search_files_paginated(None, Some(query), &filters, 0, 100)
+            .unwrap()
+            .into_iter()
+            .map(|file| file.path)
+            .collect();
+        matches.sort();
+        // Counting must parse the query the same way as fetching
+        assert_eq!(
+            fixture
+                .repository
+                .count_search_results(None, Some(query), &filters)
+                .unwrap(),
+            matches.len() as u64
+        );
+        matches
+    };
+
+    // Every include term must be present, in any order
+    assert_eq!(search("2024 report"), ["home/report 2024.txt"]);
+    assert_eq!(
+        search("work pdf"),
+        ["work/report_draft.pdf", "work/report_final.pdf"]
+    );
+
+    // A single exclusion
+    assert_eq!(
+        search("-draft"),
+        [
+            "home/100%_done.txt",
+            "home/100x done.txt",
+            "home/report 2024.txt",
+            "work/report_final.pdf"
+        ]
+    );
+
+    // Mixed include and exclude terms
+    assert_eq!(search("report -draft -2024"), ["work/report_final.pdf"]);
+
+    // LIKE wildcards are matched literally
+    assert_eq!(search("100%_"), ["home/100%_done.txt"]);
+    assert_eq!(
+        search("report_"),
+        ["work/report_draft.pdf", "work/report_final.pdf"]
+    );
+    assert!(search("%").iter().all(|path| path.contains('%')));
+}
+
+fn cached_files(count: usize) -> Vec<FileWithMetadata> {
+    (0..count)
+        .map(|index| FileWithMetadata {
+            category_name: "Work".to_string(),
+            drive_name: "Laptop".to_string(),
+            drive_available_space: 0,
+            drive_insertion_time: Local::now().naive_local(),
+            path: format!("file_{index}.txt"),
+            size_bytes: 1,
+            modified_time: None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_cache_evicts_least_recently_used_dataset() {
+    let drive_a = Some("A".to_string());
+    let drive_b = Some("B".to_string());
+    let mut cache = Cache::new(2, 100);
+    cache.store(
+        drive_a.clone(),
+        "photos".to_string(),
+        SearchFilters::default(),
+        cached_files(3),
+    );
+    cache.store(
+        drive_b.clone(),
+        "photos".to_string(),
+        SearchFilters::default(),
+        cached_files(3),
+    );
+
+    // Reading the first dataset makes the second one the least recently used
+    assert!(
+        cache
+            .get_page(
+                drive_a.as_ref(),
+                "photos",
+                &SearchFilters::default(),
+                "",
+                0,
+                10
+            )
+            .is_some()
+    );
+    cache.store(
+        None,
+        "photos".to_string(),
+        SearchFilters::default(),
+        cached_files(3),
+    );
+
+    assert!(cache.is_valid_for(drive_a.as_ref(), "photos", &SearchFilters::default()));
+    assert!(!cache.is_valid_for(drive_b.as_ref(), "photos", &SearchFilters::default()));
+    assert!(cache.is_valid_for(None, "photos", &SearchFilters::default()));
+}
+
+#[test]
+fn test_cache_is_keyed_by_filters() {
+    let pdf_only = SearchFilters {
+        extension: Some("pdf".to_string()),
+        ..SearchFilters::default()
+    };
+    let mut cache = Cache::new(4, 100);
+    cache.store(
+        None,
+        "report".to_string(),
+        pdf_only.clone(),
+        cached_files(3),
+    );
+
+    // The same query without the extension filter is another dataset
+    assert!(cache.is_valid_for(None, "report", &pdf_only));
+    assert!(!cache.is_valid_for(None, "report", &SearchFilters::default()));
+    assert_eq!(
+        cache.get_page(None, "report", &SearchFilters::default(), "", 0, 10),
+        None
+    );
+    assert_eq!(
+        cache.count(None, "report", &SearchFilters::default(), ""),
+        None
+    );
+
+    let sorted = SearchFilters {
+        sort: SortKey::Size,
+        ..pdf_only.clone()
+    };
+    assert!(!cache.is_valid_for(None, "report", &sorted));
+    assert_eq!(cache.count(None, "report", &pdf_only, ""), Some(3));
+}
+
+#[test]
+fn test_latest_request_ignores_outdated_answers() {
+    let mut live_search = LatestRequest::default();
+
+    // Typing fast schedules a search per keystroke, only the last one is run
+    let first = live_search.start();
+    let second = live_search.start();
+    let third = live_search.start();
+    assert!(!live_search.is_latest(first));
+    assert!(!live_search.is_latest(second));
+    assert!(live_search.is_latest(third));
+
+    // Submitting explicitly cancels the search scheduled while typing
+    live_search.cancel();
+    assert!(!live_search.is_latest(third));
+
+    // Answers arriving out of order are told apart by their generation
+    let mut page_load = LatestRequest::default();
+    let slow = page_load.start();
+    let fast = page_load.start();
+    let answers = [(fast, "fast"), (slow, "slow")];
+    let applied: Vec<&str> = answers
+        .iter()
+        .filter(|(generation, _)| page_load.is_latest(*generation))
+        .map(|(_, query)| *query)
+        .collect();
+    assert_eq!(applied, vec!["fast"]);
+}
+
+#[test]
+fn test_overlapping_loads_keep_latest_results() {
+    let fixture = TestFixture::new();
+    let files: Vec<FileEntry> = ["ab.txt", "abcd.txt", "abcde.txt"]
+        .iter()
+        .map(|path| FileEntry {
+            path: (*path).to_string(),
+            size_bytes: 1,
+            modified_time: None,
+            ownership: None,
+        })
+        .collect();
+    fixture
+        .repository
+        .save("Work", "Laptop", 0, &files)
+        .unwrap();
+
+    let mut page_load = LatestRequest::default();
+    let slow_generation = page_load.start();
+    let fast_generation = page_load.start();
+
+    // The query typed first answers last
+    let (sender, receiver) = std::sync::mpsc::channel();
+    thread::scope(|scope| {
+        for (generation, query, delay) in [
+            (slow_generation, "ab", Duration::from_millis(100)),
+            (fast_generation, "abcd", Duration::ZERO),
+        ] {
+            let sender = sender.clone();
+            let repository = &fixture.repository;
+            scope.spawn(move || {
+                thread::sleep(delay);
+                let results = repository
+                    .search_files_paginated(None, Some(query), &SearchFilters::default(), 0, 100)
+                    .unwrap();
+                sender.send((generation, results)).unwrap();
+            });
+        }
+    });
+    drop(sender);
+
+    let arrivals: Vec<(u64, Vec<FileWithMetadata>)> = receiver.iter().collect();
+    assert_eq!(arrivals.last().unwrap().0, slow_generation);
+
+    let mut shown = vec![];
+    for (generation, results) in arrivals {
+        if page_load.is_latest(generation) {
+            shown = results;
+        }
+    }
+    let paths: Vec<&str> = shown.iter().map(|file| file.path.as_str()).collect();
+    assert_eq!(paths, vec!["abcd.txt", "abcde.txt"]);
+}
+
+#[test]
+fn test_search_timer_transitions() {
+    let started = Instant::now();
+    let mut timer = SearchTimer::default();
+    assert_eq!(timer, SearchTimer::Idle);
+    assert!(!timer.needs_ticks());
+
+    // Ticks while idle change nothing
+    timer.tick(started + SLOW_SEARCH_THRESHOLD);
+    assert_eq!(timer, SearchTimer::Idle);
+
+    timer.start(started);
+    assert!(timer.needs_ticks());
+    timer.tick(started + SLOW_SEARCH_THRESHOLD - Duration::from_millis(1));
+    assert!(!timer.is_slow());
+
+    timer.tick(started + SLOW_SEARCH_THRESHOLD);
+    assert!(timer.is_slow());
+    assert!(!timer.needs_ticks());
+
+    // Results arriving clear the notice
+    timer.stop();
+    assert_eq!(timer, SearchTimer::Idle);
+
+    // A new search restarts the timer from scratch
+    timer.start(started);
+    timer.start(started + SLOW_SEARCH_THRESHOLD);
+    timer.tick(started + SLOW_SEARCH_THRESHOLD + Duration::from_secs(1));
+    assert!(!timer.is_slow());
+}
+
+#[test]
+fn test_cache_hit_after_switching_away_and_back() {
+    let drive_a = Some("A".to_string());
+    let drive_b = Some("B".to_string());
+    let mut cache = Cache::new(4, 100);
+    cache.store(
+        drive_a.clone(),
+        "music".to_string(),
+        SearchFilters::default(),
+        cached_files(5),
+    );
+    cache.store(
+        drive_b.clone(),
+        "music".to_string(),
+        SearchFilters::default(),
+        cached_files(2),
+    );
+
+    let page = cache
+        .get_page(
+            drive_a.as_ref(),
+            "music",
+            &SearchFilters::default(),
+            "",
+            1,
+            3,
+        )
+        .unwrap();
+    assert_eq!(page.len(), 2);
+    assert_eq!(page[0].path, "file_3.txt");
+    assert_eq!(
+        cache.count(drive_b.as_ref(), "music", &SearchFilters::default(), ""),
+        Some(2)
+    );
+    assert!(
+        cache
+            .get_page(
+                drive_a.as_ref(),
+                "other",
+                &SearchFilters::default(),
+                "",
+                0,
+                3
+            )
+            .is_none()
+    );
+}
+
+#[test]
+fn test_sum_search_results_size() {
+    let fixture = TestFixture::new();
+    let files = |sizes: &[(&str, u64)]| -> Vec<FileEntry> {
+        sizes
+            .iter()
+            .map(|(path, size_bytes)| FileEntry {
+                path: (*path).to_string(),
+                size_bytes: *size_bytes,
+                modified_time: None,
+                ownership: None,
+            })
+            .collect()
+    };
+    fixture
+        .repository
+        .save(
+            "Work",
+            "Laptop",
+            0,
+            &files(&[("report.pdf", 4000), ("notes.txt", 200)]),
+        )
+        .unwrap();
+    fixture
+        .repository
+        .save("Work", "Desktop", 0, &files(&[("report.docx", 1000)]))
+        .unwrap();
+
+    let sum = |drive: Option<&str>, query: Option<&str>| {
+        fixture
+            .repository
+            .sum_search_results_size(drive, query, &SearchFilters::default())
+            .unwrap()
+    };
+    assert_eq!(sum(None, None), 5200);
+    assert_eq!(sum(Some("Laptop"), None), 4200);
+    assert_eq!(sum(None, Some("report")), 5000);
+    assert_eq!(sum(None, Some("missing")), 0);
+
+    // The cache sums its refined rows the same way
+    let mut cache = Cache::new(1, 100);
+    cache.store(
+        None,
+        String::new(),
+        SearchFilters::default(),
+        cached_files(5),
+    );
+    assert_eq!(
+        cache.total_size(None, "", &SearchFilters::default(), ""),
+        Some(5)
+    );
+    assert_eq!(
+        cache.total_size(None, "", &SearchFilters::default(), "file_1"),
+        Some(1)
+    );
+    assert_eq!(
+        cache.total_size(None, "other", &SearchFilters::default(), ""),
+        None
+    );
+}
+
+#[test]
+fn test_status_parts() {
+    assert_eq!(
+        status_parts(
+            Some("Laptop"),
+            "report",
+            &SearchFilters::default(),
+            2,
+            4_200_000
+        ),
+        [
+            StatusPart::Drive("Laptop".to_string()),
+            StatusPart::Query("report".to_string()),
+            StatusPart::Results(2),
+            StatusPart::TotalSize(4_200_000),
+        ]
+    );
+
+    // A blank query is left out, active filters are listed
+    let filters = SearchFilters {
+        filename_only: true,
+        whole_segments: true,
+        fuzzy: true,
+        extension: Some("pdf".to_string()),
+        indexed_since: None,
+        tag: Some("offsite".to_string()),
+        permission: Some(PermissionFilter::WorldWritable),
+        sort: SortKey::Indexed,
+        top: None,
+    };
+    assert_eq!(
+        status_parts(None, "  ", &filters, 0, 0),
+        [
+            StatusPart::AllDrives,
+            StatusPart::Extension("pdf".to_string()),
+            StatusPart::Tag("offsite".to_string()),
+            StatusPart::Permission(PermissionFilter::WorldWritable),
+            StatusPart::FilenameOnly,
+            StatusPart::WholeSegments,
+            StatusPart::Fuzzy,
+            StatusPart::Results(0),
+            StatusPart::TotalSize(0),
+        ]
+    );
+}
+
+#[test]
+fn test_cache_is_bounded_by_total_rows() {
+    let mut cache = Cache::new(10, 10);
+    cache.store(
+        None,
+        "a".to_string(),
+        SearchFilters::default(),
+        cached_files(4),
+    );
+    cache.store(
+        None,
+        "b".to_string(),
+        SearchFilters::default(),
+        cached_files(4),
+    );
+    cache.store(
+        None,
+        "c".to_string(),
+        SearchFilters::default(),
+        cached_files(4),
+    );
+
+    assert_eq!(cache.total_rows(), 8);
+    assert!(!cache.is_valid_for(None, "a", &SearchFilters::default()));
+
+    // A dataset exceeding the whole budget is not kept
+    cache.store(
+        None,
+        "huge".to_string(),
+        SearchFilters::default(),
+        cached_files(11),
+    );
+    assert!(!cache.is_valid_for(None, "huge", &SearchFilters::default()));
+    assert_eq!(cache.total_rows(), 8);
+}
+
+#[test]
+fn test_group_by_parent_directory() {
+    let file = |drive: &str, path: &str, size_bytes: u64| FileWithMetadata {
+        category_name: "Work".to_string(),
+        drive_name: drive.to_string(),
+        drive_available_space: 0,
+        drive_insertion_time: Local::now().naive_local(),
+        path: path.to_string(),
+        size_bytes,
+        modified_time: None,
+    };
+    let files = vec![
+        file("Laptop", "photos/b.jpg", 200),
+        file("Laptop", "docs/report.pdf", 50),
+        file("Laptop", "photos/a.jpg", 100),
+        file("Desktop", "photos/c.jpg", 7),
+        file("Laptop", "root.txt", 1),
+    ];
+
+    let groups = group_by_parent_directory(&files);
+    let summary: Vec<(&str, &str, u64, usize)> = groups
+        .iter()
+        .map(|group| {
+            (
+                group.drive_name.as_str(),
+                group.directory.as_str(),
+                group.total_size,
+                group.files.len(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            ("Desktop", "photos", 7, 1),
+            ("Laptop", "", 1, 1),
+            ("Laptop", "docs", 50, 1),
+            ("Laptop", "photos", 300, 2),
+        ]
+    );
+
+    // Files keep their original order inside a group
+    assert_eq!(groups[3].files[0].path, "photos/b.jpg");
+    assert_eq!(groups[3].files[1].path, "photos/a.jpg");
+    assert!(group_by_parent_directory(&[]).is_empty());
+}
+
+#[test]
+fn test_rank_by_filename() {
+    let file = |path: &str| FileWithMetadata {
+        category_name: "Work".to_string(),
+        drive_name: "Laptop".to_string(),
+        drive_available_space: 0,
+        drive_insertion_time: Local::now().naive_local(),
+        path: path.to_string(),
+        size_bytes: 0,
+        modified_time: None,
+    };
+    let files = vec![
+        file("archive/quarterly_report.pdf"),
+        file("reports/photo.jpg"),
+        file("notes/rprt.txt"),
+        file("documents/Report.pdf"),
+        file("music/party.mp3"),
+    ];
+
+    // Only the filename is matched, whatever the case, closest matches first
+    let ranked = rank_by_filename(files.clone(), "RPRT");
+    let paths: Vec<&str> = ranked.iter().map(|file| file.path.as_str()).collect();
+    assert_eq!(
+        paths,
+        vec![
+            "notes/rprt.txt",
+            "documents/Report.pdf",
+            "archive/quarterly_report.pdf"
+        ]
+    );
+
+    // A query with letters out of order matches nothing
+    assert!(rank_by_filename(files.clone(), "tprr").is_empty());
+
+    // Equal scores keep the original order
+    let ranked = rank_by_filename(vec![file("b/report.pdf"), file("a/report.pdf")], "report");
+    assert_eq!(ranked[0].path, "b/report.pdf");
+    assert_eq!(ranked[1].path, "a/report.pdf");
+}
+
+#[test]
+fn test_build_path_tree() {
+    let file = |drive: &str, path: &str, size_bytes: u64| FileWithMetadata {
+        category_name: "Work".to_string(),
+        drive_name: drive.to_string(),
+        drive_available_space: 0,
+        drive_insertion_time: Local::now().naive_local(),
+        path: path.to_string(),
+        size_bytes,
+        modified_time: None,
+    };
+    let files = vec![
+        file("Laptop", "photos/2024/trip/b.jpg", 200),
+        file("Laptop", "photos/2024/trip/a.jpg", 100),
+        file("Laptop", "photos\\2023\\c.jpg", 30),
+        file("Laptop", "work/projects/lister/src/main.rs", 5),
+        file("Laptop", "root.txt", 1),
+        file("Desktop", "photos/2024/trip/b.jpg", 7),
+    ];
+
+    let tree = build_path_tree(&files);
+    let summary = |node: &PathNode| {
+        (
+            node.name.clone(),
+            node.path.clone(),
+            node.file_count,
+            node.total_size,
+        )
+    };
+
+    // Drives come first, sorted by name, and are never collapsed
+    assert_eq!(tree.len(), 2);
+    assert_eq!(
+        summary(&tree[0]),
+        ("Desktop".to_string(), String::new(), 1, 7)
+    );
+    assert_eq!(
+        summary(&tree[0].children[0]),
+        (
+            "photos/2024/trip".to_string(),
+            "photos/2024/trip".to_string(),
+            1,
+            7
+        )
+    );
+
+    let laptop = &tree[1];
+    assert_eq!(
+        summary(laptop),
+        ("Laptop".to_string(), String::new(), 5, 336)
+    );
+    assert_eq!(laptop.files.len(), 1);
+    assert_eq!(laptop.files[0].path, "root.txt");
+
+    // Both separators split folders, sub-folders being sorted by name
+    let photos = &laptop.children[0];
+    assert_eq!(
+        summary(photos),
+        ("photos".to_string(), "photos".to_string(), 3, 330)
+    );
+    assert!(photos.files.is_empty());
+    let photo_folders: Vec<_> = photos.children.iter().map(summary).collect();
+    assert_eq!(
+        photo_folders,
+        vec![
+            ("2023".to_string(), "photos/2023".to_string(), 1, 30),
+            (
+                "2024/trip".to_string(),
+                "photos/2024/trip".to_string(),
+                2,
+                300
+            ),
+        ]
+    );
+
+    // Files keep their order in the results
+    let trip_files: Vec<&str> = photos.children[1]
+        .files
+        .iter()
+        .map(|file| file.path.as_str())
+        .collect();
+    assert_eq!(
+        trip_files,
+        vec!["photos/2024/trip/b.jpg", "photos/2024/trip/a.jpg"]
+    );
+
+    // A chain of single folders collapses down to the folder holding files
+    let work = &laptop.children[1];
+    assert_eq!(
+        summary(work),
+        (
+            "work/projects/lister/src".to_string(),
+            "work/projects/lister/src".to_string(),
+            1,
+            5
+        )
+    );
+    assert!(work.children.is_empty());
+    assert_eq!(work.files[0].path, "work/projects/lister/src/main.rs");
+
+    assert!(build_path_tree(&[]).is_empty());
+}
+
+#[test]
+fn test_group_by_drive() {
+    let file = |drive: &str, path: &str, size_bytes: u64| FileWithMetadata {
+        category_name: "Work".to_string(),
+        drive_name: drive.to_string(),
+        drive_available_space: 0,
+        drive_insertion_time: Local::now().naive_local(),
+        path: path.to_string(),
+        size_bytes,
+        modified_time: None,
+    };
+    let files = vec![
+        file("Laptop", "z.txt", 200),
+        file("Desktop", "photos/c.jpg", 7),
+        file("Laptop", "a.txt", 100),
+        file("Backup", "docs/report.pdf", 50),
+        file("Desktop", "b.txt", 3),
+    ];
+
+    let groups = group_by_drive(&files);
+    let summary: Vec<(&str, u64, usize)> = groups
+        .iter()
+        .map(|group| {
+            (
+                group.drive_name.as_str(),
+                group.total_size,
+                group.files.len(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        [("Backup", 50, 1), ("Desktop", 10, 2), ("Laptop", 300, 2)]
+    );
+
+    // Files keep their original order inside a section
+    let laptop_paths: Vec<&str> = groups[2]
+        .files
+        .iter()
+        .map(|file| file.path.as_str())
+        .collect();
+    assert_eq!(laptop_paths, ["z.txt", "a.txt"]);
+
+    // The same files in another order produce the same sections
+    let mut reversed = files.clone();
+    reversed.reverse();
+    let reversed_drives: Vec<String> = group_by_drive(&reversed)
+        .into_iter()
+        .map(|group| group.drive_name)
+        .collect();
+    assert_eq!(reversed_drives, ["Backup", "Desktop", "Laptop"]);
+    assert!(group_by_drive(&[]).is_empty());
+}
+
+#[test]
+fn test_hidden_results_filter() {
+    let file = |drive: &str, category: &str, path: &str| FileWithMetadata {
+        category_name: category.to_string(),
+        drive_name: drive.to_string(),
+        drive_available_space: 0,
+        drive_insertion_time: Local::now().naive_local(),
+        path: path.to_string(),
+        size_bytes: 1,
+        modified_time: None,
+    };
+    let files = vec![
+        file("Laptop", "Work", "a.txt"),
+        file("Laptop", "Work", "b.txt"),
+        file("Desktop", "Work", "a.txt"),
+        file("Laptop", "Games", "a.txt"),
+        file("Laptop", "Work", "c.txt"),
+    ];
+
+    let mut hidden = HiddenResults::default();
+    assert!(hidden.is_empty());
+    assert_eq!(hidden.visible(&files).count(), files.len());
+
+    // Only the exact drive, category and path is hidden
+    hidden.hide(ResultKey::from(&files[0]));
+    hidden.hide(ResultKey::from(&files[4]));
+    hidden.hide(ResultKey::from(&files[4]));
+    assert_eq!(hidden.len(), 2);
+    assert!(hidden.is_hidden(&files[0]));
+    assert!(!hidden.is_hidden(&files[2]));
+    let visible: Vec<(&str, &str, &str)> = hidden
+        .visible(&files)
+        .map(|file| {
+            (
+                file.drive_name.as_str(),
+                file.category_name.as_str(),
+                file.path.as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        visible,
+        [
+            ("Laptop", "Work", "b.txt"),
+            ("Desktop", "Work", "a.txt"),
+            ("Laptop", "Games", "a.txt"),
+        ]
+    );
+
+    // Clearing, as a new search does, shows every row again
+    hidden.clear();
+    assert!(hidden.is_empty());
+    assert_eq!(hidden.visible(&files).count(), files.len());
+}
+
+#[test]
+fn test_image_preview_source() {
+    let root = Path::new("/media/usb");
+    let present = |path: &Path| path == Path::new("/media/usb/photos/beach.JPG");
+
+    assert_eq!(
+        PreviewSource::locate("photos/beach.JPG", Some(root), present),
+        PreviewSource::Available(PathBuf::from("/media/usb/photos/beach.JPG"))
+    );
+    // Unplugged drive or moved file
+    let missing = PreviewSource::locate("photos/sunset.png", Some(root), present);
+    assert_eq!(
+        missing,
+        PreviewSource::Missing(PathBuf::from("/media/usb/photos/sunset.png"))
+    );
+    assert!(missing.shows_placeholder());
+    // Drive indexed before its location was recorded, the disk is not even checked
+    let no_location = PreviewSource::locate("photos/beach.JPG", None, |_| unreachable!());
+    assert_eq!(no_location, PreviewSource::NoLocation);
+    assert!(no_location.shows_placeholder());
+    // Neither other files nor undecodable images get a preview
+    for path in ["notes.txt", "raw/IMG_0001.CR2", "photos", "beach.jpg.exe"] {
+        let source = PreviewSource::locate(path, Some(root), |_| unreachable!());
+        assert_eq!(source, PreviewSource::NotAnImage, "{path}");
+        assert!(!source.shows_placeholder());
+    }
+    assert!(is_previewable("a/b/icon.ICO"));
+    assert!(!is_previewable("scan.heic"));
+}
+
+#[test]
+fn test_file_kind_from_filename() {
+    let cases = [
+        ("holiday.JPG", FileKind::Image),
+        ("scan.png", FileKind::Image),
+        ("movie.mkv", FileKind::Video),
+        ("song.flac", FileKind::Audio),
+        ("report.pdf", FileKind::Document),
+        ("notes.txt", FileKind::Document),
+        ("main.rs", FileKind::Code),
+        ("component.ts", FileKind::Code),
+        ("backup.zip", FileKind::Archive),
+        // Only the last extension counts
+        ("backup.tar.gz", FileKind::Archive),
+        ("report.final.v2.docx", FileKind::Document),
+        ("photo.jpg.exe", FileKind::Other),
+        // No extension
+        ("Makefile", FileKind::Other),
+        (".bashrc", FileKind::Other),
+        ("trailing.", FileKind::Other),
+        ("", FileKind::Other),
+        ("unknown.xyz", FileKind::Other),
+    ];
+    for (filename, kind) in cases {
+        assert_eq!(FileKind::from_filename(filename), kind, "{filename}");
+    }
+}
+
+#[test]
+fn test_move_selection_bounds() {
+    // Nothing to select in an empty list
+    assert_eq!(move_selection(None, 1, 0), None);
+    assert_eq!(move_selection(Some(3), -1, 0), None);
+
+    // The first move selects the first or the last row
+    assert_eq!(move_selection(None, 1, 5), Some(0));
+    assert_eq!(move_selection(None, -1, 5), Some(4));
+
+    // The selection stops at both edges
+    assert_eq!(move_selection(Some(0), -1, 5), Some(0));
+    assert_eq!(move_selection(Some(4), 1, 5), Some(4));
+    assert_eq!(move_selection(Some(2), 1, 5), Some(3));
+    assert_eq!(move_selection(Some(2), -1, 5), Some(1));
+
+    // A selection past the end, after rows were hidden, comes back inside the list
+    assert_eq!(move_selection(Some(9), 1, 5), Some(4));
+    assert_eq!(move_selection(Some(9), -1, 5), Some(3));
+}
+
+#[test]
+fn test_reveal_offset() {
+    // Rows 0 to 3 are fully visible in a 100px viewport at the top
+    assert_eq!(reveal_offset(3, 25.0, 0.0, 100.0), None);
+    // The next row is aligned to the bottom of the viewport
+    assert_eq!(reveal_offset(4, 25.0, 0.0, 100.0), Some(25.0));
+    // A row above the viewport is aligned to its top
+    assert_eq!(reveal_offset(1, 25.0, 50.0, 100.0), Some(25.0));
+    assert_eq!(reveal_offset(2, 25.0, 50.0, 100.0), None);
+}
+
+#[test]
+fn test_file_with_metadata_json_round_trip() {
+    let fixture = TestFixture::new();
+    fixture
+        .repository
+        .save("Work", "Laptop", 1024, &TestFixture::create_test_files())
+        .unwrap();
+    let files = fixture
+        .repository
+        .search_files_paginated(None, None, &SearchFilters::default(), 0, 100)
+        .unwrap();
+
+    let json = serde_json::to_string(&files).unwrap();
+    let parsed: Vec<FileWithMetadata> = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, files);
+
+    // Insertion times are written in ISO 8601
+    let expected_time = files[0].drive_insertion_time.format("%Y-%m-%dT%H:%M:%S");
+    assert!(json.contains(&format!("\"drive_insertion_time\":\"{expected_time}")));
+}
+
+#[test]
+fn test_format_manifest() {
+    let indexed_at = NaiveDate::from_ymd_opt(2026, 3, 1)
+        .unwrap()
+        .and_hms_opt(8, 30, 0)
+        .unwrap();
+    let file = |category: &str, path: &str, size_bytes: u64, days_later: i64| FileWithMetadata {
+        category_name: category.to_string(),
+        drive_name: "Backup".to_string(),
+        drive_available_space: 0,
+        drive_insertion_time: indexed_at + chrono::Duration::days(days_later),
+        path: path.to_string(),
+        size_bytes,
+        modified_time: None,
+    };
+    let files = vec![
+        file("Work", "photos/2025/b.jpg", 2_000, 0),
+        file("Work", "readme.txt", 500, 0),
+        file("Work", "docs/report.pdf", 1_500, 0),
+        file("Work", "photos/2025/a.jpg", 1_000, 0),
+        file("Personal", "photos\\2024\\c.jpg", 3_000, 2),
+        // Listed under another category too, counted once
+        file("Personal", "readme.txt", 500, 2),
+    ];
+
+    assert_eq!(
+        format_manifest("Backup", &files),
+        "Drive: Backup
+Files: 5
+Total size: 8 kB
+Indexed: 2026-03-03 08:30
+
+docs/
+  report.pdf (1.50 kB)
+photos/
+  2024/
+    c.jpg (3 kB)
+  2025/
+    a.jpg (1 kB)
+    b.jpg (2 kB)
+readme.txt (500 B)
+"
+    );
+    assert_eq!(
+        format_manifest("Empty", &[]),
+        "Drive: Empty\nFiles: 0\nTotal size: 0 B\n\n"
+    );
+}
+
+#[test]
+fn test_format_markdown_table() {
+    let indexed_at = NaiveDate::from_ymd_opt(2026, 3, 1)
+        .unwrap()
+        .and_hms_opt(8, 30, 0)
+        .unwrap();
+    let file = |drive: &str, category: &str, path: &str, size_bytes: u64| FileWithMetadata {
+        category_name: category.to_string(),
+        drive_name: drive.to_string(),
+        drive_available_space: 0,
+        drive_insertion_time: indexed_at,
+        path: path.to_string(),
+        size_bytes,
+        modified_time: None,
+    };
+    let files = vec![
+        file("Backup", "Work", "docs/a|b.txt", 1_500),
+        file("Archive", "Personal", "photos/line\nbreak.jpg", 2_000_000),
+        // Too wide to line up with, the other rows keep their own width
+        file(&["Long drive name"; 4].join(" "), "Work", "readme.txt", 12),
+    ];
+
+    assert_eq!(
+        format_markdown_table(&files),
+        "| Drive   | Category |    Size | Path |
+| ------- | -------- | ------: | --- |
+| Backup  | Work     | 1.50 kB | docs/a\\|b.txt |
+| Archive | Personal |    2 MB | photos/line break.jpg |
+| Long drive name Long drive name Long drive name Long drive name | Work     |    12 B | readme.txt |
+"
+    );
+    assert_eq!(format_markdown_table(&[]).lines().count(), 2);
+}
+
+#[test]
+fn test_scan_diff_against_stored_files() {
+    let fixture = TestFixture::new();
+    fixture
+        .repository
+        .save("Work", "Laptop", 1024, &TestFixture::create_test_files())
+        .unwrap();
+    fixture
+        .repository
+        .save(
+            "Personal",
+            "Laptop",
+            1024,
+            &[FileEntry {
+                path: "other/elsewhere.txt".to_string(),
+                size_bytes: 1,
+                modified_time: None,
+                ownership: None,
+            }],
+        )
+        .unwrap();
+
+    let directory = TempDir::new().unwrap();
+    let write = |path: &str, size: usize| {
+        let path = directory.path().join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, vec![0; size]).unwrap();
+    };
+    write("documents/report.pdf", 1024);
+    write("documents/invoice.pdf", 768);
+    write("images/photo.jpg", 100);
+    write("notes.txt", 10);
+
+    let stored = fixture.repository.files_for("Work", "Laptop").unwrap();
+    assert_eq!(
+        stored
+            .iter()
+            .map(|file| file.path.as_str())
+            .collect::<Vec<_>>(),
+        [
+            "code/main.rs",
+            "documents/invoice.pdf",
+            "documents/report.pdf",
+            "images/photo.jpg"
+        ]
+    );
+    let scanned = scan_directories(
+        &[directory.path().to_path_buf()],
+        false,
+        true,
+        ErrorPolicy::FailFast,
+    )
+    .unwrap();
+    let diff = ScanDiff::between(&stored, &scanned);
+
+    assert_eq!(diff.added, ["notes.txt"]);
+    assert_eq!(diff.removed, ["code/main.rs"]);
+    assert_eq!(
+        diff.resized,
+        [SizeMismatch {
+            path: "images/photo.jpg".to_string(),
+            size_a: 2048,
+            size_b: 100,
+        }]
+    );
+    assert_eq!(diff.unchanged, 2);
+    assert_eq!(diff.stored_count(), 4);
+    assert!(!diff.is_empty());
+    assert!(ScanDiff::between(&stored, &stored).is_empty());
+}
+
+#[test]
+fn test_verify_files_still_present() {
+    let fixture = TestFixture::new();
+    fixture
+        .repository
+        .save("Work", "Laptop", 1024, &TestFixture::create_test_files())
+        .unwrap();
+    let stored = fixture.repository.files_for("Work", "Laptop").unwrap();
+
+    let root = TempDir::new().unwrap();
+    for file in &stored {
+        let path = root.path().join(&file.path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, "abc").unwrap();
+    }
+    let report = check_presence(root.path(), stored.clone()).unwrap();
+    assert_eq!(report.checked, 4);
+    assert!(report.missing.is_empty());
+
+    // Files deleted from the drive since it was indexed
+    std::fs::remove_file(root.path().join("documents/invoice.pdf")).unwrap();
+    std::fs::remove_dir_all(root.path().join("images")).unwrap();
+    let report = check_presence(root.path(), stored.clone()).unwrap();
+    assert_eq!(report.checked, 4);
+    assert_eq!(
+        report
+            .missing
+            .iter()
+            .map(|file| file.path.as_str())
+            .collect::<Vec<_>>(),
+        ["documents/invoice.pdf", "images/photo.jpg"]
+    );
+
+    // An unplugged drive is not taken for one whose files were all deleted
+    let unmounted = root.path().join("unplugged");
+    assert_eq!(
+        check_presence(&unmounted, stored.clone()),
+        Err(PresenceCheckError::NotMounted(unmounted.clone()))
+    );
+
+    // Nor is the empty folder left behind by a drive unmounted from its mount point
+    let empty_mount_point = TempDir::new().unwrap();
+    assert_eq!(
+        check_presence(empty_mount_point.path(), stored),
+        Err(PresenceCheckError::NotMounted(
+            empty_mount_point.path().to_path_buf()
+        ))
+    );
+
+    let paths: Vec<String> = report.missing.into_iter().map(|file| file.path).collect();
+    assert_eq!(
+        fixture
+            .repository
+            .remove_files("Personal", "Laptop", &paths)
+            .unwrap(),
+        0
+    );
+    assert_eq!(
+        fixture
+            .repository
+            .remove_files("Work", "Laptop", &paths)
+            .unwrap(),
+        2
+    );
+    assert_eq!(
+        fixture
+            .repository
+            .files_for("Work", "Laptop")
+            .unwrap()
+            .iter()
+            .map(|file| file.path.as_str())
+            .collect::<Vec<_>>(),
+        ["code/main.rs", "documents/report.pdf"]
+    );
+}
+
+#[test]
+fn test_launcher_passes_path_without_shell() {
+    let path = Path::new("Backup/a&calc.exe | %PATH% ^.txt");
+    let command = launcher(path);
+
+    let program = command.get_program().to_string_lossy().to_lowercase();
+    assert!(!["cmd", "cmd.exe", "sh", "bash"].contains(&program.as_str()));
+    assert_eq!(command.get_args().collect::<Vec<_>>(), [path.as_os_str()]);
+}
+
+#[test]
+fn test_headless_mode_needs_one_of_its_options() {
+    let is_requested =
+        |args: &[&str]| CliArgs::is_requested(args.iter().map(std::ffi::OsString::from));
+
+    assert!(!is_requested(&["lister"]));
+    assert!(!is_requested(&["lister", "--updated"]));
+    assert!(!is_requested(&["lister", "/home/me/photo.jpg"]));
+    assert!(!is_requested(&["lister", "-psn_0_12345"]));
+    assert!(!is_requested(&["lister", "--unknown"]));
+
+    assert!(is_requested(&["lister", "--search", "report"]));
+    assert!(is_requested(&[
+        "lister",
+        "--index=/mnt/usb",
+        "--category",
+        "Work"
+    ]));
+    assert!(is_requested(&["lister", "--help"]));
+    assert!(is_requested(&["lister", "-V"]));
+}